@@ -67,6 +67,10 @@ where
         self.map.remove(key);
     }
 
+    pub fn get_mut(&mut self, key: &RangeMapKey<RV, ID>) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
     /// Returns an iterator of all keys.
     ///
     /// A RangeMapKey includes the range and the identity.
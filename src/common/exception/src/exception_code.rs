@@ -157,6 +157,8 @@ build_exceptions! {
     WrongSequenceCount(1125),
     UnknownSequence(1126),
     UnknownQuery(1127),
+    // The query uses more memory than it's allowed and cannot spill.
+    ExceedMemoryLimit(1128),
 
     // Data Related Errors
 
@@ -152,6 +152,37 @@ impl<K: Keyable, A: Allocator + Clone + Default> HashJoinHashTable<K, A> {
         }
         unsafe { (*entry_ptr).next = remove_header_tag(old_header) };
     }
+
+    /// Inserts the entry unless the bucket already links a key equal to `key`, returns false
+    /// if the key is duplicated. The `next` of the entry is written before the entry is
+    /// published, so the concurrent inserts always see the complete links of the bucket.
+    pub fn insert_unique(&mut self, key: K, entry_ptr: *mut RawEntry<K>) -> bool
+    where A: 'static {
+        let hash = key.hash();
+        let index = (hash >> self.hash_shift) as usize;
+        let new_header = new_header(entry_ptr as u64, hash);
+        // # Safety
+        // `index` is less than the capacity of hash table.
+        let mut old_header = unsafe { (*self.atomic_pointers.add(index)).load(Ordering::Acquire) };
+        loop {
+            if self.next_contains(&key, remove_header_tag(old_header)) {
+                return false;
+            }
+            unsafe { (*entry_ptr).next = remove_header_tag(old_header) };
+            let res = unsafe {
+                (*self.atomic_pointers.add(index)).compare_exchange_weak(
+                    old_header,
+                    combine_header(new_header, old_header),
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                )
+            };
+            match res {
+                Ok(_) => return true,
+                Err(x) => old_header = x,
+            };
+        }
+    }
 }
 
 impl<K, A> HashJoinHashtableLike for HashJoinHashTable<K, A>
@@ -340,6 +371,29 @@ where
         false
     }
 
+    fn next_count(&self, key: &Self::Key, mut ptr: u64) -> usize {
+        let mut count = 0;
+        while ptr != 0 {
+            let raw_entry = unsafe { &*(ptr as *mut RawEntry<K>) };
+            if key == &raw_entry.key {
+                count += 1;
+            }
+            ptr = raw_entry.next;
+        }
+        count
+    }
+
+    fn next_first(&self, key: &Self::Key, mut ptr: u64) -> Option<RowPtr> {
+        while ptr != 0 {
+            let raw_entry = unsafe { &*(ptr as *mut RawEntry<K>) };
+            if key == &raw_entry.key {
+                return Some(raw_entry.row_ptr);
+            }
+            ptr = raw_entry.next;
+        }
+        None
+    }
+
     fn next_probe(
         &self,
         key: &Self::Key,
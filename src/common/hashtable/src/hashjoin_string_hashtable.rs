@@ -86,6 +86,36 @@ impl<A: Allocator + Clone + Default> HashJoinStringHashTable<A> {
         }
         unsafe { (*entry_ptr).next = remove_header_tag(old_header) };
     }
+
+    /// Inserts the entry unless the bucket already links a key equal to `key`, returns false
+    /// if the key is duplicated. See `HashJoinHashTable::insert_unique`.
+    pub fn insert_unique(&mut self, key: &[u8], entry_ptr: *mut StringRawEntry) -> bool
+    where A: 'static {
+        let hash = hash_join_fast_string_hash(key);
+        let index = (hash >> self.hash_shift) as usize;
+        let new_header = new_header(entry_ptr as u64, hash);
+        // # Safety
+        // `index` is less than the capacity of hash table.
+        let mut old_header = unsafe { (*self.atomic_pointers.add(index)).load(Ordering::Acquire) };
+        loop {
+            if self.next_contains(key, remove_header_tag(old_header)) {
+                return false;
+            }
+            unsafe { (*entry_ptr).next = remove_header_tag(old_header) };
+            let res = unsafe {
+                (*self.atomic_pointers.add(index)).compare_exchange_weak(
+                    old_header,
+                    combine_header(new_header, old_header),
+                    Ordering::SeqCst,
+                    Ordering::SeqCst,
+                )
+            };
+            match res {
+                Ok(_) => return true,
+                Err(x) => old_header = x,
+            };
+        }
+    }
 }
 
 impl<A> HashJoinHashtableLike for HashJoinStringHashTable<A>
@@ -279,6 +309,53 @@ where A: Allocator + Clone + 'static
         false
     }
 
+    fn next_count(&self, key: &Self::Key, mut ptr: u64) -> usize {
+        let mut count = 0;
+        while ptr != 0 {
+            let raw_entry = unsafe { &*(ptr as *mut StringRawEntry) };
+            // Compare `early` and the length of the string, the size of `early` is 4.
+            let min_len = std::cmp::min(STRING_EARLY_SIZE, key.len());
+            if raw_entry.length as usize == key.len()
+                && key[0..min_len] == raw_entry.early[0..min_len]
+            {
+                let key_ref = unsafe {
+                    std::slice::from_raw_parts(
+                        raw_entry.key as *const u8,
+                        raw_entry.length as usize,
+                    )
+                };
+                if key == key_ref {
+                    count += 1;
+                }
+            }
+            ptr = raw_entry.next;
+        }
+        count
+    }
+
+    fn next_first(&self, key: &Self::Key, mut ptr: u64) -> Option<RowPtr> {
+        while ptr != 0 {
+            let raw_entry = unsafe { &*(ptr as *mut StringRawEntry) };
+            // Compare `early` and the length of the string, the size of `early` is 4.
+            let min_len = std::cmp::min(STRING_EARLY_SIZE, key.len());
+            if raw_entry.length as usize == key.len()
+                && key[0..min_len] == raw_entry.early[0..min_len]
+            {
+                let key_ref = unsafe {
+                    std::slice::from_raw_parts(
+                        raw_entry.key as *const u8,
+                        raw_entry.length as usize,
+                    )
+                };
+                if key == key_ref {
+                    return Some(raw_entry.row_ptr);
+                }
+            }
+            ptr = raw_entry.next;
+        }
+        None
+    }
+
     fn next_probe(
         &self,
         key: &Self::Key,
@@ -549,6 +549,13 @@ pub trait HashJoinHashtableLike {
     // the ptr is the link header.
     fn next_contains(&self, key: &Self::Key, ptr: u64) -> bool;
 
+    // we use `next_count` to count the matched rows in the link, the ptr is the link header.
+    fn next_count(&self, key: &Self::Key, ptr: u64) -> usize;
+
+    // we use `next_first` to find the first matched row in the link, the ptr is the link header.
+    // It is enough to probe the build keys that are known to be unique.
+    fn next_first(&self, key: &Self::Key, ptr: u64) -> Option<RowPtr>;
+
     /// 1. `key` is the serialize probe key from one row
     /// 2. `ptr` pointers to the *RawEntry for of the bucket correlated to key.So before this method,
     ///     we will do a round probe firstly. If the ptr is zero, it means there is no correlated bucket
@@ -23,9 +23,14 @@ use bumpalo::Bump;
 use databend_common_hashtable::fast_memcmp;
 use databend_common_hashtable::DictionaryKeys;
 use databend_common_hashtable::DictionaryStringHashMap;
+use databend_common_hashtable::FastHash;
+use databend_common_hashtable::HashJoinHashMap;
+use databend_common_hashtable::HashJoinHashtableLike;
 use databend_common_hashtable::HashMap;
 use databend_common_hashtable::HashtableEntryMutRefLike;
 use databend_common_hashtable::HashtableLike;
+use databend_common_hashtable::RawEntry;
+use databend_common_hashtable::RowPtr;
 use databend_common_hashtable::ShortStringHashMap;
 use databend_common_hashtable::StackHashMap;
 use rand::distributions::Alphanumeric;
@@ -207,3 +212,85 @@ fn test_dictionary_hash_map() {
         }
     }
 }
+
+#[test]
+fn test_hash_join_next_count() {
+    let build_keys = (0..1000u64).map(|x| x % 7).collect::<Vec<_>>();
+    let mut entries = build_keys
+        .iter()
+        .enumerate()
+        .map(|(idx, key)| RawEntry {
+            row_ptr: RowPtr::new(0, idx as u32),
+            key: *key,
+            next: 0,
+        })
+        .collect::<Vec<_>>();
+
+    let mut hashtable = HashJoinHashMap::<u64>::with_build_row_num(build_keys.len());
+    for entry in entries.iter_mut() {
+        let key = entry.key;
+        hashtable.insert(key, entry as *mut RawEntry<u64>);
+    }
+
+    let mut expected = std::collections::HashMap::new();
+    for key in build_keys.iter() {
+        *expected.entry(*key).or_insert(0) += 1;
+    }
+
+    let probe_keys = (0..10u64).collect::<Vec<_>>();
+    let mut pointers = probe_keys
+        .iter()
+        .map(|key| key.fast_hash())
+        .collect::<Vec<_>>();
+    hashtable.probe(&mut pointers, None);
+    for (key, ptr) in probe_keys.iter().zip(pointers) {
+        let count = if ptr == 0 {
+            0
+        } else {
+            hashtable.next_count(key, ptr)
+        };
+        assert_eq!(count, expected.get(key).copied().unwrap_or(0));
+    }
+}
+
+#[test]
+fn test_hash_join_unique_keys() {
+    let build_keys = (0..1000u64).map(|x| x * 3).collect::<Vec<_>>();
+    let mut entries = build_keys
+        .iter()
+        .enumerate()
+        .map(|(idx, key)| RawEntry {
+            row_ptr: RowPtr::new(0, idx as u32),
+            key: *key,
+            next: 0,
+        })
+        .collect::<Vec<_>>();
+
+    let mut hashtable = HashJoinHashMap::<u64>::with_build_row_num(build_keys.len());
+    for entry in entries.iter_mut() {
+        let key = entry.key;
+        assert!(hashtable.insert_unique(key, entry as *mut RawEntry<u64>));
+    }
+
+    let probe_keys = (0..3000u64).collect::<Vec<_>>();
+    let mut pointers = probe_keys
+        .iter()
+        .map(|key| key.fast_hash())
+        .collect::<Vec<_>>();
+    hashtable.probe(&mut pointers, None);
+    let mut row_ptrs = vec![RowPtr::new(0, 0); 4];
+    for (key, ptr) in probe_keys.iter().zip(pointers) {
+        let first = hashtable.next_first(key, ptr);
+        let (count, _) = hashtable.next_probe(key, ptr, row_ptrs.as_mut_ptr(), 0, 4);
+        let expected = (key % 3 == 0).then(|| RowPtr::new(0, (key / 3) as u32));
+        assert_eq!(first, expected);
+        assert_eq!(first, (count == 1).then(|| row_ptrs[0]));
+    }
+
+    let mut duplicate = RawEntry {
+        row_ptr: RowPtr::new(1, 0),
+        key: 42,
+        next: 0,
+    };
+    assert!(!hashtable.insert_unique(42, &mut duplicate as *mut RawEntry<u64>));
+}
@@ -69,6 +69,23 @@ fn parse_time_part(buf: &[u8], size: usize) -> Result<u32> {
     }
 }
 
+/// Convert the fractional seconds digits into microseconds.
+///
+/// Timestamps only keep microsecond precision, so digits beyond the sixth
+/// are rounded half-up instead of being truncated, e.g. `.0000005` => `.000001`.
+fn parse_fraction_micros(digits: &[u8]) -> i64 {
+    let mut micros = digits
+        .iter()
+        .take(6)
+        .fold(0_i64, |acc, b| acc * 10 + (b - b'0') as i64);
+    if digits.len() < 6 {
+        micros *= 10_i64.pow(6 - digits.len() as u32);
+    } else if digits.len() > 6 && digits[6] >= b'5' {
+        micros += 1;
+    }
+    micros
+}
+
 impl<T> BufferReadDateTimeExt for Cursor<T>
 where T: AsRef<[u8]>
 {
@@ -89,6 +106,13 @@ where T: AsRef<[u8]>
         enable_dst_hour_fix: bool,
     ) -> Result<DateTimeResType> {
         // Date Part YYYY-MM-DD
+        // The years before the common era, like `-0044-03-15`, are out of the range of Date,
+        // they are rejected instead of being clamped to `1000-01-01` like the other years before 1000.
+        if self.peek_byte() == Some(b'-') {
+            return Err(ErrorCode::BadBytes(
+                "Date Parsing Error: The years before the common era are not supported",
+            ));
+        }
         let mut buf = vec![0; DATE_LEN];
         self.read_exact(buf.as_mut_slice())?;
         let mut v =
@@ -165,14 +189,7 @@ where T: AsRef<[u8]>
                         "Microsecond Parsing Error: Expecting a format like [.123456] for microseconds part",
                     ));
                 }
-                let mut scales: i64 =
-                    lexical_core::FromLexical::from_lexical(buf.as_slice()).unwrap();
-                if size <= 9 {
-                    scales *= 10_i64.pow(9 - size as u32)
-                } else {
-                    scales /= (size as i64 - 9) * 10
-                }
-                dt.checked_add_signed(Duration::nanoseconds(scales))
+                dt.checked_add_signed(Duration::microseconds(parse_fraction_micros(&buf)))
                     .unwrap()
             } else {
                 dt
@@ -32,7 +32,7 @@ fn test_read_timestamp_text() -> Result<()> {
         "2023-12-25T07:31:07.485281UTC",
         "2009-01-01T00:00:00.120UTC",
         "2009-01-01T00:00:00.123400UTC",
-        "2009-01-01T00:00:00.123456789UTC",
+        "2009-01-01T00:00:00.123457UTC",
         "2022-02-02T00:00:00UTC",
         "2022-02-02T12:00:00UTC",
         "2022-02-02T13:04:00UTC",
@@ -65,6 +65,26 @@ fn test_read_timestamp_text() -> Result<()> {
         }
     }
     assert_eq!(res, expected);
+
+    // digits beyond microsecond are rounded half-up
+    let mut reader = Cursor::new(
+        "2023-01-01 00:00:00.0000005,2023-01-01 00:00:00.0000004,2023-01-01 00:00:00.999999999,2023-01-01 00:00:00.123456500".as_bytes(),
+    );
+    let expected = vec![
+        "2023-01-01T00:00:00.000001UTC",
+        "2023-01-01T00:00:00UTC",
+        "2023-01-01T00:00:01UTC",
+        "2023-01-01T00:00:00.123457UTC",
+    ];
+    let mut res = vec![];
+    for _ in 0..expected.len() {
+        let time = reader.read_timestamp_text(&tz, false, false)?;
+        if let DateTimeResType::Datetime(time) = time {
+            res.push(format!("{:?}", time));
+            reader.ignore_byte(b',');
+        }
+    }
+    assert_eq!(res, expected);
     Ok(())
 }
 
@@ -138,3 +158,22 @@ fn test_read_date_text() -> Result<()> {
     assert_eq!(res, expected);
     Ok(())
 }
+
+#[test]
+fn test_read_negative_year_text() -> Result<()> {
+    let tz = Tz::UTC;
+
+    // The years before the common era are rejected, they are not clamped to `1000-01-01`.
+    for text in [
+        "-0044-03-15",
+        "-0044-03-15 12:30:00",
+        "-0001-12-31T23:59:59Z",
+        "-0044-02-30",
+    ] {
+        let mut reader = Cursor::new(text.as_bytes());
+        assert!(reader.read_date_text(&tz, false).is_err());
+        let mut reader = Cursor::new(text.as_bytes());
+        assert!(reader.read_timestamp_text(&tz, false, false).is_err());
+    }
+    Ok(())
+}
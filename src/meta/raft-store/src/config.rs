@@ -131,6 +131,12 @@ pub struct RaftConfig {
 
     /// Max timeout(in milli seconds) when waiting a cluster leader.
     pub wait_leader_timeout: u64,
+
+    /// The max number of watchers a client connection can register. 0 means unlimited.
+    pub watcher_max_per_client: u64,
+
+    /// The max number of watchers on this node. 0 means unlimited.
+    pub watcher_max_total: u64,
 }
 
 pub fn get_default_raft_advertise_host() -> String {
@@ -172,6 +178,8 @@ impl Default for RaftConfig {
             sled_max_cache_size_mb: 10 * 1024,
             cluster_name: "foo_cluster".to_string(),
             wait_leader_timeout: 70000,
+            watcher_max_per_client: 0,
+            watcher_max_total: 0,
         }
     }
 }
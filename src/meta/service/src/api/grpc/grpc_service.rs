@@ -415,7 +415,12 @@ impl MetaService for MetaServiceImpl {
 
         let mn = &self.meta_node;
 
-        let add_res = mn.add_watcher(request.into_inner(), tx).await;
+        let client = request
+            .remote_addr()
+            .map(|addr| addr.to_string())
+            .unwrap_or_default();
+
+        let add_res = mn.add_watcher(request.into_inner(), tx, client).await;
 
         match add_res {
             Ok(watcher) => {
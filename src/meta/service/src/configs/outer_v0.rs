@@ -303,6 +303,8 @@ pub struct ConfigViaEnv {
     pub sled_tree_prefix: String,
     pub sled_max_cache_size_mb: u64,
     pub cluster_name: String,
+    pub metasrv_watcher_max_per_client: u64,
+    pub metasrv_watcher_max_total: u64,
 }
 
 impl Default for ConfigViaEnv {
@@ -356,6 +358,8 @@ impl From<Config> for ConfigViaEnv {
             sled_tree_prefix: cfg.raft_config.sled_tree_prefix,
             sled_max_cache_size_mb: cfg.raft_config.sled_max_cache_size_mb,
             cluster_name: cfg.raft_config.cluster_name,
+            metasrv_watcher_max_per_client: cfg.raft_config.watcher_max_per_client,
+            metasrv_watcher_max_total: cfg.raft_config.watcher_max_total,
         }
     }
 }
@@ -393,6 +397,8 @@ impl Into<Config> for ConfigViaEnv {
             sled_tree_prefix: self.sled_tree_prefix,
             sled_max_cache_size_mb: self.sled_max_cache_size_mb,
             cluster_name: self.cluster_name,
+            watcher_max_per_client: self.metasrv_watcher_max_per_client,
+            watcher_max_total: self.metasrv_watcher_max_total,
         };
         let log_config = LogConfig {
             file: FileLogConfig {
@@ -564,6 +570,14 @@ pub struct RaftConfig {
     /// Max timeout(in milli seconds) when waiting a cluster leader.
     #[clap(long, default_value = "180000")]
     pub wait_leader_timeout: u64,
+
+    /// The max number of watchers a client connection can register. 0 means unlimited.
+    #[clap(long, default_value = "0")]
+    pub watcher_max_per_client: u64,
+
+    /// The max number of watchers on this node. 0 means unlimited.
+    #[clap(long, default_value = "0")]
+    pub watcher_max_total: u64,
 }
 
 // TODO(rotbl): should not be used.
@@ -602,6 +616,8 @@ impl From<RaftConfig> for InnerRaftConfig {
             sled_max_cache_size_mb: x.sled_max_cache_size_mb,
             cluster_name: x.cluster_name,
             wait_leader_timeout: x.wait_leader_timeout,
+            watcher_max_per_client: x.watcher_max_per_client,
+            watcher_max_total: x.watcher_max_total,
         }
     }
 }
@@ -635,6 +651,8 @@ impl From<InnerRaftConfig> for RaftConfig {
             sled_max_cache_size_mb: inner.sled_max_cache_size_mb,
             cluster_name: inner.cluster_name,
             wait_leader_timeout: inner.wait_leader_timeout,
+            watcher_max_per_client: inner.watcher_max_per_client,
+            watcher_max_total: inner.watcher_max_total,
         }
     }
 }
@@ -93,6 +93,7 @@ use crate::watcher::DispatcherSender;
 use crate::watcher::EventDispatcher;
 use crate::watcher::EventDispatcherHandle;
 use crate::watcher::Watcher;
+use crate::watcher::WatcherLimits;
 use crate::watcher::WatcherSender;
 use crate::Opened;
 
@@ -124,6 +125,7 @@ pub struct MetaNodeBuilder {
     raft_config: Option<Config>,
     sto: Option<RaftStore>,
     raft_service_endpoint: Option<Endpoint>,
+    watcher_limits: WatcherLimits,
 }
 
 impl MetaNodeBuilder {
@@ -153,7 +155,7 @@ impl MetaNodeBuilder {
 
         let (tx, rx) = watch::channel::<()>(());
 
-        let dispatcher_tx = EventDispatcher::spawn();
+        let dispatcher_tx = EventDispatcher::spawn(self.watcher_limits);
 
         sto.get_state_machine()
             .await
@@ -215,6 +217,10 @@ impl MetaNode {
             raft_config: Some(raft_config),
             sto: None,
             raft_service_endpoint: None,
+            watcher_limits: WatcherLimits {
+                max_per_client: config.watcher_max_per_client,
+                max_total: config.watcher_max_total,
+            },
         }
     }
 
@@ -1120,11 +1126,12 @@ impl MetaNode {
         &self,
         request: WatchRequest,
         tx: WatcherSender,
+        client: String,
     ) -> Result<Watcher, &'static str> {
         let (resp_tx, resp_rx) = oneshot::channel();
 
         self.dispatcher_handle.request(|d: &mut EventDispatcher| {
-            let add_res = d.add_watcher(request, tx);
+            let add_res = d.add_watcher(request, tx, client);
             let _ = resp_tx.send(add_res);
         });
 
@@ -62,6 +62,7 @@ pub mod server_metrics {
         proposals_failed: Counter,
         read_failed: Counter,
         watchers: Gauge,
+        watchers_closed: Family<Vec<(String, String)>, Counter>,
         version: Family<Vec<(String, String)>, Gauge>,
     }
 
@@ -83,6 +84,7 @@ pub mod server_metrics {
                 proposals_failed: Counter::default(),
                 read_failed: Counter::default(),
                 watchers: Gauge::default(),
+                watchers_closed: Family::default(),
                 version: Family::default(),
             };
 
@@ -146,6 +148,11 @@ pub mod server_metrics {
                 metrics.read_failed.clone(),
             );
             registry.register(key!("watchers"), "watchers", metrics.watchers.clone());
+            registry.register(
+                key!("watchers_closed"),
+                "number of closed watchers by reason",
+                metrics.watchers_closed.clone(),
+            );
             registry.register(key!("version"), "version", metrics.version.clone());
             metrics
         }
@@ -225,6 +232,11 @@ pub mod server_metrics {
         SERVER_METRICS.watchers.inc_by(cnt);
     }
 
+    pub fn incr_watcher_closed(reason: &str) {
+        let labels = &vec![("reason".to_string(), reason.to_string())];
+        SERVER_METRICS.watchers_closed.get_or_create(labels).inc();
+    }
+
     pub fn set_version(semver: String, sha: String) {
         let labels = &vec![
             ("component".to_string(), "metasrv".to_string()),
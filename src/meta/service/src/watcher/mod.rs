@@ -19,7 +19,10 @@ pub(crate) use watcher_manager::DispatcherSender;
 pub(crate) use watcher_manager::EventDispatcher;
 pub use watcher_manager::EventDispatcherHandle;
 pub use watcher_manager::WatcherId;
+pub use watcher_manager::WatcherLimits;
 pub use watcher_manager::WatcherSender;
+pub use watcher_stream::CloseWatcherReason;
+pub use watcher_stream::WatchBatch;
 pub use watcher_stream::WatchStream;
 pub use watcher_stream::WatchStreamHandle;
 pub use watcher_stream::Watcher;
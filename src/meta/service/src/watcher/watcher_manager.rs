@@ -13,9 +13,13 @@
 // limitations under the License.
 
 use core::ops::Range;
+use std::collections::BTreeMap;
 
+use databend_common_base::base::tokio::select;
 use databend_common_base::base::tokio::sync::mpsc;
 use databend_common_base::base::tokio::sync::oneshot;
+use databend_common_base::base::tokio::time::sleep_until;
+use databend_common_base::base::tokio::time::Instant;
 use databend_common_base::rangemap::RangeMap;
 use databend_common_base::rangemap::RangeMapKey;
 use databend_common_meta_raft_store::state_machine::StateMachineSubscriber;
@@ -30,9 +34,11 @@ use log::warn;
 use prost::Message;
 use tonic::Status;
 
+use super::CloseWatcherReason;
 use super::WatchStreamHandle;
 use crate::metrics::network_metrics;
 use crate::metrics::server_metrics;
+use crate::watcher::WatchBatch;
 use crate::watcher::Watcher;
 
 pub type WatcherId = i64;
@@ -88,25 +94,74 @@ impl EventDispatcherHandle {
     }
 }
 
+/// Limits of the number of watchers. 0 means unlimited.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WatcherLimits {
+    /// The max number of watchers a client connection can register.
+    pub max_per_client: u64,
+
+    /// The max number of watchers on this node.
+    pub max_total: u64,
+}
+
+/// Watchers with the same key range and filter type.
+///
+/// They share one entry in the range map, so that a kv change is matched and encoded once
+/// and then fanned out to every stream of the group.
+struct WatchGroup {
+    filter_type: FilterType,
+    streams: BTreeMap<WatcherId, WatchStreamHandle>,
+}
+
+/// The events buffered for a watcher that batches events.
+struct PendingBatch {
+    /// When the batch is sent if it does not become full before then.
+    flush_at: Instant,
+    events: Vec<Event>,
+}
+
 /// Receives events from event sources, dispatches them to interested watchers.
 pub struct EventDispatcher {
     event_rx: mpsc::UnboundedReceiver<WatchEvent>,
 
-    /// map range to WatcherId
-    watcher_range_map: RangeMap<String, WatcherId, WatchStreamHandle>,
+    /// map range to the group of watchers, keyed by the id of the first watcher in the group.
+    watcher_range_map: RangeMap<String, WatcherId, WatchGroup>,
+
+    /// map `(key_start, key_end, filter_type)` to the id of the group.
+    group_ids: BTreeMap<(String, String, i32), WatcherId>,
+
+    /// map WatcherId to the key of its group in `watcher_range_map`.
+    watcher_groups: BTreeMap<WatcherId, RangeMapKey<String, WatcherId>>,
 
     current_watcher_id: WatcherId,
+
+    limits: WatcherLimits,
+
+    /// The client of every registered watcher, an empty string if the client address is unknown.
+    watcher_clients: BTreeMap<WatcherId, String>,
+
+    /// The number of watchers registered by every client.
+    client_watcher_count: BTreeMap<String, u64>,
+
+    /// The events not yet sent to the watchers that batch events.
+    pending_batches: BTreeMap<WatcherId, PendingBatch>,
 }
 
 impl EventDispatcher {
     /// Spawn a dispatcher loop task.
-    pub(crate) fn spawn() -> mpsc::UnboundedSender<WatchEvent> {
+    pub(crate) fn spawn(limits: WatcherLimits) -> mpsc::UnboundedSender<WatchEvent> {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         let dispatcher = EventDispatcher {
             event_rx,
             watcher_range_map: RangeMap::new(),
+            group_ids: BTreeMap::new(),
+            watcher_groups: BTreeMap::new(),
             current_watcher_id: 1,
+            limits,
+            watcher_clients: BTreeMap::new(),
+            client_watcher_count: BTreeMap::new(),
+            pending_batches: BTreeMap::new(),
         };
 
         let _h = databend_common_base::runtime::spawn(dispatcher.main());
@@ -117,16 +172,31 @@ impl EventDispatcher {
     #[fastrace::trace]
     async fn main(mut self) {
         loop {
-            if let Some(event) = self.event_rx.recv().await {
-                match event {
-                    WatchEvent::KVChange(kv_change) => {
-                        self.dispatch_event(kv_change).await;
-                    }
-                    WatchEvent::Request { req } => req(&mut self),
+            // Wake up to send the earliest pending batch, even if no more events arrive.
+            let flush_at = self.pending_batches.values().map(|b| b.flush_at).min();
+            let wait_batch = flush_at.is_some();
+            let flush_at = flush_at.unwrap_or_else(Instant::now);
+
+            let event = select! {
+                event = self.event_rx.recv() => Some(event),
+                _ = sleep_until(flush_at), if wait_batch => None,
+            };
+
+            match event {
+                Some(Some(WatchEvent::KVChange(kv_change))) => {
+                    self.dispatch_event(kv_change).await;
+                }
+                Some(Some(WatchEvent::Request { req })) => req(&mut self),
+                Some(None) => {
+                    info!("all event senders are closed. quit.");
+                    self.flush_batches(|_| true).await;
+                    self.shutdown_watchers();
+                    break;
+                }
+                None => {
+                    let now = Instant::now();
+                    self.flush_batches(|b| b.flush_at <= now).await;
                 }
-            } else {
-                info!("all event senders are closed. quit.");
-                break;
             }
         }
     }
@@ -143,10 +213,17 @@ impl EventDispatcher {
         let prev = change.prev;
 
         let is_delete_event = current.is_none();
-        let mut remove_range_keys: Vec<RangeMapKey<String, WatcherId>> = vec![];
+        let mut removed: Vec<(RangeMapKey<String, WatcherId>, CloseWatcherReason)> = vec![];
 
-        for range_key_stream in set.iter() {
-            let filter = range_key_stream.1.watcher.filter_type;
+        let event = Event {
+            key: k.to_string(),
+            current: current.map(pb::SeqV::from),
+            prev: prev.map(pb::SeqV::from),
+        };
+        let resp = WatchResponse::new(event.clone());
+
+        for (_, group) in set.iter() {
+            let filter = group.filter_type;
 
             // filter out event
             if (filter == FilterType::Delete && !is_delete_event)
@@ -155,59 +232,140 @@ impl EventDispatcher {
                 continue;
             }
 
-            let watcher_id = range_key_stream.0.key;
-            let stream = range_key_stream.1;
-            assert_eq!(stream.watcher.id, watcher_id);
-            let resp = WatchResponse {
-                event: Some(Event {
-                    key: k.to_string(),
-                    current: current.clone().map(pb::SeqV::from),
-                    prev: prev.clone().map(pb::SeqV::from),
-                }),
-            };
+            for (watcher_id, stream) in group.streams.iter() {
+                assert_eq!(stream.watcher.id, *watcher_id);
+
+                let resp = match stream.watcher.batch {
+                    None => resp.clone(),
+                    Some(batch) => {
+                        let pending =
+                            self.pending_batches.entry(*watcher_id).or_insert_with(|| {
+                                let now = Instant::now();
+                                PendingBatch {
+                                    flush_at: now.checked_add(batch.max_delay).unwrap_or(now),
+                                    events: vec![],
+                                }
+                            });
+                        pending.events.push(event.clone());
+
+                        if pending.events.len() < batch.max_size {
+                            continue;
+                        }
+                        let pending = self.pending_batches.remove(watcher_id).unwrap();
+                        WatchResponse::new_batch(pending.events)
+                    }
+                };
 
-            network_metrics::incr_sent_bytes(resp.encoded_len() as u64);
-
-            if let Err(err) = stream.send(resp).await {
-                warn!(
-                    "close watcher stream {:?} cause send err: {:?}",
-                    watcher_id, err
-                );
-                remove_range_keys.push(RangeMapKey::new(
-                    stream.watcher.key_range.clone(),
-                    watcher_id,
-                ));
-            };
+                Self::send_response(stream, resp, &mut removed).await;
+            }
         }
 
         // TODO: when a watcher stream is dropped, send a event to remove the watcher explicitly
-        for range_key in remove_range_keys {
-            self.remove_watcher(&range_key);
+        for (range_key, reason) in removed {
+            self.remove_watcher(&range_key, reason);
+        }
+    }
+
+    /// Send the pending batches that `is_due` returns true for.
+    async fn flush_batches(&mut self, is_due: impl Fn(&PendingBatch) -> bool) {
+        let due = self
+            .pending_batches
+            .iter()
+            .filter(|(_, batch)| is_due(batch))
+            .map(|(watcher_id, _)| *watcher_id)
+            .collect::<Vec<_>>();
+
+        let mut removed: Vec<(RangeMapKey<String, WatcherId>, CloseWatcherReason)> = vec![];
+
+        for watcher_id in due {
+            let batch = self.pending_batches.remove(&watcher_id).unwrap();
+
+            let Some(range_key) = self.watcher_groups.get(&watcher_id) else {
+                continue;
+            };
+            let Some(group) = self.watcher_range_map.get_mut(range_key) else {
+                continue;
+            };
+            let Some(stream) = group.streams.get(&watcher_id) else {
+                continue;
+            };
+
+            let resp = WatchResponse::new_batch(batch.events);
+            Self::send_response(stream, resp, &mut removed).await;
+        }
+
+        for (range_key, reason) in removed {
+            self.remove_watcher(&range_key, reason);
         }
     }
 
+    /// Send a response to a watcher stream, the watcher is added to `removed` if it fails.
+    async fn send_response(
+        stream: &WatchStreamHandle,
+        resp: WatchResponse,
+        removed: &mut Vec<(RangeMapKey<String, WatcherId>, CloseWatcherReason)>,
+    ) {
+        let watcher_id = stream.watcher.id;
+
+        network_metrics::incr_sent_bytes(resp.encoded_len() as u64);
+
+        if let Err(err) = stream.send(resp).await {
+            warn!(
+                "close watcher stream {:?} cause send err: {:?}",
+                watcher_id, err
+            );
+            removed.push((
+                RangeMapKey::new(stream.watcher.key_range.clone(), watcher_id),
+                CloseWatcherReason::SendError(err),
+            ));
+        };
+    }
+
+    /// Register a watcher for `client`, the remote address of the connection.
+    ///
+    /// It returns an error if the client or this node already has too many watchers.
     #[fastrace::trace]
     pub fn add_watcher(
         &mut self,
         create: WatchRequest,
         tx: WatcherSender,
+        client: String,
     ) -> Result<Watcher, &'static str> {
-        info!("add_watcher: {:?}", create);
+        info!("add_watcher: {:?}, client: {}", create, client);
 
         let range = match EventDispatcher::build_key_range(create.key.clone(), &create.key_end) {
             Ok(range) => range,
             Err(e) => return Err(e),
         };
 
+        self.check_watcher_limits(&client)?;
+
         self.current_watcher_id += 1;
         let watcher_id = self.current_watcher_id;
         let filter: FilterType = create.filter_type();
 
-        let watcher = Watcher::new(watcher_id, filter, range.clone());
+        let watcher = Watcher::new(watcher_id, filter, range.clone())
+            .with_batch(WatchBatch::from_request(&create));
         let stream_handle = WatchStreamHandle::new(watcher.clone(), tx);
 
-        self.watcher_range_map
-            .insert(range, watcher_id, stream_handle);
+        // Identical watches share one group.
+        let group_key = (range.start.clone(), range.end.clone(), filter as i32);
+        let group_id = *self.group_ids.entry(group_key).or_insert_with(|| {
+            self.watcher_range_map
+                .insert(range.clone(), watcher_id, WatchGroup {
+                    filter_type: filter,
+                    streams: BTreeMap::new(),
+                });
+            watcher_id
+        });
+
+        let range_key = RangeMapKey::new(range, group_id);
+        let group = self.watcher_range_map.get_mut(&range_key).unwrap();
+        group.streams.insert(watcher_id, stream_handle);
+        self.watcher_groups.insert(watcher_id, range_key);
+
+        *self.client_watcher_count.entry(client.clone()).or_default() += 1;
+        self.watcher_clients.insert(watcher_id, client);
 
         server_metrics::incr_watchers(1);
 
@@ -215,13 +373,87 @@ impl EventDispatcher {
     }
 
     #[fastrace::trace]
-    pub fn remove_watcher(&mut self, key: &RangeMapKey<String, WatcherId>) {
-        info!("remove_watcher: {:?}", key);
+    pub fn remove_watcher(
+        &mut self,
+        key: &RangeMapKey<String, WatcherId>,
+        reason: CloseWatcherReason,
+    ) {
+        if reason.is_error() {
+            warn!("remove_watcher: {:?}, reason: {:?}", key, reason);
+        } else {
+            info!("remove_watcher: {:?}, reason: {:?}", key, reason);
+        }
 
-        self.watcher_range_map.remove_by_key(key);
+        // The events not yet sent are dropped along with the stream.
+        self.pending_batches.remove(&key.key);
+
+        if let Some(range_key) = self.watcher_groups.remove(&key.key) {
+            // A watcher is closed only once, even if it's removed again for another reason.
+            server_metrics::incr_watchers(-1);
+            server_metrics::incr_watcher_closed(reason.name());
+
+            if let Some(group) = self.watcher_range_map.get_mut(&range_key) {
+                group.streams.remove(&key.key);
+
+                if group.streams.is_empty() {
+                    let filter = group.filter_type as i32;
+                    let group_key = (
+                        range_key.range.start.clone(),
+                        range_key.range.end.clone(),
+                        filter,
+                    );
+                    self.group_ids.remove(&group_key);
+                    self.watcher_range_map.remove_by_key(&range_key);
+                }
+            }
+        }
 
-        // TODO: decrease it only when the key is actually removed
-        server_metrics::incr_watchers(-1);
+        if let Some(client) = self.watcher_clients.remove(&key.key) {
+            if let Some(cnt) = self.client_watcher_count.get_mut(&client) {
+                *cnt -= 1;
+                if *cnt == 0 {
+                    self.client_watcher_count.remove(&client);
+                }
+            }
+        }
+    }
+
+    /// Remove all watchers when the dispatcher quits.
+    fn shutdown_watchers(&mut self) {
+        let keys = self
+            .watchers()
+            .map(|w| RangeMapKey::new(w.key_range.clone(), w.id))
+            .collect::<Vec<_>>();
+
+        for key in keys {
+            self.remove_watcher(&key, CloseWatcherReason::Shutdown);
+        }
+    }
+
+    fn check_watcher_limits(&self, client: &str) -> Result<(), &'static str> {
+        let total = self.watcher_clients.len() as u64;
+        if self.limits.max_total > 0 && total >= self.limits.max_total {
+            warn!(
+                "reject watcher from client {}: too many watchers: {}",
+                client, total
+            );
+            return Err("too many watchers on this node");
+        }
+
+        // Watchers of unknown clients are only limited by the total count.
+        if client.is_empty() || self.limits.max_per_client == 0 {
+            return Ok(());
+        }
+
+        let cnt = self.client_watcher_count.get(client).copied().unwrap_or(0);
+        if cnt >= self.limits.max_per_client {
+            warn!(
+                "reject watcher from client {}: too many watchers: {}",
+                client, cnt
+            );
+            return Err("too many watchers of this client");
+        }
+        Ok(())
     }
 
     fn build_key_range(
@@ -239,8 +471,15 @@ impl EventDispatcher {
         }
     }
 
-    pub fn watchers(&self) -> impl Iterator<Item = &RangeMapKey<String, WatcherId>> {
-        self.watcher_range_map.keys()
+    pub fn watchers(&self) -> impl Iterator<Item = &Watcher> {
+        self.watcher_range_map
+            .values()
+            .flat_map(|group| group.streams.values().map(|stream| &stream.watcher))
+    }
+
+    /// Returns the number of distinct `(key range, filter type)` groups of watchers.
+    pub fn watch_groups(&self) -> usize {
+        self.group_ids.len()
     }
 }
 
@@ -16,11 +16,13 @@ use std::ops::Range;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
+use std::time::Duration;
 
 use databend_common_base::base::tokio::sync::mpsc::error::SendError;
 use databend_common_base::base::tokio::sync::mpsc::Receiver;
 use databend_common_base::rangemap::RangeMapKey;
 use databend_common_meta_types::protobuf::watch_request::FilterType;
+use databend_common_meta_types::protobuf::WatchRequest;
 use databend_common_meta_types::protobuf::WatchResponse;
 use futures::Stream;
 use tonic::Status;
@@ -39,6 +41,9 @@ pub struct Watcher {
 
     /// The range of key this watcher is interested in.
     pub key_range: Range<String>,
+
+    /// How to coalesce events into one response, None if every event is sent on its own.
+    pub batch: Option<WatchBatch>,
 }
 
 impl Watcher {
@@ -47,8 +52,85 @@ impl Watcher {
             id,
             filter_type,
             key_range,
+            batch: None,
+        }
+    }
+
+    pub fn with_batch(mut self, batch: Option<WatchBatch>) -> Self {
+        self.batch = batch;
+        self
+    }
+}
+
+/// Limits of a batch of events sent in one response.
+///
+/// A batch is sent when it has `max_size` events or `max_delay` after its first event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatchBatch {
+    pub max_size: usize,
+    pub max_delay: Duration,
+}
+
+impl WatchBatch {
+    /// The delay if the request specifies only the size of a batch.
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_millis(10);
+
+    /// The upper bound of the batch size a client can request.
+    pub const MAX_SIZE_LIMIT: usize = 1024;
+
+    /// The upper bound of the batch delay a client can request.
+    pub const MAX_DELAY_LIMIT: Duration = Duration::from_secs(5);
+
+    /// Build the batch limits of a watch request, None if it does not batch events.
+    ///
+    /// The limits are supplied by the client, they are clamped to `MAX_SIZE_LIMIT` and
+    /// `MAX_DELAY_LIMIT`, so that a client can not hold the events of the dispatcher forever.
+    pub fn from_request(req: &WatchRequest) -> Option<Self> {
+        let max_size = req.max_batch_size.unwrap_or(1);
+        if max_size <= 1 {
+            return None;
+        }
+
+        let max_delay = req
+            .max_batch_delay_ms
+            .map(Duration::from_millis)
+            .unwrap_or(Self::DEFAULT_MAX_DELAY)
+            .min(Self::MAX_DELAY_LIMIT);
+
+        Some(Self {
+            max_size: max_size.min(Self::MAX_SIZE_LIMIT as u64) as usize,
+            max_delay,
+        })
+    }
+}
+
+/// The reason a watcher is removed from the dispatcher.
+#[derive(Debug)]
+pub enum CloseWatcherReason {
+    /// Failed to send an event to the stream because the receiving half is closed.
+    SendError(SendError<Result<WatchResponse, Status>>),
+
+    /// The client dropped the stream.
+    StreamDropped,
+
+    /// The dispatcher is shutting down because all event sources are closed.
+    Shutdown,
+}
+
+impl CloseWatcherReason {
+    /// A short name of the reason, used as the label of metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CloseWatcherReason::SendError(_) => "send_error",
+            CloseWatcherReason::StreamDropped => "stream_dropped",
+            CloseWatcherReason::Shutdown => "shutdown",
         }
     }
+
+    /// Whether the watcher is closed because of an error, rather than a normal close.
+    pub fn is_error(&self) -> bool {
+        matches!(self, CloseWatcherReason::SendError(_))
+    }
 }
 
 /// A handle of a watching stream, for feeding messages to the stream.
@@ -85,7 +167,7 @@ impl<T> Drop for WatchStream<T> {
 
         self.dispatcher.request(move |d| {
             let key = RangeMapKey::new(rng, id);
-            d.remove_watcher(&key)
+            d.remove_watcher(&key, CloseWatcherReason::StreamDropped)
         })
     }
 }
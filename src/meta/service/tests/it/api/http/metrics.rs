@@ -41,6 +41,7 @@ async fn test_metrics() -> anyhow::Result<()> {
 
     // record some metrics to make the registry get initialized
     server_metrics::incr_leader_change();
+    server_metrics::incr_watcher_closed("stream_dropped");
     network_metrics::incr_recv_bytes(1);
     raft_metrics::network::incr_recvfrom_bytes("addr".to_string(), 1);
     raft_metrics::storage::incr_raft_storage_fail("fun", true);
@@ -133,6 +134,7 @@ async fn test_metrics() -> anyhow::Result<()> {
     assert!(metric_keys.contains("metasrv_server_proposals_applied"));
     assert!(metric_keys.contains("metasrv_server_current_leader_id"));
     assert!(metric_keys.contains("metasrv_server_current_term"));
+    assert!(metric_keys.contains("metasrv_server_watchers_closed_total"));
 
     Ok(())
 }
@@ -39,10 +39,14 @@ use databend_common_meta_types::TxnCondition;
 use databend_common_meta_types::TxnDeleteByPrefixRequest;
 use databend_common_meta_types::TxnOp;
 use databend_meta::meta_service::MetaNode;
+use databend_meta::watcher::WatchBatch;
 use log::info;
 use test_harness::test;
 
 use crate::testing::meta_service_test_harness;
+use crate::testing::meta_service_test_harness_sync;
+use crate::tests::service::MetaSrvTestContext;
+use crate::tests::start_metasrv_with_context;
 
 async fn test_watch_main(
     addr: String,
@@ -64,15 +68,15 @@ async fn test_watch_main(
 
     loop {
         if let Ok(Some(resp)) = watch_stream.message().await {
-            if let Some(event) = resp.event {
+            for event in resp.into_events() {
                 assert!(!watch_events.is_empty());
 
                 assert_eq!(watch_events.first(), Some(&event));
                 watch_events.remove(0);
+            }
 
-                if watch_events.is_empty() {
-                    break;
-                }
+            if watch_events.is_empty() {
+                break;
             }
         }
     }
@@ -98,15 +102,15 @@ async fn test_watch_txn_main(
 
     loop {
         if let Ok(Some(resp)) = watch_stream.message().await {
-            if let Some(event) = resp.event {
+            for event in resp.into_events() {
                 assert!(!watch_events.is_empty());
 
                 assert_eq!(watch_events.first(), Some(&event));
                 watch_events.remove(0);
+            }
 
-                if watch_events.is_empty() {
-                    break;
-                }
+            if watch_events.is_empty() {
+                break;
             }
         }
     }
@@ -131,6 +135,8 @@ async fn test_watch() -> anyhow::Result<()> {
             key: "a".to_string(),
             key_end: Some("z".to_string()),
             filter_type: FilterType::All.into(),
+            max_batch_size: None,
+            max_batch_delay_ms: None,
         };
 
         let key_a = s("a");
@@ -189,6 +195,8 @@ async fn test_watch() -> anyhow::Result<()> {
             key_end: None,
             // filter only delete events
             filter_type: FilterType::Delete.into(),
+            max_batch_size: None,
+            max_batch_delay_ms: None,
         };
 
         let key = s(key_str);
@@ -252,6 +260,8 @@ async fn test_watch() -> anyhow::Result<()> {
             key: start,
             key_end: Some(end),
             filter_type: FilterType::All.into(),
+            max_batch_size: None,
+            max_batch_delay_ms: None,
         };
 
         let conditions = vec![TxnCondition {
@@ -363,6 +373,8 @@ async fn test_watch_expired_events() -> anyhow::Result<()> {
             key: start,
             key_end: Some(end),
             filter_type: FilterType::All.into(),
+            max_batch_size: None,
+            max_batch_delay_ms: None,
         };
         watch_client.request(watch).await?
     };
@@ -448,6 +460,8 @@ async fn test_watch_stream_count() -> anyhow::Result<()> {
         key: "a".to_string(),
         key_end: Some("z".to_string()),
         filter_type: FilterType::All.into(),
+        max_batch_size: None,
+        max_batch_delay_ms: None,
     };
 
     let client1 = make_client(&addr)?;
@@ -505,6 +519,178 @@ async fn test_watch_stream_count() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test(harness = meta_service_test_harness)]
+#[fastrace::trace]
+async fn test_watch_stream_limits() -> anyhow::Result<()> {
+    // Watchers beyond the per-client or total limit are rejected.
+
+    let mut tc = MetaSrvTestContext::new(0);
+    tc.config.raft_config.watcher_max_per_client = 2;
+    tc.config.raft_config.watcher_max_total = 3;
+    start_metasrv_with_context(&mut tc).await?;
+    let addr = tc.config.grpc_api_address.clone();
+
+    let watch_req = || WatchRequest {
+        key: "a".to_string(),
+        key_end: Some("z".to_string()),
+        filter_type: FilterType::All.into(),
+        max_batch_size: None,
+        max_batch_delay_ms: None,
+    };
+
+    info!("client1 reaches the per-client limit");
+    let client1 = make_client(&addr)?;
+    let watch_stream1 = client1.request(watch_req()).await?;
+    let _watch_stream2 = client1.request(watch_req()).await?;
+    let res = client1.request(watch_req()).await;
+    assert!(
+        res.is_err(),
+        "the 3rd watcher of client1 should be rejected"
+    );
+
+    info!("client2 reaches the total limit");
+    let client2 = make_client(&addr)?;
+    let _watch_stream3 = client2.request(watch_req()).await?;
+    let res = client2.request(watch_req()).await;
+    assert!(
+        res.is_err(),
+        "the 4th watcher on the node should be rejected"
+    );
+
+    info!("a watcher is allowed after one is dropped");
+    drop(watch_stream1);
+    sleep(Duration::from_millis(2_000)).await;
+    let _watch_stream4 = client2.request(watch_req()).await?;
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[fastrace::trace]
+async fn test_watch_identical_ranges() -> anyhow::Result<()> {
+    // Identical watches share one group but every stream receives the event once.
+
+    let (tc, addr) = crate::tests::start_metasrv().await?;
+
+    let watch_req = || WatchRequest {
+        key: "a".to_string(),
+        key_end: Some("z".to_string()),
+        filter_type: FilterType::All.into(),
+        max_batch_size: None,
+        max_batch_delay_ms: None,
+    };
+
+    let client = make_client(&addr)?;
+    let mut watch_stream1 = client.request(watch_req()).await?;
+    let mut watch_stream2 = client.request(watch_req()).await?;
+
+    let mn: Arc<MetaNode> = tc.grpc_srv.as_ref().map(|x| x.get_meta_node()).unwrap();
+
+    let counts = Arc::new(std::sync::Mutex::new((0usize, 0usize)));
+    {
+        let counts = counts.clone();
+        mn.dispatcher_handle
+            .request_blocking(move |d| {
+                *counts.lock().unwrap() = (d.watchers().count(), d.watch_groups());
+            })
+            .await;
+    }
+    assert_eq!((2, 1), *counts.lock().unwrap());
+
+    client.upsert_kv(UpsertKVReq::update("b", b"b")).await?;
+
+    for watch_stream in [&mut watch_stream1, &mut watch_stream2] {
+        let resp = watch_stream.message().await?.unwrap();
+        let event = resp.event.unwrap();
+        assert_eq!("b", event.key);
+        assert_eq!(b"b".to_vec(), event.current.unwrap().data);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness)]
+#[fastrace::trace]
+async fn test_watch_batch() -> anyhow::Result<()> {
+    // - A batch is sent when it is full.
+    // - A batch that is not full is sent after the max delay.
+
+    let (_tc, addr) = crate::tests::start_metasrv().await?;
+
+    let watch = WatchRequest {
+        key: "a".to_string(),
+        key_end: Some("z".to_string()),
+        filter_type: FilterType::All.into(),
+        max_batch_size: Some(3),
+        max_batch_delay_ms: Some(500),
+    };
+
+    let client = make_client(&addr)?;
+    let mut watch_stream = client.request(watch).await?;
+
+    info!("a full batch");
+    {
+        for key in ["b", "c", "d"] {
+            client.upsert_kv(UpsertKVReq::update(key, b"x")).await?;
+        }
+
+        let resp = watch_stream.message().await?.unwrap();
+        assert!(resp.event.is_none());
+
+        let keys = resp
+            .into_events()
+            .into_iter()
+            .map(|e| e.key)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![s("b"), s("c"), s("d")], keys);
+    }
+
+    info!("a batch sent after the max delay");
+    {
+        client.upsert_kv(UpsertKVReq::update("e", b"x")).await?;
+
+        let resp = watch_stream.message().await?.unwrap();
+        let events = resp.into_events();
+        assert_eq!(1, events.len());
+        assert_eq!("e", events[0].key);
+    }
+
+    Ok(())
+}
+
+#[test(harness = meta_service_test_harness_sync)]
+fn test_watch_batch_limits() -> anyhow::Result<()> {
+    let watch = |max_batch_size, max_batch_delay_ms| WatchRequest {
+        key: "a".to_string(),
+        key_end: Some("z".to_string()),
+        filter_type: FilterType::All.into(),
+        max_batch_size,
+        max_batch_delay_ms,
+    };
+
+    assert_eq!(None, WatchBatch::from_request(&watch(None, Some(500))));
+    assert_eq!(None, WatchBatch::from_request(&watch(Some(1), Some(500))));
+
+    assert_eq!(
+        Some(WatchBatch {
+            max_size: 3,
+            max_delay: WatchBatch::DEFAULT_MAX_DELAY,
+        }),
+        WatchBatch::from_request(&watch(Some(3), None))
+    );
+
+    // The limits supplied by the client are clamped.
+    assert_eq!(
+        Some(WatchBatch {
+            max_size: WatchBatch::MAX_SIZE_LIMIT,
+            max_delay: WatchBatch::MAX_DELAY_LIMIT,
+        }),
+        WatchBatch::from_request(&watch(Some(u64::MAX), Some(u64::MAX)))
+    );
+
+    Ok(())
+}
+
 fn s(x: &str) -> String {
     x.to_string()
 }
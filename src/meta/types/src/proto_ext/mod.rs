@@ -20,3 +20,4 @@ mod snapshot_chunk_request_ext;
 mod stream_item_ext;
 mod transfer_leader_request_ext;
 mod txn_ext;
+mod watch_response_ext;
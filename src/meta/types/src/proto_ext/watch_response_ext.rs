@@ -0,0 +1,37 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::protobuf::Event;
+use crate::protobuf::WatchResponse;
+
+impl WatchResponse {
+    pub fn new(event: Event) -> Self {
+        WatchResponse {
+            event: Some(event),
+            events: vec![],
+        }
+    }
+
+    /// Build a response carrying a batch of events.
+    pub fn new_batch(events: Vec<Event>) -> Self {
+        WatchResponse {
+            event: None,
+            events,
+        }
+    }
+
+    /// Returns all events in this response, whether it is a single event or a batch.
+    pub fn into_events(self) -> Vec<Event> {
+        self.event.into_iter().chain(self.events).collect()
+    }
+}
@@ -35,12 +35,18 @@ use crate::ast::With;
 use crate::ast::CTE;
 
 pub(crate) fn pretty_query(query: Query) -> RcDoc<'static> {
-    pretty_with(query.with)
+    let doc = pretty_with(query.with)
         .append(pretty_body(query.body))
-        .append(pretty_order_by(query.order_by))
-        .append(pretty_limit(query.limit))
-        .append(pretty_offset(query.offset))
-        .group()
+        .append(pretty_order_by(query.order_by));
+    if query.with_ties {
+        doc.append(pretty_offset(query.offset))
+            .append(pretty_fetch_with_ties(query.limit))
+            .group()
+    } else {
+        doc.append(pretty_limit(query.limit))
+            .append(pretty_offset(query.offset))
+            .group()
+    }
 }
 
 fn pretty_with(with: Option<With>) -> RcDoc<'static> {
@@ -513,6 +519,14 @@ fn pretty_limit(limit: Vec<Expr>) -> RcDoc<'static> {
     }
 }
 
+fn pretty_fetch_with_ties(limit: Vec<Expr>) -> RcDoc<'static> {
+    RcDoc::line()
+        .append(RcDoc::text("FETCH FIRST").append(RcDoc::space().nest(NEST_FACTOR)))
+        .append(interweave_comma(limit.into_iter().map(pretty_expr)))
+        .append(RcDoc::space())
+        .append(RcDoc::text("ROWS WITH TIES"))
+}
+
 fn pretty_offset(offset: Option<Expr>) -> RcDoc<'static> {
     if let Some(offset) = offset {
         RcDoc::line()
@@ -45,6 +45,9 @@ pub struct Query {
     pub limit: Vec<Expr>,
     // `OFFSET` expr
     pub offset: Option<Expr>,
+    // `FETCH FIRST n ROWS WITH TIES`, the rows tying with the last one
+    // on the `ORDER BY` keys are also returned
+    pub with_ties: bool,
 
     // If ignore the result (not output).
     pub ignore_result: bool,
@@ -66,15 +69,25 @@ impl Display for Query {
             write_comma_separated_list(f, &self.order_by)?;
         }
 
-        // LIMIT clause
-        if !self.limit.is_empty() {
-            write!(f, " LIMIT ")?;
+        if self.with_ties {
+            // OFFSET clause precedes the FETCH clause
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {offset}")?;
+            }
+            write!(f, " FETCH FIRST ")?;
             write_comma_separated_list(f, &self.limit)?;
-        }
+            write!(f, " ROWS WITH TIES")?;
+        } else {
+            // LIMIT clause
+            if !self.limit.is_empty() {
+                write!(f, " LIMIT ")?;
+                write_comma_separated_list(f, &self.limit)?;
+            }
 
-        // TODO: We should validate if offset exists, limit should be empty or just one element
-        if let Some(offset) = &self.offset {
-            write!(f, " OFFSET {offset}")?;
+            // TODO: We should validate if offset exists, limit should be empty or just one element
+            if let Some(offset) = &self.offset {
+                write!(f, " OFFSET {offset}")?;
+            }
         }
 
         if self.ignore_result {
@@ -964,6 +977,7 @@ impl SetExpr {
                 order_by: vec![],
                 limit: vec![],
                 offset: None,
+                with_ties: false,
                 ignore_result: false,
             },
         }
@@ -946,10 +946,28 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
 
     let count_all_with_window = map(
         rule! {
-            COUNT ~ "(" ~ "*" ~ ")" ~ ( OVER ~ #window_spec_ident )?
+            COUNT ~ "(" ~ "*" ~ ")"
+            ~ ( FILTER ~ ^"(" ~ ^WHERE ~ ^#subexpr(0) ~ ^")" )?
+            ~ ( OVER ~ #window_spec_ident )?
         },
-        |(_, _, _, _, window)| ExprElement::CountAll {
-            window: window.map(|w| w.1),
+        |(count, _, _, _, filter, window)| match filter {
+            // `COUNT(*) FILTER (WHERE cond)` is the same as `count_if(cond)`.
+            Some((_, _, _, cond, _)) => ExprElement::FunctionCall {
+                func: FunctionCall {
+                    distinct: false,
+                    name: Identifier::from_name(transform_span(&[count]), "count_if"),
+                    args: vec![cond],
+                    params: vec![],
+                    window: window.map(|(_, window)| WindowDesc {
+                        ignore_nulls: None,
+                        window,
+                    }),
+                    lambda: None,
+                },
+            },
+            None => ExprElement::CountAll {
+                window: window.map(|w| w.1),
+            },
         },
     );
 
@@ -1037,18 +1055,62 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             #function_name
             ~ ("(" ~ #comma_separated_list1(subexpr(0)) ~ ")")?
             ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ #window_function?
         },
-        |(name, params, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, params, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             func: FunctionCall {
                 distinct: opt_distinct.is_some(),
                 name,
                 args: opt_args.unwrap_or_default(),
                 params: params.map(|(_, x, _)| x).unwrap_or_default(),
-                window: None,
+                window,
+                lambda: None,
+            },
+        },
+    );
+    // The ordered-set aggregate `percentile_cont(0.9) WITHIN GROUP (ORDER BY x)` is the
+    // same as `percentile_cont(0.9)(x)`.
+    let function_call_within_group = map(
+        rule! {
+            #function_name
+            ~ "(" ~ #comma_separated_list1(subexpr(0)) ~ ")"
+            ~ WITHIN ~ GROUP ~ "(" ~ ORDER ~ ^BY ~ ^#subexpr(0) ~ ASC? ~ ^")"
+            ~ #window_function?
+        },
+        |(name, _, params, _, _, _, _, _, _, arg, _, _, window)| ExprElement::FunctionCall {
+            func: FunctionCall {
+                distinct: false,
+                name,
+                args: vec![arg],
+                params,
+                window,
                 lambda: None,
             },
         },
     );
+    // The aggregate `sum(x) FILTER (WHERE cond)` is the same as `sum_if(x, cond)`.
+    let function_call_with_filter = map(
+        rule! {
+            #function_name
+            ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ FILTER ~ ^"(" ~ ^WHERE ~ ^#subexpr(0) ~ ^")"
+            ~ #window_function?
+        },
+        |(name, _, opt_distinct, opt_args, _, _, _, _, cond, _, window)| {
+            let mut args = opt_args.unwrap_or_default();
+            args.push(cond);
+            ExprElement::FunctionCall {
+                func: FunctionCall {
+                    distinct: opt_distinct.is_some(),
+                    name: Identifier::from_name(name.span, format!("{}_if", name.name)),
+                    args,
+                    params: vec![],
+                    window,
+                    lambda: None,
+                },
+            }
+        },
+    );
 
     let case = map(
         rule! {
@@ -1186,6 +1248,22 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             date,
         },
     );
+    let date_add_interval = map(
+        rule! {
+            DATE_ADD ~ "(" ~ #subexpr(0) ~ "," ~ #subexpr(0) ~ ")"
+        },
+        |(name, _, date, _, interval, _)| {
+            date_add_interval_element(name, "date_add", date, interval, false)
+        },
+    );
+    let date_sub_interval = map(
+        rule! {
+            DATE_SUB ~ "(" ~ #subexpr(0) ~ "," ~ #subexpr(0) ~ ")"
+        },
+        |(name, _, date, _, interval, _)| {
+            date_add_interval_element(name, "date_sub", date, interval, true)
+        },
+    );
     let interval = map(
         rule! {
             INTERVAL ~ #subexpr(0) ~ #interval_kind
@@ -1282,6 +1360,8 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
                 | #list_comprehensions: "[expr for x in ... [if ...]]"
                 | #count_all_with_window : "`COUNT(*) OVER ...`"
                 | #function_call_with_lambda : "`function(..., x -> ...)`"
+                | #function_call_within_group : "`function(...) WITHIN GROUP (ORDER BY <expr>)`"
+                | #function_call_with_filter : "`function(...) FILTER (WHERE <expr>)`"
                 | #function_call_with_window : "`function(...) OVER ([ PARTITION BY <expr>, ... ] [ ORDER BY <expr>, ... ] [ <window frame> ])`"
                 | #function_call_with_params : "`function(...)(...)`"
                 | #function_call : "`function(...)`"
@@ -1293,14 +1373,51 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
                 | #map_access : "[<key>] | .<key> | :<key>"
                 | #literal : "<literal>"
                 | #current_timestamp: "CURRENT_TIMESTAMP"
-                | #array : "`[<expr>, ...]`"
+            ),
+            rule!(
+                #array : "`[<expr>, ...]`"
                 | #map_expr : "`{ <literal> : <expr>, ... }`"
+                | #date_add_interval: "`DATE_ADD(..., (INTERVAL ... <unit> | <interval_string>))`"
+                | #date_sub_interval: "`DATE_SUB(..., (INTERVAL ... <unit> | <interval_string>))`"
             ),
         ))),
         |(span, elem)| WithSpan { span, elem },
     )(i)
 }
 
+/// `DATE_ADD(<date>, INTERVAL <expr> <unit>)` is the same as `DATE_ADD(<unit>, <expr>, <date>)`,
+/// otherwise the interval is a string such as `'1 year 2 months'` parsed by the function.
+fn date_add_interval_element(
+    name: Token,
+    func_name: &str,
+    date: Expr,
+    interval: Expr,
+    is_sub: bool,
+) -> ExprElement {
+    match interval {
+        Expr::Interval { expr, unit, .. } if is_sub => ExprElement::DateSub {
+            unit,
+            interval: *expr,
+            date,
+        },
+        Expr::Interval { expr, unit, .. } => ExprElement::DateAdd {
+            unit,
+            interval: *expr,
+            date,
+        },
+        interval => ExprElement::FunctionCall {
+            func: FunctionCall {
+                distinct: false,
+                name: Identifier::from_name(transform_span(&[name]), func_name),
+                args: vec![date, interval],
+                params: vec![],
+                window: None,
+                lambda: None,
+            },
+        },
+    }
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     alt((
@@ -77,6 +77,10 @@ pub enum SetOperationElement {
     Offset {
         offset: Expr,
     },
+    Fetch {
+        limit: Expr,
+        with_ties: bool,
+    },
     IgnoreResult,
     Group(SetExpr),
 }
@@ -173,6 +177,16 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
         },
         |(_, offset)| SetOperationElement::Offset { offset },
     );
+    let with_ties = alt((
+        value(false, rule! { ONLY }),
+        value(true, rule! { WITH ~ ^TIES }),
+    ));
+    let fetch = map(
+        rule! {
+            FETCH ~ ^FIRST ~ ^#expr ~ ^(ROW | ROWS) ~ ^#with_ties
+        },
+        |(_, _, limit, _, with_ties)| SetOperationElement::Fetch { limit, with_ties },
+    );
     let ignore_result = map(
         rule! {
             IGNORE_RESULT
@@ -196,6 +210,7 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
             | #order_by
             | #limit
             | #offset
+            | #fetch
             | #ignore_result
         }),
         |(span, elem)| WithSpan { span, elem },
@@ -223,6 +238,7 @@ impl<'a, I: Iterator<Item = WithSpan<'a, SetOperationElement>>> PrattParser<I>
             SetOperationElement::OrderBy { .. } => Affix::Postfix(Precedence(5)),
             SetOperationElement::Limit { .. } => Affix::Postfix(Precedence(5)),
             SetOperationElement::Offset { .. } => Affix::Postfix(Precedence(5)),
+            SetOperationElement::Fetch { .. } => Affix::Postfix(Precedence(5)),
             SetOperationElement::IgnoreResult => Affix::Postfix(Precedence(5)),
             _ => Affix::Nilfix,
         };
@@ -336,6 +352,13 @@ impl<'a, I: Iterator<Item = WithSpan<'a, SetOperationElement>>> PrattParser<I>
                 }
                 query.offset = Some(offset);
             }
+            SetOperationElement::Fetch { limit, with_ties } => {
+                if !query.limit.is_empty() {
+                    return Err("LIMIT and FETCH should not appear together");
+                }
+                query.limit = vec![limit];
+                query.with_ties = with_ties;
+            }
             SetOperationElement::IgnoreResult => {
                 query.ignore_result = true;
             }
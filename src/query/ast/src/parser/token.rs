@@ -582,6 +582,8 @@ pub enum TokenKind {
     ELSEIF,
     #[token("FALSE", ignore(ascii_case))]
     FALSE,
+    #[token("FETCH", ignore(ascii_case))]
+    FETCH,
     #[token("FIELDS", ignore(ascii_case))]
     FIELDS,
     #[token("FIELD_DELIMITER", ignore(ascii_case))]
@@ -598,6 +600,8 @@ pub enum TokenKind {
     FILE,
     #[token("FILES", ignore(ascii_case))]
     FILES,
+    #[token("FILTER", ignore(ascii_case))]
+    FILTER,
     #[token("FINAL", ignore(ascii_case))]
     FINAL,
     #[token("FLASHBACK", ignore(ascii_case))]
@@ -823,6 +827,8 @@ pub enum TokenKind {
     OFFSET,
     #[token("ON", ignore(ascii_case))]
     ON,
+    #[token("ONLY", ignore(ascii_case))]
+    ONLY,
     #[token("ON_CREATE", ignore(ascii_case))]
     ON_CREATE,
     #[token("ON_SCHEDULE", ignore(ascii_case))]
@@ -1107,6 +1113,8 @@ pub enum TokenKind {
     TENANT,
     #[token("THEN", ignore(ascii_case))]
     THEN,
+    #[token("TIES", ignore(ascii_case))]
+    TIES,
     #[token("TIMESTAMP", ignore(ascii_case))]
     TIMESTAMP,
     #[token("TIMEZONE_HOUR", ignore(ascii_case))]
@@ -1201,6 +1209,8 @@ pub enum TokenKind {
     WINDOW,
     #[token("WITH", ignore(ascii_case))]
     WITH,
+    #[token("WITHIN", ignore(ascii_case))]
+    WITHIN,
     #[token("XML", ignore(ascii_case))]
     XML,
     #[token("XOR", ignore(ascii_case))]
@@ -1652,7 +1662,7 @@ impl TokenKind {
             | TokenKind::CREATE
             | TokenKind::ATTACH
             | TokenKind::EXCEPT
-            // | TokenKind::FETCH
+            | TokenKind::FETCH
             | TokenKind::FROM
             | TokenKind::GRANT
             | TokenKind::GROUP
@@ -1046,6 +1046,7 @@ fn test_query() {
         r#"select * from t left join lateral(select 1) on true, lateral(select 2)"#,
         r#"select * from t, lateral flatten(input => u.col) f"#,
         r#"select * from flatten(input => parse_json('{"a":1, "b":[77,88]}'), outer => true)"#,
+        r#"VALUES(1,'a'),(2,'b'),(null,'c') order by col0 fetch first 2 rows with ties"#,
     ];
 
     for case in cases {
@@ -67,6 +67,32 @@ pub trait AggregateFunction: fmt::Display + Sync + Send {
         Ok(())
     }
 
+    // used by the `_if` combinator, the rows whose validity is unset are skipped
+    fn accumulate_keys_with_validity(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: InputColumns,
+        validity: &Bitmap,
+        input_rows: usize,
+    ) -> Result<()> {
+        if validity.unset_bits() == 0 {
+            return self.accumulate_keys(places, offset, columns, input_rows);
+        }
+        let columns = columns
+            .iter()
+            .map(|c| c.filter(validity))
+            .collect::<Vec<_>>();
+        let places = places
+            .iter()
+            .zip(validity.iter())
+            .filter(|(_, is_valid)| *is_valid)
+            .map(|(place, _)| *place)
+            .collect::<Vec<_>>();
+        let rows = validity.len() - validity.unset_bits();
+        self.accumulate_keys(&places, offset, (&columns).into(), rows)
+    }
+
     // Used in aggregate_null_adaptor
     fn accumulate_row(&self, _place: StateAddr, _columns: InputColumns, _row: usize) -> Result<()>;
 
@@ -24,9 +24,10 @@ use super::partitioned_payload::PartitionedPayload;
 use super::payload_flush::PayloadFlushState;
 use super::probe_state::ProbeState;
 use crate::aggregate::payload_row::row_match_columns;
-use crate::group_hash_columns;
+use crate::group_hash_columns_with;
 use crate::new_sel;
 use crate::read;
+use crate::seed_group_hashes;
 use crate::types::DataType;
 use crate::AggregateFunctionRef;
 use crate::Column;
@@ -36,14 +37,21 @@ use crate::InputColumns;
 use crate::Payload;
 use crate::StateAddr;
 use crate::BATCH_SIZE;
-use crate::LOAD_FACTOR;
+use crate::INITIAL_CAPACITY;
 use crate::MAX_PAGE_SIZE;
 
 const BATCH_ADD_SIZE: usize = 2048;
 
-// The high 16 bits are the salt, the low 48 bits are the pointer address
+// The high 16 bits are the salt, the low 48 bits are the pointer address. The rows are
+// addressed by the pointers instead of the page numbers and offsets, so neither the number
+// of pages nor the rows of a page are limited, only the addresses must fit into 48 bits,
+// which is checked when a page is allocated, see `Payload::writable_page`.
 pub type Entry = u64;
 
+// The entry of a removed group, no row is at address 1, and the entry being inserted
+// has all the bits of the pointer set, so it never collides with the others.
+const TOMBSTONE: Entry = 1;
+
 pub struct AggregateHashTable {
     pub payload: PartitionedPayload,
     // use for append rows directly during deserialize
@@ -65,7 +73,7 @@ impl AggregateHashTable {
         config: HashTableConfig,
         arena: Arc<Bump>,
     ) -> Self {
-        let capacity = Self::initial_capacity();
+        let capacity = config.initial_capacity;
         Self::new_with_capacity(group_types, aggrs, config, capacity, arena)
     }
 
@@ -176,16 +184,21 @@ impl AggregateHashTable {
         row_count: usize,
     ) -> Result<usize> {
         state.row_count = row_count;
-        group_hash_columns(group_columns, &mut state.group_hashes);
+        group_hash_columns_with(
+            group_columns,
+            self.config.hash_function,
+            &mut state.group_hashes,
+        );
+        seed_group_hashes(self.config.hash_seed, &mut state.group_hashes[0..row_count]);
 
         let new_group_count = if self.direct_append {
             for idx in 0..row_count {
                 state.empty_vector[idx] = idx;
             }
-            self.payload.append_rows(state, row_count, group_columns);
+            self.payload.append_rows(state, row_count, group_columns)?;
             row_count
         } else {
-            self.probe_and_create(state, group_columns, row_count)
+            self.probe_and_create(state, group_columns, row_count)?
         };
 
         if !self.payload.aggrs.is_empty() {
@@ -232,7 +245,7 @@ impl AggregateHashTable {
             }
 
             // check maybe_repartition
-            if self.maybe_repartition() {
+            if self.maybe_repartition()? {
                 self.clear_ht();
                 self.reset_count();
             }
@@ -246,7 +259,7 @@ impl AggregateHashTable {
         state: &mut ProbeState,
         group_columns: InputColumns,
         row_count: usize,
-    ) -> usize {
+    ) -> Result<usize> {
         // exceed capacity or should resize
         if row_count + self.count > self.resize_threshold() {
             self.resize(self.capacity * 2);
@@ -259,6 +272,9 @@ impl AggregateHashTable {
 
         let mut group_hashes = new_sel();
         let mut hash_salts = [0_u64; BATCH_SIZE];
+        // The first tombstone on the probing path of each row, reused if the group is new
+        let mut tombstone_offsets = [usize::MAX; BATCH_SIZE];
+        let mut reused_tombstones = 0;
         let mask = self.capacity - 1;
         for i in 0..row_count {
             group_hashes[i] = state.group_hashes[i] as usize & mask;
@@ -280,8 +296,17 @@ impl AggregateHashTable {
                 let salt = hash_salts[index];
 
                 loop {
-                    let entry = &mut entries[*ht_offset];
-                    if entry.is_occupied() {
+                    let entry = &entries[*ht_offset];
+                    if entry.is_tombstone() {
+                        if tombstone_offsets[index] == usize::MAX {
+                            tombstone_offsets[index] = *ht_offset;
+                        }
+                        *ht_offset += 1;
+                        if *ht_offset >= self.capacity {
+                            *ht_offset = 0;
+                        }
+                        continue;
+                    } else if entry.is_occupied() {
                         if entry.get_salt() == salt {
                             state.group_compare_vector[need_compare_count] = index;
                             need_compare_count += 1;
@@ -294,7 +319,19 @@ impl AggregateHashTable {
                             continue;
                         }
                     } else {
-                        entry.set_salt(salt);
+                        let tombstone_offset =
+                            std::mem::replace(&mut tombstone_offsets[index], usize::MAX);
+                        if tombstone_offset != usize::MAX {
+                            *ht_offset = tombstone_offset;
+                            if !entries[tombstone_offset].is_tombstone() {
+                                // The tombstone is reused by another row, which may have
+                                // the same group, so probe again from there.
+                                continue;
+                            }
+                            reused_tombstones += 1;
+                        }
+
+                        entries[*ht_offset].set_salt(salt);
                         state.empty_vector[new_entry_count] = index;
                         new_entry_count += 1;
                         break;
@@ -307,7 +344,7 @@ impl AggregateHashTable {
                 new_group_count += new_entry_count;
 
                 self.payload
-                    .append_rows(state, new_entry_count, group_columns);
+                    .append_rows(state, new_entry_count, group_columns)?;
 
                 for i in 0..new_entry_count {
                     let index = state.empty_vector[i];
@@ -360,15 +397,238 @@ impl AggregateHashTable {
             remaining_entries = no_match_count;
         }
 
-        self.count += new_group_count;
+        // The reused tombstones were already counted
+        self.count += new_group_count - reused_tombstones;
+
+        Ok(new_group_count)
+    }
+
+    /// Removes the groups of the rows from the hash table, returns the number of the removed groups.
+    ///
+    /// The entries of the groups are replaced by tombstones, and the later rows of the same
+    /// groups create new groups. The rows of the removed groups are removed from the payload
+    /// with their states, the last rows of the payloads are moved into their places.
+    pub fn remove(
+        &mut self,
+        state: &mut ProbeState,
+        group_columns: InputColumns,
+        row_count: usize,
+    ) -> usize {
+        let mut removed_count = 0;
+        for start in (0..row_count).step_by(BATCH_ADD_SIZE) {
+            let end = (start + BATCH_ADD_SIZE).min(row_count);
+            let step_group_columns = group_columns
+                .iter()
+                .map(|c| c.slice(start..end))
+                .collect::<Vec<_>>();
+            removed_count += self.remove_inner(state, (&step_group_columns).into(), end - start);
+        }
+        removed_count
+    }
+
+    fn remove_inner(
+        &mut self,
+        state: &mut ProbeState,
+        group_columns: InputColumns,
+        row_count: usize,
+    ) -> usize {
+        if self.direct_append {
+            return 0;
+        }
+
+        state.row_count = row_count;
+        group_hash_columns_with(
+            group_columns,
+            self.config.hash_function,
+            &mut state.group_hashes,
+        );
+        seed_group_hashes(self.config.hash_seed, &mut state.group_hashes[0..row_count]);
+
+        let entries = &mut self.entries;
+
+        let mut group_hashes = new_sel();
+        let mut hash_salts = [0_u64; BATCH_SIZE];
+        let mut matched = [false; BATCH_SIZE];
+        let mask = self.capacity - 1;
+        for i in 0..row_count {
+            group_hashes[i] = state.group_hashes[i] as usize & mask;
+            hash_salts[i] = state.group_hashes[i].get_salt();
+            state.no_match_vector[i] = i;
+        }
+
+        let mut removed_count = 0;
+        let mut remaining_entries = row_count;
+        while remaining_entries > 0 {
+            let mut need_compare_count = 0;
+            let mut no_match_count = 0;
+
+            // 1. find the entries of the same salt, the groups not found are skipped
+            for i in 0..remaining_entries {
+                let index = state.no_match_vector[i];
+                let ht_offset = &mut group_hashes[index];
+                let salt = hash_salts[index];
+
+                loop {
+                    let entry = &entries[*ht_offset];
+                    if !entry.is_occupied() {
+                        break;
+                    }
+                    if !entry.is_tombstone() && entry.get_salt() == salt {
+                        state.group_compare_vector[need_compare_count] = index;
+                        state.empty_vector[need_compare_count] = index;
+                        state.addresses[index] = entry.get_pointer();
+                        need_compare_count += 1;
+                        break;
+                    }
+                    *ht_offset += 1;
+                    if *ht_offset >= self.capacity {
+                        *ht_offset = 0;
+                    }
+                }
+            }
+
+            if need_compare_count == 0 {
+                break;
+            }
+
+            // 2. compare
+            for i in 0..need_compare_count {
+                matched[state.empty_vector[i]] = true;
+            }
+            unsafe {
+                row_match_columns(
+                    group_columns,
+                    &state.addresses,
+                    &mut state.group_compare_vector,
+                    &mut state.temp_vector,
+                    need_compare_count,
+                    &self.payload.validity_offsets,
+                    &self.payload.group_offsets,
+                    &mut state.no_match_vector,
+                    &mut no_match_count,
+                );
+            }
+            for i in 0..no_match_count {
+                matched[state.no_match_vector[i]] = false;
+            }
+
+            // 3. replace the matched entries by tombstones, the duplicate rows match the same entry
+            for i in 0..need_compare_count {
+                let index = state.empty_vector[i];
+                let entry = &mut entries[group_hashes[index]];
+                if !matched[index] || entry.is_tombstone() {
+                    continue;
+                }
+                // The row may have been moved by the removal of another row of the batch, so
+                // the address is taken from the entry instead of `state.addresses`.
+                let address = entry.get_pointer();
+                *entry = TOMBSTONE;
+                removed_count += 1;
+
+                let hash = state.group_hashes[index];
+                if let Some(moved) = self.payload.swap_remove_row(hash, address) {
+                    let moved_hash =
+                        unsafe { read::<u64>(address.add(self.payload.hash_offset) as _) };
+                    let mut ht_offset = moved_hash as usize & mask;
+                    // The entries are cleared by the partial aggregation, then the moved row
+                    // isn't indexed.
+                    while entries[ht_offset].is_occupied() {
+                        let entry = &mut entries[ht_offset];
+                        if !entry.is_tombstone() && entry.get_pointer() == moved {
+                            entry.set_pointer(address);
+                            break;
+                        }
+                        ht_offset += 1;
+                        if ht_offset >= self.capacity {
+                            ht_offset = 0;
+                        }
+                    }
+                }
+            }
+
+            // 4. Linear probing
+            for i in 0..no_match_count {
+                let idx = state.no_match_vector[i];
+                let ht_offset = &mut group_hashes[idx];
+                *ht_offset += 1;
+                if *ht_offset >= self.capacity {
+                    *ht_offset = 0;
+                }
+            }
+            remaining_entries = no_match_count;
+        }
 
-        new_group_count
+        removed_count
     }
 
-    pub fn combine(&mut self, other: Self, flush_state: &mut PayloadFlushState) -> Result<()> {
+    /// Merges the groups of `other` into the table.
+    ///
+    /// The states of `other` are merged into the states of the table, and are still owned and
+    /// dropped by `other`. If the table is empty, it takes over the groups and the states of
+    /// `other` instead, then `other` only drops the empty payload of the table.
+    pub fn combine(&mut self, mut other: Self, flush_state: &mut PayloadFlushState) -> Result<()> {
+        if self.len() == 0 && self.direct_append == other.direct_append {
+            std::mem::swap(&mut self.payload, &mut other.payload);
+            std::mem::swap(&mut self.entries, &mut other.entries);
+            std::mem::swap(&mut self.count, &mut other.count);
+            std::mem::swap(&mut self.capacity, &mut other.capacity);
+            std::mem::swap(&mut self.current_radix_bits, &mut other.current_radix_bits);
+            return Ok(());
+        }
         self.combine_payloads(&other.payload, flush_state)
     }
 
+    /// Reduce the tables into the final one by combining them in pairs, the pairs of
+    /// each round are combined in parallel by at most `max_threads` threads, so it takes
+    /// `log2(n)` rounds to finish. Returns `None` if there are no tables.
+    pub fn combine_parallel(mut tables: Vec<Self>, max_threads: usize) -> Result<Option<Self>> {
+        while tables.len() > 1 {
+            let mut pairs = Vec::with_capacity(tables.len().div_ceil(2));
+            let mut iter = tables.into_iter();
+            while let Some(left) = iter.next() {
+                pairs.push((left, iter.next()));
+            }
+
+            // Each thread combines a run of the pairs one after another
+            let chunk_size = pairs.len().div_ceil(max_threads.max(1));
+            let mut chunks = Vec::with_capacity(max_threads.min(pairs.len()));
+            let mut pairs = pairs.into_iter().peekable();
+            while pairs.peek().is_some() {
+                chunks.push(pairs.by_ref().take(chunk_size).collect::<Vec<_>>());
+            }
+
+            tables = std::thread::scope(|s| -> Result<Vec<Self>> {
+                let handles = chunks
+                    .into_iter()
+                    .map(|chunk| {
+                        s.spawn(move || -> Result<Vec<Self>> {
+                            let mut flush_state = PayloadFlushState::default();
+                            chunk
+                                .into_iter()
+                                .map(|(mut left, right)| -> Result<Self> {
+                                    if let Some(right) = right {
+                                        left.combine(right, &mut flush_state)?;
+                                    }
+                                    Ok(left)
+                                })
+                                .collect()
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut tables = Vec::new();
+                for handle in handles {
+                    let combined = handle
+                        .join()
+                        .unwrap_or_else(|cause| std::panic::resume_unwind(cause))?;
+                    tables.extend(combined);
+                }
+                Ok(tables)
+            })?;
+        }
+        Ok(tables.pop())
+    }
+
     pub fn combine_payloads(
         &mut self,
         payloads: &PartitionedPayload,
@@ -394,7 +654,7 @@ impl AggregateHashTable {
                 &mut flush_state.probe_state,
                 (&flush_state.group_columns).into(),
                 row_count,
-            );
+            )?;
 
             // set state places
             if !self.payload.aggrs.is_empty() {
@@ -450,10 +710,10 @@ impl AggregateHashTable {
         Ok(false)
     }
 
-    fn maybe_repartition(&mut self) -> bool {
+    fn maybe_repartition(&mut self) -> Result<bool> {
         // already final stage or the max radix bits
         if !self.config.partial_agg || (self.current_radix_bits == self.config.max_radix_bits) {
-            return false;
+            return Ok(false);
         }
 
         let bytes_per_partition = self.payload.memory_size() / self.payload.partition_count();
@@ -496,15 +756,15 @@ impl AggregateHashTable {
             let mut state = PayloadFlushState::default();
 
             self.current_radix_bits = current_max_radix_bits;
-            self.payload = payload.repartition(1 << current_max_radix_bits, &mut state);
-            return true;
+            self.payload = payload.repartition(1 << current_max_radix_bits, &mut state)?;
+            return Ok(true);
         }
-        false
+        Ok(false)
     }
 
     #[inline]
     fn resize_threshold(&self) -> usize {
-        (self.capacity as f64 / LOAD_FACTOR) as usize
+        (self.capacity as f64 / self.config.load_factor) as usize
     }
 
     // scan payload to reconstruct PointArray
@@ -524,7 +784,8 @@ impl AggregateHashTable {
 
         let mut entries = vec![0; new_capacity];
 
-        // iterate over payloads and copy to new entries
+        // iterate over payloads and copy to new entries, the tombstones are dropped, as the rows
+        // of the removed groups are no longer in the payloads
         for payload in self.payload.payloads.iter() {
             for page in payload.pages.iter() {
                 for idx in 0..page.rows {
@@ -560,12 +821,17 @@ impl AggregateHashTable {
         self.capacity = new_capacity;
     }
 
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn initial_capacity() -> usize {
-        8192 * 4
+        INITIAL_CAPACITY
     }
 
-    pub fn get_capacity_for_count(count: usize) -> usize {
-        ((count.max(Self::initial_capacity()) as f64 * LOAD_FACTOR) as usize).next_power_of_two()
+    pub fn get_capacity_for_count(count: usize, config: &HashTableConfig) -> usize {
+        ((count.max(config.initial_capacity) as f64 * config.load_factor) as usize)
+            .next_power_of_two()
     }
 
     pub fn clear_ht(&mut self) {
@@ -591,12 +857,13 @@ impl AggregateHashTable {
 /// Upper 16 bits are salt
 const SALT_MASK: u64 = 0xFFFF000000000000;
 /// Lower 48 bits are the pointer
-const POINTER_MASK: u64 = 0x0000FFFFFFFFFFFF;
+pub(crate) const POINTER_MASK: u64 = 0x0000FFFFFFFFFFFF;
 
 pub(crate) trait EntryLike {
     fn get_salt(&self) -> u64;
     fn set_salt(&mut self, _salt: u64);
     fn is_occupied(&self) -> bool;
+    fn is_tombstone(&self) -> bool;
 
     fn get_pointer(&self) -> *const u8;
     fn set_pointer(&mut self, ptr: *const u8);
@@ -618,6 +885,11 @@ impl EntryLike for u64 {
         *self != 0
     }
 
+    #[inline]
+    fn is_tombstone(&self) -> bool {
+        *self == TOMBSTONE
+    }
+
     #[inline]
     fn get_pointer(&self) -> *const u8 {
         (*self & POINTER_MASK) as *const u8
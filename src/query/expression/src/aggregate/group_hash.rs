@@ -12,8 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+
 use ethnum::i256;
+use naive_cityhash::cityhash64_with_seed;
 use ordered_float::OrderedFloat;
+use twox_hash::XxHash64;
 
 use crate::types::decimal::DecimalType;
 use crate::types::geometry::GeometryType;
@@ -38,13 +43,76 @@ use crate::ScalarRef;
 
 const NULL_HASH_VAL: u64 = 0xd1cefa08eb382d69;
 
+/// The hash function of the group keys, selected by the config of the hash table. All the hash
+/// tables of a query must use the same function, the stored hashes are reused by `resize`,
+/// `combine` and the repartition.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GroupHashFunction {
+    #[default]
+    Default,
+    XxHash,
+    AHash,
+    CityHash,
+}
+
+/// Hashes the integers and the bytes of the group keys.
+pub trait GroupHasher {
+    fn hash_u64(x: u64) -> u64;
+
+    fn hash_bytes(x: &[u8]) -> u64;
+}
+
+pub struct DefaultGroupHasher;
+pub struct XxGroupHasher;
+pub struct AGroupHasher;
+pub struct CityGroupHasher;
+
 pub fn group_hash_columns(cols: InputColumns, values: &mut [u64]) {
+    group_hash_columns_with(cols, GroupHashFunction::Default, values)
+}
+
+pub fn group_hash_columns_with(
+    cols: InputColumns,
+    hash_function: GroupHashFunction,
+    values: &mut [u64],
+) {
+    match hash_function {
+        GroupHashFunction::Default => group_hash_columns_by::<DefaultGroupHasher>(cols, values),
+        GroupHashFunction::XxHash => group_hash_columns_by::<XxGroupHasher>(cols, values),
+        GroupHashFunction::AHash => group_hash_columns_by::<AGroupHasher>(cols, values),
+        GroupHashFunction::CityHash => group_hash_columns_by::<CityGroupHasher>(cols, values),
+    }
+}
+
+fn group_hash_columns_by<H: GroupHasher>(cols: InputColumns, values: &mut [u64]) {
     debug_assert!(!cols.is_empty());
     let mut iter = cols.iter();
-    combine_group_hash_column::<true>(iter.next().unwrap(), values);
+    combine_group_hash_column_by::<true, H>(iter.next().unwrap(), values);
     for col in iter {
-        combine_group_hash_column::<false>(col, values);
+        combine_group_hash_column_by::<false, H>(col, values);
+    }
+}
+
+/// Mix the seed into the group hashes, so the collisions can't be predicted from the keys.
+/// The hashes are left unchanged if the seed is zero.
+pub fn seed_group_hashes(seed: u64, values: &mut [u64]) {
+    if seed == 0 {
+        return;
     }
+    for value in values.iter_mut() {
+        *value = fmix64(*value ^ seed);
+    }
+}
+
+/// The finalizer of MurmurHash3, every bit of the input affects every bit of the output.
+#[inline(always)]
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^= k >> 33;
+    k
 }
 
 pub fn group_hash_columns_slice(cols: &[Column], values: &mut [u64]) {
@@ -57,35 +125,48 @@ pub fn group_hash_columns_slice(cols: &[Column], values: &mut [u64]) {
 }
 
 pub fn combine_group_hash_column<const IS_FIRST: bool>(c: &Column, values: &mut [u64]) {
+    combine_group_hash_column_by::<IS_FIRST, DefaultGroupHasher>(c, values)
+}
+
+fn combine_group_hash_column_by<const IS_FIRST: bool, H: GroupHasher>(
+    c: &Column,
+    values: &mut [u64],
+) {
     match c.data_type() {
         DataType::Null => {}
         DataType::EmptyArray => {}
         DataType::EmptyMap => {}
         DataType::Number(v) => with_number_mapped_type!(|NUM_TYPE| match v {
             NumberDataType::NUM_TYPE => {
-                combine_group_hash_type_column::<IS_FIRST, NumberType<NUM_TYPE>>(c, values)
+                combine_group_hash_type_column::<IS_FIRST, H, NumberType<NUM_TYPE>>(c, values)
             }
         }),
         DataType::Decimal(v) => match v {
             DecimalDataType::Decimal128(_) => {
-                combine_group_hash_type_column::<IS_FIRST, DecimalType<i128>>(c, values)
+                combine_group_hash_type_column::<IS_FIRST, H, DecimalType<i128>>(c, values)
             }
             DecimalDataType::Decimal256(_) => {
-                combine_group_hash_type_column::<IS_FIRST, DecimalType<i256>>(c, values)
+                combine_group_hash_type_column::<IS_FIRST, H, DecimalType<i256>>(c, values)
             }
         },
-        DataType::Boolean => combine_group_hash_type_column::<IS_FIRST, BooleanType>(c, values),
-        DataType::Timestamp => combine_group_hash_type_column::<IS_FIRST, TimestampType>(c, values),
-        DataType::Date => combine_group_hash_type_column::<IS_FIRST, DateType>(c, values),
-        DataType::Binary => combine_group_hash_string_column::<IS_FIRST, BinaryType>(c, values),
-        DataType::String => combine_group_hash_string_column::<IS_FIRST, StringType>(c, values),
-        DataType::Bitmap => combine_group_hash_string_column::<IS_FIRST, BitmapType>(c, values),
-        DataType::Variant => combine_group_hash_string_column::<IS_FIRST, VariantType>(c, values),
-        DataType::Geometry => combine_group_hash_string_column::<IS_FIRST, GeometryType>(c, values),
+        DataType::Boolean => combine_group_hash_type_column::<IS_FIRST, H, BooleanType>(c, values),
+        DataType::Timestamp => {
+            combine_group_hash_type_column::<IS_FIRST, H, TimestampType>(c, values)
+        }
+        DataType::Date => combine_group_hash_type_column::<IS_FIRST, H, DateType>(c, values),
+        DataType::Binary => combine_group_hash_string_column::<IS_FIRST, H, BinaryType>(c, values),
+        DataType::String => combine_group_hash_string_column::<IS_FIRST, H, StringType>(c, values),
+        DataType::Bitmap => combine_group_hash_string_column::<IS_FIRST, H, BitmapType>(c, values),
+        DataType::Variant => {
+            combine_group_hash_string_column::<IS_FIRST, H, VariantType>(c, values)
+        }
+        DataType::Geometry => {
+            combine_group_hash_string_column::<IS_FIRST, H, GeometryType>(c, values)
+        }
         DataType::Nullable(_) => {
             let col = c.as_nullable().unwrap();
             if IS_FIRST {
-                combine_group_hash_column::<IS_FIRST>(&col.column, values);
+                combine_group_hash_column_by::<IS_FIRST, H>(&col.column, values);
                 for (val, ok) in values.iter_mut().zip(col.validity.iter()) {
                     if !ok {
                         *val = NULL_HASH_VAL;
@@ -93,7 +174,7 @@ pub fn combine_group_hash_column<const IS_FIRST: bool>(c: &Column, values: &mut
                 }
             } else {
                 let mut values2 = vec![0; c.len()];
-                combine_group_hash_column::<true>(&col.column, &mut values2);
+                combine_group_hash_column_by::<true, H>(&col.column, &mut values2);
 
                 for ((x, val), ok) in values2
                     .iter()
@@ -109,11 +190,11 @@ pub fn combine_group_hash_column<const IS_FIRST: bool>(c: &Column, values: &mut
             }
         }
         DataType::Generic(_) => unreachable!(),
-        _ => combine_group_hash_type_column::<IS_FIRST, AnyType>(c, values),
+        _ => combine_group_hash_type_column::<IS_FIRST, H, AnyType>(c, values),
     }
 }
 
-fn combine_group_hash_type_column<const IS_FIRST: bool, T: ValueType>(
+fn combine_group_hash_type_column<const IS_FIRST: bool, H: GroupHasher, T: ValueType>(
     col: &Column,
     values: &mut [u64],
 ) where
@@ -122,16 +203,16 @@ fn combine_group_hash_type_column<const IS_FIRST: bool, T: ValueType>(
     let c = T::try_downcast_column(col).unwrap();
     if IS_FIRST {
         for (x, val) in T::iter_column(&c).zip(values.iter_mut()) {
-            *val = x.agg_hash();
+            *val = x.agg_hash::<H>();
         }
     } else {
         for (x, val) in T::iter_column(&c).zip(values.iter_mut()) {
-            *val = (*val).wrapping_mul(NULL_HASH_VAL) ^ x.agg_hash();
+            *val = (*val).wrapping_mul(NULL_HASH_VAL) ^ x.agg_hash::<H>();
         }
     }
 }
 
-fn combine_group_hash_string_column<const IS_FIRST: bool, T: ArgType>(
+fn combine_group_hash_string_column<const IS_FIRST: bool, H: GroupHasher, T: ArgType>(
     col: &Column,
     values: &mut [u64],
 ) where
@@ -140,17 +221,17 @@ fn combine_group_hash_string_column<const IS_FIRST: bool, T: ArgType>(
     let c = T::try_downcast_column(col).unwrap();
     if IS_FIRST {
         for (x, val) in T::iter_column(&c).zip(values.iter_mut()) {
-            *val = x.as_ref().agg_hash();
+            *val = H::hash_bytes(x.as_ref());
         }
     } else {
         for (x, val) in T::iter_column(&c).zip(values.iter_mut()) {
-            *val = (*val).wrapping_mul(NULL_HASH_VAL) ^ x.as_ref().agg_hash();
+            *val = (*val).wrapping_mul(NULL_HASH_VAL) ^ H::hash_bytes(x.as_ref());
         }
     }
 }
 
 pub trait AggHash {
-    fn agg_hash(&self) -> u64;
+    fn agg_hash<H: GroupHasher>(&self) -> u64;
 }
 
 // MIT License
@@ -158,17 +239,27 @@ pub trait AggHash {
 // https://github.com/martinus/robin-hood-hashing/blob/3.11.5/LICENSE
 // Rewrite using chatgpt
 
-impl AggHash for [u8] {
-    fn agg_hash(&self) -> u64 {
+impl GroupHasher for DefaultGroupHasher {
+    #[inline(always)]
+    fn hash_u64(mut x: u64) -> u64 {
+        x ^= x >> 32;
+        x = x.wrapping_mul(0xd6e8feb86659fd93);
+        x ^= x >> 32;
+        x = x.wrapping_mul(0xd6e8feb86659fd93);
+        x ^= x >> 32;
+        x
+    }
+
+    fn hash_bytes(x: &[u8]) -> u64 {
         const M: u64 = 0xc6a4a7935bd1e995;
         const SEED: u64 = 0xe17a1465;
         const R: u64 = 47;
 
-        let mut h = SEED ^ (self.len() as u64).wrapping_mul(M);
-        let n_blocks = self.len() / 8;
+        let mut h = SEED ^ (x.len() as u64).wrapping_mul(M);
+        let n_blocks = x.len() / 8;
 
         for i in 0..n_blocks {
-            let mut k = unsafe { (&self[i * 8] as *const u8 as *const u64).read_unaligned() };
+            let mut k = unsafe { (&x[i * 8] as *const u8 as *const u64).read_unaligned() };
 
             k = k.wrapping_mul(M);
             k ^= k >> R;
@@ -178,7 +269,7 @@ impl AggHash for [u8] {
             h = h.wrapping_mul(M);
         }
 
-        let data8 = &self[n_blocks * 8..];
+        let data8 = &x[n_blocks * 8..];
         for (i, &value) in data8.iter().enumerate() {
             h ^= (value as u64) << (8 * (data8.len() - i - 1));
         }
@@ -191,18 +282,69 @@ impl AggHash for [u8] {
     }
 }
 
+impl GroupHasher for XxGroupHasher {
+    #[inline(always)]
+    fn hash_u64(x: u64) -> u64 {
+        let mut hasher = XxHash64::default();
+        hasher.write_u64(x);
+        hasher.finish()
+    }
+
+    fn hash_bytes(x: &[u8]) -> u64 {
+        let mut hasher = XxHash64::default();
+        hasher.write(x);
+        hasher.finish()
+    }
+}
+
+const AHASH_SEEDS: [u64; 4] = [
+    0x243f6a8885a308d3,
+    0x13198a2e03707344,
+    0xa4093822299f31d0,
+    0x082efa98ec4e6c89,
+];
+
+impl GroupHasher for AGroupHasher {
+    #[inline(always)]
+    fn hash_u64(x: u64) -> u64 {
+        let [k0, k1, k2, k3] = AHASH_SEEDS;
+        let mut hasher = ahash::RandomState::with_seeds(k0, k1, k2, k3).build_hasher();
+        hasher.write_u64(x);
+        hasher.finish()
+    }
+
+    fn hash_bytes(x: &[u8]) -> u64 {
+        let [k0, k1, k2, k3] = AHASH_SEEDS;
+        let mut hasher = ahash::RandomState::with_seeds(k0, k1, k2, k3).build_hasher();
+        hasher.write(x);
+        hasher.finish()
+    }
+}
+
+impl GroupHasher for CityGroupHasher {
+    #[inline(always)]
+    fn hash_u64(x: u64) -> u64 {
+        cityhash64_with_seed(&x.to_le_bytes(), 0)
+    }
+
+    fn hash_bytes(x: &[u8]) -> u64 {
+        cityhash64_with_seed(x, 0)
+    }
+}
+
+impl AggHash for [u8] {
+    #[inline(always)]
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
+        H::hash_bytes(self)
+    }
+}
+
 macro_rules! impl_agg_hash_for_primitive_types {
     ($t: ty) => {
         impl AggHash for $t {
             #[inline(always)]
-            fn agg_hash(&self) -> u64 {
-                let mut x = *self as u64;
-                x ^= x >> 32;
-                x = x.wrapping_mul(0xd6e8feb86659fd93);
-                x ^= x >> 32;
-                x = x.wrapping_mul(0xd6e8feb86659fd93);
-                x ^= x >> 32;
-                x
+            fn agg_hash<H: GroupHasher>(&self) -> u64 {
+                H::hash_u64(*self as u64)
             }
         }
     };
@@ -218,48 +360,48 @@ impl_agg_hash_for_primitive_types!(u64);
 impl_agg_hash_for_primitive_types!(i64);
 
 impl AggHash for bool {
-    fn agg_hash(&self) -> u64 {
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
         *self as u64
     }
 }
 
 impl AggHash for i128 {
-    fn agg_hash(&self) -> u64 {
-        self.to_le_bytes().agg_hash()
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
+        H::hash_bytes(&self.to_le_bytes())
     }
 }
 
 impl AggHash for i256 {
-    fn agg_hash(&self) -> u64 {
-        self.to_le_bytes().agg_hash()
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
+        H::hash_bytes(&self.to_le_bytes())
     }
 }
 
 impl AggHash for OrderedFloat<f32> {
     #[inline(always)]
-    fn agg_hash(&self) -> u64 {
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
         if self.is_nan() {
-            f32::NAN.to_bits().agg_hash()
+            f32::NAN.to_bits().agg_hash::<H>()
         } else {
-            self.to_bits().agg_hash()
+            self.to_bits().agg_hash::<H>()
         }
     }
 }
 
 impl AggHash for OrderedFloat<f64> {
     #[inline(always)]
-    fn agg_hash(&self) -> u64 {
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
         if self.is_nan() {
-            f64::NAN.to_bits().agg_hash()
+            f64::NAN.to_bits().agg_hash::<H>()
         } else {
-            self.to_bits().agg_hash()
+            self.to_bits().agg_hash::<H>()
         }
     }
 }
 
 impl AggHash for ScalarRef<'_> {
     #[inline(always)]
-    fn agg_hash(&self) -> u64 {
-        self.to_string().as_bytes().agg_hash()
+    fn agg_hash<H: GroupHasher>(&self) -> u64 {
+        H::hash_bytes(self.to_string().as_bytes())
     }
 }
@@ -23,6 +23,7 @@ mod partitioned_payload;
 mod payload;
 mod payload_flush;
 mod payload_row;
+mod payload_serde;
 mod probe_state;
 
 use std::sync::atomic::AtomicU64;
@@ -47,6 +48,10 @@ pub fn new_sel() -> SelectVector {
 // A batch size to probe, flush, repartition, etc.
 pub(crate) const BATCH_SIZE: usize = 2048;
 pub(crate) const LOAD_FACTOR: f64 = 1.5;
+pub(crate) const MAX_LOAD_FACTOR: f64 = 16.0;
+pub(crate) const INITIAL_CAPACITY: usize = 8192 * 4;
+pub(crate) const MIN_INITIAL_CAPACITY: usize = 1 << 10;
+pub(crate) const MAX_INITIAL_CAPACITY: usize = 1 << 17;
 pub(crate) const MAX_PAGE_SIZE: usize = 256 * 1024;
 
 // Assume (1 << 15) = 32KB L1 cache per core, divided by two because hyperthreading
@@ -66,6 +71,14 @@ pub struct HashTableConfig {
     pub block_fill_factor: f64,
     pub partial_agg: bool,
     pub max_partial_capacity: usize,
+    // Mixed into the group hashes, all the hash tables of a query must share the same seed
+    pub hash_seed: u64,
+    // Hashes the group keys, all the hash tables of a query must share the same function
+    pub hash_function: GroupHashFunction,
+    // The hash table grows once the capacity is less than the groups times the load factor
+    pub load_factor: f64,
+    // The capacity of the hash table when it is created, a power of two
+    pub initial_capacity: usize,
 }
 
 impl Default for HashTableConfig {
@@ -78,6 +91,10 @@ impl Default for HashTableConfig {
             block_fill_factor: 1.8,
             partial_agg: false,
             max_partial_capacity: 131072,
+            hash_seed: 0,
+            hash_function: GroupHashFunction::Default,
+            load_factor: LOAD_FACTOR,
+            initial_capacity: INITIAL_CAPACITY,
         }
     }
 }
@@ -89,6 +106,38 @@ impl HashTableConfig {
         self
     }
 
+    pub fn with_hash_seed(mut self, hash_seed: u64) -> Self {
+        self.hash_seed = hash_seed;
+        self
+    }
+
+    pub fn with_hash_function(mut self, hash_function: GroupHashFunction) -> Self {
+        self.hash_function = hash_function;
+        self
+    }
+
+    // Falls back to the default if the load factor is not in (1, 16]
+    pub fn with_load_factor(mut self, load_factor: f64) -> Self {
+        self.load_factor = if load_factor > 1.0 && load_factor <= MAX_LOAD_FACTOR {
+            load_factor
+        } else {
+            LOAD_FACTOR
+        };
+        self
+    }
+
+    // Rounded up to a power of two, falls back to the default if the capacity is not in
+    // [2^10, 2^17], the default max partial capacity
+    pub fn with_initial_capacity(mut self, initial_capacity: usize) -> Self {
+        self.initial_capacity =
+            if (MIN_INITIAL_CAPACITY..=MAX_INITIAL_CAPACITY).contains(&initial_capacity) {
+                initial_capacity.next_power_of_two()
+            } else {
+                INITIAL_CAPACITY
+            };
+        self
+    }
+
     pub fn with_partial(mut self, partial_agg: bool, active_threads: usize) -> Self {
         self.partial_agg = partial_agg;
 
@@ -96,9 +145,11 @@ impl HashTableConfig {
         let total_shared_cache_size = active_threads * L3_CACHE_SIZE;
         let cache_per_active_thread =
             L1_CACHE_SIZE + L2_CACHE_SIZE + total_shared_cache_size / active_threads;
-        let size_per_entry = (8_f64 * LOAD_FACTOR) as usize;
+        let size_per_entry = (8_f64 * self.load_factor) as usize;
         let capacity = (cache_per_active_thread / size_per_entry).next_power_of_two();
         self.max_partial_capacity = capacity;
+        // The partial hash table is repartitioned once it reaches the max capacity
+        self.initial_capacity = self.initial_capacity.min(capacity);
 
         self
     }
@@ -16,6 +16,7 @@ use std::alloc::Layout;
 use std::sync::Arc;
 
 use bumpalo::Bump;
+use databend_common_exception::Result;
 use itertools::Itertools;
 
 use super::payload::Payload;
@@ -102,7 +103,7 @@ impl PartitionedPayload {
         state: &mut ProbeState,
         new_group_rows: usize,
         group_columns: InputColumns,
-    ) {
+    ) -> Result<()> {
         if self.payloads.len() == 1 {
             self.payloads[0].reserve_append_rows(
                 &state.empty_vector,
@@ -110,7 +111,7 @@ impl PartitionedPayload {
                 &mut state.addresses,
                 new_group_rows,
                 group_columns,
-            );
+            )?;
         } else {
             // generate partition selection indices
             state.reset_partitions(self.partition_count());
@@ -136,15 +137,20 @@ impl PartitionedPayload {
                         &mut state.addresses,
                         count,
                         group_columns,
-                    );
+                    )?;
                 }
             }
         }
+        Ok(())
     }
 
-    pub fn repartition(self, new_partition_count: usize, state: &mut PayloadFlushState) -> Self {
+    pub fn repartition(
+        self,
+        new_partition_count: usize,
+        state: &mut PayloadFlushState,
+    ) -> Result<Self> {
         if self.partition_count() == new_partition_count {
-            return self;
+            return Ok(self);
         }
 
         let mut new_partition_payload = PartitionedPayload::new(
@@ -154,11 +160,15 @@ impl PartitionedPayload {
             self.arenas.clone(),
         );
 
-        new_partition_payload.combine(self, state);
-        new_partition_payload
+        new_partition_payload.combine(self, state)?;
+        Ok(new_partition_payload)
     }
 
-    pub fn combine(&mut self, other: PartitionedPayload, state: &mut PayloadFlushState) {
+    pub fn combine(
+        &mut self,
+        other: PartitionedPayload,
+        state: &mut PayloadFlushState,
+    ) -> Result<()> {
         if other.partition_count == self.partition_count {
             for (l, r) in self.payloads.iter_mut().zip(other.payloads.into_iter()) {
                 l.combine(r);
@@ -167,9 +177,10 @@ impl PartitionedPayload {
             state.clear();
 
             for payload in other.payloads.into_iter() {
-                self.combine_single(payload, state, None)
+                self.combine_single(payload, state, None)?;
             }
         }
+        Ok(())
     }
 
     pub fn combine_single(
@@ -177,9 +188,9 @@ impl PartitionedPayload {
         mut other: Payload,
         state: &mut PayloadFlushState,
         only_bucket: Option<usize>,
-    ) {
+    ) -> Result<()> {
         if other.len() == 0 {
-            return;
+            return Ok(());
         }
 
         if self.partition_count == 1 {
@@ -198,12 +209,13 @@ impl PartitionedPayload {
 
                     if count > 0 {
                         let sel = &state.probe_state.partition_entries[partition];
-                        payload.copy_rows(sel, count, &state.addresses);
+                        payload.copy_rows(sel, count, &state.addresses)?;
                     }
                 }
             }
             other.state_move_out = true;
         }
+        Ok(())
     }
 
     // for each page's row, compute which partition it belongs to
@@ -243,6 +255,12 @@ impl PartitionedPayload {
         true
     }
 
+    /// Removes the row at `address` from the partition of `hash`, see `Payload::swap_remove_row`.
+    pub fn swap_remove_row(&mut self, hash: u64, address: *const u8) -> Option<*const u8> {
+        let partition_idx = ((hash & self.mask_v) >> self.shift_v) as usize;
+        self.payloads[partition_idx].swap_remove_row(address)
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.payloads.iter().map(|x| x.len()).sum()
@@ -18,9 +18,13 @@ use std::sync::Arc;
 
 use bumpalo::Bump;
 use databend_common_base::runtime::drop_guard;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
 use itertools::Itertools;
 use strength_reduce::StrengthReducedU64;
 
+use super::aggregate_hashtable::POINTER_MASK;
+use super::payload_row::deserialize_scalar_from_rowformat;
 use super::payload_row::rowformat_size;
 use super::payload_row::serialize_column_to_rowformat;
 use crate::get_layout_offsets;
@@ -33,6 +37,7 @@ use crate::ColumnBuilder;
 use crate::DataBlock;
 use crate::InputColumns;
 use crate::PayloadFlushState;
+use crate::Scalar;
 use crate::SelectVector;
 use crate::StateAddr;
 use crate::BATCH_SIZE;
@@ -167,21 +172,31 @@ impl Payload {
     }
 
     #[inline]
-    pub fn writable_page(&mut self) -> &mut Page {
+    pub fn writable_page(&mut self) -> Result<&mut Page> {
         if self.current_write_page == 0
             || self.pages[self.current_write_page - 1].rows
                 == self.pages[self.current_write_page - 1].capacity
         {
-            self.current_write_page += 1;
-            if self.current_write_page > self.pages.len() {
+            if self.current_write_page == self.pages.len() {
+                let data = Vec::with_capacity(self.row_per_page * self.tuple_size);
+                // The entries of the hash table keep only 48 bits of the row addresses, a
+                // page beyond them would corrupt the entries silently.
+                let end = data.as_ptr() as u64 + (self.row_per_page * self.tuple_size) as u64;
+                if end & !POINTER_MASK != 0 {
+                    return Err(ErrorCode::Internal(format!(
+                        "The page of the aggregate payload at {:#x} exceeds the 48-bit address of the hash table entries",
+                        end
+                    )));
+                }
                 self.pages.push(Page {
-                    data: Vec::with_capacity(self.row_per_page * self.tuple_size),
+                    data,
                     rows: 0,
                     capacity: self.row_per_page,
                 });
             }
+            self.current_write_page += 1;
         }
-        &mut self.pages[self.current_write_page - 1]
+        Ok(&mut self.pages[self.current_write_page - 1])
     }
 
     #[inline]
@@ -189,6 +204,15 @@ impl Payload {
         unsafe { page.data.as_ptr().add(row * self.tuple_size) as _ }
     }
 
+    /// Iterates the rows of the payload in page order.
+    pub fn iter_rows(&self) -> PayloadRowIter<'_> {
+        PayloadRowIter {
+            payload: self,
+            page: 0,
+            row: 0,
+        }
+    }
+
     pub fn reserve_append_rows(
         &mut self,
         select_vector: &SelectVector,
@@ -196,15 +220,15 @@ impl Payload {
         address: &mut [*const u8],
         new_group_rows: usize,
         group_columns: InputColumns,
-    ) {
+    ) -> Result<()> {
         let tuple_size = self.tuple_size;
-        let mut page = self.writable_page();
+        let mut page = self.writable_page()?;
         for idx in select_vector.iter().take(new_group_rows).copied() {
             address[idx] = unsafe { page.data.as_ptr().add(page.rows * tuple_size) as *const u8 };
             page.rows += 1;
 
             if page.rows == page.capacity {
-                page = self.writable_page();
+                page = self.writable_page()?;
             }
         }
 
@@ -221,7 +245,8 @@ impl Payload {
             address,
             new_group_rows,
             group_columns,
-        )
+        );
+        Ok(())
     }
 
     pub fn append_rows(
@@ -312,6 +337,49 @@ impl Payload {
 
         self.total_rows += other.total_rows;
         self.pages.append(other.pages.as_mut());
+        // The states are moved with the pages, they are dropped by this payload only.
+        other.state_move_out = true;
+    }
+
+    /// Removes the row at `address` and drops its states, the last row of the payload is moved
+    /// into its place, so the pages stay dense and the emptied last page is freed. Returns the
+    /// former address of the moved row, or `None` if the removed row was the last one.
+    pub fn swap_remove_row(&mut self, address: *const u8) -> Option<*const u8> {
+        if !self.state_move_out && !self.aggrs.is_empty() {
+            let place = unsafe { read::<u64>(address.add(self.state_offset) as _) };
+            for (aggr, addr_offset) in self.aggrs.iter().zip(self.state_addr_offsets.iter()) {
+                if aggr.need_manual_drop_state() {
+                    let place = StateAddr::new(place as usize);
+                    unsafe { aggr.drop_state(place.next(*addr_offset)) };
+                }
+            }
+        }
+
+        // `writable_page` may have allocated an empty page after the last row
+        while self.pages.last().is_some_and(|page| page.rows == 0) {
+            self.pages.pop();
+        }
+        let tuple_size = self.tuple_size;
+        let page = self.pages.last_mut().unwrap();
+        page.rows -= 1;
+        let last = unsafe { page.data.as_ptr().add(page.rows * tuple_size) as *const u8 };
+        let moved = if last != address {
+            unsafe { std::ptr::copy_nonoverlapping(last, address as *mut u8, tuple_size) };
+            Some(last)
+        } else {
+            None
+        };
+        if page.rows == 0 {
+            self.pages.pop();
+        }
+        self.current_write_page = self.current_write_page.min(self.pages.len());
+        self.total_rows -= 1;
+
+        debug_assert_eq!(
+            self.total_rows,
+            self.pages.iter().map(|x| x.rows).sum::<usize>()
+        );
+        moved
     }
 
     pub fn mark_min_cardinality(&mut self) {
@@ -325,9 +393,9 @@ impl Payload {
         select_vector: &SelectVector,
         row_count: usize,
         address: &[*const u8],
-    ) {
+    ) -> Result<()> {
         let tuple_size = self.tuple_size;
-        let mut page = self.writable_page();
+        let mut page = self.writable_page()?;
         for i in 0..row_count {
             let index = select_vector[i];
 
@@ -341,7 +409,7 @@ impl Payload {
             page.rows += 1;
 
             if page.rows == page.capacity {
-                page = self.writable_page();
+                page = self.writable_page()?;
             }
         }
 
@@ -351,6 +419,7 @@ impl Payload {
             self.total_rows,
             self.pages.iter().map(|x| x.rows).sum::<usize>()
         );
+        Ok(())
     }
 
     pub fn scatter(&self, state: &mut PayloadFlushState, partition_count: usize) -> bool {
@@ -405,6 +474,94 @@ impl Payload {
     }
 }
 
+/// A row of the payload, which gives the group values and the states of the row without
+/// the offset math of the row layout.
+#[derive(Clone, Copy)]
+pub struct PayloadRow<'a> {
+    payload: &'a Payload,
+    address: *const u8,
+}
+
+impl<'a> PayloadRow<'a> {
+    #[inline]
+    pub fn address(&self) -> *const u8 {
+        self.address
+    }
+
+    #[inline]
+    pub fn hash(&self) -> u64 {
+        unsafe { read::<u64>(self.address.add(self.payload.hash_offset) as _) }
+    }
+
+    /// The place of the states of the row, `None` if the payload has no aggregate functions.
+    #[inline]
+    pub fn state_place(&self) -> Option<StateAddr> {
+        if self.payload.aggrs.is_empty() {
+            return None;
+        }
+        let place = unsafe { read::<u64>(self.address.add(self.payload.state_offset) as _) };
+        Some(StateAddr::new(place as usize))
+    }
+
+    /// The places of the state of each aggregate function.
+    pub fn state_places(&self) -> Vec<StateAddr> {
+        match self.state_place() {
+            Some(place) => self
+                .payload
+                .state_addr_offsets
+                .iter()
+                .map(|offset| place.next(*offset))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Decodes the value of the group `col_index`.
+    pub fn group_value(&self, col_index: usize) -> Scalar {
+        unsafe {
+            deserialize_scalar_from_rowformat(
+                &self.payload.group_types[col_index],
+                self.address,
+                self.payload.group_offsets[col_index],
+                self.payload.validity_offsets[col_index],
+            )
+        }
+    }
+
+    /// Decodes the values of all the groups.
+    pub fn group_values(&self) -> Vec<Scalar> {
+        (0..self.payload.group_types.len())
+            .map(|col_index| self.group_value(col_index))
+            .collect()
+    }
+}
+
+pub struct PayloadRowIter<'a> {
+    payload: &'a Payload,
+    page: usize,
+    row: usize,
+}
+
+impl<'a> Iterator for PayloadRowIter<'a> {
+    type Item = PayloadRow<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(page) = self.payload.pages.get(self.page) {
+            if self.row < page.rows {
+                let address = self.payload.data_ptr(page, self.row);
+                self.row += 1;
+                return Some(PayloadRow {
+                    payload: self.payload,
+                    address,
+                });
+            }
+            self.page += 1;
+            self.row = 0;
+        }
+        None
+    }
+}
+
 impl Drop for Payload {
     fn drop(&mut self) {
         drop_guard(move || {
@@ -412,17 +569,9 @@ impl Drop for Payload {
             if !self.state_move_out {
                 for (aggr, addr_offset) in self.aggrs.iter().zip(self.state_addr_offsets.iter()) {
                     if aggr.need_manual_drop_state() {
-                        for page in self.pages.iter() {
-                            for row in 0..page.rows {
-                                unsafe {
-                                    let state_place = StateAddr::new(read::<u64>(
-                                        self.data_ptr(page, row).add(self.state_offset) as _,
-                                    )
-                                        as usize);
-
-                                    aggr.drop_state(state_place.next(*addr_offset));
-                                }
-                            }
+                        for row in self.iter_rows() {
+                            let state_place = row.state_place().unwrap();
+                            unsafe { aggr.drop_state(state_place.next(*addr_offset)) };
                         }
                     }
                 }
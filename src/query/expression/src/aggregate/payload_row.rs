@@ -22,6 +22,8 @@ use crate::read;
 use crate::store;
 use crate::types::binary::BinaryColumn;
 use crate::types::decimal::DecimalColumn;
+use crate::types::decimal::DecimalDataType;
+use crate::types::decimal::DecimalScalar;
 use crate::types::decimal::DecimalType;
 use crate::types::AnyType;
 use crate::types::ArgType;
@@ -30,6 +32,7 @@ use crate::types::BooleanType;
 use crate::types::DataType;
 use crate::types::DateType;
 use crate::types::NumberColumn;
+use crate::types::NumberDataType;
 use crate::types::NumberType;
 use crate::types::TimestampType;
 use crate::types::ValueType;
@@ -166,6 +169,57 @@ pub unsafe fn serialize_column_to_rowformat(
     }
 }
 
+/// This deserialize the value of a group at `offset` of the row back into a scalar,
+/// the validity of the nullable group is stored at `validity_offset`.
+pub unsafe fn deserialize_scalar_from_rowformat(
+    data_type: &DataType,
+    address: *const u8,
+    offset: usize,
+    validity_offset: usize,
+) -> Scalar {
+    if data_type.is_nullable() && !read::<bool>(address.add(validity_offset) as _) {
+        return Scalar::Null;
+    }
+
+    match data_type.remove_nullable() {
+        DataType::Null => Scalar::Null,
+        DataType::EmptyArray => Scalar::EmptyArray,
+        DataType::EmptyMap => Scalar::EmptyMap,
+        DataType::Boolean => Scalar::Boolean(read::<bool>(address.add(offset) as _)),
+        DataType::Number(v) => with_number_mapped_type!(|NUM_TYPE| match v {
+            NumberDataType::NUM_TYPE =>
+                NumberType::<NUM_TYPE>::upcast_scalar(read::<NUM_TYPE>(address.add(offset) as _)),
+        }),
+        DataType::Decimal(v) => match v {
+            DecimalDataType::Decimal128(s) => Scalar::Decimal(DecimalScalar::Decimal128(
+                read::<i128>(address.add(offset) as _),
+                s,
+            )),
+            DecimalDataType::Decimal256(s) => Scalar::Decimal(DecimalScalar::Decimal256(
+                read::<i256>(address.add(offset) as _),
+                s,
+            )),
+        },
+        DataType::Timestamp => Scalar::Timestamp(read::<i64>(address.add(offset) as _)),
+        DataType::Date => Scalar::Date(read::<i32>(address.add(offset) as _)),
+        other => {
+            let len = read::<u32>(address.add(offset) as _) as usize;
+            let data_address = read::<u64>(address.add(offset + 4) as _) as usize as *const u8;
+            let data = std::slice::from_raw_parts(data_address, len);
+
+            match other {
+                DataType::Binary => Scalar::Binary(data.to_vec()),
+                DataType::String => Scalar::String(std::str::from_utf8_unchecked(data).to_string()),
+                DataType::Bitmap => Scalar::Bitmap(data.to_vec()),
+                DataType::Variant => Scalar::Variant(data.to_vec()),
+                DataType::Geometry => Scalar::Geometry(data.to_vec()),
+                // for complex column
+                _ => bincode_deserialize_from_slice(data).unwrap(),
+            }
+        }
+    }
+}
+
 pub unsafe fn row_match_columns(
     cols: InputColumns,
     address: &[*const u8],
@@ -0,0 +1,191 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+
+use super::payload::Payload;
+use crate::new_sel;
+use crate::read;
+use crate::types::DataType;
+use crate::types::NumberDataType;
+use crate::types::UInt64Type;
+use crate::types::ValueType;
+use crate::utils::arrow::deserialize_column;
+use crate::utils::arrow::serialize_column;
+use crate::AggregateFunctionRef;
+use crate::BlockEntry;
+use crate::ColumnBuilder;
+use crate::DataBlock;
+use crate::FromData;
+use crate::PayloadFlushState;
+use crate::StateAddr;
+use crate::Value;
+use crate::BATCH_SIZE;
+
+// serialized payload layout
+// [ROWS][LEN][COLUMN]...
+// [ROWS] is the number of rows, u64 in little endian
+// [LEN][COLUMN] is the length in bytes and the arrow IPC bytes of a column, the columns are
+// the serialized states of the aggregate functions, the group columns and the group hashes
+impl Payload {
+    /// Serializes the rows for the transfer of the partial aggregates between the nodes, the
+    /// bytes are independent of the pages, so they can be deserialized by `Payload::deserialize`
+    /// with other page sizes. The group hashes are kept, as they are seeded per query.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let mut state = PayloadFlushState::default();
+        let mut blocks = vec![];
+        while let Some(mut block) = self.aggregate_flush(&mut state)? {
+            let hashes = state.probe_state.group_hashes[..state.row_count].to_vec();
+            block.add_column(BlockEntry::new(
+                DataType::Number(NumberDataType::UInt64),
+                Value::Column(UInt64Type::from_data(hashes)),
+            ));
+            blocks.push(block);
+        }
+        let block = if blocks.is_empty() {
+            let columns = self
+                .aggrs
+                .iter()
+                .map(|_| ColumnBuilder::with_capacity(&DataType::Binary, 0).build())
+                .chain(
+                    self.group_types
+                        .iter()
+                        .map(|t| ColumnBuilder::with_capacity(t, 0).build()),
+                )
+                .chain(std::iter::once(UInt64Type::from_data(vec![])))
+                .collect::<Vec<_>>();
+            DataBlock::new_from_columns(columns)
+        } else {
+            DataBlock::concat(&blocks)?
+        };
+
+        let num_rows = block.num_rows();
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(num_rows as u64).to_le_bytes());
+        for entry in block.columns() {
+            let column = entry
+                .value
+                .convert_to_full_column(&entry.data_type, num_rows);
+            let column_bytes = serialize_column(&column);
+            bytes.extend_from_slice(&(column_bytes.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&column_bytes);
+        }
+        Ok(bytes)
+    }
+
+    /// Reconstructs the payload serialized by `Payload::serialize`, the states are initialized
+    /// and merged with the serialized states.
+    pub fn deserialize(
+        arena: Arc<Bump>,
+        group_types: Vec<DataType>,
+        aggrs: Vec<AggregateFunctionRef>,
+        mut bytes: &[u8],
+    ) -> Result<Payload> {
+        let num_rows = read_len(&mut bytes)?;
+        let num_columns = aggrs.len() + group_types.len() + 1;
+        let mut columns = Vec::with_capacity(num_columns);
+        for _ in 0..num_columns {
+            let len = read_len(&mut bytes)?;
+            if bytes.len() < len {
+                return Err(ErrorCode::BadBytes("The serialized payload is truncated"));
+            }
+            let (column_bytes, rest) = bytes.split_at(len);
+            let column = deserialize_column(column_bytes)?;
+            if column.len() != num_rows {
+                return Err(ErrorCode::BadBytes(format!(
+                    "The serialized payload has {} rows, but a column has {} rows",
+                    num_rows,
+                    column.len()
+                )));
+            }
+            let expected_type = match columns.len().checked_sub(aggrs.len()) {
+                // the serialized states of the aggregate functions
+                None => DataType::Binary,
+                Some(idx) if idx < group_types.len() => group_types[idx].clone(),
+                // the group hashes
+                Some(_) => DataType::Number(NumberDataType::UInt64),
+            };
+            if column.data_type() != expected_type {
+                return Err(ErrorCode::BadBytes(format!(
+                    "The column {} of the serialized payload must be {}, but got {}",
+                    columns.len(),
+                    expected_type,
+                    column.data_type()
+                )));
+            }
+            columns.push(column);
+            bytes = rest;
+        }
+        if !bytes.is_empty() {
+            return Err(ErrorCode::BadBytes(
+                "The serialized payload has trailing bytes",
+            ));
+        }
+        let hashes = UInt64Type::try_downcast_column(&columns[num_columns - 1]).unwrap();
+
+        let mut payload = Payload::new(arena, group_types, aggrs);
+        let mut select_vector = new_sel();
+        for (idx, row) in select_vector.iter_mut().enumerate() {
+            *row = idx;
+        }
+        let mut address = [std::ptr::null::<u8>(); BATCH_SIZE];
+        let mut places = Vec::with_capacity(BATCH_SIZE);
+        let (states, groups) = columns[..num_columns - 1].split_at(payload.aggrs.len());
+
+        for start in (0..num_rows).step_by(BATCH_SIZE) {
+            let rows = BATCH_SIZE.min(num_rows - start);
+            let group_columns = groups
+                .iter()
+                .map(|column| column.slice(start..start + rows))
+                .collect::<Vec<_>>();
+            payload.reserve_append_rows(
+                &select_vector,
+                &hashes[start..start + rows],
+                &mut address,
+                rows,
+                group_columns.as_slice().into(),
+            )?;
+
+            if payload.aggrs.is_empty() {
+                continue;
+            }
+            places.clear();
+            places.extend(address[..rows].iter().map(|addr| unsafe {
+                StateAddr::new(read::<u64>(addr.add(payload.state_offset) as _) as usize)
+            }));
+            for ((aggr, addr_offset), state) in payload
+                .aggrs
+                .iter()
+                .zip(payload.state_addr_offsets.iter())
+                .zip(states.iter())
+            {
+                aggr.batch_merge(&places, *addr_offset, &state.slice(start..start + rows))?;
+            }
+        }
+        Ok(payload)
+    }
+}
+
+fn read_len(bytes: &mut &[u8]) -> Result<usize> {
+    if bytes.len() < 8 {
+        return Err(ErrorCode::BadBytes("The serialized payload is truncated"));
+    }
+    let (len, rest) = bytes.split_at(8);
+    *bytes = rest;
+    Ok(u64::from_le_bytes(len.try_into().unwrap()) as usize)
+}
@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use std::sync::Mutex;
+
 use crate::new_sel;
 use crate::SelectVector;
 use crate::StateAddr;
@@ -70,4 +75,93 @@ impl ProbeState {
             self.partition_count[i] = 0;
         }
     }
+
+    /// Resets the state to the same as a new one. The selection vectors of the partitions are
+    /// dropped, so a pooled state doesn't hold them while it's idle.
+    pub fn reset(&mut self) {
+        self.group_hashes.fill(0);
+        self.addresses.fill(std::ptr::null::<u8>());
+        self.state_places.fill(StateAddr::new(0));
+        self.group_compare_vector.fill(0);
+        self.no_match_vector.fill(0);
+        self.empty_vector.fill(0);
+        self.temp_vector.fill(0);
+        self.row_count = 0;
+        self.partition_entries = vec![];
+        self.partition_count = vec![];
+    }
+}
+
+/// A pool of the probe states shared by the aggregation threads of a query. The probe states
+/// are large, so they are borrowed from the pool and returned when the hash table is done with
+/// them, rather than allocated for each hash table. The pool is dropped with the aggregation,
+/// so the idle states are never held beyond the query that allocated them.
+pub struct ProbeStatePool {
+    states: Mutex<Vec<Box<ProbeState>>>,
+    // The max number of the idle states, the pool is sized to the number of the threads.
+    capacity: usize,
+}
+
+impl ProbeStatePool {
+    pub fn create(capacity: usize) -> Arc<ProbeStatePool> {
+        Arc::new(ProbeStatePool {
+            states: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of the idle states in the pool.
+    pub fn idle(&self) -> usize {
+        self.states.lock().unwrap().len()
+    }
+
+    /// Borrows a state from the pool, a new state is created if the pool is empty. The state
+    /// is returned to the pool when it's dropped.
+    pub fn get(self: &Arc<Self>) -> PooledProbeState {
+        let state = self.states.lock().unwrap().pop();
+        PooledProbeState {
+            state: Some(state.unwrap_or_default()),
+            pool: self.clone(),
+        }
+    }
+
+    fn put(&self, mut state: Box<ProbeState>) {
+        state.reset();
+        let mut states = self.states.lock().unwrap();
+        if states.len() < self.capacity {
+            states.push(state);
+        }
+    }
+}
+
+/// A probe state borrowed from the `ProbeStatePool`.
+pub struct PooledProbeState {
+    state: Option<Box<ProbeState>>,
+    pool: Arc<ProbeStatePool>,
+}
+
+impl Deref for PooledProbeState {
+    type Target = ProbeState;
+
+    fn deref(&self) -> &ProbeState {
+        self.state.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledProbeState {
+    fn deref_mut(&mut self) -> &mut ProbeState {
+        self.state.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledProbeState {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            self.pool.put(state);
+        }
+    }
 }
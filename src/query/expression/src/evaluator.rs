@@ -52,6 +52,9 @@ use crate::FunctionRegistry;
 use crate::RemoteExpr;
 use crate::ScalarRef;
 
+/// The functions whose timestamps are truncated to `timestamp_output_precision`.
+const TIMESTAMP_OUTPUT_FUNCTIONS: &[&str] = &["now", "to_timestamp", "try_to_timestamp"];
+
 #[derive(Default)]
 pub struct EvaluateOptions<'a> {
     pub selection: Option<&'a [u32]>,
@@ -216,6 +219,7 @@ impl<'a> Evaluator<'a> {
                     num_rows: self.data_block.num_rows(),
                     validity,
                     errors,
+                    error_code: None,
                     func_ctx: self.func_ctx,
                     suppress_error: options.suppress_error,
                 };
@@ -264,6 +268,19 @@ impl<'a> Evaluator<'a> {
             }
         };
 
+        // Only the timestamps produced from the clock or parsed by the functions are truncated,
+        // the casts and the other functions keep the timestamps as they are, e.g. on insertion.
+        let result = match expr {
+            Expr::FunctionCall { function, .. }
+                if self.func_ctx.timestamp_output_precision < 6
+                    && TIMESTAMP_OUTPUT_FUNCTIONS.contains(&function.signature.name.as_str())
+                    && expr.data_type().remove_nullable() == DataType::Timestamp =>
+            {
+                result.map(|value| self.truncate_timestamps(value))
+            }
+            _ => result,
+        };
+
         match &result {
             Ok(Value::Scalar(result)) => {
                 assert!(
@@ -306,6 +323,32 @@ impl<'a> Evaluator<'a> {
         result
     }
 
+    /// Truncates the fractional seconds of the timestamps to `timestamp_output_precision` digits,
+    /// the timestamps before 1970 are truncated toward the earlier second as well.
+    fn truncate_timestamps(&self, value: Value<AnyType>) -> Value<AnyType> {
+        fn truncate_column(column: Column, step: i64) -> Column {
+            match column {
+                Column::Timestamp(col) => {
+                    Column::Timestamp(col.iter().map(|ts| ts - ts.rem_euclid(step)).collect())
+                }
+                Column::Nullable(col) => {
+                    let column = truncate_column(col.column, step);
+                    NullableColumn::new_column(column, col.validity)
+                }
+                column => column,
+            }
+        }
+
+        let step = 10_i64.pow(6 - self.func_ctx.timestamp_output_precision as u32);
+        match value {
+            Value::Scalar(Scalar::Timestamp(ts)) => {
+                Value::Scalar(Scalar::Timestamp(ts - ts.rem_euclid(step)))
+            }
+            Value::Column(column) => Value::Column(truncate_column(column, step)),
+            value => value,
+        }
+    }
+
     pub fn run_cast(
         &self,
         span: Span,
@@ -1018,6 +1061,7 @@ impl<'a> Evaluator<'a> {
                     num_rows: self.data_block.num_rows(),
                     validity: None,
                     errors: None,
+                    error_code: None,
                     func_ctx: self.func_ctx,
                     suppress_error: false,
                 };
@@ -1366,6 +1410,7 @@ impl<'a> Evaluator<'a> {
                     num_rows: self.data_block.num_rows(),
                     validity: None,
                     errors,
+                    error_code: None,
                     func_ctx: self.func_ctx,
                     suppress_error: options.suppress_error,
                 };
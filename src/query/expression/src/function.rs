@@ -95,6 +95,17 @@ pub enum FunctionEval {
     },
 }
 
+/// How the leap second `60` of the timestamp strings is parsed, the parsers reject it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LeapSecondPolicy {
+    #[default]
+    Error,
+    /// The following second, `23:59:60` is `00:00:00` of the next day.
+    RollForward,
+    /// The last microsecond of the minute, `23:59:60` is `23:59:59.999999`.
+    Clamp,
+}
+
 #[derive(Clone)]
 pub struct FunctionContext {
     pub tz: TzLUT,
@@ -113,7 +124,21 @@ pub struct FunctionContext {
     pub parse_datetime_ignore_remainder: bool,
     pub enable_dst_hour_fix: bool,
     pub enable_strict_datetime_parser: bool,
+    pub clamp_date_arithmetic: bool,
+    pub clamp_timestamp_arithmetic: bool,
+    pub datetime_parse_error_with_value: bool,
+    pub datetime_parse_error_summary: bool,
+    pub parse_datetime_relative_keywords: bool,
+    pub parse_datetime_comma_fraction: bool,
+    pub parse_datetime_ordinal_suffix: bool,
+    pub leap_second_policy: LeapSecondPolicy,
+    pub two_digit_year_pivot: u8,
     pub random_function_seed: bool,
+    // The fractional digits kept in the timestamps produced by `now()` and `to_timestamp()`
+    pub timestamp_output_precision: u8,
+    // The formats of `to_string` on the timestamps and the dates
+    pub timestamp_display_format: String,
+    pub date_display_format: String,
 }
 
 impl Default for FunctionContext {
@@ -134,7 +159,19 @@ impl Default for FunctionContext {
             parse_datetime_ignore_remainder: false,
             enable_dst_hour_fix: false,
             enable_strict_datetime_parser: true,
+            clamp_date_arithmetic: false,
+            clamp_timestamp_arithmetic: false,
+            datetime_parse_error_with_value: false,
+            datetime_parse_error_summary: false,
+            parse_datetime_relative_keywords: false,
+            parse_datetime_comma_fraction: false,
+            parse_datetime_ordinal_suffix: false,
+            leap_second_policy: LeapSecondPolicy::Error,
+            two_digit_year_pivot: 0,
             random_function_seed: false,
+            timestamp_output_precision: 6,
+            timestamp_display_format: "%Y-%m-%d %H:%M:%S%.6f".to_string(),
+            date_display_format: "%Y-%m-%d".to_string(),
         }
     }
 }
@@ -150,6 +187,8 @@ pub struct EvalContext<'a> {
     /// default value in nullable's inner column.
     pub validity: Option<Bitmap>,
     pub errors: Option<(MutableBitmap, String)>,
+    /// The code of the error raised for `errors`, `ErrorCode::BadArguments` if not set.
+    pub error_code: Option<u16>,
     pub suppress_error: bool,
 }
 
@@ -578,10 +617,21 @@ impl<'a> EvalContext<'a> {
                 let mut valids = Bitmap::new_constant(true, self.num_rows.max(1)).make_mut();
                 valids.set(row, false);
                 self.errors = Some((valids, error_msg.into()));
+                self.error_code = None;
             }
         }
     }
 
+    /// Sets the error of the row like `set_error`, the error is raised with the `code` if it's
+    /// the first error of the context.
+    pub fn set_error_with_code(&mut self, row: usize, code: u16, error_msg: impl Into<String>) {
+        let is_first_error = self.errors.is_none();
+        self.set_error(row, error_msg);
+        if is_first_error && self.errors.is_some() {
+            self.error_code = Some(code);
+        }
+    }
+
     pub fn render_error(
         &self,
         span: Span,
@@ -635,7 +685,11 @@ impl<'a> EvalContext<'a> {
                     )
                 };
 
-                Err(ErrorCode::BadArguments(err_msg).set_span(span))
+                let error = match self.error_code {
+                    Some(ErrorCode::OVERFLOW) => ErrorCode::Overflow(err_msg),
+                    _ => ErrorCode::BadArguments(err_msg),
+                };
+                Err(error.set_span(span))
             }
             None => Ok(()),
         }
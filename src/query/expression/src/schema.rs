@@ -67,6 +67,10 @@ pub const CHANGE_IS_UPDATE_COL_NAME: &str = "change$is_update";
 pub const CHANGE_ROW_ID_COL_NAME: &str = "change$row_id";
 
 pub const ROW_NUMBER_COL_NAME: &str = "_row_number";
+// the `build_row_id` of the matched build rows emitted by the inner hash join.
+pub const BUILD_ROW_ID_COL_NAME: &str = "_build_row_id";
+// the number of the build rows matched by each probe row of the left semi hash join.
+pub const MATCH_COUNT_COL_NAME: &str = "_match_count";
 pub const PREDICATE_COLUMN_NAME: &str = "_predicate";
 
 // stream column id.
@@ -198,15 +198,36 @@ impl TzLUT {
             Round::Hour => self.round_down(us, 3600),
             Round::Day => {
                 let dt = self.to_datetime_from_us(us);
-                let dt = self
-                    .tz
-                    .with_ymd_and_hms(dt.year(), dt.month(), dt.day(), 0, 0, 0)
-                    .unwrap();
-                dt.timestamp() * MICROS_IN_A_SEC
+                let midnight = dt.date_naive().and_time(NaiveTime::MIN);
+                let start = match self.tz.from_local_datetime(&midnight) {
+                    LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => t.timestamp(),
+                    // The local midnight is skipped by a DST gap, the day starts at the first
+                    // instant after the gap.
+                    LocalResult::None => self.start_of_local_day(&midnight, dt.timestamp()),
+                };
+                start * MICROS_IN_A_SEC
             }
         }
     }
 
+    /// Searches the first second whose local date is the date of `midnight`,
+    /// `secs` is a second of the same local date.
+    fn start_of_local_day(&self, midnight: &NaiveDateTime, secs: i64) -> i64 {
+        let date = midnight.date();
+        // The offsets are less than a day, so the local date of `lo` is always before.
+        let mut lo = midnight.and_utc().timestamp() - 86400;
+        let mut hi = secs;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.tz.timestamp_opt(mid, 0).unwrap().date_naive() >= date {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        hi
+    }
+
     #[inline]
     pub fn to_minute(&self, us: i64) -> u8 {
         if us >= 0 && self.offset_round_hour {
@@ -239,9 +260,57 @@ impl TzLUT {
     }
 }
 
+/// Converts the timestamps of a column to the datetimes in `tz`, the offset of the hour of the
+/// last timestamp is cached, so the transitions of the timezone are not searched for each row.
+///
+/// The hours containing a transition are not cached, the offsets of their timestamps are
+/// resolved one by one.
+pub struct TzOffsetCache {
+    tz: Tz,
+    // The hours since the epoch in UTC and their offset.
+    hour: i64,
+    offset: Option<<Tz as TimeZone>::Offset>,
+}
+
+impl TzOffsetCache {
+    pub fn new(tz: Tz) -> Self {
+        Self {
+            tz,
+            hour: i64::MIN,
+            offset: None,
+        }
+    }
+
+    /// The same as `micros.to_timestamp_opt(tz)`.
+    pub fn to_timestamp_opt(&mut self, micros: i64) -> Option<DateTime<Tz>> {
+        let secs = micros.div_euclid(MICROS_IN_A_SEC);
+        let nanos = (micros.rem_euclid(MICROS_IN_A_SEC) * 1_000) as u32;
+        let utc = DateTime::from_timestamp(secs, nanos)?.naive_utc();
+
+        let hour = secs.div_euclid(3600);
+        if hour != self.hour {
+            self.hour = hour;
+            self.offset = self.offset_of_hour(hour);
+        }
+        match self.offset {
+            Some(offset) => Some(DateTime::from_naive_utc_and_offset(utc, offset)),
+            None => Some(self.tz.from_utc_datetime(&utc)),
+        }
+    }
+
+    // Returns `None` if the offset changes in the hour.
+    fn offset_of_hour(&self, hour: i64) -> Option<<Tz as TimeZone>::Offset> {
+        let start = DateTime::from_timestamp(hour * 3600, 0)?.naive_utc();
+        let end = DateTime::from_timestamp(hour * 3600 + 3599, 0)?.naive_utc();
+        let offset = self.tz.offset_from_utc_datetime(&start);
+        (offset == self.tz.offset_from_utc_datetime(&end)).then_some(offset)
+    }
+}
+
 pub trait DateConverter {
     fn to_date(&self, tz: Tz) -> NaiveDate;
     fn to_timestamp(&self, tz: Tz) -> DateTime<Tz>;
+    fn to_timestamp_opt(&self, tz: Tz) -> Option<DateTime<Tz>>;
 }
 
 impl<T> DateConverter for T
@@ -254,6 +323,11 @@ where T: AsPrimitive<i64>
     }
 
     fn to_timestamp(&self, tz: Tz) -> DateTime<Tz> {
+        self.to_timestamp_opt(tz).unwrap()
+    }
+
+    /// Returns `None` if the micros can't be represented in `tz`.
+    fn to_timestamp_opt(&self, tz: Tz) -> Option<DateTime<Tz>> {
         // Can't use `tz.timestamp_nanos(self.as_() * 1000)` directly, is may cause multiply with overflow.
         let micros = self.as_();
         let (mut secs, mut nanos) = (micros / MICROS_IN_A_SEC, (micros % MICROS_IN_A_SEC) * 1_000);
@@ -261,7 +335,7 @@ where T: AsPrimitive<i64>
             secs -= 1;
             nanos += 1_000_000_000;
         }
-        tz.timestamp_opt(secs, nanos as u32).unwrap()
+        tz.timestamp_opt(secs, nanos as u32).single()
     }
 }
 
@@ -386,6 +460,165 @@ impl AddTimesImpl {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateDiffUnit {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateDiffUnit {
+    pub fn parse(unit: &str) -> Result<Self, String> {
+        match unit.trim().to_ascii_lowercase().as_str() {
+            "year" | "years" | "yy" | "yyyy" => Ok(DateDiffUnit::Year),
+            "quarter" | "quarters" | "qq" | "q" => Ok(DateDiffUnit::Quarter),
+            "month" | "months" | "mm" | "m" => Ok(DateDiffUnit::Month),
+            "week" | "weeks" | "wk" | "ww" => Ok(DateDiffUnit::Week),
+            "day" | "days" | "dd" | "d" => Ok(DateDiffUnit::Day),
+            "hour" | "hours" | "hh" => Ok(DateDiffUnit::Hour),
+            "minute" | "minutes" | "mi" | "n" => Ok(DateDiffUnit::Minute),
+            "second" | "seconds" | "ss" | "s" => Ok(DateDiffUnit::Second),
+            _ => Err(format!("Unknown date_diff unit '{unit}'")),
+        }
+    }
+
+    pub fn is_sub_day(&self) -> bool {
+        matches!(
+            self,
+            DateDiffUnit::Hour | DateDiffUnit::Minute | DateDiffUnit::Second
+        )
+    }
+}
+
+/// Counts the unit boundaries crossed from `start` to `end`, weeks start on Monday.
+pub struct DateDiffImpl;
+
+impl DateDiffImpl {
+    pub fn eval_date(unit: DateDiffUnit, start: i32, end: i32) -> Result<i64, String> {
+        if unit.is_sub_day() {
+            return Err(format!(
+                "Unit {unit:?} is not supported for Date, cast the arguments to Timestamp first"
+            ));
+        }
+        Ok(Self::eval_days(
+            unit,
+            &start.to_date(Tz::UTC),
+            &end.to_date(Tz::UTC),
+            end as i64 - start as i64,
+        ))
+    }
+
+    pub fn eval_timestamp(unit: DateDiffUnit, start: i64, end: i64, tz: TzLUT) -> i64 {
+        let start = start.to_timestamp(tz.tz).naive_local();
+        let end = end.to_timestamp(tz.tz).naive_local();
+        let secs = |dt: &NaiveDateTime| dt.and_utc().timestamp();
+        match unit {
+            DateDiffUnit::Hour => secs(&end).div_euclid(3600) - secs(&start).div_euclid(3600),
+            DateDiffUnit::Minute => secs(&end).div_euclid(60) - secs(&start).div_euclid(60),
+            DateDiffUnit::Second => secs(&end) - secs(&start),
+            _ => {
+                let days = end.date().signed_duration_since(start.date()).num_days();
+                Self::eval_days(unit, &start.date(), &end.date(), days)
+            }
+        }
+    }
+
+    fn eval_days(unit: DateDiffUnit, start: &NaiveDate, end: &NaiveDate, days: i64) -> i64 {
+        let months = |date: &NaiveDate| date.year() as i64 * 12 + date.month0() as i64;
+        // 1970-01-01 is a Thursday, shift it to count the weeks from a Monday.
+        let weeks = |date: &NaiveDate| {
+            (date
+                .signed_duration_since(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+                .num_days()
+                + 3)
+            .div_euclid(7)
+        };
+        match unit {
+            DateDiffUnit::Year => end.year() as i64 - start.year() as i64,
+            DateDiffUnit::Quarter => months(end).div_euclid(3) - months(start).div_euclid(3),
+            DateDiffUnit::Month => months(end) - months(start),
+            DateDiffUnit::Week => weeks(end) - weeks(start),
+            _ => days,
+        }
+    }
+}
+
+/// The interval string of `date_add` and `date_sub`, such as `'3 days'` or `'1 year 2 months'`.
+///
+/// The parts are applied in order, since adding months first may clamp the day of month
+/// differently than adding days first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateInterval {
+    parts: Vec<(i64, DateDiffUnit)>,
+}
+
+impl DateInterval {
+    pub fn parse(interval: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid interval '{interval}', expect '<number> <unit> ...'");
+
+        let mut parts = vec![];
+        let mut tokens = interval.split_whitespace();
+        while let Some(amount) = tokens.next() {
+            let amount = amount.parse::<i64>().map_err(|_| invalid())?;
+            let unit = tokens.next().ok_or_else(invalid)?;
+            let unit = DateDiffUnit::parse(unit)
+                .map_err(|_| format!("Unsupported interval unit '{unit}' in '{interval}'"))?;
+            parts.push((amount, unit));
+        }
+        if parts.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self { parts })
+    }
+
+    /// The interval in the opposite direction, for `date_sub`.
+    pub fn negate(mut self) -> Self {
+        for (amount, _) in self.parts.iter_mut() {
+            *amount = amount.wrapping_neg();
+        }
+        self
+    }
+
+    pub fn eval_date(&self, date: i32, tz: TzLUT) -> Result<i32, String> {
+        self.parts
+            .iter()
+            .try_fold(date, |date, &(amount, unit)| match unit {
+                DateDiffUnit::Year => AddYearsImpl::eval_date(date, tz, amount),
+                DateDiffUnit::Quarter => {
+                    AddMonthsImpl::eval_date(date, tz, amount.saturating_mul(3))
+                }
+                DateDiffUnit::Month => AddMonthsImpl::eval_date(date, tz, amount),
+                DateDiffUnit::Week => AddDaysImpl::eval_date(date, amount.saturating_mul(7)),
+                DateDiffUnit::Day => AddDaysImpl::eval_date(date, amount),
+                _ => Err(format!(
+                    "Unit {unit:?} is not supported for Date, cast the date to Timestamp first"
+                )),
+            })
+    }
+
+    pub fn eval_timestamp(&self, us: i64, tz: TzLUT) -> Result<i64, String> {
+        self.parts
+            .iter()
+            .try_fold(us, |us, &(amount, unit)| match unit {
+                DateDiffUnit::Year => AddYearsImpl::eval_timestamp(us, tz, amount),
+                DateDiffUnit::Quarter => {
+                    AddMonthsImpl::eval_timestamp(us, tz, amount.saturating_mul(3))
+                }
+                DateDiffUnit::Month => AddMonthsImpl::eval_timestamp(us, tz, amount),
+                DateDiffUnit::Week => AddDaysImpl::eval_timestamp(us, amount.saturating_mul(7)),
+                DateDiffUnit::Day => AddDaysImpl::eval_timestamp(us, amount),
+                DateDiffUnit::Hour => AddTimesImpl::eval_timestamp(us, amount, FACTOR_HOUR),
+                DateDiffUnit::Minute => AddTimesImpl::eval_timestamp(us, amount, FACTOR_MINUTE),
+                DateDiffUnit::Second => AddTimesImpl::eval_timestamp(us, amount, FACTOR_SECOND),
+            })
+    }
+}
+
 #[inline]
 pub fn today_date(now: DateTime<Utc>, tz: TzLUT) -> i32 {
     let now = now.with_timezone(&tz.tz);
@@ -395,6 +628,33 @@ pub fn today_date(now: DateTime<Utc>, tz: TzLUT) -> i32 {
         .num_days() as i32
 }
 
+/// Parses a quarter like `2023Q3` or `2023-Q3` into the first day of the quarter.
+pub fn quarter_to_date(quarter: &str) -> Result<i32, String> {
+    let invalid = || format!("Invalid quarter '{quarter}', expect '<year>Q<quarter>'");
+
+    let (year, q) = quarter.trim().split_once(['Q', 'q']).ok_or_else(invalid)?;
+    let year = year.strip_suffix('-').unwrap_or(year);
+    if year.len() != 4 || !year.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if q.is_empty() || !q.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let year = year.parse::<i32>().map_err(|_| invalid())?;
+    let q = q.parse::<u32>().map_err(|_| invalid())?;
+    if !(1..=4).contains(&q) {
+        return Err(format!(
+            "Invalid quarter number {q} in '{quarter}', expect 1 to 4"
+        ));
+    }
+
+    let date = NaiveDate::from_ymd_opt(year, (q - 1) * 3 + 1, 1).ok_or_else(invalid)?;
+    let days = date
+        .signed_duration_since(NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+        .num_days();
+    check_date(days)
+}
+
 pub trait ToNumber<N> {
     fn to_number(dt: &DateTime<Tz>) -> N;
 }
@@ -434,6 +694,8 @@ pub struct ToSecond;
 pub struct ToUnixTimestamp;
 
 pub struct ToWeekOfYear;
+pub struct ToWeekOfMonth;
+pub struct ToCalendarWeekOfMonth;
 
 impl ToNumber<u32> for ToYYYYMM {
     fn to_number(dt: &DateTime<Tz>) -> u32 {
@@ -447,6 +709,24 @@ impl ToNumber<u32> for ToWeekOfYear {
     }
 }
 
+/// The days 1 to 7 of the month are in the first week, so it's `ceil(day / 7)`.
+impl ToNumber<u8> for ToWeekOfMonth {
+    fn to_number(dt: &DateTime<Tz>) -> u8 {
+        dt.day().div_ceil(7) as u8
+    }
+}
+
+/// The weeks start on Monday like the ISO weeks, the days before the first Monday
+/// are in the first (partial) week, so a month spans up to 6 weeks.
+impl ToNumber<u8> for ToCalendarWeekOfMonth {
+    fn to_number(dt: &DateTime<Tz>) -> u8 {
+        let day0 = dt.day0();
+        // The weekday of the first day of the month, from Monday as 0.
+        let first_weekday = (dt.weekday().num_days_from_monday() + 35 - day0) % 7;
+        ((day0 + first_weekday) / 7 + 1) as u8
+    }
+}
+
 impl ToNumber<u32> for ToYYYYMMDD {
     fn to_number(dt: &DateTime<Tz>) -> u32 {
         dt.year() as u32 * 10_000 + dt.month() * 100 + dt.day()
@@ -34,6 +34,7 @@ use rust_decimal::Decimal;
 use rust_decimal::RoundingStrategy;
 
 use crate::block::DataBlock;
+use crate::date_helper::DateConverter;
 use crate::expression::Expr;
 use crate::expression::RawExpr;
 use crate::function::Function;
@@ -225,7 +226,11 @@ impl<'a> Display for ScalarRef<'a> {
                 Ok(())
             }
             ScalarRef::String(s) => write!(f, "'{s}'"),
-            ScalarRef::Timestamp(t) => write!(f, "'{}'", timestamp_to_string(*t, Tz::UTC)),
+            ScalarRef::Timestamp(t) => match t.to_timestamp_opt(Tz::UTC) {
+                Some(_) => write!(f, "'{}'", timestamp_to_string(*t, Tz::UTC)),
+                // Out of range micros are shown as is, to keep the error rendering panic-free.
+                None => write!(f, "{t}"),
+            },
             ScalarRef::Date(d) => write!(f, "'{}'", date_to_string(*d as i64, Tz::UTC)),
             ScalarRef::Array(col) => write!(f, "[{}]", col.iter().join(", ")),
             ScalarRef::Map(col) => {
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use chrono_tz::Tz;
+use databend_common_expression::date_helper::DateConverter;
+use databend_common_expression::date_helper::TzOffsetCache;
 use databend_common_expression::types::timestamp::timestamp_to_string;
 
 #[test]
@@ -26,3 +28,33 @@ fn test_timestamp_to_string_formats() {
         "2024-01-01 01:02:03.000000"
     );
 }
+
+#[test]
+fn test_tz_offset_cache() {
+    // The transitions of DST, and a transition of LMT in the middle of an hour.
+    for (tz, start) in [
+        (Tz::America__New_York, 1_710_050_400),
+        (Tz::America__New_York, 1_730_606_400),
+        (Tz::Europe__Amsterdam, -1_025_748_000),
+        (Tz::Asia__Shanghai, -2_177_488_800),
+    ] {
+        let mut cache = TzOffsetCache::new(tz);
+        for secs in (start..start + 4 * 3600).step_by(97) {
+            let micros: i64 = secs * 1_000_000 + 123_456;
+            let format = "%Y-%m-%d %H:%M:%S%.6f %Z %:z";
+            assert_eq!(
+                cache
+                    .to_timestamp_opt(micros)
+                    .unwrap()
+                    .format(format)
+                    .to_string(),
+                micros.to_timestamp(tz).format(format).to_string(),
+            );
+        }
+    }
+    assert!(
+        TzOffsetCache::new(Tz::UTC)
+            .to_timestamp_opt(i64::MAX)
+            .is_none()
+    );
+}
@@ -18,11 +18,22 @@ extern crate criterion;
 #[path = "../tests/it/scalars/parser.rs"]
 mod parser;
 
+use bumpalo::Bump;
+use chrono_tz::Tz;
 use criterion::Criterion;
+use databend_common_expression::date_helper::TzFactory;
 use databend_common_expression::type_check;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::TimestampType;
 use databend_common_expression::DataBlock;
 use databend_common_expression::Evaluator;
+use databend_common_expression::FromData;
 use databend_common_expression::FunctionContext;
+use databend_common_functions::aggregates::AggregateFunctionFactory;
+use databend_common_functions::aggregates::StateAddr;
 use databend_common_functions::BUILTIN_FUNCTIONS;
 
 fn bench(c: &mut Criterion) {
@@ -50,5 +61,143 @@ fn bench(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench);
+fn bench_sum_keys(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_sum_keys");
+
+    let rows = 65536;
+    let column = Int32Type::from_data((0..rows as i32).collect::<Vec<_>>());
+    let func = AggregateFunctionFactory::instance()
+        .get("sum", vec![], vec![column.data_type()])
+        .unwrap();
+
+    for groups in [4, 64] {
+        let arena = Bump::new();
+        let addrs: Vec<StateAddr> = (0..groups)
+            .map(|_| {
+                let addr = arena.alloc_layout(func.state_layout()).into();
+                func.init_state(addr);
+                addr
+            })
+            .collect();
+
+        // Interleaved groups take the per-row path, sorted groups are summed in runs.
+        let interleaved: Vec<StateAddr> = (0..rows).map(|i| addrs[i % groups]).collect();
+        let sorted: Vec<StateAddr> = (0..rows).map(|i| addrs[i * groups / rows]).collect();
+
+        for (name, places) in [("interleaved", interleaved), ("sorted", sorted)] {
+            group.bench_function(format!("{name}/{groups}"), |b| {
+                b.iter(|| {
+                    func.accumulate_keys(&places, 0, (&[column.clone()]).into(), rows)
+                        .unwrap()
+                })
+            });
+        }
+    }
+}
+
+fn bench_null_if(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_null_if");
+
+    let rows = 65536;
+    let lhs = Int32Type::from_data_with_validity(
+        (0..rows).map(|i| i % 7).collect::<Vec<_>>(),
+        (0..rows).map(|i| i % 3 != 0).collect::<Vec<_>>(),
+    );
+    let rhs = Int32Type::from_data_with_validity(
+        (0..rows).map(|i| i % 5).collect::<Vec<_>>(),
+        (0..rows).map(|i| i % 4 != 0).collect::<Vec<_>>(),
+    );
+    let block = DataBlock::new_from_columns(vec![lhs, rhs]);
+    let data_type = DataType::Nullable(Box::new(DataType::Number(NumberDataType::Int32)));
+    let columns = [("a", data_type.clone()), ("b", data_type)];
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    // The functions on the validity bitmaps against the rewrites with `if`.
+    for (name, text) in [
+        ("nullif", "nullif(a, b)"),
+        ("if_eq", "if(a = b, NULL, a)"),
+        ("ifnull", "ifnull(a, b)"),
+        (
+            "coalesce",
+            "if(is_not_null(a), assume_not_null(a), is_not_null(b), assume_not_null(b), NULL)",
+        ),
+    ] {
+        let raw_expr = parser::parse_raw_expr(text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(name, |b| b.iter(|| evaluator.run(&expr).unwrap()));
+    }
+}
+
+fn bench_to_timestamp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_to_timestamp");
+
+    let rows = 65536;
+    let format = "%Y-%m-%d %H:%M:%S";
+    let timestamps = (0..rows)
+        .map(|i| format!("2024-01-{:02} 12:{:02}:{:02}", i % 28 + 1, i % 60, i % 59))
+        .collect::<Vec<_>>();
+    let formats = vec![format; rows];
+    let block = DataBlock::new_from_columns(vec![
+        StringType::from_data(timestamps),
+        StringType::from_data(formats),
+    ]);
+    let columns = [("a", DataType::String), ("f", DataType::String)];
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    // The constant format is parsed once, the format column is parsed for each row.
+    for (name, text) in [
+        ("constant", format!("to_timestamp(a, '{format}')")),
+        ("column", "to_timestamp(a, f)".to_string()),
+    ] {
+        let raw_expr = parser::parse_raw_expr(&text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(name, |b| b.iter(|| evaluator.run(&expr).unwrap()));
+    }
+}
+
+fn bench_timestamp_to_string(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_timestamp_to_string");
+
+    // One row per 10 seconds from 2024-03-09, it straddles the transition of DST.
+    let rows = 65536;
+    let start = 1_709_942_400_000_000i64;
+    let timestamps = (0..rows as i64)
+        .map(|i| start + i * 10_000_000)
+        .collect::<Vec<_>>();
+    let format = "%Y-%m-%d %H:%M:%S %Z";
+    let formats = vec![format; rows];
+    let block = DataBlock::new_from_columns(vec![
+        TimestampType::from_data(timestamps),
+        StringType::from_data(formats),
+    ]);
+    let columns = [("a", DataType::Timestamp), ("f", DataType::String)];
+    let func_ctx = FunctionContext {
+        tz: TzFactory::instance().get(Tz::America__New_York),
+        ..FunctionContext::default()
+    };
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+
+    // The offset of the timezone is cached with the constant format, and resolved for each
+    // row with the format column.
+    for (name, text) in [
+        ("default", "to_string(a)".to_string()),
+        ("constant", format!("to_string(a, '{format}')")),
+        ("column", "to_string(a, f)".to_string()),
+    ] {
+        let raw_expr = parser::parse_raw_expr(&text, &columns);
+        let expr = type_check::check(&raw_expr, &BUILTIN_FUNCTIONS).unwrap();
+        group.bench_function(name, |b| b.iter(|| evaluator.run(&expr).unwrap()));
+    }
+}
+
+criterion_group!(
+    benches,
+    bench,
+    bench_sum_keys,
+    bench_null_if,
+    bench_to_timestamp,
+    bench_timestamp_to_string
+);
 criterion_main!(benches);
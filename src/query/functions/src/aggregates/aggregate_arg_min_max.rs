@@ -49,6 +49,8 @@ use crate::with_simple_no_number_mapped_type;
 // State for arg_min(arg, val) and arg_max(arg, val)
 // A: ValueType for arg.
 // V: ValueType for val.
+// The state of min_by and max_by is the same, except that the ties of val resolve to the smallest
+// arg rather than the first one, so the result doesn't depend on the order of the rows.
 pub trait AggregateArgMinMaxState<A: ValueType, V: ValueType>:
     BorshSerialize + BorshDeserialize + Send + Sync + 'static
 {
@@ -68,7 +70,7 @@ pub trait AggregateArgMinMaxState<A: ValueType, V: ValueType>:
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
-struct ArgMinMaxState<A, V, C>
+struct ArgMinMaxState<A, V, C, const BY: bool>
 where
     V: ValueType,
     V::Scalar: BorshSerialize + BorshDeserialize,
@@ -80,13 +82,37 @@ where
     _c: PhantomData<C>,
 }
 
-impl<A, V, C> AggregateArgMinMaxState<A, V> for ArgMinMaxState<A, V, C>
+impl<A, V, C, const BY: bool> ArgMinMaxState<A, V, C, BY>
 where
     A: ValueType,
     A::Scalar: Send + Sync + BorshSerialize + BorshDeserialize,
     V: ValueType,
     V::Scalar: Send + Sync + BorshSerialize + BorshDeserialize,
     C: ChangeIf<V> + Default,
+    CmpMin: ChangeIf<A>,
+{
+    // Whether `val` ties with the current val and the current arg is kept, only for min_by and
+    // max_by.
+    fn keep_on_tie(&self, val: &V::ScalarRef<'_>, arg: &A::ScalarRef<'_>) -> bool {
+        match &self.data {
+            Some((cur_val, cur_arg)) if BY => {
+                let cur_val = V::to_scalar_ref(cur_val);
+                let is_tie = !C::change_if(&cur_val, val) && !C::change_if(val, &cur_val);
+                is_tie && !<CmpMin as ChangeIf<A>>::change_if(&A::to_scalar_ref(cur_arg), arg)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<A, V, C, const BY: bool> AggregateArgMinMaxState<A, V> for ArgMinMaxState<A, V, C, BY>
+where
+    A: ValueType,
+    A::Scalar: Send + Sync + BorshSerialize + BorshDeserialize,
+    V: ValueType,
+    V::Scalar: Send + Sync + BorshSerialize + BorshDeserialize,
+    C: ChangeIf<V> + Default,
+    CmpMin: ChangeIf<A>,
 {
     fn new() -> Self {
         Self {
@@ -97,12 +123,17 @@ where
 
     fn change(&self, other: &V::ScalarRef<'_>) -> bool {
         match &self.data {
+            // The ties are resolved by `update` for min_by and max_by
+            Some((val, _)) if BY => !C::change_if(other, &V::to_scalar_ref(val)),
             Some((val, _)) => C::change_if(&V::to_scalar_ref(val), other),
             None => true,
         }
     }
 
     fn update(&mut self, other: V::ScalarRef<'_>, arg: A::ScalarRef<'_>) {
+        if self.keep_on_tie(&other, &arg) {
+            return;
+        }
         self.data = Some((V::to_owned_scalar(other), A::to_owned_scalar(arg)));
     }
 
@@ -116,6 +147,15 @@ where
         if column_len == 0 {
             return Ok(());
         }
+        if BY {
+            // Every tie is compared by the arg
+            for (row, val) in V::iter_column(val_col).enumerate() {
+                if validity.map_or(true, |bit| bit.get_bit(row)) && self.change(&val) {
+                    self.update(val, A::index_column(arg_col, row).unwrap());
+                }
+            }
+            return Ok(());
+        }
         let acc = if let Some(bit) = validity {
             if bit.unset_bits() == column_len {
                 return Ok(());
@@ -154,7 +194,8 @@ where
 
     fn merge_from(&mut self, rhs: Self) -> Result<()> {
         if let Some((r_val, r_arg)) = rhs.data {
-            if self.change(&V::to_scalar_ref(&r_val)) {
+            let val = V::to_scalar_ref(&r_val);
+            if self.change(&val) && !self.keep_on_tie(&val, &A::to_scalar_ref(&r_arg)) {
                 self.data = Some((r_val, r_arg));
             }
         }
@@ -163,7 +204,8 @@ where
 
     fn merge(&mut self, rhs: &Self) -> Result<()> {
         if let Some((r_val, r_arg)) = &rhs.data {
-            if self.change(&V::to_scalar_ref(r_val)) {
+            let val = V::to_scalar_ref(r_val);
+            if self.change(&val) && !self.keep_on_tie(&val, &A::to_scalar_ref(r_arg)) {
                 self.data = Some((r_val.to_owned(), r_arg.to_owned()));
             }
         }
@@ -333,7 +375,7 @@ where
     }
 }
 
-pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
+pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8, const BY: bool>(
     display_name: &str,
     _params: Vec<Scalar>,
     arguments: Vec<DataType>,
@@ -348,7 +390,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                 DataType::ARG_TYPE => {
                     with_simple_no_number_mapped_type!(|VAL_TYPE| match val_type {
                         DataType::VAL_TYPE => {
-                            type State = ArgMinMaxState<ARG_TYPE, VAL_TYPE, CMP>;
+                            type State = ArgMinMaxState<ARG_TYPE, VAL_TYPE, CMP, BY>;
                             AggregateArgMinMaxFunction::<ARG_TYPE, VAL_TYPE, CMP, State>::try_create(
                                 display_name,
                                 arg_type,
@@ -357,7 +399,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                         DataType::Number(num_type) => {
                             with_number_mapped_type!(|NUM| match num_type {
                                 NumberDataType::NUM => {
-                                    type State = ArgMinMaxState<ARG_TYPE, NumberType<NUM>, CMP>;
+                                    type State = ArgMinMaxState<ARG_TYPE, NumberType<NUM>, CMP, BY>;
                                     AggregateArgMinMaxFunction::<
                                         ARG_TYPE,
                                         NumberType<NUM>,
@@ -370,7 +412,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                             })
                         }
                         _ => {
-                            type State = ArgMinMaxState<ARG_TYPE, AnyType, CMP>;
+                            type State = ArgMinMaxState<ARG_TYPE, AnyType, CMP, BY>;
                             AggregateArgMinMaxFunction::<ARG_TYPE, AnyType, CMP, State>::try_create(
                                 display_name,
                                 arg_type,
@@ -383,7 +425,8 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                         NumberDataType::ARG_NUM => {
                             with_simple_no_number_mapped_type!(|VAL_TYPE| match val_type {
                                 DataType::VAL_TYPE => {
-                                    type State = ArgMinMaxState<NumberType<ARG_NUM>, VAL_TYPE, CMP>;
+                                    type State =
+                                        ArgMinMaxState<NumberType<ARG_NUM>, VAL_TYPE, CMP, BY>;
                                     AggregateArgMinMaxFunction::<
                                         NumberType<ARG_NUM>,
                                         VAL_TYPE,
@@ -400,6 +443,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                                                 NumberType<ARG_NUM>,
                                                 NumberType<VAL_NUM>,
                                                 CMP,
+                                                BY,
                                             >;
                                             AggregateArgMinMaxFunction::<
                                                 NumberType<ARG_NUM>,
@@ -413,7 +457,8 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                                     })
                                 }
                                 _ => {
-                                    type State = ArgMinMaxState<NumberType<ARG_NUM>, AnyType, CMP>;
+                                    type State =
+                                        ArgMinMaxState<NumberType<ARG_NUM>, AnyType, CMP, BY>;
                                     AggregateArgMinMaxFunction::<
                                         NumberType<ARG_NUM>,
                                         AnyType,
@@ -430,7 +475,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                 _ => {
                     with_simple_no_number_mapped_type!(|VAL_TYPE| match val_type {
                         DataType::VAL_TYPE => {
-                            type State = ArgMinMaxState<AnyType, VAL_TYPE, CMP>;
+                            type State = ArgMinMaxState<AnyType, VAL_TYPE, CMP, BY>;
                             AggregateArgMinMaxFunction::<AnyType, VAL_TYPE, CMP, State>::try_create(
                                 display_name,
                                 arg_type,
@@ -439,7 +484,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                         DataType::Number(num_type) => {
                             with_number_mapped_type!(|NUM| match num_type {
                                 NumberDataType::NUM => {
-                                    type State = ArgMinMaxState<AnyType, NumberType<NUM>, CMP>;
+                                    type State = ArgMinMaxState<AnyType, NumberType<NUM>, CMP, BY>;
                                     AggregateArgMinMaxFunction::<
                                         AnyType,
                                         NumberType<NUM>,
@@ -452,7 +497,7 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
                             })
                         }
                         _ => {
-                            type State = ArgMinMaxState<AnyType, AnyType, CMP>;
+                            type State = ArgMinMaxState<AnyType, AnyType, CMP, BY>;
                             AggregateArgMinMaxFunction::<AnyType, AnyType, CMP, State>::try_create(
                                 display_name,
                                 arg_type,
@@ -471,12 +516,24 @@ pub fn try_create_aggregate_arg_minmax_function<const CMP_TYPE: u8>(
 
 pub fn aggregate_arg_min_function_desc() -> AggregateFunctionDescription {
     AggregateFunctionDescription::creator(Box::new(
-        try_create_aggregate_arg_minmax_function::<TYPE_MIN>,
+        try_create_aggregate_arg_minmax_function::<TYPE_MIN, false>,
     ))
 }
 
 pub fn aggregate_arg_max_function_desc() -> AggregateFunctionDescription {
     AggregateFunctionDescription::creator(Box::new(
-        try_create_aggregate_arg_minmax_function::<TYPE_MAX>,
+        try_create_aggregate_arg_minmax_function::<TYPE_MAX, false>,
+    ))
+}
+
+pub fn aggregate_min_by_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(
+        try_create_aggregate_arg_minmax_function::<TYPE_MIN, true>,
+    ))
+}
+
+pub fn aggregate_max_by_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(
+        try_create_aggregate_arg_minmax_function::<TYPE_MAX, true>,
     ))
 }
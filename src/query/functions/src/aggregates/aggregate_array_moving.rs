@@ -20,6 +20,7 @@ use std::sync::Arc;
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::bitmap::MutableBitmap;
 use databend_common_arrow::arrow::buffer::Buffer;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
@@ -31,6 +32,7 @@ use databend_common_expression::types::DataType;
 use databend_common_expression::types::DecimalDataType;
 use databend_common_expression::types::Float64Type;
 use databend_common_expression::types::Int8Type;
+use databend_common_expression::types::NullableColumn;
 use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::NumberType;
 use databend_common_expression::types::ValueType;
@@ -58,9 +60,46 @@ use crate::aggregates::assert_unary_arguments;
 use crate::aggregates::assert_variadic_params;
 use crate::BUILTIN_FUNCTIONS;
 
+/// Whether the row of the column is not NULL.
+fn row_is_valid(column: &Column, row: usize) -> bool {
+    match column {
+        Column::Null { .. } => false,
+        Column::Nullable(box nullable_column) => nullable_column.validity.get_bit(row),
+        _ => true,
+    }
+}
+
+/// The validity of the column combined with the validity of the input rows.
+fn column_validity(column: &Column, validity: Option<&Bitmap>) -> Option<Bitmap> {
+    match (column, validity) {
+        (Column::Nullable(box nullable_column), Some(validity)) => {
+            Some(&nullable_column.validity & validity)
+        }
+        (Column::Nullable(box nullable_column), None) => Some(nullable_column.validity.clone()),
+        (_, validity) => validity.cloned(),
+    }
+}
+
+/// Pushes the moving values as an array, the windows without any valid value are NULL
+/// if the element type is nullable.
+fn push_moving_values(builder: &mut ColumnBuilder, column: Column, validity: MutableBitmap) {
+    let data_type = builder.data_type();
+    let column = if data_type.as_array().unwrap().is_nullable() {
+        NullableColumn::new_column(column, validity.into())
+    } else {
+        column
+    };
+    builder.push(ScalarRef::Array(column));
+}
+
 #[derive(Default, Debug, BorshDeserialize, BorshSerialize)]
 pub struct NumberArrayMovingSumState<T, TSum> {
     values: Vec<T>,
+    // The NULLs are pushed as the default values and marked as invalid, so the empty windows
+    // can be told apart from the windows summing to zero. It's serialized after the state only
+    // for the nullable arguments, to keep the layout of the non-nullable states unchanged.
+    #[borsh(skip)]
+    validity: Vec<bool>,
     #[borsh(skip)]
     _t: PhantomData<TSum>,
 }
@@ -74,6 +113,7 @@ where
         let buffer = match column {
             Column::Null { .. } => {
                 self.values.push(T::default());
+                self.validity.push(false);
                 return Ok(());
             }
             Column::Nullable(box nullable_column) => {
@@ -81,7 +121,10 @@ where
             }
             _ => NumberType::<T>::try_downcast_column(column).unwrap(),
         };
-        self.values.push(buffer[row]);
+        let is_valid = row_is_valid(column, row);
+        self.values
+            .push(if is_valid { buffer[row] } else { T::default() });
+        self.validity.push(is_valid);
         Ok(())
     }
 
@@ -90,6 +133,7 @@ where
             Column::Null { len } => {
                 for _ in 0..*len {
                     self.values.push(T::default());
+                    self.validity.push(false);
                 }
                 return Ok(());
             }
@@ -98,17 +142,19 @@ where
             }
             _ => NumberType::<T>::try_downcast_column(column).unwrap(),
         };
-        if let Some(validity) = validity {
+        if let Some(validity) = column_validity(column, validity) {
             buffer.iter().zip(validity.iter()).for_each(|(v, b)| {
                 if b {
                     self.values.push(*v);
                 } else {
                     self.values.push(T::default());
                 }
+                self.validity.push(b);
             });
         } else {
             buffer.iter().for_each(|v| {
                 self.values.push(*v);
+                self.validity.push(true);
             });
         }
         Ok(())
@@ -122,17 +168,27 @@ where
             }
             _ => NumberType::<T>::try_downcast_column(columns).unwrap(),
         };
-        buffer.iter().zip(places.iter()).for_each(|(c, place)| {
-            let place = place.next(offset);
-            let state = place.get::<Self>();
-            state.values.push(*c);
-        });
+        buffer
+            .iter()
+            .zip(places.iter())
+            .enumerate()
+            .for_each(|(row, (c, place))| {
+                let place = place.next(offset);
+                let state = place.get::<Self>();
+                let is_valid = row_is_valid(columns, row);
+                state.values.push(if is_valid { *c } else { T::default() });
+                state.validity.push(is_valid);
+            });
         Ok(())
     }
 
     #[inline(always)]
     fn merge(&mut self, other: &Self) -> Result<()> {
+        // The validity of a deserialized non-nullable state is empty, all its values are valid.
+        self.validity.resize(self.values.len(), true);
         self.values.extend_from_slice(&other.values);
+        self.validity.extend_from_slice(&other.validity);
+        self.validity.resize(self.values.len(), true);
         Ok(())
     }
 
@@ -147,18 +203,23 @@ where
         };
 
         let mut sum = TSum::default();
+        // The number of valid values in the current window.
+        let mut count = 0;
         let mut sum_values: Vec<TSum> = Vec::with_capacity(self.values.len());
+        let mut validity = MutableBitmap::with_capacity(self.values.len());
         for (i, value) in self.values.iter().enumerate() {
             sum += value.as_();
+            count += self.validity[i] as usize;
             if i >= window_size {
                 sum -= self.values[i - window_size].as_();
+                count -= self.validity[i - window_size] as usize;
             }
             sum_values.push(sum);
+            validity.push(count > 0);
         }
 
         let inner_col = NumberType::<TSum>::upcast_column(sum_values.into());
-        let array_value = ScalarRef::Array(inner_col);
-        builder.push(array_value);
+        push_moving_values(builder, inner_col, validity);
 
         Ok(())
     }
@@ -176,19 +237,27 @@ where
         };
 
         let mut sum = TSum::default();
+        let mut count = 0;
         let mut avg_values: Vec<F64> = Vec::with_capacity(self.values.len());
+        let mut validity = MutableBitmap::with_capacity(self.values.len());
         for (i, value) in self.values.iter().enumerate() {
             sum += value.as_();
+            count += self.validity[i] as usize;
             if i >= window_size {
                 sum -= self.values[i - window_size].as_();
+                count -= self.validity[i - window_size] as usize;
             }
-            let avg_val = sum.as_() / (window_size as f64);
-            avg_values.push(avg_val.into());
+            if count > 0 {
+                let avg_val = sum.as_() / (window_size as f64);
+                avg_values.push(avg_val.into());
+            } else {
+                avg_values.push(F64::default());
+            }
+            validity.push(count > 0);
         }
 
         let inner_col = NumberType::<F64>::upcast_column(avg_values.into());
-        let array_value = ScalarRef::Array(inner_col);
-        builder.push(array_value);
+        push_moving_values(builder, inner_col, validity);
 
         Ok(())
     }
@@ -197,6 +266,8 @@ where
 #[derive(Default, BorshDeserialize, BorshSerialize)]
 pub struct DecimalArrayMovingSumState<T> {
     pub values: Vec<T>,
+    #[borsh(skip)]
+    pub validity: Vec<bool>,
 }
 
 impl<T> DecimalArrayMovingSumState<T>
@@ -238,6 +309,7 @@ where T: Decimal
         let buffer = match column {
             Column::Null { .. } => {
                 self.values.push(T::default());
+                self.validity.push(false);
                 return Ok(());
             }
             Column::Nullable(box nullable_column) => {
@@ -245,7 +317,10 @@ where T: Decimal
             }
             _ => T::try_downcast_column(column).unwrap().0,
         };
-        self.values.push(buffer[row]);
+        let is_valid = row_is_valid(column, row);
+        self.values
+            .push(if is_valid { buffer[row] } else { T::default() });
+        self.validity.push(is_valid);
         Ok(())
     }
 
@@ -254,6 +329,7 @@ where T: Decimal
             Column::Null { len } => {
                 for _ in 0..*len {
                     self.values.push(T::default());
+                    self.validity.push(false);
                 }
                 return Ok(());
             }
@@ -262,7 +338,7 @@ where T: Decimal
             }
             _ => T::try_downcast_column(column).unwrap().0,
         };
-        match validity {
+        match column_validity(column, validity) {
             Some(validity) => {
                 for (i, v) in validity.iter().enumerate() {
                     if v {
@@ -270,11 +346,13 @@ where T: Decimal
                     } else {
                         self.values.push(T::default());
                     }
+                    self.validity.push(v);
                 }
             }
             None => {
                 for v in buffer.iter() {
                     self.values.push(*v);
+                    self.validity.push(true);
                 }
             }
         }
@@ -289,17 +367,27 @@ where T: Decimal
             }
             _ => T::try_downcast_column(columns).unwrap().0,
         };
-        buffer.iter().zip(places.iter()).for_each(|(c, place)| {
-            let place = place.next(offset);
-            let state = place.get::<Self>();
-            state.values.push(*c);
-        });
+        buffer
+            .iter()
+            .zip(places.iter())
+            .enumerate()
+            .for_each(|(row, (c, place))| {
+                let place = place.next(offset);
+                let state = place.get::<Self>();
+                let is_valid = row_is_valid(columns, row);
+                state.values.push(if is_valid { *c } else { T::default() });
+                state.validity.push(is_valid);
+            });
         Ok(())
     }
 
     #[inline(always)]
     fn merge(&mut self, other: &Self) -> Result<()> {
+        // The validity of a deserialized non-nullable state is empty, all its values are valid.
+        self.validity.resize(self.values.len(), true);
         self.values.extend_from_slice(&other.values);
+        self.validity.extend_from_slice(&other.validity);
+        self.validity.resize(self.values.len(), true);
         Ok(())
     }
 
@@ -314,23 +402,28 @@ where T: Decimal
         };
 
         let mut sum = T::default();
+        // The number of valid values in the current window.
+        let mut count = 0;
         let mut sum_values: Vec<T> = Vec::with_capacity(self.values.len());
+        let mut validity = MutableBitmap::with_capacity(self.values.len());
         for (i, value) in self.values.iter().enumerate() {
             sum += *value;
             self.check_over_flow(sum)?;
+            count += self.validity[i] as usize;
             if i >= window_size {
                 sum -= self.values[i - window_size];
+                count -= self.validity[i - window_size] as usize;
             }
             sum_values.push(sum);
+            validity.push(count > 0);
         }
 
         let data_type = builder.data_type();
-        let inner_type = data_type.as_array().unwrap();
+        let inner_type = data_type.as_array().unwrap().remove_nullable();
         let decimal_type = inner_type.as_decimal().unwrap();
 
         let inner_col = T::upcast_column(sum_values.into(), decimal_type.size());
-        let array_value = ScalarRef::Array(inner_col);
-        builder.push(array_value);
+        push_moving_values(builder, inner_col, validity);
 
         Ok(())
     }
@@ -348,12 +441,21 @@ where T: Decimal
         };
 
         let mut sum = T::default();
+        let mut count = 0;
         let mut avg_values: Vec<T> = Vec::with_capacity(self.values.len());
+        let mut validity = MutableBitmap::with_capacity(self.values.len());
         for (i, value) in self.values.iter().enumerate() {
             sum += *value;
             self.check_over_flow(sum)?;
+            count += self.validity[i] as usize;
             if i >= window_size {
                 sum -= self.values[i - window_size];
+                count -= self.validity[i - window_size] as usize;
+            }
+            validity.push(count > 0);
+            if count == 0 {
+                avg_values.push(T::default());
+                continue;
             }
             let avg_val = match sum
                 .checked_mul(T::e(scale_add as u32))
@@ -372,28 +474,96 @@ where T: Decimal
         }
 
         let data_type = builder.data_type();
-        let inner_type = data_type.as_array().unwrap();
+        let inner_type = data_type.as_array().unwrap().remove_nullable();
         let decimal_type = inner_type.as_decimal().unwrap();
 
         let inner_col = T::upcast_column(avg_values.into(), decimal_type.size());
-        let array_value = ScalarRef::Array(inner_col);
-        builder.push(array_value);
+        push_moving_values(builder, inner_col, validity);
 
         Ok(())
     }
 }
 
+/// The states of the moving functions, the validity of the values is serialized separately
+/// after the state if the argument is nullable.
+pub trait ArrayMovingState: SumState {
+    fn validity(&mut self) -> &mut Vec<bool>;
+}
+
+impl<T, TSum> ArrayMovingState for NumberArrayMovingSumState<T, TSum>
+where
+    T: Number + AsPrimitive<TSum> + BorshSerialize + BorshDeserialize,
+    TSum: Number + AsPrimitive<f64> + std::ops::AddAssign + std::ops::SubAssign,
+{
+    fn validity(&mut self) -> &mut Vec<bool> {
+        &mut self.validity
+    }
+}
+
+impl<T> ArrayMovingState for DecimalArrayMovingSumState<T>
+where T: Decimal
+        + std::ops::AddAssign
+        + std::ops::SubAssign
+        + BorshSerialize
+        + BorshDeserialize
+        + Copy
+        + Clone
+        + std::fmt::Debug
+        + std::cmp::PartialOrd
+{
+    fn validity(&mut self) -> &mut Vec<bool> {
+        &mut self.validity
+    }
+}
+
+fn serialize_moving_state<State: ArrayMovingState>(
+    state: &mut State,
+    is_nullable: bool,
+    writer: &mut Vec<u8>,
+) -> Result<()> {
+    borsh_serialize_state(writer, state)?;
+    if is_nullable {
+        borsh_serialize_state(writer, state.validity())?;
+    }
+    Ok(())
+}
+
+fn deserialize_moving_state<State: ArrayMovingState>(
+    reader: &mut &[u8],
+    is_nullable: bool,
+) -> Result<State> {
+    let mut state: State = borsh_deserialize_state(reader)?;
+    if is_nullable {
+        *state.validity() = borsh_deserialize_state(reader)?;
+    }
+    Ok(state)
+}
+
+/// The moving values of a nullable argument are `Array(Nullable(T))` rather than `Array(T)`,
+/// the windows without any valid value are NULL. The return type of the non-nullable
+/// arguments is unchanged.
+fn moving_return_type(inner_type: DataType, is_nullable: bool) -> DataType {
+    if is_nullable {
+        DataType::Array(Box::new(inner_type.wrap_nullable()))
+    } else {
+        DataType::Array(Box::new(inner_type))
+    }
+}
+
+/// The moving averages of the values in the order they are accumulated, see
+/// `AggregateArrayMovingSumFunction` for the order of the merged states.
 #[derive(Clone)]
 pub struct AggregateArrayMovingAvgFunction<State> {
     display_name: String,
     window_size: Option<usize>,
     sum_t: PhantomData<State>,
     return_type: DataType,
+    is_nullable: bool,
     scale_add: u8,
 }
 
 impl<State> AggregateFunction for AggregateArrayMovingAvgFunction<State>
-where State: SumState
+where State: ArrayMovingState
 {
     fn name(&self) -> &str {
         "AggregateArrayMovingAvgFunction"
@@ -439,12 +609,12 @@ where State: SumState
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
         let state = place.get::<State>();
-        borsh_serialize_state(writer, state)
+        serialize_moving_state(state, self.is_nullable, writer)
     }
 
     fn merge(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
         let state = place.get::<State>();
-        let rhs: State = borsh_deserialize_state(reader)?;
+        let rhs: State = deserialize_moving_state(reader, self.is_nullable)?;
 
         state.merge(&rhs)
     }
@@ -477,12 +647,13 @@ impl<State> fmt::Display for AggregateArrayMovingAvgFunction<State> {
 }
 
 impl<State> AggregateArrayMovingAvgFunction<State>
-where State: SumState
+where State: ArrayMovingState
 {
     pub fn try_create(
         display_name: &str,
         params: Vec<Scalar>,
-        return_type: DataType,
+        inner_type: DataType,
+        is_nullable: bool,
         scale_add: u8,
     ) -> Result<AggregateFunctionRef> {
         let window_size = if params.len() == 1 {
@@ -500,12 +671,14 @@ where State: SumState
         } else {
             None
         };
+        let return_type = moving_return_type(inner_type, is_nullable);
 
         Ok(Arc::new(Self {
             display_name: display_name.to_owned(),
             window_size,
             sum_t: PhantomData,
             return_type,
+            is_nullable,
             scale_add,
         }))
     }
@@ -524,6 +697,7 @@ pub fn try_create_aggregate_array_moving_avg_function(
     } else {
         arguments[0].remove_nullable()
     };
+    let is_nullable = arguments[0].is_nullable_or_null();
     with_number_mapped_type!(|NUM_TYPE| match &data_type {
         DataType::Number(NumberDataType::NUM_TYPE) => {
             type TSum = <NUM_TYPE as ResultTypeOfUnary>::Sum;
@@ -531,7 +705,8 @@ pub fn try_create_aggregate_array_moving_avg_function(
             AggregateArrayMovingAvgFunction::<State>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(Float64Type::data_type())),
+                Float64Type::data_type(),
+                is_nullable,
                 0,
             )
         }
@@ -545,9 +720,8 @@ pub fn try_create_aggregate_array_moving_avg_function(
             AggregateArrayMovingAvgFunction::<DecimalArrayMovingSumState<i128>>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(DataType::Decimal(DecimalDataType::from_size(
-                    decimal_size,
-                )?))),
+                DataType::Decimal(DecimalDataType::from_size(decimal_size)?),
+                is_nullable,
                 decimal_size.scale - s.scale,
             )
         }
@@ -561,9 +735,8 @@ pub fn try_create_aggregate_array_moving_avg_function(
             AggregateArrayMovingAvgFunction::<DecimalArrayMovingSumState<i256>>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(DataType::Decimal(DecimalDataType::from_size(
-                    decimal_size,
-                )?))),
+                DataType::Decimal(DecimalDataType::from_size(decimal_size)?),
+                is_nullable,
                 decimal_size.scale - s.scale,
             )
         }
@@ -578,16 +751,21 @@ pub fn aggregate_array_moving_avg_function_desc() -> AggregateFunctionDescriptio
     AggregateFunctionDescription::creator(Box::new(try_create_aggregate_array_moving_avg_function))
 }
 
+/// The moving sums of the values in the order they are accumulated, the values of a merged
+/// state are appended after the values of the state it's merged into. The partial states of
+/// a group are merged in no particular order, so the result is only meaningful if the group
+/// is aggregated in a single partition, e.g. with `max_threads` set to 1 on a single node.
 #[derive(Clone)]
 pub struct AggregateArrayMovingSumFunction<State> {
     display_name: String,
     window_size: Option<usize>,
     sum_t: PhantomData<State>,
     return_type: DataType,
+    is_nullable: bool,
 }
 
 impl<State> AggregateFunction for AggregateArrayMovingSumFunction<State>
-where State: SumState
+where State: ArrayMovingState
 {
     fn name(&self) -> &str {
         "AggregateArrayMovingSumFunction"
@@ -633,12 +811,12 @@ where State: SumState
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
         let state = place.get::<State>();
-        borsh_serialize_state(writer, state)
+        serialize_moving_state(state, self.is_nullable, writer)
     }
 
     fn merge(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
         let state = place.get::<State>();
-        let rhs: State = borsh_deserialize_state(reader)?;
+        let rhs: State = deserialize_moving_state(reader, self.is_nullable)?;
 
         state.merge(&rhs)
     }
@@ -671,12 +849,13 @@ impl<State> fmt::Display for AggregateArrayMovingSumFunction<State> {
 }
 
 impl<State> AggregateArrayMovingSumFunction<State>
-where State: SumState
+where State: ArrayMovingState
 {
     pub fn try_create(
         display_name: &str,
         params: Vec<Scalar>,
-        return_type: DataType,
+        inner_type: DataType,
+        is_nullable: bool,
     ) -> Result<AggregateFunctionRef> {
         let window_size = if params.len() == 1 {
             let window_size = check_number::<_, u64>(
@@ -693,12 +872,14 @@ where State: SumState
         } else {
             None
         };
+        let return_type = moving_return_type(inner_type, is_nullable);
 
         Ok(Arc::new(Self {
             display_name: display_name.to_owned(),
             window_size,
             sum_t: PhantomData,
             return_type,
+            is_nullable,
         }))
     }
 }
@@ -716,6 +897,7 @@ pub fn try_create_aggregate_array_moving_sum_function(
     } else {
         arguments[0].remove_nullable()
     };
+    let is_nullable = arguments[0].is_nullable_or_null();
     with_number_mapped_type!(|NUM_TYPE| match &data_type {
         DataType::Number(NumberDataType::NUM_TYPE) => {
             type TSum = <NUM_TYPE as ResultTypeOfUnary>::Sum;
@@ -723,7 +905,8 @@ pub fn try_create_aggregate_array_moving_sum_function(
             AggregateArrayMovingSumFunction::<State>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(NumberType::<TSum>::data_type())),
+                NumberType::<TSum>::data_type(),
+                is_nullable,
             )
         }
         DataType::Decimal(DecimalDataType::Decimal128(s)) => {
@@ -736,9 +919,8 @@ pub fn try_create_aggregate_array_moving_sum_function(
             AggregateArrayMovingSumFunction::<DecimalArrayMovingSumState<i128>>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(DataType::Decimal(DecimalDataType::from_size(
-                    decimal_size,
-                )?))),
+                DataType::Decimal(DecimalDataType::from_size(decimal_size)?),
+                is_nullable,
             )
         }
         DataType::Decimal(DecimalDataType::Decimal256(s)) => {
@@ -751,9 +933,8 @@ pub fn try_create_aggregate_array_moving_sum_function(
             AggregateArrayMovingSumFunction::<DecimalArrayMovingSumState<i256>>::try_create(
                 display_name,
                 params,
-                DataType::Array(Box::new(DataType::Decimal(DecimalDataType::from_size(
-                    decimal_size,
-                )?))),
+                DataType::Decimal(DecimalDataType::from_size(decimal_size)?),
+                is_nullable,
             )
         }
         _ => Err(ErrorCode::BadDataValueType(format!(
@@ -18,11 +18,13 @@ use std::sync::Arc;
 
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
+use databend_common_arrow::arrow::bitmap::Bitmap;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_expression::types::decimal::*;
 use databend_common_expression::types::*;
 use databend_common_expression::utils::arithmetics_type::ResultTypeOfUnary;
+use databend_common_expression::with_integer_mapped_type;
 use databend_common_expression::with_number_mapped_type;
 use databend_common_expression::Scalar;
 use num_traits::AsPrimitive;
@@ -98,10 +100,155 @@ where
     }
 }
 
+/// The average of booleans is the fraction of trues.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct BooleanAvgState {
+    pub trues: u64,
+    pub count: u64,
+}
+
+impl UnaryState<BooleanType, Float64Type> for BooleanAvgState {
+    fn add(&mut self, other: bool, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.count += 1;
+        self.trues += other as u64;
+        Ok(())
+    }
+
+    fn add_batch(
+        &mut self,
+        column: &Bitmap,
+        validity: Option<&Bitmap>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let (count, falses) = match validity {
+            Some(validity) => (
+                column.len() - validity.unset_bits(),
+                (column & validity).unset_bits() - validity.unset_bits(),
+            ),
+            None => (column.len(), column.unset_bits()),
+        };
+        self.count += count as u64;
+        self.trues += (count - falses) as u64;
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.count += rhs.count;
+        self.trues += rhs.trues;
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<F64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value = self.trues as f64 / (self.count as f64);
+        builder.push(F64::from(value));
+        Ok(())
+    }
+}
+
+/// The average of dates is the date of the average day count, rounded to the nearest day.
+///
+/// Only `avg` accepts dates, since the sum of dates isn't meaningful.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct DateAvgState {
+    pub days: i64,
+    pub count: u64,
+}
+
+impl UnaryState<DateType, DateType> for DateAvgState {
+    fn add(&mut self, other: i32, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.count += 1;
+        self.days += other as i64;
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.count += rhs.count;
+        self.days += rhs.days;
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<i32>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value = (self.days as f64 / self.count as f64).round();
+        builder.push(value as i32);
+        Ok(())
+    }
+}
+
+/// How the sum is divided by the count in the decimal average, taken from the first
+/// parameter of `avg`, such as `avg('half_even')(x)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecimalAvgRoundingMode {
+    Truncate,
+    /// Rounds the half away from zero.
+    HalfUp,
+    /// Rounds the half to the even neighbour, aka banker's rounding.
+    HalfEven,
+}
+
+impl DecimalAvgRoundingMode {
+    pub fn try_create(display_name: &str, params: &[Scalar]) -> Result<Self> {
+        match params.first() {
+            None => Ok(Self::HalfUp),
+            Some(Scalar::String(mode)) => match mode.to_lowercase().as_str() {
+                "truncate" => Ok(Self::Truncate),
+                "half_up" => Ok(Self::HalfUp),
+                "half_even" => Ok(Self::HalfEven),
+                _ => Err(ErrorCode::BadArguments(format!(
+                    "{} does not support rounding mode '{}', expected 'truncate', 'half_up' or 'half_even'",
+                    display_name, mode
+                ))),
+            },
+            Some(param) => Err(ErrorCode::BadArguments(format!(
+                "The rounding mode of {} must be a constant string, but got '{}'",
+                display_name, param
+            ))),
+        }
+    }
+
+    /// Divides `value` by the positive `count`, returns `None` on overflow.
+    pub fn div<T: Decimal>(self, value: T, count: T) -> Option<T> {
+        let quotient = value.checked_div(count)?;
+        let remainder = value.checked_rem(count)?;
+        if self == Self::Truncate || remainder == T::zero() {
+            return Some(quotient);
+        }
+
+        let twice = remainder.checked_add(remainder)?;
+        let twice = if twice < T::zero() {
+            T::zero().checked_sub(twice)?
+        } else {
+            twice
+        };
+        let away_from_zero = if twice == count {
+            match self {
+                Self::HalfEven => quotient.checked_rem(T::from_i128(2))? != T::zero(),
+                _ => true,
+            }
+        } else {
+            twice > count
+        };
+
+        match (away_from_zero, value < T::zero()) {
+            (false, _) => Some(quotient),
+            (true, true) => quotient.checked_sub(T::one()),
+            (true, false) => quotient.checked_add(T::one()),
+        }
+    }
+}
+
 struct DecimalAvgData {
     // only for decimals
     // AVG：AVG(DECIMAL(a, b)) -> DECIMAL(38 or 76, max(b, 4))。
     pub scale_add: u8,
+    pub rounding_mode: DecimalAvgRoundingMode,
 }
 
 impl FunctionData for DecimalAvgData {
@@ -186,8 +333,10 @@ where
         match self
             .value
             .checked_mul(T::Scalar::e(decimal_avg_data.scale_add as u32))
-            .and_then(|v| v.checked_div(T::Scalar::from_i128(self.count)))
-        {
+            .and_then(|v| {
+                let count = T::Scalar::from_i128(self.count);
+                decimal_avg_data.rounding_mode.div(v, count)
+            }) {
             Some(value) => {
                 T::push_item(builder, T::to_scalar_ref(&value));
                 Ok(())
@@ -201,6 +350,31 @@ where
     }
 }
 
+/// The integers are summed into a `Decimal128` with the scale 0, so the sum of
+/// 64-bit integers doesn't overflow before it's divided by the count.
+impl<const OVERFLOW: bool, N> UnaryState<NumberType<N>, Float64Type>
+    for DecimalAvgState<OVERFLOW, Decimal128Type>
+where N: Number + AsPrimitive<i128>
+{
+    fn add(&mut self, other: N, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.add_internal(1, AsPrimitive::<i128>::as_(other))
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.add_internal(rhs.count, rhs.value)
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<F64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value = self.value as f64 / (self.count as f64);
+        builder.push(F64::from(value));
+        Ok(())
+    }
+}
+
 pub fn try_create_aggregate_avg_function(
     display_name: &str,
     params: Vec<Scalar>,
@@ -214,6 +388,32 @@ pub fn try_create_aggregate_avg_function(
         arguments[0].clone()
     };
 
+    // `avg('decimal')(x)` accumulates the integers into a decimal, the same as `sum('decimal')(x)`.
+    let into_decimal = data_type.is_integer()
+        && matches!(params.first(), Some(Scalar::String(accumulator))
+            if accumulator.eq_ignore_ascii_case("decimal"));
+    let rounding_mode = if into_decimal {
+        DecimalAvgRoundingMode::HalfUp
+    } else {
+        DecimalAvgRoundingMode::try_create(display_name, &params)?
+    };
+
+    with_integer_mapped_type!(|NUM| match &data_type {
+        DataType::Number(NumberDataType::NUM) if into_decimal => {
+            return AggregateUnaryFunction::<
+                DecimalAvgState<true, Decimal128Type>,
+                NumberType<NUM>,
+                Float64Type,
+            >::try_create_unary(
+                display_name,
+                Float64Type::data_type(),
+                params,
+                arguments[0].clone(),
+            );
+        }
+        _ => {}
+    });
+
     with_number_mapped_type!(|NUM| match &data_type {
         DataType::Number(NumberDataType::NUM) => {
             type TSum = <NUM as ResultTypeOfUnary>::Sum;
@@ -244,7 +444,10 @@ pub fn try_create_aggregate_avg_function(
                 >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
-                .with_function_data(Box::new(DecimalAvgData { scale_add }));
+                .with_function_data(Box::new(DecimalAvgData {
+                    scale_add,
+                    rounding_mode,
+                }));
                 Ok(Arc::new(func))
             } else {
                 let func = AggregateUnaryFunction::<
@@ -254,7 +457,10 @@ pub fn try_create_aggregate_avg_function(
                 >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
-                .with_function_data(Box::new(DecimalAvgData { scale_add }));
+                .with_function_data(Box::new(DecimalAvgData {
+                    scale_add,
+                    rounding_mode,
+                }));
                 Ok(Arc::new(func))
             }
         }
@@ -277,7 +483,10 @@ pub fn try_create_aggregate_avg_function(
                 >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
-                .with_function_data(Box::new(DecimalAvgData { scale_add }));
+                .with_function_data(Box::new(DecimalAvgData {
+                    scale_add,
+                    rounding_mode,
+                }));
                 Ok(Arc::new(func))
             } else {
                 let func = AggregateUnaryFunction::<
@@ -287,10 +496,31 @@ pub fn try_create_aggregate_avg_function(
                 >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
-                .with_function_data(Box::new(DecimalAvgData { scale_add }));
+                .with_function_data(Box::new(DecimalAvgData {
+                    scale_add,
+                    rounding_mode,
+                }));
                 Ok(Arc::new(func))
             }
         }
+        DataType::Boolean => {
+            let return_type = Float64Type::data_type();
+            AggregateUnaryFunction::<BooleanAvgState, BooleanType, Float64Type>::try_create_unary(
+                display_name,
+                return_type,
+                params,
+                arguments[0].clone(),
+            )
+        }
+        DataType::Date => {
+            let return_type = DateType::data_type();
+            AggregateUnaryFunction::<DateAvgState, DateType, DateType>::try_create_unary(
+                display_name,
+                return_type,
+                params,
+                arguments[0].clone(),
+            )
+        }
         _ => Err(ErrorCode::BadDataValueType(format!(
             "{} does not support type '{:?}'",
             display_name, arguments[0]
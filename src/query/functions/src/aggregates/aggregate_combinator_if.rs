@@ -22,7 +22,6 @@ use databend_common_exception::Result;
 use databend_common_expression::types::BooleanType;
 use databend_common_expression::types::DataType;
 use databend_common_expression::types::ValueType;
-use databend_common_expression::Column;
 use databend_common_expression::ColumnBuilder;
 use databend_common_expression::InputColumns;
 use databend_common_expression::Scalar;
@@ -32,7 +31,6 @@ use crate::aggregates::aggregate_function_factory::AggregateFunctionCreator;
 use crate::aggregates::aggregate_function_factory::CombinatorDescription;
 use crate::aggregates::AggregateFunction;
 use crate::aggregates::AggregateFunctionRef;
-use crate::aggregates::StateAddrs;
 
 #[derive(Clone)]
 pub struct AggregateIfCombinator {
@@ -127,17 +125,17 @@ impl AggregateFunction for AggregateIfCombinator {
         places: &[StateAddr],
         offset: usize,
         columns: InputColumns,
-        _input_rows: usize,
+        input_rows: usize,
     ) -> Result<()> {
         let predicate: Bitmap =
             BooleanType::try_downcast_column(&columns[self.argument_len - 1]).unwrap();
-        let (columns, row_size) =
-            self.filter_column(columns.slice(0..self.argument_len - 1), &predicate);
-        let new_places = Self::filter_place(places, &predicate);
-
-        let new_places_slice = new_places.as_slice();
-        self.nested
-            .accumulate_keys(new_places_slice, offset, (&columns).into(), row_size)
+        self.nested.accumulate_keys_with_validity(
+            places,
+            offset,
+            columns.slice(0..self.argument_len - 1),
+            &predicate,
+            input_rows,
+        )
     }
 
     fn accumulate_row(&self, place: StateAddr, columns: InputColumns, row: usize) -> Result<()> {
@@ -187,30 +185,3 @@ impl fmt::Display for AggregateIfCombinator {
         write!(f, "{}_if", self.nested_name)
     }
 }
-
-impl AggregateIfCombinator {
-    #[inline]
-    fn filter_column(&self, columns: InputColumns, predicate: &Bitmap) -> (Vec<Column>, usize) {
-        let columns = columns
-            .iter()
-            .map(|c| c.filter(predicate))
-            .collect::<Vec<_>>();
-
-        let rows = predicate.len() - predicate.unset_bits();
-
-        (columns, rows)
-    }
-
-    fn filter_place(places: &[StateAddr], predicate: &Bitmap) -> StateAddrs {
-        if predicate.unset_bits() == 0 {
-            return places.to_vec();
-        }
-        let it = predicate
-            .iter()
-            .zip(places.iter())
-            .filter(|(v, _)| *v)
-            .map(|(_, c)| *c);
-
-        Vec::from_iter(it)
-    }
-}
@@ -62,7 +62,7 @@ impl AggregateCovarianceState {
     // This equals the formula in the paper. Here we take the same approach as Clickhouse
     // does. Thanks Clickhouse!
     #[inline(always)]
-    fn add(&mut self, s: f64, t: f64) {
+    pub(crate) fn add(&mut self, s: f64, t: f64) {
         let left_delta = s - self.left_mean;
         let right_delta = t - self.right_mean;
 
@@ -80,7 +80,7 @@ impl AggregateCovarianceState {
     // Clickhouse also has some optimization when two data sets are large and comparable in size.
     // Here we take the same approach as Clickhouse does. Thanks Clickhouse!
     #[inline(always)]
-    fn merge(&mut self, other: &Self) {
+    pub(crate) fn merge(&mut self, other: &Self) {
         let total = self.count + other.count;
         if total == 0 {
             return;
@@ -56,20 +56,88 @@ impl FunctionData for QuantileData {
         self
     }
 }
+
+// Sorts the values unless they're known to be sorted.
+fn sort_values<V: Ord>(values: &mut [V], sorted: &mut bool) {
+    if !*sorted {
+        values.sort_unstable();
+        *sorted = true;
+    }
+}
+
+// Whether the values are sorted, the serialized values are sorted but not marked as sorted,
+// the flag is not part of the serialized state.
+fn is_sorted<V: Ord>(values: &[V], sorted: bool) -> bool {
+    sorted || values.windows(2).all(|w| w[0] <= w[1])
+}
+
+// Merges the values of `rhs` into `lhs`. If both are sorted, they are merged as two sorted runs
+// in place, so the merged values are sorted without sorting them all again. Otherwise the values are
+// appended and left unsorted, they're only sorted when the state is serialized.
+fn merge_values<V: Ord + Copy>(
+    lhs: &mut Vec<V>,
+    lhs_sorted: &mut bool,
+    rhs: &[V],
+    rhs_sorted: bool,
+) {
+    if rhs.is_empty() {
+        return;
+    }
+    if lhs.is_empty() {
+        lhs.extend_from_slice(rhs);
+        *lhs_sorted = is_sorted(rhs, rhs_sorted);
+        return;
+    }
+    if !*lhs_sorted || !is_sorted(rhs, rhs_sorted) {
+        lhs.extend_from_slice(rhs);
+        *lhs_sorted = false;
+        return;
+    }
+
+    // Merged in place from the back, only the values of `lhs` greater than the first value of
+    // `rhs` are moved, the buffer of `lhs` is reused.
+    let mut i = lhs.len();
+    let mut j = rhs.len();
+    lhs.extend_from_slice(rhs);
+    let mut k = lhs.len();
+    while j > 0 && i > 0 {
+        k -= 1;
+        if rhs[j - 1] < lhs[i - 1] {
+            lhs[k] = lhs[i - 1];
+            i -= 1;
+        } else {
+            lhs[k] = rhs[j - 1];
+            j -= 1;
+        }
+    }
+    lhs[..j].copy_from_slice(&rhs[..j]);
+}
+
+// The `whole`-th value in order, the sorted values are indexed directly.
+fn nth_value<V: Ord + Copy>(values: &mut [V], sorted: bool, whole: usize) -> V {
+    if !sorted {
+        values.select_nth_unstable(whole);
+    }
+    values[whole]
+}
+
+/// The values are sorted before the state is serialized, and the sorted partial states are
+/// merged as sorted runs, so the final state is usually sorted when the quantiles are computed.
+/// The sorted flag is not serialized, the layout of the state is the same as the unsorted one.
 #[derive(Default, BorshSerialize, BorshDeserialize)]
 struct QuantileContState {
     pub value: Vec<OrderedFloat<f64>>,
+    #[borsh(skip)]
+    pub sorted: bool,
 }
 
 impl QuantileContState {
     fn compute_result(&mut self, whole: usize, frac: f64, value_len: usize) -> f64 {
-        self.value.as_mut_slice().select_nth_unstable(whole);
-        let value = self.value.get(whole).unwrap().0;
+        let value = nth_value(&mut self.value, self.sorted, whole).0;
         let value1 = if whole + 1 >= value_len {
             value
         } else {
-            self.value.as_mut_slice().select_nth_unstable(whole + 1);
-            self.value.get(whole + 1).unwrap().0
+            nth_value(&mut self.value, self.sorted, whole + 1).0
         };
 
         value + (value1 - value) * frac
@@ -89,14 +157,19 @@ where
     ) -> Result<()> {
         let other = T::to_owned_scalar(other).as_();
         self.value.push(other.into());
+        self.sorted = false;
         Ok(())
     }
 
     fn merge(&mut self, rhs: &Self) -> Result<()> {
-        self.value.extend(rhs.value.iter());
+        merge_values(&mut self.value, &mut self.sorted, &rhs.value, rhs.sorted);
         Ok(())
     }
 
+    fn before_serialize(&mut self) {
+        sort_values(&mut self.value, &mut self.sorted);
+    }
+
     fn merge_result(
         &mut self,
         builder: &mut R::ColumnBuilder,
@@ -158,6 +231,8 @@ where
     T::Scalar: Decimal + BorshSerialize + BorshDeserialize,
 {
     pub value: Vec<T::Scalar>,
+    #[borsh(skip)]
+    pub sorted: bool,
 }
 
 impl<T> Default for DecimalQuantileContState<T>
@@ -166,7 +241,10 @@ where
     T::Scalar: BorshDeserialize + BorshSerialize + Decimal,
 {
     fn default() -> Self {
-        Self { value: vec![] }
+        Self {
+            value: vec![],
+            sorted: false,
+        }
     }
 }
 
@@ -176,13 +254,11 @@ where
     T::Scalar: Decimal + BorshSerialize + BorshDeserialize,
 {
     fn compute_result(&mut self, whole: usize, frac: f64, value_len: usize) -> Result<T::Scalar> {
-        self.value.as_mut_slice().select_nth_unstable(whole);
-        let value = *self.value.get(whole).unwrap();
+        let value = nth_value(&mut self.value, self.sorted, whole);
         let value1 = if whole + 1 >= value_len {
             value
         } else {
-            self.value.as_mut_slice().select_nth_unstable(whole + 1);
-            *self.value.get(whole + 1).unwrap()
+            nth_value(&mut self.value, self.sorted, whole + 1)
         };
 
         let result = value1
@@ -208,18 +284,19 @@ where
         _function_data: Option<&dyn FunctionData>,
     ) -> Result<()> {
         self.value.push(T::to_owned_scalar(other));
+        self.sorted = false;
         Ok(())
     }
 
     fn merge(&mut self, rhs: &Self) -> Result<()> {
-        self.value.extend(
-            rhs.value
-                .iter()
-                .map(|v| T::to_owned_scalar(T::to_scalar_ref(v))),
-        );
+        merge_values(&mut self.value, &mut self.sorted, &rhs.value, rhs.sorted);
         Ok(())
     }
 
+    fn before_serialize(&mut self) {
+        sort_values(&mut self.value, &mut self.sorted);
+    }
+
     fn merge_result(
         &mut self,
         builder: &mut ArrayColumnBuilder<T>,
@@ -267,18 +344,19 @@ where
         _function_data: Option<&dyn FunctionData>,
     ) -> Result<()> {
         self.value.push(T::to_owned_scalar(other));
+        self.sorted = false;
         Ok(())
     }
 
     fn merge(&mut self, rhs: &Self) -> Result<()> {
-        self.value.extend(
-            rhs.value
-                .iter()
-                .map(|v| T::to_owned_scalar(T::to_scalar_ref(v))),
-        );
+        merge_values(&mut self.value, &mut self.sorted, &rhs.value, rhs.sorted);
         Ok(())
     }
 
+    fn before_serialize(&mut self) {
+        sort_values(&mut self.value, &mut self.sorted);
+    }
+
     fn merge_result(
         &mut self,
         builder: &mut T::ColumnBuilder,
@@ -305,6 +383,167 @@ where
     }
 }
 
+/// State of `quantile_cont` over `Date` and `Timestamp`. The days or micros are interpolated as
+/// integers, so the results are always valid values of the argument type.
+#[derive(Default, BorshSerialize, BorshDeserialize)]
+struct TemporalQuantileContState {
+    pub value: Vec<i64>,
+    #[borsh(skip)]
+    pub sorted: bool,
+}
+
+impl TemporalQuantileContState {
+    fn compute_result(&mut self, whole: usize, frac: f64, value_len: usize) -> i64 {
+        let value = nth_value(&mut self.value, self.sorted, whole);
+        let value1 = if whole + 1 >= value_len {
+            value
+        } else {
+            nth_value(&mut self.value, self.sorted, whole + 1)
+        };
+
+        value + ((value1 - value) as f64 * frac).round() as i64
+    }
+}
+
+impl<T> UnaryState<T, ArrayType<T>> for TemporalQuantileContState
+where
+    T: ValueType,
+    T::Scalar: AsPrimitive<i64>,
+    i64: AsPrimitive<T::Scalar>,
+{
+    fn add(
+        &mut self,
+        other: T::ScalarRef<'_>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        self.value.push(T::to_owned_scalar(other).as_());
+        self.sorted = false;
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        merge_values(&mut self.value, &mut self.sorted, &rhs.value, rhs.sorted);
+        Ok(())
+    }
+
+    fn before_serialize(&mut self) {
+        sort_values(&mut self.value, &mut self.sorted);
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut ArrayColumnBuilder<T>,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value_len = self.value.len();
+        let quantile_cont_data = unsafe {
+            function_data
+                .unwrap()
+                .as_any()
+                .downcast_ref_unchecked::<QuantileData>()
+        };
+
+        for level in quantile_cont_data.levels.iter() {
+            let (frac, whole) = libm::modf((value_len - 1) as f64 * level);
+            let whole = whole as usize;
+            if whole >= value_len {
+                builder.push_default();
+            } else {
+                let n: T::Scalar = self.compute_result(whole, frac, value_len).as_();
+                builder.put_item(T::to_scalar_ref(&n));
+            }
+        }
+        builder.commit_row();
+        Ok(())
+    }
+}
+
+impl<T> UnaryState<T, T> for TemporalQuantileContState
+where
+    T: ValueType,
+    T::Scalar: AsPrimitive<i64>,
+    i64: AsPrimitive<T::Scalar>,
+{
+    fn add(
+        &mut self,
+        other: T::ScalarRef<'_>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        self.value.push(T::to_owned_scalar(other).as_());
+        self.sorted = false;
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        merge_values(&mut self.value, &mut self.sorted, &rhs.value, rhs.sorted);
+        Ok(())
+    }
+
+    fn before_serialize(&mut self) {
+        sort_values(&mut self.value, &mut self.sorted);
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut T::ColumnBuilder,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let value_len = self.value.len();
+        let quantile_cont_data = unsafe {
+            function_data
+                .unwrap()
+                .as_any()
+                .downcast_ref_unchecked::<QuantileData>()
+        };
+
+        let (frac, whole) = libm::modf((value_len - 1) as f64 * quantile_cont_data.levels[0]);
+        let whole = whole as usize;
+        if whole >= value_len {
+            T::push_default(builder);
+        } else {
+            let n: T::Scalar = self.compute_result(whole, frac, value_len).as_();
+            T::push_item(builder, T::to_scalar_ref(&n));
+        }
+
+        Ok(())
+    }
+}
+
+fn try_create_temporal_quantile_cont_function<T>(
+    display_name: &str,
+    params: Vec<Scalar>,
+    argument: DataType,
+    levels: Vec<f64>,
+) -> Result<AggregateFunctionRef>
+where
+    T: ArgType + Send + Sync,
+    T::Scalar: AsPrimitive<i64>,
+    i64: AsPrimitive<T::Scalar>,
+{
+    if params.len() > 1 {
+        let func =
+            AggregateUnaryFunction::<TemporalQuantileContState, T, ArrayType<T>>::try_create(
+                display_name,
+                DataType::Array(Box::new(T::data_type())),
+                params,
+                argument,
+            )
+            .with_function_data(Box::new(QuantileData { levels }))
+            .with_need_drop(true);
+        Ok(Arc::new(func))
+    } else {
+        let func = AggregateUnaryFunction::<TemporalQuantileContState, T, T>::try_create(
+            display_name,
+            T::data_type(),
+            params,
+            argument,
+        )
+        .with_function_data(Box::new(QuantileData { levels }))
+        .with_need_drop(true);
+        Ok(Arc::new(func))
+    }
+}
+
 pub(crate) fn get_levels(params: &Vec<Scalar>) -> Result<Vec<f64>> {
     let levels = if params.len() == 1 {
         let level: F64 = check_number(
@@ -470,6 +709,18 @@ pub fn try_create_aggregate_quantile_cont_function<const TYPE: u8>(
                 Ok(Arc::new(func))
             }
         }
+        DataType::Date => try_create_temporal_quantile_cont_function::<DateType>(
+            display_name,
+            params,
+            arguments[0].clone(),
+            levels,
+        ),
+        DataType::Timestamp => try_create_temporal_quantile_cont_function::<TimestampType>(
+            display_name,
+            params,
+            arguments[0].clone(),
+            levels,
+        ),
 
         _ => Err(ErrorCode::BadDataValueType(format!(
             "{} does not support type '{:?}'",
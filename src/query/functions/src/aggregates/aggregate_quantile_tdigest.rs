@@ -399,7 +399,49 @@ where T: Number + AsPrimitive<f64>
         params: Vec<Scalar>,
         arguments: Vec<DataType>,
     ) -> Result<Arc<dyn AggregateFunction>> {
-        let levels = if params.len() == 1 {
+        let levels = get_levels(params)?;
+        let func = AggregateQuantileTDigestFunction::<T> {
+            display_name: display_name.to_string(),
+            return_type,
+            levels,
+            _arguments: arguments,
+            _t: PhantomData,
+        };
+        Ok(Arc::new(func))
+    }
+}
+
+/// Get the quantile levels from the params, defaults to the median.
+pub(crate) fn get_levels(params: Vec<Scalar>) -> Result<Vec<f64>> {
+    if params.len() == 1 {
+        let level: F64 = check_number(
+            None,
+            &FunctionContext::default(),
+            &Expr::<usize>::Cast {
+                span: None,
+                is_try: false,
+                expr: Box::new(Expr::Constant {
+                    span: None,
+                    scalar: params[0].clone(),
+                    data_type: params[0].as_ref().infer_data_type(),
+                }),
+                dest_type: DataType::Number(NumberDataType::Float64),
+            },
+            &BUILTIN_FUNCTIONS,
+        )?;
+        let level = level.0;
+        if !(0.0..=1.0).contains(&level) {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "level range between [0, 1], got: {:?}",
+                level
+            )));
+        }
+        Ok(vec![level])
+    } else if params.is_empty() {
+        Ok(vec![0.5f64])
+    } else {
+        let mut levels = Vec::with_capacity(params.len());
+        for param in params {
             let level: F64 = check_number(
                 None,
                 &FunctionContext::default(),
@@ -408,8 +450,8 @@ where T: Number + AsPrimitive<f64>
                     is_try: false,
                     expr: Box::new(Expr::Constant {
                         span: None,
-                        scalar: params[0].clone(),
-                        data_type: params[0].as_ref().infer_data_type(),
+                        scalar: param.clone(),
+                        data_type: param.as_ref().infer_data_type(),
                     }),
                     dest_type: DataType::Number(NumberDataType::Float64),
                 },
@@ -418,50 +460,13 @@ where T: Number + AsPrimitive<f64>
             let level = level.0;
             if !(0.0..=1.0).contains(&level) {
                 return Err(ErrorCode::BadDataValueType(format!(
-                    "level range between [0, 1], got: {:?}",
+                    "level range between [0, 1], got: {:?} in levels",
                     level
                 )));
             }
-            vec![level]
-        } else if params.is_empty() {
-            vec![0.5f64]
-        } else {
-            let mut levels = Vec::with_capacity(params.len());
-            for param in params {
-                let level: F64 = check_number(
-                    None,
-                    &FunctionContext::default(),
-                    &Expr::<usize>::Cast {
-                        span: None,
-                        is_try: false,
-                        expr: Box::new(Expr::Constant {
-                            span: None,
-                            scalar: param.clone(),
-                            data_type: param.as_ref().infer_data_type(),
-                        }),
-                        dest_type: DataType::Number(NumberDataType::Float64),
-                    },
-                    &BUILTIN_FUNCTIONS,
-                )?;
-                let level = level.0;
-                if !(0.0..=1.0).contains(&level) {
-                    return Err(ErrorCode::BadDataValueType(format!(
-                        "level range between [0, 1], got: {:?} in levels",
-                        level
-                    )));
-                }
-                levels.push(level);
-            }
-            levels
-        };
-        let func = AggregateQuantileTDigestFunction::<T> {
-            display_name: display_name.to_string(),
-            return_type,
-            levels,
-            _arguments: arguments,
-            _t: PhantomData,
-        };
-        Ok(Arc::new(func))
+            levels.push(level);
+        }
+        Ok(levels)
     }
 }
 
@@ -0,0 +1,402 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::number::Number;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::NumberType;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::with_number_mapped_type;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::InputColumns;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use num_traits::AsPrimitive;
+
+use super::aggregate_covariance::AggregateCovarianceState;
+use super::borsh_deserialize_state;
+use super::borsh_serialize_state;
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionFeatures;
+use crate::aggregates::aggregator_common::assert_binary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+/// The co-moments of `y` and `x` accumulated the same way as `covar_pop`, with the sums of the
+/// squared differences from the means of `y` and `x`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AggregateRegressionState {
+    // The left is `y` and the right is `x`.
+    pub covariance: AggregateCovarianceState,
+    pub y_m2: f64,
+    pub x_m2: f64,
+}
+
+impl AggregateRegressionState {
+    #[inline(always)]
+    fn add(&mut self, y: f64, x: f64) {
+        let y_mean = self.covariance.left_mean;
+        let x_mean = self.covariance.right_mean;
+        self.covariance.add(y, x);
+
+        self.y_m2 += (y - y_mean) * (y - self.covariance.left_mean);
+        self.x_m2 += (x - x_mean) * (x - self.covariance.right_mean);
+    }
+
+    // The same as the co-moments, the formula III.6 in the paper of `AggregateCovarianceState`.
+    #[inline(always)]
+    fn merge(&mut self, other: &Self) {
+        let total = self.covariance.count + other.covariance.count;
+        if total == 0 {
+            return;
+        }
+
+        let factor = self.covariance.count as f64 * other.covariance.count as f64 / total as f64;
+        let y_delta = self.covariance.left_mean - other.covariance.left_mean;
+        let x_delta = self.covariance.right_mean - other.covariance.right_mean;
+        self.y_m2 += other.y_m2 + y_delta * y_delta * factor;
+        self.x_m2 += other.x_m2 + x_delta * x_delta * factor;
+
+        self.covariance.merge(&other.covariance);
+    }
+
+    // The slope of the least-squares-fit line, `None` if there are less than two rows or all
+    // the `x` are the same.
+    fn slope(&self) -> Option<f64> {
+        if self.covariance.count < 2 || self.x_m2 == 0.0 {
+            None
+        } else {
+            Some(self.covariance.co_moments / self.x_m2)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateRegressionFunction<T0, T1, R> {
+    display_name: String,
+    _t0: PhantomData<T0>,
+    _t1: PhantomData<T1>,
+    _r: PhantomData<R>,
+}
+
+impl<T0, T1, R> AggregateFunction for AggregateRegressionFunction<T0, T1, R>
+where
+    T0: Number + AsPrimitive<f64>,
+    T1: Number + AsPrimitive<f64>,
+    R: AggregateRegression,
+{
+    fn name(&self) -> &str {
+        R::name()
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(R::return_type())
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateRegressionState {
+            covariance: AggregateCovarianceState {
+                count: 0,
+                co_moments: 0.0,
+                left_mean: 0.0,
+                right_mean: 0.0,
+            },
+            y_m2: 0.0,
+            x_m2: 0.0,
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateRegressionState>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: InputColumns,
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateRegressionState>();
+        let y = NumberType::<T0>::try_downcast_column(&columns[0]).unwrap();
+        let x = NumberType::<T1>::try_downcast_column(&columns[1]).unwrap();
+
+        match validity {
+            Some(bitmap) => {
+                y.iter()
+                    .zip(x.iter())
+                    .zip(bitmap.iter())
+                    .for_each(|((y_val, x_val), valid)| {
+                        if valid {
+                            state.add(y_val.as_(), x_val.as_());
+                        }
+                    });
+            }
+            None => {
+                y.iter().zip(x.iter()).for_each(|(y_val, x_val)| {
+                    state.add(y_val.as_(), x_val.as_());
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: InputColumns,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let y = NumberType::<T0>::try_downcast_column(&columns[0]).unwrap();
+        let x = NumberType::<T1>::try_downcast_column(&columns[1]).unwrap();
+
+        y.iter()
+            .zip(x.iter())
+            .zip(places.iter())
+            .for_each(|((y_val, x_val), place)| {
+                let place = place.next(offset);
+                let state = place.get::<AggregateRegressionState>();
+                state.add(y_val.as_(), x_val.as_());
+            });
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: InputColumns, row: usize) -> Result<()> {
+        let y = NumberType::<T0>::try_downcast_column(&columns[0]).unwrap();
+        let x = NumberType::<T1>::try_downcast_column(&columns[1]).unwrap();
+
+        let y_val = unsafe { y.get_unchecked(row) };
+        let x_val = unsafe { x.get_unchecked(row) };
+
+        let state = place.get::<AggregateRegressionState>();
+        state.add(y_val.as_(), x_val.as_());
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<AggregateRegressionState>();
+        borsh_serialize_state(writer, state)
+    }
+
+    fn merge(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateRegressionState>();
+        let rhs: AggregateRegressionState = borsh_deserialize_state(reader)?;
+        state.merge(&rhs);
+        Ok(())
+    }
+
+    fn merge_states(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateRegressionState>();
+        let other = rhs.get::<AggregateRegressionState>();
+        state.merge(other);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<AggregateRegressionState>();
+        R::merge_result(state, builder);
+        Ok(())
+    }
+}
+
+impl<T0, T1, R> fmt::Display for AggregateRegressionFunction<T0, T1, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl<T0, T1, R> AggregateRegressionFunction<T0, T1, R>
+where
+    T0: Number + AsPrimitive<f64>,
+    T1: Number + AsPrimitive<f64>,
+    R: AggregateRegression,
+{
+    pub fn try_create(
+        display_name: &str,
+        _arguments: Vec<DataType>,
+    ) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            _t0: PhantomData,
+            _t1: PhantomData,
+            _r: PhantomData,
+        }))
+    }
+}
+
+pub fn try_create_aggregate_regression<R: AggregateRegression>(
+    display_name: &str,
+    _params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    assert_binary_arguments(display_name, arguments.len())?;
+
+    with_number_mapped_type!(|NUM_TYPE0| match &arguments[0] {
+        DataType::Number(NumberDataType::NUM_TYPE0) =>
+            with_number_mapped_type!(|NUM_TYPE1| match &arguments[1] {
+                DataType::Number(NumberDataType::NUM_TYPE1) => {
+                    return AggregateRegressionFunction::<NUM_TYPE0, NUM_TYPE1, R>::try_create(
+                        display_name,
+                        arguments,
+                    );
+                }
+                _ => (),
+            }),
+        _ => (),
+    });
+
+    Err(ErrorCode::BadDataValueType(format!(
+        "Expected number data type, but got {:?}",
+        arguments
+    )))
+}
+
+pub trait AggregateRegression: Send + Sync + 'static {
+    fn name() -> &'static str;
+
+    fn return_type() -> DataType;
+
+    fn merge_result(state: &AggregateRegressionState, builder: &mut ColumnBuilder);
+}
+
+fn push_float64(builder: &mut ColumnBuilder, value: Option<f64>) {
+    match value {
+        Some(value) => builder.push(ScalarRef::Number(NumberScalar::Float64(value.into()))),
+        None => builder.push(ScalarRef::Null),
+    }
+}
+
+fn regression_desc<R: AggregateRegression>(
+    returns_default_when_only_null: bool,
+) -> AggregateFunctionDescription {
+    let features = AggregateFunctionFeatures {
+        returns_default_when_only_null,
+        is_decomposable: true,
+        ..Default::default()
+    };
+    AggregateFunctionDescription::creator_with_features(
+        Box::new(try_create_aggregate_regression::<R>),
+        features,
+    )
+}
+
+// The slope of the least-squares-fit line.
+struct AggregateRegrSlopeImpl;
+
+impl AggregateRegression for AggregateRegrSlopeImpl {
+    fn name() -> &'static str {
+        "AggregateRegrSlopeFunction"
+    }
+
+    fn return_type() -> DataType {
+        DataType::Number(NumberDataType::Float64).wrap_nullable()
+    }
+
+    fn merge_result(state: &AggregateRegressionState, builder: &mut ColumnBuilder) {
+        push_float64(builder, state.slope());
+    }
+}
+
+pub fn aggregate_regr_slope_desc() -> AggregateFunctionDescription {
+    regression_desc::<AggregateRegrSlopeImpl>(false)
+}
+
+// The y-intercept of the least-squares-fit line.
+struct AggregateRegrInterceptImpl;
+
+impl AggregateRegression for AggregateRegrInterceptImpl {
+    fn name() -> &'static str {
+        "AggregateRegrInterceptFunction"
+    }
+
+    fn return_type() -> DataType {
+        DataType::Number(NumberDataType::Float64).wrap_nullable()
+    }
+
+    fn merge_result(state: &AggregateRegressionState, builder: &mut ColumnBuilder) {
+        let intercept = state
+            .slope()
+            .map(|slope| state.covariance.left_mean - slope * state.covariance.right_mean);
+        push_float64(builder, intercept);
+    }
+}
+
+pub fn aggregate_regr_intercept_desc() -> AggregateFunctionDescription {
+    regression_desc::<AggregateRegrInterceptImpl>(false)
+}
+
+// The square of the correlation coefficient, it's 1 if all the `y` are the same.
+struct AggregateRegrR2Impl;
+
+impl AggregateRegression for AggregateRegrR2Impl {
+    fn name() -> &'static str {
+        "AggregateRegrR2Function"
+    }
+
+    fn return_type() -> DataType {
+        DataType::Number(NumberDataType::Float64).wrap_nullable()
+    }
+
+    fn merge_result(state: &AggregateRegressionState, builder: &mut ColumnBuilder) {
+        let r2 = if state.covariance.count < 2 || state.x_m2 == 0.0 {
+            None
+        } else if state.y_m2 == 0.0 {
+            Some(1.0)
+        } else {
+            let co_moments = state.covariance.co_moments;
+            Some(co_moments * co_moments / (state.x_m2 * state.y_m2))
+        };
+        push_float64(builder, r2);
+    }
+}
+
+pub fn aggregate_regr_r2_desc() -> AggregateFunctionDescription {
+    regression_desc::<AggregateRegrR2Impl>(false)
+}
+
+// The number of rows where neither input is NULL.
+struct AggregateRegrCountImpl;
+
+impl AggregateRegression for AggregateRegrCountImpl {
+    fn name() -> &'static str {
+        "AggregateRegrCountFunction"
+    }
+
+    fn return_type() -> DataType {
+        DataType::Number(NumberDataType::UInt64)
+    }
+
+    fn merge_result(state: &AggregateRegressionState, builder: &mut ColumnBuilder) {
+        builder.push(ScalarRef::Number(NumberScalar::UInt64(
+            state.covariance.count,
+        )));
+    }
+}
+
+pub fn aggregate_regr_count_desc() -> AggregateFunctionDescription {
+    regression_desc::<AggregateRegrCountImpl>(true)
+}
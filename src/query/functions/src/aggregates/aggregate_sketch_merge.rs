@@ -0,0 +1,282 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Aggregate functions merging the sketches computed offline, e.g. by pre-aggregated rollups.
+//!
+//! The sketches are the serialized states of `quantile_tdigest`/`median_tdigest` and
+//! `approx_count_distinct`, which can be produced by the `_state` combinator, such as
+//! `quantile_tdigest_state(x)`. All integers and floats are little-endian, a `Vec` is
+//! encoded as its length in `u32` followed by the elements.
+//!
+//! The t-digest sketch accepted by `tdigest_merge` is the Borsh encoding of:
+//!
+//! | field                   | type       |
+//! |-------------------------|------------|
+//! | epsilon                 | `u32`      |
+//! | max_centroids           | `u64`      |
+//! | total_weight            | `f64`      |
+//! | weights                 | `Vec<f64>` |
+//! | means                   | `Vec<f64>` |
+//! | unmerged_total_weight   | `f64`      |
+//! | unmerged_weights        | `Vec<f64>` |
+//! | unmerged_means          | `Vec<f64>` |
+//! | min                     | `f64`      |
+//! | max                     | `f64`      |
+//!
+//! The HyperLogLog sketch accepted by `hll_merge` is the Borsh encoding of
+//! `simple_hll::HyperLogLog<14>`, the default precision of `approx_count_distinct`.
+
+use std::alloc::Layout;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use borsh::BorshDeserialize;
+use borsh::BorshSerialize;
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::BinaryType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::InputColumns;
+use databend_common_expression::Scalar;
+use simple_hll::HyperLogLog;
+
+use super::aggregate_function_factory::AggregateFunctionDescription;
+use super::aggregate_quantile_tdigest::get_levels;
+use super::aggregate_quantile_tdigest::QuantileTDigestState;
+use super::borsh_deserialize_state;
+use super::borsh_serialize_state;
+use super::AggregateUnaryFunction;
+use super::FunctionData;
+use super::StateAddr;
+use super::UnaryState;
+use crate::aggregates::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+
+const HLL_P: usize = 14;
+
+fn check_sketch_argument(display_name: &str, arguments: &[DataType]) -> Result<()> {
+    assert_unary_arguments(display_name, arguments.len())?;
+    if arguments[0].remove_nullable() != DataType::Binary {
+        return Err(ErrorCode::BadDataValueType(format!(
+            "{} just support binary sketch, but got '{:?}'",
+            display_name, arguments[0]
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct AggregateTDigestMergeFunction {
+    display_name: String,
+    return_type: DataType,
+    levels: Vec<f64>,
+}
+
+impl Display for AggregateTDigestMergeFunction {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl AggregateTDigestMergeFunction {
+    fn merge_sketch(state: &mut QuantileTDigestState, mut sketch: &[u8]) -> Result<()> {
+        let mut rhs: QuantileTDigestState = borsh_deserialize_state(&mut sketch)?;
+        state.merge(&mut rhs)
+    }
+}
+
+impl AggregateFunction for AggregateTDigestMergeFunction {
+    fn name(&self) -> &str {
+        "AggregateTDigestMergeFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(QuantileTDigestState::new)
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<QuantileTDigestState>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: InputColumns,
+        validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = BinaryType::try_downcast_column(&columns[0]).unwrap();
+        let state = place.get::<QuantileTDigestState>();
+        match validity {
+            Some(bitmap) => {
+                for (sketch, is_valid) in column.iter().zip(bitmap.iter()) {
+                    if is_valid {
+                        Self::merge_sketch(state, sketch)?;
+                    }
+                }
+            }
+            None => {
+                for sketch in column.iter() {
+                    Self::merge_sketch(state, sketch)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: InputColumns, row: usize) -> Result<()> {
+        let column = BinaryType::try_downcast_column(&columns[0]).unwrap();
+        if let Some(sketch) = BinaryType::index_column(&column, row) {
+            let state = place.get::<QuantileTDigestState>();
+            Self::merge_sketch(state, sketch)?;
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: InputColumns,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = BinaryType::try_downcast_column(&columns[0]).unwrap();
+        for (sketch, place) in column.iter().zip(places.iter()) {
+            let state = place.next(offset).get::<QuantileTDigestState>();
+            Self::merge_sketch(state, sketch)?;
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = place.get::<QuantileTDigestState>();
+        borsh_serialize_state(writer, state)
+    }
+
+    fn merge(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<QuantileTDigestState>();
+        let mut rhs: QuantileTDigestState = borsh_deserialize_state(reader)?;
+        state.merge(&mut rhs)
+    }
+
+    fn merge_states(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<QuantileTDigestState>();
+        let other = rhs.get::<QuantileTDigestState>();
+        state.merge(other)
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let state = place.get::<QuantileTDigestState>();
+        state.merge_result(builder, self.levels.clone())
+    }
+
+    fn need_manual_drop_state(&self) -> bool {
+        true
+    }
+
+    unsafe fn drop_state(&self, place: StateAddr) {
+        let state = place.get::<QuantileTDigestState>();
+        std::ptr::drop_in_place(state);
+    }
+}
+
+pub fn try_create_aggregate_tdigest_merge_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    check_sketch_argument(display_name, &arguments)?;
+
+    let return_type = if params.len() > 1 {
+        DataType::Array(Box::new(DataType::Number(NumberDataType::Float64)))
+    } else {
+        DataType::Number(NumberDataType::Float64)
+    };
+    let func = AggregateTDigestMergeFunction {
+        display_name: display_name.to_string(),
+        return_type,
+        levels: get_levels(params)?,
+    };
+    Ok(Arc::new(func))
+}
+
+pub fn aggregate_tdigest_merge_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_tdigest_merge_function))
+}
+
+/// Wraps the state of `approx_count_distinct`, the serialized bytes are the same.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct HllMergeState(HyperLogLog<HLL_P>);
+
+impl UnaryState<BinaryType, UInt64Type> for HllMergeState {
+    fn add(&mut self, mut sketch: &[u8], _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        let rhs: HyperLogLog<HLL_P> = borsh_deserialize_state(&mut sketch)?;
+        self.0.merge(&rhs);
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.0.merge(&rhs.0);
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<u64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        builder.push(self.0.count() as u64);
+        Ok(())
+    }
+}
+
+pub fn try_create_aggregate_hll_merge_function(
+    display_name: &str,
+    params: Vec<Scalar>,
+    arguments: Vec<DataType>,
+) -> Result<AggregateFunctionRef> {
+    check_sketch_argument(display_name, &arguments)?;
+
+    let func = AggregateUnaryFunction::<HllMergeState, BinaryType, UInt64Type>::try_create(
+        display_name,
+        DataType::Number(NumberDataType::UInt64),
+        params,
+        arguments[0].clone(),
+    )
+    .with_need_drop(true);
+    Ok(Arc::new(func))
+}
+
+pub fn aggregate_hll_merge_function_desc() -> AggregateFunctionDescription {
+    let features = super::aggregate_function_factory::AggregateFunctionFeatures {
+        returns_default_when_only_null: true,
+        ..Default::default()
+    };
+
+    AggregateFunctionDescription::creator_with_features(
+        Box::new(try_create_aggregate_hll_merge_function),
+        features,
+    )
+}
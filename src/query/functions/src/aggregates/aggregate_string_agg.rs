@@ -35,9 +35,19 @@ use super::StateAddr;
 use crate::aggregates::assert_variadic_arguments;
 use crate::aggregates::AggregateFunction;
 
+/// The aggregated values are kept as raw bytes, so input that is not valid UTF-8
+/// will be passed through as is instead of panicking.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct StringAggState {
-    values: String,
+    values: Vec<u8>,
+}
+
+impl StringAggState {
+    #[inline]
+    fn push(&mut self, value: &[u8], delimiter: &str) {
+        self.values.extend_from_slice(value);
+        self.values.extend_from_slice(delimiter.as_bytes());
+    }
 }
 
 #[derive(Clone)]
@@ -56,9 +66,7 @@ impl AggregateFunction for AggregateStringAggFunction {
     }
 
     fn init_state(&self, place: StateAddr) {
-        place.write(|| StringAggState {
-            values: String::new(),
-        });
+        place.write(|| StringAggState { values: Vec::new() });
     }
 
     fn state_layout(&self) -> Layout {
@@ -76,17 +84,18 @@ impl AggregateFunction for AggregateStringAggFunction {
         let state = place.get::<StringAggState>();
         match validity {
             Some(validity) => {
-                column.iter().zip(validity.iter()).for_each(|(v, b)| {
-                    if b {
-                        state.values.push_str(v);
-                        state.values.push_str(&self.delimiter);
-                    }
-                });
+                column
+                    .iter_binary()
+                    .zip(validity.iter())
+                    .for_each(|(v, b)| {
+                        if b {
+                            state.push(v, &self.delimiter);
+                        }
+                    });
             }
             None => {
-                column.iter().for_each(|v| {
-                    state.values.push_str(v);
-                    state.values.push_str(&self.delimiter);
+                column.iter_binary().for_each(|v| {
+                    state.push(v, &self.delimiter);
                 });
             }
         }
@@ -101,23 +110,23 @@ impl AggregateFunction for AggregateStringAggFunction {
         _input_rows: usize,
     ) -> Result<()> {
         let column = StringType::try_downcast_column(&columns[0]).unwrap();
-        let column_iter = StringType::iter_column(&column);
-        column_iter.zip(places.iter()).for_each(|(v, place)| {
-            let addr = place.next(offset);
-            let state = addr.get::<StringAggState>();
-            state.values.push_str(v);
-            state.values.push_str(&self.delimiter);
-        });
+        column
+            .iter_binary()
+            .zip(places.iter())
+            .for_each(|(v, place)| {
+                let addr = place.next(offset);
+                let state = addr.get::<StringAggState>();
+                state.push(v, &self.delimiter);
+            });
         Ok(())
     }
 
     fn accumulate_row(&self, place: StateAddr, columns: InputColumns, row: usize) -> Result<()> {
         let column = StringType::try_downcast_column(&columns[0]).unwrap();
-        let v = StringType::index_column(&column, row);
-        if let Some(v) = v {
+        if row < column.len() {
+            let v = unsafe { column.index_unchecked_bytes(row) };
             let state = place.get::<StringAggState>();
-            state.values.push_str(v);
-            state.values.push_str(&self.delimiter);
+            state.push(v, &self.delimiter);
         }
         Ok(())
     }
@@ -131,14 +140,14 @@ impl AggregateFunction for AggregateStringAggFunction {
     fn merge(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
         let state = place.get::<StringAggState>();
         let rhs: StringAggState = borsh_deserialize_state(reader)?;
-        state.values.push_str(&rhs.values);
+        state.values.extend_from_slice(&rhs.values);
         Ok(())
     }
 
     fn merge_states(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
         let state = place.get::<StringAggState>();
         let other = rhs.get::<StringAggState>();
-        state.values.push_str(&other.values);
+        state.values.extend_from_slice(&other.values);
         Ok(())
     }
 
@@ -147,7 +156,8 @@ impl AggregateFunction for AggregateStringAggFunction {
         let builder = StringType::try_downcast_builder(builder).unwrap();
         if !state.values.is_empty() {
             let len = state.values.len() - self.delimiter.len();
-            builder.put_str(&state.values[..len]);
+            // Write the bytes directly, `put_slice` would reject non-UTF-8 data in debug builds.
+            builder.data.extend_from_slice(&state.values[..len]);
         }
         builder.commit_row();
         Ok(())
@@ -12,15 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::any::Any;
+use std::sync::Arc;
+
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::buffer::Buffer;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_expression::types::decimal::*;
+use databend_common_expression::types::nullable::NullableColumnBuilder;
 use databend_common_expression::types::number::*;
 use databend_common_expression::types::*;
 use databend_common_expression::utils::arithmetics_type::ResultTypeOfUnary;
+use databend_common_expression::with_float_mapped_type;
+use databend_common_expression::with_integer_mapped_type;
 use databend_common_expression::with_number_mapped_type;
 use databend_common_expression::AggregateFunctionRef;
 use databend_common_expression::Column;
@@ -80,20 +87,41 @@ where
     }
 }
 
-impl<T, N> UnaryState<T, N> for NumberSumState<N>
+impl<T, N> UnaryState<NumberType<T>, N> for NumberSumState<N>
 where
-    T: ValueType + Sync + Send,
+    T: Number + AsPrimitive<N::Scalar>,
     N: ValueType,
-    T::Scalar: Number + AsPrimitive<N::Scalar>,
     N::Scalar: Number + AsPrimitive<f64> + BorshSerialize + BorshDeserialize + std::ops::AddAssign,
 {
-    fn add(
-        &mut self,
-        other: T::ScalarRef<'_>,
+    fn add(&mut self, other: T, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.value += other.as_();
+        Ok(())
+    }
+
+    fn add_keys(
+        places: &[StateAddr],
+        offset: usize,
+        column: &Buffer<T>,
         _function_data: Option<&dyn FunctionData>,
     ) -> Result<()> {
-        let other = T::to_owned_scalar(other).as_();
-        self.value += other;
+        // The rows of a low-cardinality GROUP BY tend to come in runs of the same group,
+        // each run is summed in a tight loop and added to the state once.
+        let mut start = 0;
+        while start < places.len() {
+            let place = places[start];
+            let mut end = start + 1;
+            while end < places.len() && places[end].addr() == place.addr() {
+                end += 1;
+            }
+
+            let mut sum = N::Scalar::default();
+            for value in &column[start..end] {
+                sum += value.as_();
+            }
+            let state: &mut Self = place.next(offset).get::<Self>();
+            state.value += sum;
+            start = end;
+        }
         Ok(())
     }
 
@@ -112,6 +140,108 @@ where
     }
 }
 
+/// The sum of booleans counts the trues.
+impl UnaryState<BooleanType, UInt64Type> for NumberSumState<UInt64Type> {
+    fn add(&mut self, other: bool, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.value += other as u64;
+        Ok(())
+    }
+
+    fn add_batch(
+        &mut self,
+        column: &Bitmap,
+        validity: Option<&Bitmap>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let falses = match validity {
+            Some(validity) => (column & validity).unset_bits(),
+            None => column.unset_bits(),
+        };
+        self.value += (column.len() - falses) as u64;
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.value += rhs.value;
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<u64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        builder.push(self.value);
+        Ok(())
+    }
+}
+
+/// The float sum ignoring the NaN and infinite inputs like NULLs, or raising an error on
+/// them if `ERROR` is set.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct FloatSumState<const ERROR: bool> {
+    pub value: F64,
+}
+
+impl<const ERROR: bool> FloatSumState<ERROR> {
+    #[inline]
+    fn add_value(&mut self, value: F64) -> Result<()> {
+        if value.0.is_finite() {
+            self.value += value;
+        } else if ERROR {
+            return Err(ErrorCode::BadArguments(format!(
+                "sum got the non-finite value {value}, set `float_sum_non_finite` to 'skip' to ignore it"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<T, const ERROR: bool> UnaryState<NumberType<T>, Float64Type> for FloatSumState<ERROR>
+where T: Number + AsPrimitive<F64>
+{
+    fn add(&mut self, other: T, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.add_value(other.as_())
+    }
+
+    fn add_batch(
+        &mut self,
+        column: &Buffer<T>,
+        validity: Option<&Bitmap>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        match validity {
+            Some(bitmap) if bitmap.unset_bits() > 0 => {
+                for (value, is_valid) in column.iter().zip(bitmap.iter()) {
+                    if is_valid {
+                        self.add_value(value.as_())?;
+                    }
+                }
+            }
+            _ => {
+                for value in column.iter() {
+                    self.add_value(value.as_())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.value += rhs.value;
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<F64>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        builder.push(self.value);
+        Ok(())
+    }
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct DecimalSumState<const OVERFLOW: bool, T>
 where
@@ -133,6 +263,45 @@ where
     }
 }
 
+impl<const OVERFLOW: bool, T> DecimalSumState<OVERFLOW, T>
+where
+    T: ValueType,
+    T::Scalar: Decimal + std::ops::AddAssign + BorshSerialize + BorshDeserialize,
+{
+    // The sum is checked against the precision of the result, or the whole range of the
+    // decimal type without it, e.g. the merged states and the sum state reused by `avg`.
+    fn check_overflow(&self, function_data: Option<&dyn FunctionData>) -> Result<()> {
+        let (min, max) =
+            match function_data.and_then(|data| data.as_any().downcast_ref::<DecimalSumData>()) {
+                Some(data) => (
+                    T::Scalar::min_for_precision(data.precision),
+                    T::Scalar::max_for_precision(data.precision),
+                ),
+                None => (T::Scalar::MIN, T::Scalar::MAX),
+            };
+        if self.value > max || self.value < min {
+            return Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: {:?} not in [{}, {}]",
+                self.value, min, max,
+            )));
+        }
+        Ok(())
+    }
+
+    fn checked_add(&mut self, other: T::Scalar) -> Result<()> {
+        match self.value.checked_add(other) {
+            Some(value) => {
+                self.value = value;
+                Ok(())
+            }
+            None => Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: {:?} + {:?}",
+                self.value, other
+            ))),
+        }
+    }
+}
+
 impl<const OVERFLOW: bool, T> UnaryState<T, T> for DecimalSumState<OVERFLOW, T>
 where
     T: ValueType,
@@ -141,34 +310,206 @@ where
     fn add(
         &mut self,
         other: T::ScalarRef<'_>,
-        _function_data: Option<&dyn FunctionData>,
+        function_data: Option<&dyn FunctionData>,
     ) -> Result<()> {
-        self.value += T::to_owned_scalar(other);
-        if OVERFLOW && (self.value > T::Scalar::MAX || self.value < T::Scalar::MIN) {
-            return Err(ErrorCode::Overflow(format!(
-                "Decimal overflow: {:?} not in [{}, {}]",
-                self.value,
-                T::Scalar::MIN,
-                T::Scalar::MAX,
-            )));
+        if OVERFLOW {
+            self.checked_add(T::to_owned_scalar(other))?;
+        } else {
+            self.value += T::to_owned_scalar(other);
         }
-        Ok(())
+        self.check_overflow(function_data)
+    }
+
+    // The range is checked once per batch. The OVERFLOW variant is used when the precision
+    // of the result is the max one, the underlying integer may overflow within a batch
+    // then, so the values are added with checks.
+    fn add_batch(
+        &mut self,
+        column: &T::Column,
+        validity: Option<&Bitmap>,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        match validity {
+            Some(bitmap) if bitmap.unset_bits() > 0 => {
+                for (value, is_valid) in T::iter_column(column).zip(bitmap.iter()) {
+                    if is_valid {
+                        if OVERFLOW {
+                            self.checked_add(T::to_owned_scalar(value))?;
+                        } else {
+                            self.value += T::to_owned_scalar(value);
+                        }
+                    }
+                }
+            }
+            _ => {
+                for value in T::iter_column(column) {
+                    if OVERFLOW {
+                        self.checked_add(T::to_owned_scalar(value))?;
+                    } else {
+                        self.value += T::to_owned_scalar(value);
+                    }
+                }
+            }
+        }
+        self.check_overflow(function_data)
     }
 
     fn merge(&mut self, rhs: &Self) -> Result<()> {
         self.add(T::to_scalar_ref(&rhs.value), None)
     }
 
+    // The merged states are only checked against the whole range, the precision of the
+    // result is checked once the sum is final.
     fn merge_result(
         &mut self,
         builder: &mut T::ColumnBuilder,
-        _function_data: Option<&dyn FunctionData>,
+        function_data: Option<&dyn FunctionData>,
     ) -> Result<()> {
+        self.check_overflow(function_data)?;
         T::push_item(builder, T::to_scalar_ref(&self.value));
         Ok(())
     }
 }
 
+/// The integers are summed into a `Decimal128` with the scale 0, the sum of
+/// 64-bit integers can't overflow it within practical ranges.
+impl<const OVERFLOW: bool, N> UnaryState<NumberType<N>, Decimal128Type>
+    for DecimalSumState<OVERFLOW, Decimal128Type>
+where N: Number + AsPrimitive<i128>
+{
+    fn add(&mut self, other: N, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.value += AsPrimitive::<i128>::as_(other);
+        self.check_overflow(None)
+    }
+
+    fn add_batch(
+        &mut self,
+        column: &Buffer<N>,
+        validity: Option<&Bitmap>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let sum: i128 = match validity {
+            Some(bitmap) if bitmap.unset_bits() > 0 => column
+                .iter()
+                .zip(bitmap.iter())
+                .filter(|(_, is_valid)| *is_valid)
+                .map(|(value, _)| AsPrimitive::<i128>::as_(*value))
+                .sum(),
+            _ => column
+                .iter()
+                .map(|value| AsPrimitive::<i128>::as_(*value))
+                .sum(),
+        };
+        self.value += sum;
+        self.check_overflow(None)
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        self.value += rhs.value;
+        self.check_overflow(None)
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut Vec<i128>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        builder.push(self.value);
+        Ok(())
+    }
+}
+
+/// The element-wise sums of the arrays, all the arrays of a group must be of the same length.
+/// The result is NULL if there is no array.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ArraySumState<TSum> {
+    sums: Option<Vec<TSum>>,
+}
+
+impl<TSum> Default for ArraySumState<TSum> {
+    fn default() -> Self {
+        ArraySumState { sums: None }
+    }
+}
+
+impl<TSum> ArraySumState<TSum>
+where TSum: Number + std::ops::AddAssign
+{
+    fn add_sums(&mut self, values: impl ExactSizeIterator<Item = TSum>) -> Result<()> {
+        match &mut self.sums {
+            None => self.sums = Some(values.collect()),
+            Some(sums) => {
+                if sums.len() != values.len() {
+                    return Err(ErrorCode::BadDataArrayLength(format!(
+                        "sum of arrays expects the arrays of the same length, but got {} and {}",
+                        sums.len(),
+                        values.len()
+                    )));
+                }
+                for (sum, value) in sums.iter_mut().zip(values) {
+                    *sum += value;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, TSum> UnaryState<ArrayType<NumberType<T>>, NullableType<ArrayType<NumberType<TSum>>>>
+    for ArraySumState<TSum>
+where
+    T: Number + AsPrimitive<TSum>,
+    TSum: Number + BorshSerialize + BorshDeserialize + std::ops::AddAssign,
+{
+    fn add(&mut self, other: Buffer<T>, _function_data: Option<&dyn FunctionData>) -> Result<()> {
+        self.add_sums(other.iter().map(|value| value.as_()))
+    }
+
+    fn merge(&mut self, rhs: &Self) -> Result<()> {
+        if let Some(rhs) = &rhs.sums {
+            self.add_sums(rhs.iter().copied())?;
+        }
+        Ok(())
+    }
+
+    fn merge_result(
+        &mut self,
+        builder: &mut NullableColumnBuilder<ArrayType<NumberType<TSum>>>,
+        _function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        match &self.sums {
+            Some(sums) => builder.push(sums.clone().into()),
+            None => builder.push_null(),
+        }
+        Ok(())
+    }
+}
+
+/// The sum of `DECIMAL(P, S)` is `DECIMAL(P + 10, S)`, the precision is capped at the max
+/// precision of the decimal type of the argument.
+const DECIMAL_SUM_PRECISION_GROWTH: u8 = 10;
+
+struct DecimalSumData {
+    // The precision of the result, the sum is checked against it.
+    pub precision: u8,
+}
+
+impl FunctionData for DecimalSumData {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn decimal_sum_size(size: &DecimalSize, max_precision: u8) -> DecimalSize {
+    DecimalSize {
+        precision: size
+            .precision
+            .saturating_add(DECIMAL_SUM_PRECISION_GROWTH)
+            .min(max_precision),
+        scale: size.scale,
+    }
+}
+
 pub fn try_create_aggregate_sum_function(
     display_name: &str,
     params: Vec<Scalar>,
@@ -182,6 +523,53 @@ pub fn try_create_aggregate_sum_function(
         data_type = Int8Type::data_type();
     }
 
+    // `sum('decimal')(x)` accumulates the integers into a decimal, `sum('skip')(x)` and
+    // `sum('error')(x)` ignore or reject the NaN and infinite floats.
+    let accumulator = match params.first() {
+        None => None,
+        Some(Scalar::String(accumulator))
+            if ["decimal", "skip", "error"]
+                .iter()
+                .any(|name| accumulator.eq_ignore_ascii_case(name)) =>
+        {
+            Some(accumulator.to_ascii_lowercase())
+        }
+        Some(param) => {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support the accumulator '{}', expected 'decimal', 'skip' or 'error'",
+                display_name, param
+            )));
+        }
+    };
+    let into_decimal = accumulator.as_deref() == Some("decimal");
+
+    match accumulator.as_deref() {
+        Some("skip") => {
+            return try_create_float_sum::<false>(display_name, params, &data_type, &arguments[0]);
+        }
+        Some("error") => {
+            return try_create_float_sum::<true>(display_name, params, &data_type, &arguments[0]);
+        }
+        _ => {}
+    }
+
+    with_integer_mapped_type!(|NUM| match &data_type {
+        DataType::Number(NumberDataType::NUM) if into_decimal => {
+            let return_type = DataType::Decimal(DecimalDataType::Decimal128(DecimalSize {
+                precision: MAX_DECIMAL128_PRECISION,
+                scale: 0,
+            }));
+            return AggregateUnaryFunction::<
+                DecimalSumState<true, Decimal128Type>,
+                NumberType<NUM>,
+                Decimal128Type,
+            >::try_create_unary(
+                display_name, return_type, params, arguments[0].clone()
+            );
+        }
+        _ => {}
+    });
+
     with_number_mapped_type!(|NUM| match &data_type {
         DataType::Number(NumberDataType::NUM) => {
             type TSum = <NUM as ResultTypeOfUnary>::Sum;
@@ -192,63 +580,80 @@ pub fn try_create_aggregate_sum_function(
                 NumberType<TSum>,
             >::try_create_unary(display_name, return_type, params, arguments[0].clone())
         }
+        DataType::Array(box DataType::Number(NumberDataType::NUM)) => {
+            type TSum = <NUM as ResultTypeOfUnary>::Sum;
+            let return_type =
+                DataType::Array(Box::new(NumberType::<TSum>::data_type())).wrap_nullable();
+            AggregateUnaryFunction::<
+                ArraySumState<TSum>,
+                ArrayType<NumberType<NUM>>,
+                NullableType<ArrayType<NumberType<TSum>>>,
+            >::try_create_unary(display_name, return_type, params, arguments[0].clone())
+        }
         DataType::Decimal(DecimalDataType::Decimal128(s)) => {
-            let p = MAX_DECIMAL128_PRECISION;
-            let decimal_size = DecimalSize {
-                precision: p,
-                scale: s.scale,
-            };
-
-            // DecimalWidth<int64_t> = 18
-            let overflow = s.precision > 18;
-            let return_type = DataType::Decimal(DecimalDataType::from_size(decimal_size)?);
-
-            if overflow {
-                AggregateUnaryFunction::<
+            let size = decimal_sum_size(s, MAX_DECIMAL128_PRECISION);
+            let return_type = DataType::Decimal(DecimalDataType::Decimal128(size));
+            let data = Box::new(DecimalSumData {
+                precision: size.precision,
+            });
+            if size.precision < MAX_DECIMAL128_PRECISION {
+                let func = AggregateUnaryFunction::<
                     DecimalSumState<false, Decimal128Type>,
                     Decimal128Type,
                     Decimal128Type,
-                >::try_create_unary(
+                >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
+                .with_function_data(data);
+                Ok(Arc::new(func))
             } else {
-                AggregateUnaryFunction::<
+                let func = AggregateUnaryFunction::<
                     DecimalSumState<true, Decimal128Type>,
                     Decimal128Type,
                     Decimal128Type,
-                >::try_create_unary(
+                >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
+                .with_function_data(data);
+                Ok(Arc::new(func))
             }
         }
         DataType::Decimal(DecimalDataType::Decimal256(s)) => {
-            let p = MAX_DECIMAL256_PRECISION;
-            let decimal_size = DecimalSize {
-                precision: p,
-                scale: s.scale,
-            };
-
-            let overflow = s.precision > 18;
-            let return_type = DataType::Decimal(DecimalDataType::from_size(decimal_size)?);
-
-            if overflow {
-                AggregateUnaryFunction::<
+            let size = decimal_sum_size(s, MAX_DECIMAL256_PRECISION);
+            let return_type = DataType::Decimal(DecimalDataType::Decimal256(size));
+            let data = Box::new(DecimalSumData {
+                precision: size.precision,
+            });
+            if size.precision < MAX_DECIMAL256_PRECISION {
+                let func = AggregateUnaryFunction::<
                     DecimalSumState<false, Decimal256Type>,
                     Decimal256Type,
                     Decimal256Type,
-                >::try_create_unary(
+                >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
+                .with_function_data(data);
+                Ok(Arc::new(func))
             } else {
-                AggregateUnaryFunction::<
+                let func = AggregateUnaryFunction::<
                     DecimalSumState<true, Decimal256Type>,
                     Decimal256Type,
                     Decimal256Type,
-                >::try_create_unary(
+                >::try_create(
                     display_name, return_type, params, arguments[0].clone()
                 )
+                .with_function_data(data);
+                Ok(Arc::new(func))
             }
         }
+        DataType::Boolean => {
+            let return_type = UInt64Type::data_type();
+            AggregateUnaryFunction::<
+                NumberSumState<UInt64Type>,
+                BooleanType,
+                UInt64Type,
+            >::try_create_unary(display_name, return_type, params, arguments[0].clone())
+        }
         _ => Err(ErrorCode::BadDataValueType(format!(
             "{} does not support type '{:?}'",
             display_name, arguments[0]
@@ -256,6 +661,30 @@ pub fn try_create_aggregate_sum_function(
     })
 }
 
+fn try_create_float_sum<const ERROR: bool>(
+    display_name: &str,
+    params: Vec<Scalar>,
+    data_type: &DataType,
+    argument: &DataType,
+) -> Result<AggregateFunctionRef> {
+    with_float_mapped_type!(|NUM| match data_type {
+        DataType::Number(NumberDataType::NUM) => AggregateUnaryFunction::<
+            FloatSumState<ERROR>,
+            NumberType<NUM>,
+            Float64Type,
+        >::try_create_unary(
+            display_name,
+            Float64Type::data_type(),
+            params,
+            argument.clone(),
+        ),
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "{} with the accumulator '{}' does not support type '{:?}'",
+            display_name, params[0], argument
+        ))),
+    })
+}
+
 pub fn aggregate_sum_function_desc() -> AggregateFunctionDescription {
     let features = super::aggregate_function_factory::AggregateFunctionFeatures {
         is_decomposable: true,
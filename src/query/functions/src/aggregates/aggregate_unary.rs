@@ -44,8 +44,75 @@ where
         function_data: Option<&dyn FunctionData>,
     ) -> Result<()>;
 
+    /// Accumulates a whole column, the rows whose validity is unset are skipped.
+    ///
+    /// States can override it with a faster path than calling `add` per row.
+    fn add_batch(
+        &mut self,
+        column: &T::Column,
+        validity: Option<&Bitmap>,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        let column_iter = T::iter_column(column);
+        match validity {
+            Some(bitmap) => {
+                for (value, is_valid) in column_iter.zip(bitmap.iter()) {
+                    if is_valid {
+                        self.add(value, function_data)?;
+                    }
+                }
+            }
+            None => {
+                for value in column_iter {
+                    self.add(value, function_data)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Accumulates each row into the state of its group, the `i`-th row goes to `places[i]`.
+    ///
+    /// States can override it to exploit the consecutive rows sharing a group.
+    fn add_keys(
+        places: &[StateAddr],
+        offset: usize,
+        column: &T::Column,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        for (value, place) in T::iter_column(column).zip(places.iter()) {
+            let state: &mut Self = place.next(offset).get::<Self>();
+            state.add(value, function_data)?;
+        }
+        Ok(())
+    }
+
+    /// Same as `add_keys`, but the rows whose validity is unset are skipped.
+    fn add_keys_with_validity(
+        places: &[StateAddr],
+        offset: usize,
+        column: &T::Column,
+        validity: &Bitmap,
+        function_data: Option<&dyn FunctionData>,
+    ) -> Result<()> {
+        for ((value, place), is_valid) in T::iter_column(column)
+            .zip(places.iter())
+            .zip(validity.iter())
+        {
+            if is_valid {
+                let state: &mut Self = place.next(offset).get::<Self>();
+                state.add(value, function_data)?;
+            }
+        }
+        Ok(())
+    }
+
     fn merge(&mut self, rhs: &Self) -> Result<()>;
 
+    /// Called before the state is serialized as a partial state, e.g. to put the state into
+    /// a form that is cheaper to merge.
+    fn before_serialize(&mut self) {}
+
     fn merge_result(
         &mut self,
         builder: &mut R::ColumnBuilder,
@@ -194,24 +261,8 @@ where
         _input_rows: usize,
     ) -> Result<()> {
         let column = T::try_downcast_column(&columns[0]).unwrap();
-        let column_iter = T::iter_column(&column);
         let state: &mut S = place.get::<S>();
-        match validity {
-            Some(bitmap) => {
-                for (value, is_valid) in column_iter.zip(bitmap.iter()) {
-                    if is_valid {
-                        state.add(value, self.function_data.as_deref())?;
-                    }
-                }
-            }
-            None => {
-                for value in column_iter {
-                    state.add(value, self.function_data.as_deref())?;
-                }
-            }
-        }
-
-        Ok(())
+        state.add_batch(&column, validity, self.function_data.as_deref())
     }
 
     fn accumulate_row(&self, place: StateAddr, columns: InputColumns, row: usize) -> Result<()> {
@@ -231,20 +282,30 @@ where
         _input_rows: usize,
     ) -> Result<()> {
         let column = T::try_downcast_column(&columns[0]).unwrap();
+        S::add_keys(places, offset, &column, self.function_data.as_deref())
+    }
 
-        for (i, place) in places.iter().enumerate() {
-            let state: &mut S = place.next(offset).get::<S>();
-            state.add(
-                T::index_column(&column, i).unwrap(),
-                self.function_data.as_deref(),
-            )?;
-        }
-
-        Ok(())
+    fn accumulate_keys_with_validity(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        columns: InputColumns,
+        validity: &Bitmap,
+        _input_rows: usize,
+    ) -> Result<()> {
+        let column = T::try_downcast_column(&columns[0]).unwrap();
+        S::add_keys_with_validity(
+            places,
+            offset,
+            &column,
+            validity,
+            self.function_data.as_deref(),
+        )
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
         let state: &mut S = place.get::<S>();
+        state.before_serialize();
         Ok(borsh::to_writer(writer, state)?)
     }
 
@@ -15,6 +15,8 @@
 use super::aggregate_approx_count_distinct::aggregate_approx_count_distinct_function_desc;
 use super::aggregate_arg_min_max::aggregate_arg_max_function_desc;
 use super::aggregate_arg_min_max::aggregate_arg_min_function_desc;
+use super::aggregate_arg_min_max::aggregate_max_by_function_desc;
+use super::aggregate_arg_min_max::aggregate_min_by_function_desc;
 use super::aggregate_avg::aggregate_avg_function_desc;
 use super::aggregate_bitmap::aggregate_bitmap_and_count_function_desc;
 use super::aggregate_bitmap::aggregate_bitmap_intersect_count_function_desc;
@@ -31,6 +33,10 @@ use super::aggregate_covariance::aggregate_covariance_sample_desc;
 use super::aggregate_min_max_any::aggregate_any_function_desc;
 use super::aggregate_min_max_any::aggregate_max_function_desc;
 use super::aggregate_min_max_any::aggregate_min_function_desc;
+use super::aggregate_regression::aggregate_regr_count_desc;
+use super::aggregate_regression::aggregate_regr_intercept_desc;
+use super::aggregate_regression::aggregate_regr_r2_desc;
+use super::aggregate_regression::aggregate_regr_slope_desc;
 use super::aggregate_stddev::aggregate_stddev_pop_function_desc;
 use super::aggregate_stddev::aggregate_stddev_samp_function_desc;
 use super::aggregate_window_funnel::aggregate_window_funnel_function_desc;
@@ -41,6 +47,7 @@ use crate::aggregates::aggregate_array_agg_function_desc;
 use crate::aggregates::aggregate_array_moving_avg_function_desc;
 use crate::aggregates::aggregate_array_moving_sum_function_desc;
 use crate::aggregates::aggregate_histogram_function_desc;
+use crate::aggregates::aggregate_hll_merge_function_desc;
 use crate::aggregates::aggregate_json_array_agg_function_desc;
 use crate::aggregates::aggregate_json_object_agg_function_desc;
 use crate::aggregates::aggregate_kurtosis_function_desc;
@@ -55,6 +62,7 @@ use crate::aggregates::aggregate_retention_function_desc;
 use crate::aggregates::aggregate_skewness_function_desc;
 use crate::aggregates::aggregate_string_agg_function_desc;
 use crate::aggregates::aggregate_sum_function_desc;
+use crate::aggregates::aggregate_tdigest_merge_function_desc;
 
 pub struct Aggregators;
 
@@ -71,9 +79,15 @@ impl Aggregators {
         factory.register("any", aggregate_any_function_desc());
         factory.register("arg_min", aggregate_arg_min_function_desc());
         factory.register("arg_max", aggregate_arg_max_function_desc());
+        factory.register("min_by", aggregate_min_by_function_desc());
+        factory.register("max_by", aggregate_max_by_function_desc());
 
         factory.register("covar_samp", aggregate_covariance_sample_desc());
         factory.register("covar_pop", aggregate_covariance_population_desc());
+        factory.register("regr_slope", aggregate_regr_slope_desc());
+        factory.register("regr_intercept", aggregate_regr_intercept_desc());
+        factory.register("regr_r2", aggregate_regr_r2_desc());
+        factory.register("regr_count", aggregate_regr_count_desc());
         factory.register("stddev_samp", aggregate_stddev_samp_function_desc());
         factory.register("stddev_pop", aggregate_stddev_pop_function_desc());
         factory.register("stddev", aggregate_stddev_samp_function_desc());
@@ -81,6 +95,8 @@ impl Aggregators {
         factory.register("quantile", aggregate_quantile_disc_function_desc());
         factory.register("quantile_disc", aggregate_quantile_disc_function_desc());
         factory.register("quantile_cont", aggregate_quantile_cont_function_desc());
+        factory.register("percentile_disc", aggregate_quantile_disc_function_desc());
+        factory.register("percentile_cont", aggregate_quantile_cont_function_desc());
         factory.register(
             "quantile_tdigest",
             aggregate_quantile_tdigest_function_desc(),
@@ -100,6 +116,8 @@ impl Aggregators {
             "approx_count_distinct",
             aggregate_approx_count_distinct_function_desc(),
         );
+        factory.register("tdigest_merge", aggregate_tdigest_merge_function_desc());
+        factory.register("hll_merge", aggregate_hll_merge_function_desc());
         factory.register("retention", aggregate_retention_function_desc());
         factory.register("array_agg", aggregate_array_agg_function_desc());
         factory.register("list", aggregate_array_agg_function_desc());
@@ -38,8 +38,10 @@ mod aggregate_quantile_cont;
 mod aggregate_quantile_disc;
 mod aggregate_quantile_tdigest;
 mod aggregate_quantile_tdigest_weighted;
+mod aggregate_regression;
 mod aggregate_retention;
 mod aggregate_scalar_state;
+mod aggregate_sketch_merge;
 mod aggregate_skewness;
 mod aggregate_stddev;
 mod aggregate_string_agg;
@@ -69,7 +71,9 @@ pub use aggregate_quantile_cont::*;
 pub use aggregate_quantile_disc::*;
 pub use aggregate_quantile_tdigest::*;
 pub use aggregate_quantile_tdigest_weighted::*;
+pub use aggregate_regression::AggregateRegressionFunction;
 pub use aggregate_retention::*;
+pub use aggregate_sketch_merge::*;
 pub use aggregate_skewness::*;
 pub use aggregate_string_agg::*;
 pub use aggregate_sum::*;
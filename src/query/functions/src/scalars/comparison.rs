@@ -30,6 +30,7 @@ use databend_common_expression::types::EmptyArrayType;
 use databend_common_expression::types::GenericType;
 use databend_common_expression::types::NumberClass;
 use databend_common_expression::types::NumberType;
+use databend_common_expression::types::SimpleDomain;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::TimestampType;
 use databend_common_expression::types::ValueType;
@@ -40,6 +41,7 @@ use databend_common_expression::with_number_mapped_type;
 use databend_common_expression::Column;
 use databend_common_expression::EvalContext;
 use databend_common_expression::Function;
+use databend_common_expression::FunctionContext;
 use databend_common_expression::FunctionDomain;
 use databend_common_expression::FunctionEval;
 use databend_common_expression::FunctionRegistry;
@@ -51,6 +53,7 @@ use databend_common_expression::ValueRef;
 use memchr::memmem;
 use regex::Regex;
 
+use crate::scalars::datetime::calc_date_to_timestamp;
 use crate::scalars::decimal::register_decimal_compare_op;
 use crate::scalars::string_multi_args::regexp;
 
@@ -59,6 +62,7 @@ pub fn register(registry: &mut FunctionRegistry) {
     register_string_cmp(registry);
     register_date_cmp(registry);
     register_timestamp_cmp(registry);
+    register_date_timestamp_cmp(registry);
     register_number_cmp(registry);
     register_boolean_cmp(registry);
     register_array_cmp(registry);
@@ -170,6 +174,43 @@ fn register_timestamp_cmp(registry: &mut FunctionRegistry) {
     register_simple_domain_type_cmp!(registry, TimestampType);
 }
 
+macro_rules! register_date_timestamp_cmp_op {
+    ($registry:ident, $name:expr, $domain_cmp:ident, $op:tt) => {
+        $registry.register_2_arg::<DateType, TimestampType, BooleanType, _, _>(
+            $name,
+            |ctx, d1, d2| date_domain_to_timestamp(ctx, d1).$domain_cmp(d2),
+            |lhs, rhs, ctx| calc_date_to_timestamp(lhs, ctx.func_ctx.tz.tz) $op rhs,
+        );
+        $registry.register_2_arg::<TimestampType, DateType, BooleanType, _, _>(
+            $name,
+            |ctx, d1, d2| d1.$domain_cmp(&date_domain_to_timestamp(ctx, d2)),
+            |lhs, rhs, ctx| lhs $op calc_date_to_timestamp(rhs, ctx.func_ctx.tz.tz),
+        );
+    };
+}
+
+/// Compares a date with a timestamp by promoting the date to its midnight in the
+/// session timezone, so `date '2023-01-01' = timestamp '2023-01-01 00:00:00'` holds.
+fn register_date_timestamp_cmp(registry: &mut FunctionRegistry) {
+    register_date_timestamp_cmp_op!(registry, "eq", domain_eq, ==);
+    register_date_timestamp_cmp_op!(registry, "noteq", domain_noteq, !=);
+    register_date_timestamp_cmp_op!(registry, "gt", domain_gt, >);
+    register_date_timestamp_cmp_op!(registry, "gte", domain_gte, >=);
+    register_date_timestamp_cmp_op!(registry, "lt", domain_lt, <);
+    register_date_timestamp_cmp_op!(registry, "lte", domain_lte, <=);
+}
+
+fn date_domain_to_timestamp(
+    ctx: &FunctionContext,
+    domain: &SimpleDomain<i32>,
+) -> SimpleDomain<i64> {
+    let tz = ctx.tz.tz;
+    SimpleDomain {
+        min: calc_date_to_timestamp(domain.min, tz),
+        max: calc_date_to_timestamp(domain.max, tz),
+    }
+}
+
 fn register_boolean_cmp(registry: &mut FunctionRegistry) {
     registry.register_2_arg::<BooleanType, BooleanType, BooleanType, _, _>(
         "eq",
@@ -14,7 +14,10 @@
 
 use std::sync::Arc;
 
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::bitmap::MutableBitmap;
 use databend_common_expression::types::boolean::BooleanDomain;
+use databend_common_expression::types::decimal::DecimalColumn;
 use databend_common_expression::types::nullable::NullableColumn;
 use databend_common_expression::types::nullable::NullableDomain;
 use databend_common_expression::types::BooleanType;
@@ -22,12 +25,20 @@ use databend_common_expression::types::DataType;
 use databend_common_expression::types::GenericType;
 use databend_common_expression::types::NullType;
 use databend_common_expression::types::NullableType;
+use databend_common_expression::types::NumberColumn;
+use databend_common_expression::types::NumberType;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::with_number_mapped_type;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
 use databend_common_expression::Domain;
+use databend_common_expression::EvalContext;
 use databend_common_expression::Function;
 use databend_common_expression::FunctionDomain;
 use databend_common_expression::FunctionEval;
 use databend_common_expression::FunctionRegistry;
 use databend_common_expression::FunctionSignature;
+use databend_common_expression::Scalar;
 use databend_common_expression::Value;
 use databend_common_expression::ValueRef;
 
@@ -103,34 +114,161 @@ pub fn register(registry: &mut FunctionRegistry) {
         }))
     });
 
-    registry.register_1_arg_core::<NullType, BooleanType, _, _>(
-        "is_not_null",
-        |_, _| {
-            FunctionDomain::Domain(BooleanDomain {
-                has_true: false,
-                has_false: true,
-            })
-        },
-        |_, _| Value::Scalar(false),
-    );
-    registry.register_1_arg_core::<NullableType<GenericType<0>>, BooleanType, _, _>(
-        "is_not_null",
-        |_, NullableDomain { has_null, value }| {
-            FunctionDomain::Domain(BooleanDomain {
-                has_true: value.is_some(),
-                has_false: *has_null,
+    // `IS NULL` and `IS NOT NULL` are answered by the validity bitmap alone, the bitmap of a
+    // nullable column is copied or negated as a whole, and a non-nullable argument is a constant.
+    for (name, is_null) in [("is_not_null", false), ("is_null", true)] {
+        // Registered first, so that a non-nullable argument is not cast to nullable.
+        registry.register_function_factory(name, move |_, args_type| {
+            if args_type.len() != 1 || args_type[0].is_nullable_or_null() {
+                return None;
+            }
+            Some(Arc::new(Function {
+                signature: FunctionSignature {
+                    name: name.to_string(),
+                    args_type: args_type.to_vec(),
+                    return_type: DataType::Boolean,
+                },
+                eval: FunctionEval::Scalar {
+                    calc_domain: Box::new(move |_, _| {
+                        FunctionDomain::Domain(Domain::Boolean(BooleanDomain {
+                            has_true: !is_null,
+                            has_false: is_null,
+                        }))
+                    }),
+                    eval: Box::new(move |_, _| Value::Scalar(Scalar::Boolean(!is_null))),
+                },
+            }))
+        });
+        registry.register_1_arg_core::<NullType, BooleanType, _, _>(
+            name,
+            move |_, _| {
+                FunctionDomain::Domain(BooleanDomain {
+                    has_true: is_null,
+                    has_false: !is_null,
+                })
+            },
+            move |_, _| Value::Scalar(is_null),
+        );
+        registry.register_1_arg_core::<NullableType<GenericType<0>>, BooleanType, _, _>(
+            name,
+            move |_, NullableDomain { has_null, value }| {
+                let (has_valid, has_null) = (value.is_some(), *has_null);
+                FunctionDomain::Domain(BooleanDomain {
+                    has_true: if is_null { has_null } else { has_valid },
+                    has_false: if is_null { has_valid } else { has_null },
+                })
+            },
+            move |arg, _| match &arg {
+                ValueRef::Column(NullableColumn { validity, .. }) => {
+                    Value::Column(if is_null { !validity } else { validity.clone() })
+                }
+                ValueRef::Scalar(value) => Value::Scalar(value.is_none() == is_null),
+            },
+        );
+    }
+
+    // `nullif` and `ifnull` work on the validity bitmaps directly instead of being
+    // rewritten to `if` and `coalesce`, which pick the values row by row.
+    registry.register_2_arg_core::<
+        NullableType<GenericType<0>>,
+        NullableType<GenericType<0>>,
+        NullableType<GenericType<0>>,
+        _,
+        _,
+    >(
+        "nullif",
+        |_, lhs, _| {
+            FunctionDomain::Domain(NullableDomain {
+                has_null: true,
+                value: lhs.value.clone(),
             })
         },
-        |arg, _| match &arg {
-            ValueRef::Column(NullableColumn { validity, .. }) => {
-                let bitmap = validity.clone();
-                Value::Column(bitmap)
+        |lhs, rhs, ctx| match (lhs, rhs) {
+            (ValueRef::Scalar(None), _) => Value::Scalar(None),
+            (lhs, ValueRef::Scalar(None)) => lhs.to_owned(),
+            (ValueRef::Scalar(Some(lhs)), ValueRef::Scalar(Some(rhs))) => {
+                Value::Scalar((lhs != rhs).then(|| lhs.to_owned()))
+            }
+            (lhs, rhs) => {
+                let data_type = &ctx.generics[0];
+                let lhs = to_nullable_column(lhs, data_type, ctx.num_rows);
+                let rhs = to_nullable_column(rhs, data_type, ctx.num_rows);
+                let equal = column_eq(&lhs.column, &rhs.column);
+                let validity = &lhs.validity & &!&(&equal & &rhs.validity);
+                Value::Column(NullableColumn {
+                    column: lhs.column,
+                    validity,
+                })
             }
-            ValueRef::Scalar(None) => Value::Scalar(false),
-            ValueRef::Scalar(Some(_)) => Value::Scalar(true),
         },
     );
 
+    // Registered first, as `(T0 NULL, T0)` would bind `T0` to the nullable type of `y`.
+    registry.register_function_factory("ifnull", |_, args_type| {
+        if args_type.len() != 2
+            || !args_type[1].is_nullable_or_null()
+            || (args_type[0] == DataType::Null && args_type[1] == DataType::Null)
+        {
+            return None;
+        }
+        let nullable = DataType::Nullable(Box::new(DataType::Generic(0)));
+        Some(Arc::new(Function {
+            signature: FunctionSignature {
+                name: "ifnull".to_string(),
+                args_type: vec![nullable.clone(), nullable.clone()],
+                return_type: nullable,
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, args_domain| {
+                    let lhs = args_domain[0].as_nullable().unwrap();
+                    let rhs = args_domain[1].as_nullable().unwrap();
+                    let value = match (&lhs.value, &rhs.value) {
+                        (Some(lhs_value), Some(rhs_value)) if lhs.has_null => {
+                            Some(Box::new(lhs_value.merge(rhs_value)))
+                        }
+                        (Some(lhs_value), _) => Some(lhs_value.clone()),
+                        (None, rhs_value) => rhs_value.clone(),
+                    };
+                    FunctionDomain::Domain(Domain::Nullable(NullableDomain {
+                        has_null: lhs.has_null && rhs.has_null,
+                        value,
+                    }))
+                }),
+                eval: Box::new(|args, ctx| {
+                    let lhs = args[0].try_downcast().unwrap();
+                    let rhs = args[1].try_downcast().unwrap();
+                    Value::upcast(eval_nullable_ifnull(lhs, rhs, ctx))
+                }),
+            },
+        }))
+    });
+    registry
+        .register_2_arg_core::<NullableType<GenericType<0>>, GenericType<0>, GenericType<0>, _, _>(
+            "ifnull",
+            |_, lhs, rhs| {
+                FunctionDomain::Domain(match &lhs.value {
+                    Some(value) if lhs.has_null => value.merge(rhs),
+                    Some(value) => value.as_ref().clone(),
+                    None => rhs.clone(),
+                })
+            },
+            |lhs, rhs, ctx| match (lhs, rhs) {
+                (ValueRef::Scalar(Some(lhs)), _) => Value::Scalar(lhs.to_owned()),
+                (ValueRef::Scalar(None), rhs) => rhs.to_owned(),
+                (ValueRef::Column(lhs), rhs) => {
+                    if lhs.validity.unset_bits() == 0 {
+                        return Value::Column(lhs.column);
+                    }
+                    let rhs = match rhs {
+                        ValueRef::Scalar(rhs) => {
+                            ColumnBuilder::repeat(&rhs, ctx.num_rows, &ctx.generics[0]).build()
+                        }
+                        ValueRef::Column(rhs) => rhs,
+                    };
+                    Value::Column(select_valid(&lhs.column, &lhs.validity, &rhs))
+                }
+            },
+        );
     registry.register_1_arg_core::<GenericType<0>, BooleanType, _, _>(
         "is_not_error",
         |_, _| FunctionDomain::Full,
@@ -143,3 +281,100 @@ pub fn register(registry: &mut FunctionRegistry) {
         },
     );
 }
+
+fn eval_nullable_ifnull(
+    lhs: ValueRef<NullableType<GenericType<0>>>,
+    rhs: ValueRef<NullableType<GenericType<0>>>,
+    ctx: &mut EvalContext,
+) -> Value<NullableType<GenericType<0>>> {
+    match (lhs, rhs) {
+        (ValueRef::Scalar(Some(lhs)), _) => Value::Scalar(Some(lhs.to_owned())),
+        (ValueRef::Scalar(None), rhs) => rhs.to_owned(),
+        (lhs, ValueRef::Scalar(None)) => lhs.to_owned(),
+        (ValueRef::Column(lhs), rhs) => {
+            if lhs.validity.unset_bits() == 0 {
+                return Value::Column(lhs);
+            }
+            let rhs = to_nullable_column(rhs, &ctx.generics[0], ctx.num_rows);
+            let column = select_valid(&lhs.column, &lhs.validity, &rhs.column);
+            Value::Column(NullableColumn {
+                column,
+                validity: &lhs.validity | &rhs.validity,
+            })
+        }
+    }
+}
+
+fn to_nullable_column(
+    value: ValueRef<NullableType<GenericType<0>>>,
+    data_type: &DataType,
+    num_rows: usize,
+) -> NullableColumn<GenericType<0>> {
+    match value {
+        ValueRef::Column(column) => column,
+        ValueRef::Scalar(Some(scalar)) => NullableColumn {
+            column: ColumnBuilder::repeat(&scalar, num_rows, data_type).build(),
+            validity: Bitmap::new_constant(true, num_rows),
+        },
+        ValueRef::Scalar(None) => NullableColumn {
+            column: ColumnBuilder::repeat(
+                &Scalar::default_value(data_type).as_ref(),
+                num_rows,
+                data_type,
+            )
+            .build(),
+            validity: Bitmap::new_constant(false, num_rows),
+        },
+    }
+}
+
+/// Compares the values of the columns row by row, the values must be of the same type.
+fn column_eq(lhs: &Column, rhs: &Column) -> Bitmap {
+    fn zip_eq<T: PartialEq>(lhs: impl Iterator<Item = T>, rhs: impl Iterator<Item = T>) -> Bitmap {
+        MutableBitmap::from_iter(lhs.zip(rhs).map(|(lhs, rhs)| lhs == rhs)).into()
+    }
+
+    with_number_mapped_type!(|NUM| match (lhs, rhs) {
+        (Column::Number(NumberColumn::NUM(lhs)), Column::Number(NumberColumn::NUM(rhs))) => {
+            zip_eq(lhs.iter(), rhs.iter())
+        }
+        (
+            Column::Decimal(DecimalColumn::Decimal128(lhs, _)),
+            Column::Decimal(DecimalColumn::Decimal128(rhs, _)),
+        ) => zip_eq(lhs.iter(), rhs.iter()),
+        (
+            Column::Decimal(DecimalColumn::Decimal256(lhs, _)),
+            Column::Decimal(DecimalColumn::Decimal256(rhs, _)),
+        ) => zip_eq(lhs.iter(), rhs.iter()),
+        (Column::Boolean(lhs), Column::Boolean(rhs)) => zip_eq(lhs.iter(), rhs.iter()),
+        (Column::String(lhs), Column::String(rhs)) => zip_eq(lhs.iter(), rhs.iter()),
+        (Column::Timestamp(lhs), Column::Timestamp(rhs)) => zip_eq(lhs.iter(), rhs.iter()),
+        (Column::Date(lhs), Column::Date(rhs)) => zip_eq(lhs.iter(), rhs.iter()),
+        (lhs, rhs) => zip_eq(lhs.iter(), rhs.iter()),
+    })
+}
+
+/// Takes the value of `lhs` where `validity` is set, otherwise the value of `rhs`.
+fn select_valid(lhs: &Column, validity: &Bitmap, rhs: &Column) -> Column {
+    with_number_mapped_type!(|NUM| match (lhs, rhs) {
+        (Column::Number(NumberColumn::NUM(lhs)), Column::Number(NumberColumn::NUM(rhs))) => {
+            let values = lhs
+                .iter()
+                .zip(rhs.iter())
+                .zip(validity.iter())
+                .map(|((lhs, rhs), is_valid)| if is_valid { *lhs } else { *rhs })
+                .collect::<Vec<_>>();
+            NumberType::<NUM>::upcast_column(values.into())
+        }
+        (Column::Boolean(lhs), Column::Boolean(rhs)) => {
+            Column::Boolean(&(lhs & validity) | &(rhs & &!validity))
+        }
+        (lhs, rhs) => {
+            let mut builder = ColumnBuilder::with_capacity(&lhs.data_type(), lhs.len());
+            for ((lhs, rhs), is_valid) in lhs.iter().zip(rhs.iter()).zip(validity.iter()) {
+                builder.push(if is_valid { lhs } else { rhs });
+            }
+            builder.build()
+        }
+    })
+}
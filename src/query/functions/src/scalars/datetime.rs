@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Cow;
 use std::io::Write;
+use std::sync::Arc;
 
 use chrono::format::parse_and_remainder;
+use chrono::format::Item;
 use chrono::format::Parsed;
 use chrono::format::StrftimeItems;
 use chrono::prelude::*;
@@ -31,9 +34,11 @@ use databend_common_expression::error_to_null;
 use databend_common_expression::types::date::check_date;
 use databend_common_expression::types::date::date_to_string;
 use databend_common_expression::types::date::string_to_date;
+use databend_common_expression::types::date::DATE_FORMAT;
 use databend_common_expression::types::date::DATE_MAX;
 use databend_common_expression::types::date::DATE_MIN;
 use databend_common_expression::types::nullable::NullableColumn;
+use databend_common_expression::types::nullable::NullableColumnBuilder;
 use databend_common_expression::types::nullable::NullableDomain;
 use databend_common_expression::types::number::Int64Type;
 use databend_common_expression::types::number::SimpleDomain;
@@ -44,26 +49,44 @@ use databend_common_expression::types::number::UInt8Type;
 use databend_common_expression::types::string::StringDomain;
 use databend_common_expression::types::timestamp::check_timestamp;
 use databend_common_expression::types::timestamp::string_to_timestamp;
-use databend_common_expression::types::timestamp::timestamp_to_string;
 use databend_common_expression::types::timestamp::MICROS_IN_A_MILLI;
 use databend_common_expression::types::timestamp::MICROS_IN_A_SEC;
+use databend_common_expression::types::timestamp::TIMESTAMP_FORMAT;
+use databend_common_expression::types::timestamp::TIMESTAMP_MAX;
+use databend_common_expression::types::timestamp::TIMESTAMP_MIN;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::types::ArgType;
+use databend_common_expression::types::ArrayType;
+use databend_common_expression::types::DataType;
 use databend_common_expression::types::DateType;
 use databend_common_expression::types::Float64Type;
 use databend_common_expression::types::Int32Type;
 use databend_common_expression::types::NullableType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
 use databend_common_expression::types::NumberType;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::TimestampType;
+use databend_common_expression::types::ValueType;
 use databend_common_expression::types::F64;
 use databend_common_expression::utils::date_helper::*;
 use databend_common_expression::vectorize_1_arg;
 use databend_common_expression::vectorize_2_arg;
 use databend_common_expression::vectorize_with_builder_1_arg;
 use databend_common_expression::vectorize_with_builder_2_arg;
+use databend_common_expression::vectorize_with_builder_3_arg;
+use databend_common_expression::Column;
 use databend_common_expression::EvalContext;
+use databend_common_expression::Function;
+use databend_common_expression::FunctionContext;
 use databend_common_expression::FunctionDomain;
+use databend_common_expression::FunctionEval;
 use databend_common_expression::FunctionProperty;
 use databend_common_expression::FunctionRegistry;
+use databend_common_expression::FunctionSignature;
+use databend_common_expression::LeapSecondPolicy;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
 use databend_common_expression::Value;
 use databend_common_expression::ValueRef;
 use databend_common_io::cursor_ext::unwrap_local_time;
@@ -83,10 +106,17 @@ pub fn register(registry: &mut FunctionRegistry) {
     register_timestamp_to_date(registry);
     register_number_to_date(registry);
 
+    // to_timestamp_tz(string) -> (timestamp, offset_seconds)
+    // timestamptz_offset((timestamp, offset_seconds))
+    register_timestamp_tz(registry);
+
     // cast([date | timestamp] AS string)
     // to_string([date | timestamp])
     register_to_string(registry);
 
+    // to_iso8601([date | timestamp]), to_rfc3339(timestamp)
+    register_canonical_to_string(registry);
+
     // cast([date | timestamp] AS [uint8 | int8 | ...])
     // to_[uint8 | int8 | ...]([date | timestamp])
     register_to_number(registry);
@@ -97,17 +127,36 @@ pub fn register(registry: &mut FunctionRegistry) {
     register_add_functions(registry);
     register_sub_functions(registry);
 
-    // now, today, yesterday, tomorrow
+    // date_[add | sub]([date | timestamp], '<number> <unit> ...')
+    register_interval_string_functions(registry);
+
+    // quarter_to_date('<year>Q<quarter>') -> date
+    register_quarter_to_date(registry);
+
+    // julian_to_[date | timestamp](float64), [date | timestamp]_to_julian([date | timestamp])
+    register_julian_functions(registry);
+
+    // now, today, yesterday, tomorrow, current_timezone, current_timestamp_tz
     register_real_time_functions(registry);
 
     // to_*([date | timestamp]) -> number
     register_to_number_functions(registry);
+    register_week_of_month(registry);
 
     // to_*([date | timestamp]) -> [date | timestamp]
     register_rounder_functions(registry);
 
     // [date | timestamp] +/- number
     register_timestamp_add_sub(registry);
+
+    // date_diff(unit, [date | timestamp], [date | timestamp])
+    register_diff_functions(registry);
+
+    // datetime(timestamp, modifier, ...)
+    register_datetime_modifiers(registry);
+
+    // [monthname | dayname]([date | timestamp] [, locale])
+    register_name_functions(registry);
 }
 
 /// Check if timestamp is within range, and return the timestamp in micros.
@@ -122,6 +171,355 @@ fn int64_to_timestamp(n: i64) -> Result<i64, String> {
     }
 }
 
+const MAX_ERROR_VALUE_CHARS: usize = 64;
+
+/// Build the error message of a failed datetime parse. If `datetime_parse_error_with_value`
+/// is enabled, the offending value (truncated) and its row in the block are included,
+/// which helps to locate the bad data in a large file.
+fn datetime_parse_error(
+    ctx: &EvalContext,
+    row: usize,
+    val: &str,
+    ty: &str,
+    err: impl std::fmt::Display,
+) -> String {
+    if ctx.func_ctx.datetime_parse_error_with_value {
+        let value = truncate_error_value(val);
+        format!("cannot parse to type `{ty}`. {err}, row {row}: '{value}'")
+    } else {
+        format!("cannot parse to type `{ty}`. {err}")
+    }
+}
+
+fn truncate_error_value(val: &str) -> Cow<'_, str> {
+    match val.char_indices().nth(MAX_ERROR_VALUE_CHARS) {
+        Some((idx, _)) => Cow::Owned(format!("{}...", &val[..idx])),
+        None => Cow::Borrowed(val),
+    }
+}
+
+/// Build the error message of a failed datetime format like `datetime_parse_error`, the
+/// offending value is the format or the timestamp which can't be formatted.
+fn datetime_format_error(
+    ctx: &EvalContext,
+    row: usize,
+    val: impl std::fmt::Display,
+    err: impl std::fmt::Display,
+) -> String {
+    if ctx.func_ctx.datetime_parse_error_with_value {
+        let val = val.to_string();
+        let value = truncate_error_value(&val);
+        format!("{err}, row {row}: '{value}'")
+    } else {
+        err.to_string()
+    }
+}
+
+const MAX_ERROR_SAMPLES: usize = 3;
+
+/// If `datetime_parse_error_summary` is enabled, appends the number of the failed rows of the
+/// block and some of their values to the error of the first one. The query still fails, but
+/// the error tells how much of the data is bad.
+fn summarize_datetime_parse_errors(ctx: &mut EvalContext, val: &ValueRef<StringType>) {
+    if !ctx.func_ctx.datetime_parse_error_summary {
+        return;
+    }
+    let Some((valids, msg)) = ctx.errors.as_mut() else {
+        return;
+    };
+
+    let failed_rows = (0..valids.len()).filter(|row| !valids.get(*row));
+    let samples = failed_rows
+        .clone()
+        .take(MAX_ERROR_SAMPLES)
+        .map(|row| {
+            let value = match val {
+                ValueRef::Scalar(value) => *value,
+                ValueRef::Column(column) => column.index(row).unwrap(),
+            };
+            format!("'{}'", truncate_error_value(value))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let failed = failed_rows.count();
+    *msg = format!("{msg}. {failed} rows of the block failed to parse, such as {samples}");
+}
+
+/// Resolve the relative keywords `now`, `today`, `yesterday` and `tomorrow` against the
+/// query time, consistent with the functions of the same names. `today` and its neighbours
+/// resolve to the start of the day in the timezone `tz`.
+///
+/// Returns `None` if `parse_datetime_relative_keywords` is disabled or `val` is not a keyword.
+fn relative_datetime_keyword(ctx: &EvalContext, tz: Tz, val: &str) -> Option<DateTime<Tz>> {
+    if !ctx.func_ctx.parse_datetime_relative_keywords {
+        return None;
+    }
+
+    let now = ctx.func_ctx.now.with_timezone(&tz);
+    let keyword = val.trim();
+    let days = if keyword.eq_ignore_ascii_case("now") {
+        return Some(now);
+    } else if keyword.eq_ignore_ascii_case("today") {
+        0
+    } else if keyword.eq_ignore_ascii_case("yesterday") {
+        -1
+    } else if keyword.eq_ignore_ascii_case("tomorrow") {
+        1
+    } else {
+        return None;
+    };
+
+    let date = now.date_naive() + Duration::days(days);
+    tz.from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+}
+
+/// Evaluates with the session timezone replaced by UTC, e.g. the strings without a timezone
+/// are parsed as UTC. The errors are kept in `ctx`.
+fn eval_in_utc<T>(ctx: &mut EvalContext, eval: impl FnOnce(&mut EvalContext) -> T) -> T {
+    let func_ctx = FunctionContext {
+        tz: TzLUT::default(),
+        ..ctx.func_ctx.clone()
+    };
+    let mut utc_ctx = EvalContext {
+        generics: ctx.generics,
+        num_rows: ctx.num_rows,
+        func_ctx: &func_ctx,
+        validity: ctx.validity.clone(),
+        errors: ctx.errors.take(),
+        error_code: ctx.error_code,
+        suppress_error: ctx.suppress_error,
+    };
+    let value = eval(&mut utc_ctx);
+    ctx.errors = utc_ctx.errors;
+    ctx.error_code = utc_ctx.error_code;
+    value
+}
+
+/// Replaces the comma before the fractional seconds by a dot, like `2023-01-01 12:00:00,500`.
+/// Only a comma following the minutes and seconds `:MM:SS` and followed by a digit is
+/// replaced, the other commas are kept.
+///
+/// Returns `val` itself if `parse_datetime_comma_fraction` is disabled or there is no such comma.
+fn normalize_comma_fraction<'a>(ctx: &EvalContext, val: &'a str) -> Cow<'a, str> {
+    if !ctx.func_ctx.parse_datetime_comma_fraction {
+        return Cow::Borrowed(val);
+    }
+
+    let pos = val.as_bytes().windows(8).position(|w| {
+        w[0] == b':'
+            && w[1].is_ascii_digit()
+            && w[2].is_ascii_digit()
+            && w[3] == b':'
+            && w[4].is_ascii_digit()
+            && w[5].is_ascii_digit()
+            && w[6] == b','
+            && w[7].is_ascii_digit()
+    });
+    match pos {
+        Some(pos) => {
+            let mut val = val.to_string();
+            val.replace_range(pos + 6..pos + 7, ".");
+            Cow::Owned(val)
+        }
+        None => Cow::Borrowed(val),
+    }
+}
+
+/// Removes the ordinal suffixes `st`, `nd`, `rd` and `th` following the day numbers, like
+/// `March 1st, 2023`. Only a suffix following one or two digits and not followed by a letter
+/// or digit is removed, the suffix is not checked against the number.
+///
+/// Returns `val` itself if `parse_datetime_ordinal_suffix` is disabled or there is no suffix.
+fn strip_ordinal_suffix<'a>(ctx: &EvalContext, val: &'a str) -> Cow<'a, str> {
+    if !ctx.func_ctx.parse_datetime_ordinal_suffix {
+        return Cow::Borrowed(val);
+    }
+
+    let bytes = val.as_bytes();
+    let is_suffix = |pos: usize| {
+        let digits = bytes[..pos]
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        (1..=2).contains(&digits)
+            && ["st", "nd", "rd", "th"]
+                .iter()
+                .any(|suffix| bytes[pos..pos + 2].eq_ignore_ascii_case(suffix.as_bytes()))
+            && bytes
+                .get(pos + 2)
+                .map_or(true, |b| !b.is_ascii_alphanumeric())
+    };
+    let positions = (1..bytes.len().saturating_sub(1))
+        .filter(|pos| is_suffix(*pos))
+        .collect::<Vec<_>>();
+    if positions.is_empty() {
+        return Cow::Borrowed(val);
+    }
+
+    let mut stripped = String::with_capacity(val.len());
+    let mut start = 0;
+    for pos in positions {
+        stripped.push_str(&val[start..pos]);
+        start = pos + 2;
+    }
+    stripped.push_str(&val[start..]);
+    Cow::Owned(stripped)
+}
+
+/// Parses the timestamp, the string without a timezone is in the timezone `tz`.
+fn push_string_to_timestamp(val: &str, tz: Tz, output: &mut Vec<i64>, ctx: &mut EvalContext) {
+    let enable_dst_hour_fix = ctx.func_ctx.enable_dst_hour_fix;
+    let val = normalize_comma_fraction(ctx, val);
+    let val = strip_ordinal_suffix(ctx, val.as_ref());
+    let (val, roll_forward) = rewrite_leap_second(ctx, val.as_ref());
+    let val = val.as_ref();
+    let row = output.len();
+    if ctx.func_ctx.enable_strict_datetime_parser {
+        match string_to_timestamp(val, tz, enable_dst_hour_fix) {
+            Ok(ts) => output.push(ts.timestamp_micros()),
+            Err(e) => {
+                let msg = datetime_parse_error(ctx, output.len(), val, "TIMESTAMP", e);
+                ctx.set_error(output.len(), msg);
+                output.push(0);
+            }
+        }
+    } else if let Some(dt) = relative_datetime_keyword(ctx, tz, val) {
+        output.push(dt.timestamp_micros());
+    } else {
+        match parse(val) {
+            Ok((naive_dt, parse_tz)) => {
+                if let Some(parse_tz) = parse_tz {
+                    match naive_dt.and_local_timezone(parse_tz) {
+                        MappedLocalTime::Single(res) => {
+                            output.push(res.with_timezone(&tz).timestamp_micros())
+                        }
+                        MappedLocalTime::None => {
+                            if enable_dst_hour_fix {
+                                if let Some(res2) =
+                                    naive_dt.checked_add_signed(Duration::seconds(3600))
+                                {
+                                    match tz.from_local_datetime(&res2) {
+                                        MappedLocalTime::Single(t) => {
+                                            output.push(t.timestamp_micros())
+                                        }
+                                        MappedLocalTime::Ambiguous(t1, _) => {
+                                            output.push(t1.timestamp_micros())
+                                        }
+                                        MappedLocalTime::None => {
+                                            let err = format!(
+                                                "Local Time Error: The local time {:?}, {} can not map to a single unique result with timezone {}",
+                                                naive_dt, res2, tz
+                                            );
+                                            let msg = datetime_parse_error(
+                                                ctx,
+                                                output.len(),
+                                                val,
+                                                "TIMESTAMP",
+                                                err,
+                                            );
+                                            ctx.set_error(output.len(), msg);
+                                            output.push(0);
+                                        }
+                                    }
+                                }
+                            } else {
+                                let err = format!(
+                                    "The time {:?} can not map to a single unique result with timezone {}",
+                                    naive_dt, tz
+                                );
+                                let msg =
+                                    datetime_parse_error(ctx, output.len(), val, "TIMESTAMP", err);
+                                ctx.set_error(output.len(), msg);
+                                output.push(0);
+                            }
+                        }
+                        MappedLocalTime::Ambiguous(t1, t2) => {
+                            if enable_dst_hour_fix {
+                                output.push(t1.with_timezone(&tz).timestamp_micros());
+                            } else {
+                                output.push(t2.with_timezone(&tz).timestamp_micros());
+                            }
+                        }
+                    }
+                } else {
+                    match unwrap_local_time(&tz, enable_dst_hour_fix, &naive_dt) {
+                        Ok(res) => output.push(res.timestamp_micros()),
+                        Err(e) => {
+                            let msg = datetime_parse_error(ctx, output.len(), val, "TIMESTAMP", e);
+                            ctx.set_error(output.len(), msg);
+                            output.push(0);
+                        }
+                    }
+                }
+            }
+            Err(err) => {
+                let msg = datetime_parse_error(ctx, output.len(), val, "TIMESTAMP", err);
+                ctx.set_error(output.len(), msg);
+                output.push(0);
+            }
+        }
+    }
+
+    // The leap second is rolled forward unless the parsing fails
+    if roll_forward
+        && ctx
+            .errors
+            .as_ref()
+            .map_or(true, |(valids, _)| valids.get(row))
+    {
+        match check_timestamp(output[row] + MICROS_IN_A_SEC) {
+            Ok(ts) => output[row] = ts,
+            Err(e) => ctx.set_error(row, e),
+        }
+    }
+}
+
+/// Rewrites the leap second `:60` following the hours and minutes `HH:MM` as `:59`. With
+/// `LeapSecondPolicy::Clamp` the fractional seconds are replaced with `.999999`, with
+/// `LeapSecondPolicy::RollForward` they are kept and the returned flag tells to add a second
+/// after the parsing, so `23:59:60.5` is `00:00:00.5` of the next day.
+///
+/// Returns `val` itself if the policy is `LeapSecondPolicy::Error` or there is no leap second.
+fn rewrite_leap_second<'a>(ctx: &EvalContext, val: &'a str) -> (Cow<'a, str>, bool) {
+    let policy = ctx.func_ctx.leap_second_policy;
+    if policy == LeapSecondPolicy::Error {
+        return (Cow::Borrowed(val), false);
+    }
+
+    let bytes = val.as_bytes();
+    let pos = bytes.windows(6).enumerate().position(|(pos, w)| {
+        w[0] == b':'
+            && w[1].is_ascii_digit()
+            && w[2].is_ascii_digit()
+            && w[3] == b':'
+            && &w[4..] == b"60"
+            && bytes.get(pos + 6).map_or(true, |b| !b.is_ascii_digit())
+    });
+    let Some(pos) = pos else {
+        return (Cow::Borrowed(val), false);
+    };
+
+    let mut rewritten = val.to_string();
+    rewritten.replace_range(pos + 4..pos + 6, "59");
+    if policy == LeapSecondPolicy::RollForward {
+        return (Cow::Owned(rewritten), true);
+    }
+    let fraction_end = match bytes.get(pos + 6) {
+        Some(b'.') => {
+            pos + 7
+                + bytes[pos + 7..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count()
+        }
+        _ => pos + 6,
+    };
+    rewritten.replace_range(pos + 6..fraction_end, ".999999");
+    (Cow::Owned(rewritten), false)
+}
+
 fn int64_domain_to_timestamp_domain<T: AsPrimitive<i64>>(
     domain: &SimpleDomain<T>,
 ) -> Option<SimpleDomain<i64>> {
@@ -139,6 +537,7 @@ fn register_string_to_timestamp(registry: &mut FunctionRegistry) {
     registry.register_aliases("to_month", &["month"]);
     registry.register_aliases("to_quarter", &["quarter"]);
     registry.register_aliases("to_week_of_year", &["week", "weekofyear"]);
+    registry.register_aliases("to_week_of_month", &["weekofmonth"]);
 
     registry.register_aliases("to_timestamp", &["to_datetime", "str_to_timestamp"]);
     registry.register_aliases("try_to_timestamp", &["try_to_datetime"]);
@@ -154,222 +553,387 @@ fn register_string_to_timestamp(registry: &mut FunctionRegistry) {
         error_to_null(eval_string_to_timestamp),
     );
 
+    registry.register_function_factory("try_to_timestamp_struct", |_, args_type| {
+        if args_type.len() != 1 {
+            return None;
+        }
+        let has_null = args_type[0].is_nullable_or_null();
+        if !matches!(
+            args_type[0].remove_nullable(),
+            DataType::String | DataType::Null
+        ) {
+            return None;
+        }
+
+        let f = Function {
+            signature: FunctionSignature {
+                name: "try_to_timestamp_struct".to_string(),
+                args_type: vec![DataType::String],
+                return_type: timestamp_struct_type(),
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, _| FunctionDomain::Full),
+                eval: Box::new(eval_string_to_timestamp_struct),
+            },
+        };
+        if has_null {
+            Some(Arc::new(f.passthrough_nullable()))
+        } else {
+            Some(Arc::new(f))
+        }
+    });
+
+    registry.register_passthrough_nullable_1_arg::<StringType, TimestampType, _, _>(
+        "to_timestamp_utc",
+        |_, _| FunctionDomain::MayThrow,
+        |val, ctx| eval_in_utc(ctx, |ctx| eval_string_to_timestamp(val, ctx)),
+    );
+
+    registry.register_passthrough_nullable_2_arg::<StringType, StringType, TimestampType, _, _>(
+        "to_timestamp_in_tz",
+        |_, _, _| FunctionDomain::MayThrow,
+        eval_string_to_timestamp_in_tz,
+    );
+
     fn eval_string_to_timestamp(
         val: ValueRef<StringType>,
         ctx: &mut EvalContext,
     ) -> Value<TimestampType> {
-        vectorize_with_builder_1_arg::<StringType, TimestampType>(|val, output, ctx| {
-            let tz = ctx.func_ctx.tz.tz;
-            let enable_dst_hour_fix = ctx.func_ctx.enable_dst_hour_fix;
-            if ctx.func_ctx.enable_strict_datetime_parser {
-                match string_to_timestamp(val, tz, enable_dst_hour_fix) {
-                    Ok(ts) => output.push(ts.timestamp_micros()),
-                    Err(e) => {
-                        ctx.set_error(
-                            output.len(),
-                            format!("cannot parse to type `TIMESTAMP`. {}", e),
-                        );
-                        output.push(0);
-                    }
-                }
-            } else {
-                match parse(val) {
-                    Ok((naive_dt, parse_tz)) => {
-                        if let Some(parse_tz) = parse_tz {
-                            match naive_dt.and_local_timezone(parse_tz) {
-                                MappedLocalTime::Single(res) => {
-                                    output.push(res.with_timezone(&tz).timestamp_micros())
-                                }
-                                MappedLocalTime::None => {
-                                    if enable_dst_hour_fix {
-                                        if let Some(res2) =
-                                            naive_dt.checked_add_signed(Duration::seconds(3600))
-                                        {
-                                            match tz.from_local_datetime(&res2) {
-                                                MappedLocalTime::Single(t) => {
-                                                    output.push(t.timestamp_micros())
-                                                }
-                                                MappedLocalTime::Ambiguous(t1, _) => {
-                                                    output.push(t1.timestamp_micros())
-                                                }
-                                                MappedLocalTime::None => {
-                                                    let err = format!(
-                                                        "Local Time Error: The local time {:?}, {} can not map to a single unique result with timezone {}",
-                                                        naive_dt, res2, tz
-                                                    );
-                                                    ctx.set_error(
-                                                        output.len(),
-                                                        format!(
-                                                            "cannot parse to type `TIMESTAMP`. {}",
-                                                            err
-                                                        ),
-                                                    );
-                                                    output.push(0);
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        let err = format!(
-                                            "The time {:?} can not map to a single unique result with timezone {}",
-                                            naive_dt, tz
-                                        );
-                                        ctx.set_error(
-                                            output.len(),
-                                            format!("cannot parse to type `TIMESTAMP`. {}", err),
-                                        );
-                                        output.push(0);
-                                    }
-                                }
-                                MappedLocalTime::Ambiguous(t1, t2) => {
-                                    if enable_dst_hour_fix {
-                                        output.push(t1.with_timezone(&tz).timestamp_micros());
-                                    } else {
-                                        output.push(t2.with_timezone(&tz).timestamp_micros());
-                                    }
-                                }
-                            }
-                        } else {
-                            match unwrap_local_time(&tz, enable_dst_hour_fix, &naive_dt) {
-                                Ok(res) => output.push(res.timestamp_micros()),
-                                Err(e) => {
-                                    ctx.set_error(
-                                        output.len(),
-                                        format!("cannot parse to type `TIMESTAMP`. {}", e),
-                                    );
-                                    output.push(0);
-                                }
-                            }
-                        }
-                    }
-                    Err(err) => {
-                        ctx.set_error(
-                            output.len(),
-                            format!("cannot parse to type `TIMESTAMP`. {}", err),
-                        );
-                        output.push(0);
-                    }
-                }
-            }
-        })(val, ctx)
+        let value =
+            vectorize_with_builder_1_arg::<StringType, TimestampType>(|val, output, ctx| {
+                push_string_to_timestamp(val, ctx.func_ctx.tz.tz, output, ctx)
+            })(val.clone(), ctx);
+        summarize_datetime_parse_errors(ctx, &val);
+        value
+    }
+
+    /// The parsed timestamp, whether the string is parsed and the string itself, so the
+    /// unparsed strings can be kept instead of turning into NULLs.
+    fn eval_string_to_timestamp_struct(
+        args: &[ValueRef<AnyType>],
+        ctx: &mut EvalContext,
+    ) -> Value<AnyType> {
+        let val = args[0].try_downcast::<StringType>().unwrap();
+        match error_to_null(eval_string_to_timestamp)(val.clone(), ctx) {
+            Value::Scalar(ts) => Value::Scalar(Scalar::Tuple(vec![
+                ts.map_or(Scalar::Null, Scalar::Timestamp),
+                Scalar::Boolean(ts.is_some()),
+                Scalar::String(val.into_scalar().unwrap().to_string()),
+            ])),
+            Value::Column(col) => Value::Column(Column::Tuple(vec![
+                NullableType::<TimestampType>::upcast_column(col.clone()),
+                Column::Boolean(col.validity),
+                Column::String(val.into_column().unwrap()),
+            ])),
+        }
     }
 
     registry.register_combine_nullable_2_arg::<StringType, StringType, TimestampType, _, _>(
         "to_timestamp",
         |_, _, _| FunctionDomain::MayThrow,
-        vectorize_with_builder_2_arg::<StringType, StringType, NullableType<TimestampType>>(
-            |timestamp, format, output, ctx| match string_to_format_timestamp(
-                timestamp, format, ctx,
-            ) {
-                Ok((ts, need_null)) => {
-                    if need_null {
-                        output.push_null();
-                    } else {
-                        output.push(ts);
-                    }
-                }
-                Err(e) => {
-                    ctx.set_error(output.len(), e.to_string());
-                    output.push_null();
-                }
-            },
-        ),
+        |timestamp, format, ctx| eval_string_to_format_timestamp(timestamp, format, ctx, false),
     );
 
     registry.register_combine_nullable_2_arg::<StringType, StringType, TimestampType, _, _>(
         "try_to_timestamp",
         |_, _, _| FunctionDomain::MayThrow,
-        vectorize_with_builder_2_arg::<StringType, StringType, NullableType<TimestampType>>(
-            |timestamp, format, output, ctx| match string_to_format_timestamp(
-                timestamp, format, ctx,
-            ) {
-                Ok((ts, need_null)) => {
-                    if need_null {
-                        output.push_null();
-                    } else {
-                        output.push(ts);
-                    }
-                }
-                Err(_) => {
-                    output.push_null();
-                }
-            },
-        ),
+        |timestamp, format, ctx| eval_string_to_format_timestamp(timestamp, format, ctx, true),
     );
 
+    registry
+        .register_combine_nullable_2_arg::<StringType, ArrayType<StringType>, TimestampType, _, _>(
+            "to_timestamp",
+            |_, _, _| FunctionDomain::MayThrow,
+            |timestamp, formats, ctx| {
+                eval_string_to_format_array_timestamp(timestamp, formats, ctx, false)
+            },
+        );
+
+    registry
+        .register_combine_nullable_2_arg::<StringType, ArrayType<StringType>, TimestampType, _, _>(
+            "try_to_timestamp",
+            |_, _, _| FunctionDomain::MayThrow,
+            |timestamp, formats, ctx| {
+                eval_string_to_format_array_timestamp(timestamp, formats, ctx, true)
+            },
+        );
+
     registry.register_combine_nullable_2_arg::<StringType, StringType, DateType, _, _>(
         "to_date",
         |_, _, _| FunctionDomain::MayThrow,
-        vectorize_with_builder_2_arg::<StringType, StringType, NullableType<DateType>>(
-            |date, format, output, ctx| {
-                if format.is_empty() {
-                    output.push_null();
-                } else {
-                    match NaiveDate::parse_from_str(date, format) {
-                        Ok(res) => {
-                            output.push(res.num_days_from_ce() - EPOCH_DAYS_FROM_CE);
-                        }
-                        Err(e) => {
-                            ctx.set_error(output.len(), e.to_string());
-                            output.push_null();
-                        }
-                    }
-                }
-            },
-        ),
+        |date, format, ctx| eval_string_to_format_date(date, format, ctx, false),
     );
 
     registry.register_combine_nullable_2_arg::<StringType, StringType, DateType, _, _>(
         "try_to_date",
         |_, _, _| FunctionDomain::MayThrow,
-        vectorize_with_builder_2_arg::<StringType, StringType, NullableType<DateType>>(
-            |date, format, output, _| {
-                if format.is_empty() {
-                    output.push_null();
-                } else {
-                    match NaiveDate::parse_from_str(date, format) {
-                        Ok(res) => {
-                            output.push(res.num_days_from_ce() - EPOCH_DAYS_FROM_CE);
-                        }
-                        Err(_) => {
-                            output.push_null();
-                        }
+        |date, format, ctx| eval_string_to_format_date(date, format, ctx, true),
+    );
+}
+
+/// Parses the timestamps, the strings without a timezone are in the timezone of their own row.
+fn eval_string_to_timestamp_in_tz(
+    val: ValueRef<StringType>,
+    tz: ValueRef<StringType>,
+    ctx: &mut EvalContext,
+) -> Value<TimestampType> {
+    let value = match tz {
+        ValueRef::Scalar(tz_name) => match tz_name.parse::<Tz>() {
+            Ok(tz) => {
+                vectorize_with_builder_1_arg::<StringType, TimestampType>(|val, output, ctx| {
+                    push_string_to_timestamp(val, tz, output, ctx)
+                })(val.clone(), ctx)
+            }
+            Err(_) => {
+                ctx.set_error(0, format!("Invalid timezone: {tz_name:?}"));
+                Value::Scalar(0)
+            }
+        },
+        ValueRef::Column(tz_column) => {
+            let mut output = Vec::with_capacity(tz_column.len());
+            // The rows of the same timezone are usually adjacent, so the last one is reused
+            // instead of looking up the timezone for every row.
+            let mut last_tz: Option<(&str, Option<Tz>)> = None;
+            for (row, tz_name) in tz_column.iter().enumerate() {
+                let val = match &val {
+                    ValueRef::Scalar(val) => *val,
+                    ValueRef::Column(column) => unsafe { column.index_unchecked(row) },
+                };
+                let tz = match last_tz {
+                    Some((last_name, tz)) if last_name == tz_name => tz,
+                    _ => {
+                        let tz = tz_name.parse::<Tz>().ok();
+                        last_tz = Some((tz_name, tz));
+                        tz
+                    }
+                };
+                match tz {
+                    Some(tz) => push_string_to_timestamp(val, tz, &mut output, ctx),
+                    None => {
+                        ctx.set_error(row, format!("Invalid timezone: {tz_name:?}"));
+                        output.push(0);
                     }
                 }
-            },
-        ),
-    );
+            }
+            Value::Column(output.into())
+        }
+    };
+    summarize_datetime_parse_errors(ctx, &val);
+    value
+}
+
+/// The format of `to_timestamp` and `to_date`, which is parsed only once if it's a constant.
+struct DatetimeFormat<'a> {
+    items: Vec<Item<'a>>,
+    // Whether the format has the timezone.
+    parse_tz: bool,
+}
+
+impl<'a> DatetimeFormat<'a> {
+    fn new(format: &'a str) -> Self {
+        // Parse with extra checks for timezone
+        // %Z	ACST	Local time zone name. Skips all non-whitespace characters during parsing. Identical to %:z when formatting. 6
+        // %z	+0930	Offset from the local time to UTC (with UTC being +0000).
+        // %:z	+09:30	Same as %z but with a colon.
+        // %::z	+09:30:00	Offset from the local time to UTC with seconds.
+        // %:::z	+09	Offset from the local time to UTC without minutes.
+        // %#z	+09	Parsing only: Same as %z but allows minutes to be missing or present.
+        let timezone_strftime = ["%Z", "%z", "%:z", "%::z", "%:::z", "%#z"];
+        let parse_tz = timezone_strftime
+            .iter()
+            .any(|&pattern| format.contains(pattern));
+        Self {
+            items: StrftimeItems::new(format).collect(),
+            parse_tz,
+        }
+    }
+
+    // Only the empty format has no items.
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+fn eval_string_to_format_timestamp(
+    timestamp: ValueRef<StringType>,
+    format: ValueRef<StringType>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) -> Value<NullableType<TimestampType>> {
+    let value = match format {
+        ValueRef::Scalar(format) => {
+            let format = DatetimeFormat::new(format);
+            vectorize_with_builder_1_arg::<StringType, NullableType<TimestampType>>(
+                |timestamp, output, ctx| {
+                    push_format_timestamp(timestamp, &format, output, ctx, is_try)
+                },
+            )(timestamp.clone(), ctx)
+        }
+        ValueRef::Column(_) => {
+            vectorize_with_builder_2_arg::<StringType, StringType, NullableType<TimestampType>>(
+                |timestamp, format, output, ctx| {
+                    let format = DatetimeFormat::new(format);
+                    push_format_timestamp(timestamp, &format, output, ctx, is_try)
+                },
+            )(timestamp.clone(), format, ctx)
+        }
+    };
+    summarize_datetime_parse_errors(ctx, &timestamp);
+    value
+}
+
+/// Parse the timestamp with each of the formats in order, the first one that matches wins.
+fn eval_string_to_format_array_timestamp(
+    timestamp: ValueRef<StringType>,
+    formats: ValueRef<ArrayType<StringType>>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) -> Value<NullableType<TimestampType>> {
+    let value = match formats {
+        ValueRef::Scalar(formats) => {
+            let formats = formats.iter().map(DatetimeFormat::new).collect::<Vec<_>>();
+            vectorize_with_builder_1_arg::<StringType, NullableType<TimestampType>>(
+                |timestamp, output, ctx| {
+                    push_format_array_timestamp(timestamp, &formats, output, ctx, is_try)
+                },
+            )(timestamp.clone(), ctx)
+        }
+        ValueRef::Column(_) => vectorize_with_builder_2_arg::<
+            StringType,
+            ArrayType<StringType>,
+            NullableType<TimestampType>,
+        >(|timestamp, formats, output, ctx| {
+            let formats = formats.iter().map(DatetimeFormat::new).collect::<Vec<_>>();
+            push_format_array_timestamp(timestamp, &formats, output, ctx, is_try)
+        })(timestamp.clone(), formats, ctx),
+    };
+    summarize_datetime_parse_errors(ctx, &timestamp);
+    value
+}
+
+fn eval_string_to_format_date(
+    date: ValueRef<StringType>,
+    format: ValueRef<StringType>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) -> Value<NullableType<DateType>> {
+    let value = match format {
+        ValueRef::Scalar(format) => {
+            let format = DatetimeFormat::new(format);
+            vectorize_with_builder_1_arg::<StringType, NullableType<DateType>>(
+                |date, output, ctx| push_format_date(date, &format, output, ctx, is_try),
+            )(date.clone(), ctx)
+        }
+        ValueRef::Column(_) => {
+            vectorize_with_builder_2_arg::<StringType, StringType, NullableType<DateType>>(
+                |date, format, output, ctx| {
+                    let format = DatetimeFormat::new(format);
+                    push_format_date(date, &format, output, ctx, is_try)
+                },
+            )(date.clone(), format, ctx)
+        }
+    };
+    summarize_datetime_parse_errors(ctx, &date);
+    value
+}
+
+fn push_format_timestamp(
+    timestamp: &str,
+    format: &DatetimeFormat,
+    output: &mut NullableColumnBuilder<TimestampType>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) {
+    match string_to_format_timestamp(timestamp, format, ctx) {
+        Ok((_, true)) => output.push_null(),
+        Ok((ts, false)) => output.push(ts),
+        Err(e) => {
+            if !is_try {
+                ctx.set_error(output.len(), e.to_string());
+            }
+            output.push_null();
+        }
+    }
+}
+
+// The empty array of formats is NULL, the same as the empty format.
+fn push_format_array_timestamp(
+    timestamp: &str,
+    formats: &[DatetimeFormat],
+    output: &mut NullableColumnBuilder<TimestampType>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) {
+    let parsed = formats
+        .iter()
+        .find_map(|format| string_to_format_timestamp(timestamp, format, ctx).ok());
+    match parsed {
+        Some((ts, false)) => output.push(ts),
+        Some((_, true)) => output.push_null(),
+        None if formats.is_empty() => output.push_null(),
+        None => {
+            if !is_try {
+                ctx.set_error(
+                    output.len(),
+                    format!(
+                        "Cannot parse '{}' with any of the {} formats",
+                        truncate_error_value(timestamp),
+                        formats.len()
+                    ),
+                );
+            }
+            output.push_null();
+        }
+    }
+}
+
+fn push_format_date(
+    date: &str,
+    format: &DatetimeFormat,
+    output: &mut NullableColumnBuilder<DateType>,
+    ctx: &mut EvalContext,
+    is_try: bool,
+) {
+    if format.is_empty() {
+        output.push_null();
+        return;
+    }
+    match string_to_format_date(date, format, ctx) {
+        Ok(res) => output.push(res),
+        Err(e) => {
+            if !is_try {
+                ctx.set_error(output.len(), e);
+            }
+            output.push_null();
+        }
+    }
 }
 
 fn string_to_format_timestamp(
     timestamp: &str,
-    format: &str,
+    format: &DatetimeFormat,
     ctx: &mut EvalContext,
 ) -> Result<(i64, bool), ErrorCode> {
     if format.is_empty() {
         return Ok((0, true));
     }
-    // Parse with extra checks for timezone
-    // %Z	ACST	Local time zone name. Skips all non-whitespace characters during parsing. Identical to %:z when formatting. 6
-    // %z	+0930	Offset from the local time to UTC (with UTC being +0000).
-    // %:z	+09:30	Same as %z but with a colon.
-    // %::z	+09:30:00	Offset from the local time to UTC with seconds.
-    // %:::z	+09	Offset from the local time to UTC without minutes.
-    // %#z	+09	Parsing only: Same as %z but allows minutes to be missing or present.
-    let timezone_strftime = ["%Z", "%z", "%:z", "%::z", "%:::z", "%#z"];
-    let parse_tz = timezone_strftime
-        .iter()
-        .any(|&pattern| format.contains(pattern));
+    let timestamp = strip_ordinal_suffix(ctx, timestamp);
+    let timestamp = timestamp.as_ref();
+    let parse_tz = format.parse_tz;
     let enable_dst_hour_fix = ctx.func_ctx.enable_dst_hour_fix;
     let tz = ctx.func_ctx.tz.tz;
+    let two_digit_year_pivot = ctx.func_ctx.two_digit_year_pivot;
+    let mut parsed = Parsed::new();
     if ctx.func_ctx.parse_datetime_ignore_remainder {
-        let mut parsed = Parsed::new();
-        if let Err(e) = parse_and_remainder(&mut parsed, timestamp, StrftimeItems::new(format)) {
+        if let Err(e) = parse_and_remainder(&mut parsed, timestamp, format.items.iter()) {
             return Err(ErrorCode::BadArguments(format!("{}", e)));
         }
         // Additional checks and adjustments for parsed timestamp
         // If parsed.timestamp is Some no need to pad default year.
         if parsed.timestamp.is_none() {
-            if parsed.year.is_none() {
+            // Without the pivot, the year parsed by `%y` is padded as well.
+            if parsed.year.is_none() && (two_digit_year_pivot == 0 || parsed.year_mod_100.is_none())
+            {
                 parsed.year = Some(1970);
                 parsed.year_div_100 = Some(19);
                 parsed.year_mod_100 = Some(70);
@@ -391,40 +955,70 @@ fn string_to_format_timestamp(
                 parsed.second = Some(0);
             }
         }
-
         if parse_tz {
             parsed.offset.get_or_insert(0);
-            parsed
-                .to_datetime()
-                .map(|res| (res.timestamp_micros(), false))
-                .map_err(|err| ErrorCode::BadArguments(format!("{err}")))
-        } else {
-            parsed
-                .to_naive_datetime_with_offset(0)
-                .map_err(|err| ErrorCode::BadArguments(format!("{err}")))
-                .and_then(
-                    |res| match unwrap_local_time(&tz, enable_dst_hour_fix, &res) {
-                        Ok(res) => Ok((res.timestamp_micros(), false)),
-                        Err(e) => Err(e),
-                    },
-                )
         }
-    } else if parse_tz {
-        DateTime::parse_from_str(timestamp, format)
-            .map(|res| (res.timestamp_micros(), false))
-            .map_err(|err| ErrorCode::BadArguments(format!("{}", err)))
+    } else if let Err(e) = chrono::format::parse(&mut parsed, timestamp, format.items.iter()) {
+        return Err(ErrorCode::BadArguments(format!("{}", e)));
+    }
+    resolve_two_digit_year(&mut parsed, two_digit_year_pivot);
+
+    if parse_tz {
+        parsed
+            .to_datetime()
+            .map(|res| (round_micros(&res), false))
+            .map_err(|err| ErrorCode::BadArguments(format!("{err}")))
     } else {
-        NaiveDateTime::parse_from_str(timestamp, format)
-            .map_err(|err| ErrorCode::BadArguments(format!("{}", err)))
+        parsed
+            .to_naive_datetime_with_offset(0)
+            .map_err(|err| ErrorCode::BadArguments(format!("{err}")))
             .and_then(
                 |res| match unwrap_local_time(&tz, enable_dst_hour_fix, &res) {
-                    Ok(res) => Ok((res.timestamp_micros(), false)),
+                    Ok(res) => Ok((round_micros(&res), false)),
                     Err(e) => Err(e),
                 },
             )
     }
 }
 
+/// Parse the date with the format, the century of the two-digit year is resolved by the pivot.
+fn string_to_format_date(
+    date: &str,
+    format: &DatetimeFormat,
+    ctx: &EvalContext,
+) -> Result<i32, String> {
+    let date = strip_ordinal_suffix(ctx, date);
+    let mut parsed = Parsed::new();
+    chrono::format::parse(&mut parsed, &date, format.items.iter()).map_err(|e| e.to_string())?;
+    resolve_two_digit_year(&mut parsed, ctx.func_ctx.two_digit_year_pivot);
+    parsed
+        .to_naive_date()
+        .map(|res| res.num_days_from_ce() - EPOCH_DAYS_FROM_CE)
+        .map_err(|e| e.to_string())
+}
+
+/// Resolve the century of the year parsed by `%y` without `%C`, the years below the pivot
+/// are in the 2000s, and the others are in the 1900s. The pivot 0 keeps the century
+/// resolved by chrono.
+fn resolve_two_digit_year(parsed: &mut Parsed, pivot: u8) {
+    if pivot != 0 && parsed.year.is_none() && parsed.year_div_100.is_none() {
+        if let Some(year_mod_100) = parsed.year_mod_100 {
+            parsed.year_div_100 = Some(if year_mod_100 < pivot as i32 { 20 } else { 19 });
+        }
+    }
+}
+
+/// Convert a parsed datetime into micros, the sub-microsecond part is rounded half-up.
+#[inline]
+fn round_micros<T: TimeZone>(dt: &DateTime<T>) -> i64 {
+    let micros = dt.timestamp_micros();
+    if dt.timestamp_subsec_nanos() % 1_000 >= 500 {
+        micros + 1
+    } else {
+        micros
+    }
+}
+
 fn register_date_to_timestamp(registry: &mut FunctionRegistry) {
     registry.register_passthrough_nullable_1_arg::<DateType, TimestampType, _, _>(
         "to_timestamp",
@@ -461,22 +1055,23 @@ fn register_date_to_timestamp(registry: &mut FunctionRegistry) {
             output.push(calc_date_to_timestamp(val, tz));
         })(val, ctx)
     }
+}
 
-    fn calc_date_to_timestamp(val: i32, tz: Tz) -> i64 {
-        let ts = (val as i64) * 24 * 3600 * MICROS_IN_A_SEC;
-        let epoch_time_with_ltz = tz
-            .from_utc_datetime(
-                &NaiveDate::from_ymd_opt(1970, 1, 1)
-                    .unwrap()
-                    .and_hms_micro_opt(0, 0, 0, 0)
-                    .unwrap(),
-            )
-            .naive_local()
-            .and_utc()
-            .timestamp_micros();
-
-        ts - epoch_time_with_ltz
-    }
+/// Promotes a date to the timestamp of its midnight in the given timezone.
+pub(crate) fn calc_date_to_timestamp(val: i32, tz: Tz) -> i64 {
+    let ts = (val as i64) * 24 * 3600 * MICROS_IN_A_SEC;
+    let epoch_time_with_ltz = tz
+        .from_utc_datetime(
+            &NaiveDate::from_ymd_opt(1970, 1, 1)
+                .unwrap()
+                .and_hms_micro_opt(0, 0, 0, 0)
+                .unwrap(),
+        )
+        .naive_local()
+        .and_utc()
+        .timestamp_micros();
+
+    ts - epoch_time_with_ltz
 }
 
 fn register_number_to_timestamp(registry: &mut FunctionRegistry) {
@@ -533,27 +1128,35 @@ fn register_string_to_date(registry: &mut FunctionRegistry) {
     );
 
     fn eval_string_to_date(val: ValueRef<StringType>, ctx: &mut EvalContext) -> Value<DateType> {
-        vectorize_with_builder_1_arg::<StringType, DateType>(|val, output, ctx| {
+        let value = vectorize_with_builder_1_arg::<StringType, DateType>(|val, output, ctx| {
+            let val = strip_ordinal_suffix(ctx, val);
+            let val = val.as_ref();
             if ctx.func_ctx.enable_strict_datetime_parser {
                 match string_to_date(val, ctx.func_ctx.tz.tz, ctx.func_ctx.enable_dst_hour_fix) {
                     Ok(d) => output.push(d.num_days_from_ce() - EPOCH_DAYS_FROM_CE),
                     Err(e) => {
-                        ctx.set_error(output.len(), format!("cannot parse to type `DATE`. {}", e));
+                        let msg = datetime_parse_error(ctx, output.len(), val, "DATE", e);
+                        ctx.set_error(output.len(), msg);
                         output.push(0);
                     }
                 }
+            } else if let Some(dt) = relative_datetime_keyword(ctx, ctx.func_ctx.tz.tz, val) {
+                output.push(dt.date_naive().num_days_from_ce() - EPOCH_DAYS_FROM_CE);
             } else {
                 match parse(val) {
                     Ok((naive_dt, _)) => {
                         output.push(naive_dt.date().num_days_from_ce() - EPOCH_DAYS_FROM_CE);
                     }
                     Err(e) => {
-                        ctx.set_error(output.len(), format!("cannot parse to type `DATE`. {}", e));
+                        let msg = datetime_parse_error(ctx, output.len(), val, "DATE", e);
+                        ctx.set_error(output.len(), msg);
                         output.push(0);
                     }
                 }
             }
-        })(val, ctx)
+        })(val.clone(), ctx);
+        summarize_datetime_parse_errors(ctx, &val);
+        value
     }
 }
 
@@ -636,45 +1239,226 @@ fn register_number_to_date(registry: &mut FunctionRegistry) {
     }
 }
 
+/// The outcome of `try_to_timestamp_struct`: the nullable timestamp, whether the string is
+/// parsed, and the original string.
+fn timestamp_struct_type() -> DataType {
+    DataType::Tuple(vec![
+        DataType::Timestamp.wrap_nullable(),
+        DataType::Boolean,
+        DataType::String,
+    ])
+}
+
+/// The timestamp with its original UTC offset in seconds, the foundation of a timestamptz type.
+fn timestamp_tz_type() -> DataType {
+    DataType::Tuple(vec![
+        DataType::Timestamp,
+        DataType::Number(NumberDataType::Int32),
+    ])
+}
+
+fn register_timestamp_tz(registry: &mut FunctionRegistry) {
+    registry.register_function_factory("to_timestamp_tz", |_, args_type| {
+        if args_type.len() != 1 {
+            return None;
+        }
+        let has_null = args_type[0].is_nullable_or_null();
+        if !matches!(
+            args_type[0].remove_nullable(),
+            DataType::String | DataType::Null
+        ) {
+            return None;
+        }
+
+        let f = Function {
+            signature: FunctionSignature {
+                name: "to_timestamp_tz".to_string(),
+                args_type: vec![DataType::String],
+                return_type: timestamp_tz_type(),
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, _| FunctionDomain::MayThrow),
+                eval: Box::new(eval_string_to_timestamp_tz),
+            },
+        };
+        if has_null {
+            Some(Arc::new(f.passthrough_nullable()))
+        } else {
+            Some(Arc::new(f))
+        }
+    });
+
+    registry.register_function_factory("timestamptz_offset", |_, args_type| {
+        if args_type.len() != 1 {
+            return None;
+        }
+        let has_null = args_type[0].is_nullable_or_null();
+        if !args_type[0].is_null() && args_type[0].remove_nullable() != timestamp_tz_type() {
+            return None;
+        }
+
+        let f = Function {
+            signature: FunctionSignature {
+                name: "timestamptz_offset".to_string(),
+                args_type: vec![timestamp_tz_type()],
+                return_type: DataType::Number(NumberDataType::Int32),
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, args_domain| {
+                    FunctionDomain::Domain(args_domain[0].as_tuple().unwrap()[1].clone())
+                }),
+                eval: Box::new(|args, _| match &args[0] {
+                    ValueRef::Scalar(ScalarRef::Tuple(fields)) => {
+                        Value::Scalar(fields[1].to_owned())
+                    }
+                    ValueRef::Column(Column::Tuple(fields)) => Value::Column(fields[1].clone()),
+                    _ => unreachable!(),
+                }),
+            },
+        };
+        if has_null {
+            Some(Arc::new(f.passthrough_nullable()))
+        } else {
+            Some(Arc::new(f))
+        }
+    });
+
+    fn eval_string_to_timestamp_tz(
+        args: &[ValueRef<AnyType>],
+        ctx: &mut EvalContext,
+    ) -> Value<AnyType> {
+        match args[0].try_downcast::<StringType>().unwrap() {
+            ValueRef::Scalar(val) => {
+                let (micros, offset) = string_to_timestamp_tz(ctx, 0, val);
+                Value::Scalar(Scalar::Tuple(vec![
+                    Scalar::Timestamp(micros),
+                    Scalar::Number(NumberScalar::Int32(offset)),
+                ]))
+            }
+            ValueRef::Column(col) => {
+                let mut micros = Vec::with_capacity(col.len());
+                let mut offsets = Vec::with_capacity(col.len());
+                for (row, val) in col.iter().enumerate() {
+                    let (ts, offset) = string_to_timestamp_tz(ctx, row, val);
+                    micros.push(ts);
+                    offsets.push(offset);
+                }
+                Value::Column(Column::Tuple(vec![
+                    TimestampType::upcast_column(micros.into()),
+                    Int32Type::upcast_column(offsets.into()),
+                ]))
+            }
+        }
+    }
+
+    /// Keeps the offset written in the string, the session timezone applies when it is absent.
+    fn string_to_timestamp_tz(ctx: &mut EvalContext, row: usize, val: &str) -> (i64, i32) {
+        let tz = ctx.func_ctx.tz.tz;
+        let res = match parse(val) {
+            Ok((naive_dt, Some(offset))) => naive_dt
+                .and_local_timezone(offset)
+                .single()
+                .map(|dt| (dt.timestamp_micros(), offset.local_minus_utc())),
+            Ok((naive_dt, None)) => tz
+                .from_local_datetime(&naive_dt)
+                .earliest()
+                .map(|dt| (dt.timestamp_micros(), dt.offset().fix().local_minus_utc())),
+            Err(err) => {
+                let msg = datetime_parse_error(ctx, row, val, "TIMESTAMP", err);
+                ctx.set_error(row, msg);
+                return (0, 0);
+            }
+        };
+        let res = res
+            .ok_or_else(|| format!("The local time can not map to a timestamp in timezone {tz}"))
+            .and_then(|(micros, offset)| Ok((check_timestamp(micros)?, offset)));
+        match res {
+            Ok(res) => res,
+            Err(err) => {
+                let msg = datetime_parse_error(ctx, row, val, "TIMESTAMP", err);
+                ctx.set_error(row, msg);
+                (0, 0)
+            }
+        }
+    }
+}
+
 fn register_to_string(registry: &mut FunctionRegistry) {
     registry.register_aliases("to_string", &["date_format"]);
     registry.register_combine_nullable_2_arg::<TimestampType, StringType, StringType, _, _>(
         "to_string",
         |_, _, _| FunctionDomain::MayThrow,
-        vectorize_with_builder_2_arg::<TimestampType, StringType, NullableType<StringType>>(
-            |date, format, output, ctx| {
-                if format.is_empty() {
-                    output.push_null();
-                } else {
-                    let ts = date.to_timestamp(ctx.func_ctx.tz.tz);
-                    let res = ts.format(format).to_string();
-                    output.push(&res);
-                }
-            },
-        ),
+        |date, format, ctx| match format {
+            ValueRef::Scalar(format) => eval_timestamp_to_string::<NullableType<StringType>>(
+                date,
+                ctx,
+                |micros, ts, output, ctx| push_formatted_timestamp(micros, ts, format, output, ctx),
+            ),
+            ValueRef::Column(_) => {
+                let tz = ctx.func_ctx.tz.tz;
+                vectorize_with_builder_2_arg::<TimestampType, StringType, NullableType<StringType>>(
+                    |date, format, output, ctx| {
+                        let ts = date.to_timestamp_opt(tz);
+                        push_formatted_timestamp(date, ts, format, output, ctx)
+                    },
+                )(date, format, ctx)
+            }
+        },
     );
 
     registry.register_passthrough_nullable_1_arg::<DateType, StringType, _, _>(
         "to_string",
-        |_, _| FunctionDomain::Full,
-        vectorize_with_builder_1_arg::<DateType, StringType>(|val, output, ctx| {
-            write!(output.data, "{}", date_to_string(val, ctx.func_ctx.tz.tz)).unwrap();
-            output.commit_row();
-        }),
+        |_, _| FunctionDomain::MayThrow,
+        |val, ctx| {
+            let func_ctx = ctx.func_ctx;
+            let format = display_format(&func_ctx.date_display_format, DATE_FORMAT);
+            vectorize_with_builder_1_arg::<DateType, StringType>(|val, output, ctx| {
+                match &format {
+                    Ok(DATE_FORMAT) => {
+                        write!(output.data, "{}", date_to_string(val, ctx.func_ctx.tz.tz)).unwrap()
+                    }
+                    Ok(format) => write!(
+                        output.data,
+                        "{}",
+                        format_date(val, format, ctx.func_ctx.tz.tz)
+                    )
+                    .unwrap(),
+                    Err(err) => {
+                        let format = &func_ctx.date_display_format;
+                        let msg = datetime_format_error(ctx, output.len(), format, err);
+                        ctx.set_error(output.len(), msg)
+                    }
+                }
+                output.commit_row();
+            })(val, ctx)
+        },
     );
 
     registry.register_passthrough_nullable_1_arg::<TimestampType, StringType, _, _>(
         "to_string",
-        |_, _| FunctionDomain::Full,
-        vectorize_with_builder_1_arg::<TimestampType, StringType>(|val, output, ctx| {
-            write!(
-                output.data,
-                "{}",
-                timestamp_to_string(val, ctx.func_ctx.tz.tz)
-            )
-            .unwrap();
-            output.commit_row();
-        }),
+        |_, _| FunctionDomain::MayThrow,
+        |val, ctx| {
+            let func_ctx = ctx.func_ctx;
+            let format = display_format(&func_ctx.timestamp_display_format, TIMESTAMP_FORMAT);
+            eval_timestamp_to_string::<StringType>(val, ctx, |micros, ts, output, ctx| {
+                match (ts, &format) {
+                    (Some(ts), Ok(format)) => {
+                        write!(output.data, "{}", FormattedTimestamp { ts, format }).unwrap()
+                    }
+                    (None, _) => {
+                        let err = "timestamp is out of range";
+                        let msg = datetime_format_error(ctx, output.len(), micros, err);
+                        ctx.set_error(output.len(), msg)
+                    }
+                    (_, Err(err)) => {
+                        let format = &func_ctx.timestamp_display_format;
+                        let msg = datetime_format_error(ctx, output.len(), format, err);
+                        ctx.set_error(output.len(), msg)
+                    }
+                }
+                output.commit_row();
+            })
+        },
     );
 
     registry.register_combine_nullable_1_arg::<DateType, StringType, _, _>(
@@ -688,41 +1472,262 @@ fn register_to_string(registry: &mut FunctionRegistry) {
                 })),
             })
         },
-        vectorize_with_builder_1_arg::<DateType, NullableType<StringType>>(|val, output, ctx| {
-            write!(
-                output.builder.data,
-                "{}",
-                date_to_string(val, ctx.func_ctx.tz.tz)
-            )
-            .unwrap();
-            output.builder.commit_row();
-            output.validity.push(true);
-        }),
+        |val, ctx| {
+            let func_ctx = ctx.func_ctx;
+            let format = display_format(&func_ctx.date_display_format, DATE_FORMAT);
+            vectorize_with_builder_1_arg::<DateType, NullableType<StringType>>(
+                |val, output, ctx| match &format {
+                    Ok(format) => {
+                        let tz = ctx.func_ctx.tz.tz;
+                        write!(output.builder.data, "{}", format_date(val, format, tz)).unwrap();
+                        output.builder.commit_row();
+                        output.validity.push(true);
+                    }
+                    Err(_) => output.push_null(),
+                },
+            )(val, ctx)
+        },
     );
 
     registry.register_combine_nullable_1_arg::<TimestampType, StringType, _, _>(
         "try_to_string",
         |_, _| {
             FunctionDomain::Domain(NullableDomain {
-                has_null: false,
+                has_null: true,
                 value: Some(Box::new(StringDomain {
                     min: "".to_string(),
                     max: None,
                 })),
             })
         },
-        vectorize_with_builder_1_arg::<TimestampType, NullableType<StringType>>(
-            |val, output, ctx| {
-                write!(
-                    output.builder.data,
-                    "{}",
-                    timestamp_to_string(val, ctx.func_ctx.tz.tz)
-                )
-                .unwrap();
-                output.builder.commit_row();
-                output.validity.push(true);
-            },
-        ),
+        |val, ctx| {
+            let func_ctx = ctx.func_ctx;
+            let format = display_format(&func_ctx.timestamp_display_format, TIMESTAMP_FORMAT);
+            eval_timestamp_to_string::<NullableType<StringType>>(
+                val,
+                ctx,
+                |_, ts, output, _| match (ts, &format) {
+                    (Some(ts), Ok(format)) => {
+                        write!(output.builder.data, "{}", FormattedTimestamp { ts, format })
+                            .unwrap();
+                        output.builder.commit_row();
+                        output.validity.push(true);
+                    }
+                    _ => output.push_null(),
+                },
+            )
+        },
+    );
+}
+
+/// The display format of `to_string` from the session settings, the empty format is the
+/// default one. The other formats are checked once instead of for each row.
+fn display_format<'a>(format: &'a str, default: &'a str) -> std::result::Result<&'a str, String> {
+    if format.is_empty() || format == default {
+        return Ok(default);
+    }
+    check_timestamp_format(format)?;
+    Ok(format)
+}
+
+// The date is rendered as the midnight of UTC, so the time and the timezone specifiers are
+// valid too.
+fn format_date(date: i32, format: &str, tz: Tz) -> impl std::fmt::Display + '_ {
+    let ts = date.to_date(tz).and_time(NaiveTime::MIN).and_utc();
+    FormattedTimestamp { ts, format }
+}
+
+/// The timestamp formatted with strftime. `%Y` is zero-padded to 4 digits and only the negative
+/// years have a sign, e.g. `-0044` and `10000`, while chrono renders the years after 9999 with
+/// a `+` sign. The years of `%F`, `%c` and `%+` are rendered the same way.
+struct FormattedTimestamp<'a, T: TimeZone> {
+    ts: DateTime<T>,
+    format: &'a str,
+}
+
+impl<T: TimeZone> std::fmt::Display for FormattedTimestamp<'_, T>
+where T::Offset: std::fmt::Display
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let year = self.ts.year();
+        if (0..=9999).contains(&year) {
+            return write!(f, "{}", self.ts.format(self.format));
+        }
+
+        // The `%Y` and the years of `%F`, `%c` and `%+` are replaced by the literal year
+        let year = if year < 0 {
+            format!("-{:04}", -(year as i64))
+        } else {
+            format!("{year:04}")
+        };
+        let mut format = String::with_capacity(self.format.len() + year.len());
+        let mut rest = self.format;
+        while let Some(pos) = rest.find('%') {
+            format.push_str(&rest[..pos]);
+            let spec = &rest[pos..];
+            let len = spec[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+            match &spec[..len] {
+                "%Y" => format.push_str(&year),
+                "%F" => {
+                    format.push_str(&year);
+                    format.push_str("-%m-%d");
+                }
+                "%c" => {
+                    format.push_str("%a %b %e %H:%M:%S ");
+                    format.push_str(&year);
+                }
+                "%+" => {
+                    format.push_str(&year);
+                    format.push_str("-%m-%dT%H:%M:%S%.f%:z");
+                }
+                spec => format.push_str(spec),
+            }
+            rest = &rest[pos + len..];
+        }
+        format.push_str(rest);
+        write!(f, "{}", self.ts.format(&format))
+    }
+}
+
+/// Renders the timestamps in the timezone of the session, the offset of the timezone is
+/// cached across the rows instead of resolved for each row.
+fn eval_timestamp_to_string<O: ArgType>(
+    val: ValueRef<TimestampType>,
+    ctx: &mut EvalContext,
+    push: impl Fn(i64, Option<DateTime<Tz>>, &mut O::ColumnBuilder, &mut EvalContext),
+) -> Value<O> {
+    let mut cache = TzOffsetCache::new(ctx.func_ctx.tz.tz);
+    let generics = &(ctx.generics.to_owned());
+    match val {
+        ValueRef::Scalar(val) => {
+            let mut builder = O::create_builder(1, generics);
+            push(val, cache.to_timestamp_opt(val), &mut builder, ctx);
+            Value::Scalar(O::build_scalar(builder))
+        }
+        ValueRef::Column(col) => {
+            let mut builder = O::create_builder(col.len(), generics);
+            for val in TimestampType::iter_column(&col) {
+                push(val, cache.to_timestamp_opt(val), &mut builder, ctx);
+            }
+            Value::Column(O::build_column(builder))
+        }
+    }
+}
+
+fn push_formatted_timestamp(
+    micros: i64,
+    ts: Option<DateTime<Tz>>,
+    format: &str,
+    output: &mut NullableColumnBuilder<StringType>,
+    ctx: &mut EvalContext,
+) {
+    if format.is_empty() {
+        output.push_null();
+        return;
+    }
+    if let Err(err) = check_timestamp_format(format) {
+        let msg = datetime_format_error(ctx, output.len(), format, err);
+        ctx.set_error(output.len(), msg);
+        output.push_null();
+        return;
+    }
+    match ts {
+        Some(ts) => {
+            let res = FormattedTimestamp { ts, format }.to_string();
+            output.push(&res);
+        }
+        None => {
+            let msg = datetime_format_error(ctx, output.len(), micros, "timestamp is out of range");
+            ctx.set_error(output.len(), msg);
+            output.push_null();
+        }
+    }
+}
+
+/// Checks the format of `to_string(timestamp, format)`, the specifiers are the ones of strftime:
+///
+/// - date: `%Y %C %y %m %b %B %h %d %e %a %A %w %u %U %W %G %g %V %j %D %x %F %v`
+/// - time: `%H %k %I %l %P %p %M %S %f %.f %.3f %.6f %.9f %3f %6f %9f %R %T %X %r`
+/// - timezone: `%Z %z %:z %::z %:::z`, and `%c %+ %s %t %n %%`
+///
+/// `%e`, `%k` and `%l` are padded with a space, `%p` is `AM`/`PM` and `%P` is `am`/`pm`, they
+/// are not localized. The padding of the numbers can be changed by `%-d`, `%_d` and `%0e`.
+/// The other specifiers, like `%E` and `%O` of the locale or `%Q`, are not supported.
+fn check_timestamp_format(format: &str) -> std::result::Result<(), String> {
+    if !StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Ok(());
+    }
+
+    // Find the first unsupported specifier for the message.
+    let mut rest = format;
+    while let Some(pos) = rest.find('%') {
+        let spec = &rest[pos..];
+        if spec.starts_with("%%") {
+            rest = &spec[2..];
+            continue;
+        }
+        let end = spec[1..]
+            .find(|c: char| c.is_ascii_alphabetic() || c == '%' || c == '+')
+            .map_or(spec.len(), |p| {
+                p + 1 + spec[p + 1..].chars().next().unwrap().len_utf8()
+            });
+        let spec = &spec[..end];
+        if StrftimeItems::new(spec).any(|item| matches!(item, Item::Error)) {
+            return Err(format!(
+                "Unsupported format specifier '{spec}' in '{format}'"
+            ));
+        }
+        rest = &rest[pos + end..];
+    }
+    Err(format!("Invalid format '{format}'"))
+}
+
+fn register_canonical_to_string(registry: &mut FunctionRegistry) {
+    registry.register_passthrough_nullable_1_arg::<DateType, StringType, _, _>(
+        "to_iso8601",
+        |_, _| FunctionDomain::Full,
+        vectorize_with_builder_1_arg::<DateType, StringType>(|val, output, ctx| {
+            write!(output.data, "{}", date_to_string(val, ctx.func_ctx.tz.tz)).unwrap();
+            output.commit_row();
+        }),
+    );
+
+    registry.register_passthrough_nullable_1_arg::<TimestampType, StringType, _, _>(
+        "to_iso8601",
+        |_, _| FunctionDomain::MayThrow,
+        |val, ctx| {
+            eval_timestamp_to_string::<StringType>(val, ctx, |micros, ts, output, ctx| {
+                match ts {
+                    Some(ts) => {
+                        write!(output.data, "{}", ts.format("%Y-%m-%dT%H:%M:%S%.6f%:z")).unwrap()
+                    }
+                    None => {
+                        let err = "timestamp is out of range";
+                        let msg = datetime_format_error(ctx, output.len(), micros, err);
+                        ctx.set_error(output.len(), msg)
+                    }
+                }
+                output.commit_row();
+            })
+        },
+    );
+
+    registry.register_passthrough_nullable_1_arg::<TimestampType, StringType, _, _>(
+        "to_rfc3339",
+        |_, _| FunctionDomain::MayThrow,
+        |val, ctx| {
+            eval_timestamp_to_string::<StringType>(val, ctx, |micros, ts, output, ctx| {
+                match ts {
+                    Some(ts) => write!(output.data, "{}", ts.to_rfc3339()).unwrap(),
+                    None => {
+                        let err = "timestamp is out of range";
+                        let msg = datetime_format_error(ctx, output.len(), micros, err);
+                        ctx.set_error(output.len(), msg)
+                    }
+                }
+                output.commit_row();
+            })
+        },
     );
 }
 
@@ -1050,6 +2055,154 @@ macro_rules! impl_register_arith_functions {
 impl_register_arith_functions!(register_add_functions, "add", unsigned_ident);
 impl_register_arith_functions!(register_sub_functions, "subtract", signed_ident);
 
+fn register_interval_string_functions(registry: &mut FunctionRegistry) {
+    for (name, is_sub) in [("date_add", false), ("date_sub", true)] {
+        let parse = move |interval: &str| {
+            DateInterval::parse(interval).map(|i| if is_sub { i.negate() } else { i })
+        };
+
+        registry.register_passthrough_nullable_2_arg::<DateType, StringType, DateType, _, _>(
+            name,
+            |_, _, _| FunctionDomain::MayThrow,
+            vectorize_with_builder_2_arg::<DateType, StringType, DateType>(
+                move |date, interval, builder, ctx| match parse(interval)
+                    .and_then(|i| i.eval_date(date, ctx.func_ctx.tz))
+                {
+                    Ok(d) => builder.push(d),
+                    Err(e) => {
+                        ctx.set_error(builder.len(), e);
+                        builder.push(0);
+                    }
+                },
+            ),
+        );
+        registry
+            .register_passthrough_nullable_2_arg::<TimestampType, StringType, TimestampType, _, _>(
+                name,
+                |_, _, _| FunctionDomain::MayThrow,
+                vectorize_with_builder_2_arg::<TimestampType, StringType, TimestampType>(
+                    move |ts, interval, builder, ctx| match parse(interval)
+                        .and_then(|i| i.eval_timestamp(ts, ctx.func_ctx.tz))
+                    {
+                        Ok(t) => builder.push(t),
+                        Err(e) => {
+                            ctx.set_error(builder.len(), e);
+                            builder.push(0);
+                        }
+                    },
+                ),
+            );
+    }
+}
+
+fn register_quarter_to_date(registry: &mut FunctionRegistry) {
+    registry.register_passthrough_nullable_1_arg::<StringType, DateType, _, _>(
+        "quarter_to_date",
+        |_, _| FunctionDomain::MayThrow,
+        eval_quarter_to_date,
+    );
+    registry.register_combine_nullable_1_arg::<StringType, DateType, _, _>(
+        "try_quarter_to_date",
+        |_, _| FunctionDomain::Full,
+        error_to_null(eval_quarter_to_date),
+    );
+
+    fn eval_quarter_to_date(val: ValueRef<StringType>, ctx: &mut EvalContext) -> Value<DateType> {
+        vectorize_with_builder_1_arg::<StringType, DateType>(|val, output, ctx| {
+            match quarter_to_date(val) {
+                Ok(d) => output.push(d),
+                Err(e) => {
+                    ctx.set_error(output.len(), e);
+                    output.push(0);
+                }
+            }
+        })(val, ctx)
+    }
+}
+
+/// The Julian date of the Unix epoch, 1970-01-01 00:00:00 UTC. The Julian days start at noon,
+/// so the Julian day number of a date is the Julian date of its noon.
+const JULIAN_DATE_OF_EPOCH: f64 = 2440587.5;
+
+const MICROS_IN_A_DAY: f64 = (24 * 3600 * MICROS_IN_A_SEC) as f64;
+
+fn register_julian_functions(registry: &mut FunctionRegistry) {
+    registry.register_passthrough_nullable_1_arg::<Float64Type, DateType, _, _>(
+        "julian_to_date",
+        |_, _| FunctionDomain::MayThrow,
+        eval_julian_to_date,
+    );
+    registry.register_combine_nullable_1_arg::<Float64Type, DateType, _, _>(
+        "try_julian_to_date",
+        |_, _| FunctionDomain::Full,
+        error_to_null(eval_julian_to_date),
+    );
+    registry.register_passthrough_nullable_1_arg::<Float64Type, TimestampType, _, _>(
+        "julian_to_timestamp",
+        |_, _| FunctionDomain::MayThrow,
+        eval_julian_to_timestamp,
+    );
+    registry.register_combine_nullable_1_arg::<Float64Type, TimestampType, _, _>(
+        "try_julian_to_timestamp",
+        |_, _| FunctionDomain::Full,
+        error_to_null(eval_julian_to_timestamp),
+    );
+    registry.register_passthrough_nullable_1_arg::<DateType, Float64Type, _, _>(
+        "date_to_julian",
+        |_, _| FunctionDomain::Full,
+        vectorize_1_arg::<DateType, Float64Type>(|val, _| {
+            (val as f64 + JULIAN_DATE_OF_EPOCH + 0.5).into()
+        }),
+    );
+    registry.register_passthrough_nullable_1_arg::<TimestampType, Float64Type, _, _>(
+        "timestamp_to_julian",
+        |_, _| FunctionDomain::Full,
+        vectorize_1_arg::<TimestampType, Float64Type>(|val, _| {
+            (val as f64 / MICROS_IN_A_DAY + JULIAN_DATE_OF_EPOCH).into()
+        }),
+    );
+
+    // The date in UTC of the Julian date, the same date is returned for the whole day.
+    fn eval_julian_to_date(val: ValueRef<Float64Type>, ctx: &mut EvalContext) -> Value<DateType> {
+        vectorize_with_builder_1_arg::<Float64Type, DateType>(|val, output, ctx| {
+            let days = (val.0 - JULIAN_DATE_OF_EPOCH).floor();
+            let date = if days.is_finite() {
+                check_date(days as i64)
+            } else {
+                Err("date is out of range".to_string())
+            };
+            match date {
+                Ok(d) => output.push(d),
+                Err(e) => {
+                    ctx.set_error(output.len(), e);
+                    output.push(0);
+                }
+            }
+        })(val, ctx)
+    }
+
+    fn eval_julian_to_timestamp(
+        val: ValueRef<Float64Type>,
+        ctx: &mut EvalContext,
+    ) -> Value<TimestampType> {
+        vectorize_with_builder_1_arg::<Float64Type, TimestampType>(|val, output, ctx| {
+            let micros = ((val.0 - JULIAN_DATE_OF_EPOCH) * MICROS_IN_A_DAY).round();
+            let ts = if micros.is_finite() {
+                check_timestamp(micros as i64)
+            } else {
+                Err("timestamp is out of range".to_string())
+            };
+            match ts {
+                Ok(ts) => output.push(ts),
+                Err(e) => {
+                    ctx.set_error(output.len(), e);
+                    output.push(0);
+                }
+            }
+        })(val, ctx)
+    }
+}
+
 fn register_real_time_functions(registry: &mut FunctionRegistry) {
     registry.register_aliases("now", &["current_timestamp"]);
 
@@ -1069,6 +2222,14 @@ fn register_real_time_functions(registry: &mut FunctionRegistry) {
         "tomorrow".to_string(),
         FunctionProperty::default().non_deterministic(),
     );
+    registry.properties.insert(
+        "current_timestamp_tz".to_string(),
+        FunctionProperty::default().non_deterministic(),
+    );
+    registry.properties.insert(
+        "now_utc".to_string(),
+        FunctionProperty::default().non_deterministic(),
+    );
 
     registry.register_0_arg_core::<TimestampType, _, _>(
         "now",
@@ -1076,6 +2237,32 @@ fn register_real_time_functions(registry: &mut FunctionRegistry) {
         |ctx| Value::Scalar(ctx.func_ctx.now.timestamp_micros()),
     );
 
+    // The same instant as `now()`, the timestamps are stored as UTC micros.
+    registry.register_0_arg_core::<TimestampType, _, _>(
+        "now_utc",
+        |_| FunctionDomain::Full,
+        |ctx| Value::Scalar(ctx.func_ctx.now.timestamp_micros()),
+    );
+
+    registry.register_0_arg_core::<StringType, _, _>(
+        "current_timezone",
+        |_| FunctionDomain::Full,
+        |ctx| Value::Scalar(ctx.func_ctx.tz.tz.name().to_string()),
+    );
+
+    registry.register_0_arg_core::<StringType, _, _>(
+        "current_timestamp_tz",
+        |_| FunctionDomain::Full,
+        |ctx| {
+            let now = ctx.func_ctx.now.with_timezone(&ctx.func_ctx.tz.tz);
+            Value::Scalar(format!(
+                "{} {}",
+                now.format(TIMESTAMP_FORMAT),
+                now.format("%:z")
+            ))
+        },
+    );
+
     registry.register_0_arg_core::<DateType, _, _>(
         "today",
         |_| FunctionDomain::Full,
@@ -1387,10 +2574,114 @@ fn register_to_number_functions(registry: &mut FunctionRegistry) {
     );
 }
 
+fn register_week_of_month(registry: &mut FunctionRegistry) {
+    registry.register_passthrough_nullable_1_arg::<DateType, UInt8Type, _, _>(
+        "to_week_of_month",
+        |_, _| FunctionDomain::Full,
+        vectorize_with_builder_1_arg::<DateType, UInt8Type>(|val, output, ctx| {
+            match ToNumberImpl::eval_date::<ToWeekOfMonth, _>(
+                val,
+                ctx.func_ctx.tz,
+                ctx.func_ctx.enable_dst_hour_fix,
+            ) {
+                Ok(t) => output.push(t),
+                Err(e) => {
+                    ctx.set_error(output.len(), format!("cannot parse to type `Date`. {}", e));
+                    output.push(0);
+                }
+            }
+        }),
+    );
+    registry.register_passthrough_nullable_1_arg::<TimestampType, UInt8Type, _, _>(
+        "to_week_of_month",
+        |_, _| FunctionDomain::Full,
+        vectorize_1_arg::<TimestampType, UInt8Type>(|val, ctx| {
+            ToNumberImpl::eval_timestamp::<ToWeekOfMonth, _>(val, ctx.func_ctx.tz)
+        }),
+    );
+
+    // The mode 0 is the same as `to_week_of_month(x)`, and the weeks start on Monday in the mode 1.
+    registry.register_passthrough_nullable_2_arg::<DateType, Int64Type, UInt8Type, _, _>(
+        "to_week_of_month",
+        |_, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_2_arg::<DateType, Int64Type, UInt8Type>(|val, mode, output, ctx| {
+            let tz = ctx.func_ctx.tz;
+            let enable_dst_hour_fix = ctx.func_ctx.enable_dst_hour_fix;
+            let week = match mode {
+                0 => ToNumberImpl::eval_date::<ToWeekOfMonth, _>(val, tz, enable_dst_hour_fix),
+                1 => ToNumberImpl::eval_date::<ToCalendarWeekOfMonth, _>(
+                    val,
+                    tz,
+                    enable_dst_hour_fix,
+                ),
+                _ => {
+                    ctx.set_error(output.len(), week_of_month_mode_error(mode));
+                    output.push(0);
+                    return;
+                }
+            };
+            match week {
+                Ok(t) => output.push(t),
+                Err(e) => {
+                    ctx.set_error(output.len(), format!("cannot parse to type `Date`. {}", e));
+                    output.push(0);
+                }
+            }
+        }),
+    );
+    registry.register_passthrough_nullable_2_arg::<TimestampType, Int64Type, UInt8Type, _, _>(
+        "to_week_of_month",
+        |_, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_2_arg::<TimestampType, Int64Type, UInt8Type>(
+            |val, mode, output, ctx| match mode {
+                0 => output.push(ToNumberImpl::eval_timestamp::<ToWeekOfMonth, _>(
+                    val,
+                    ctx.func_ctx.tz,
+                )),
+                1 => output.push(ToNumberImpl::eval_timestamp::<ToCalendarWeekOfMonth, _>(
+                    val,
+                    ctx.func_ctx.tz,
+                )),
+                _ => {
+                    ctx.set_error(output.len(), week_of_month_mode_error(mode));
+                    output.push(0);
+                }
+            },
+        ),
+    );
+}
+
+fn week_of_month_mode_error(mode: i64) -> String {
+    format!("Invalid mode {mode} of to_week_of_month, it must be 0 or 1")
+}
+
+/// Check the result of date arithmetic, it's saturated into the valid range
+/// instead of raising an error if `clamp` is set.
+#[inline]
+fn check_date_arithmetic(days: i64, clamp: bool) -> Result<i32, String> {
+    if clamp {
+        Ok(days.clamp(DATE_MIN as i64, DATE_MAX as i64) as i32)
+    } else {
+        check_date(days)
+    }
+}
+
+/// Check the result of timestamp arithmetic, it's saturated into the valid range
+/// instead of raising an error if `clamp` is set.
+#[inline]
+fn check_timestamp_arithmetic(micros: i64, clamp: bool) -> Result<i64, String> {
+    if clamp {
+        Ok(micros.clamp(TIMESTAMP_MIN, TIMESTAMP_MAX))
+    } else {
+        check_timestamp(micros)
+    }
+}
+
 fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
     registry.register_passthrough_nullable_2_arg::<DateType, Int64Type, DateType, _, _>(
         "plus",
-        |_, lhs, rhs| {
+        |ctx, lhs, rhs| {
+            let clamp = ctx.clamp_date_arithmetic;
             (|| {
                 let lm: i64 = num_traits::cast::cast(lhs.max)?;
                 let ln: i64 = num_traits::cast::cast(lhs.min)?;
@@ -1398,17 +2689,18 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
                 let rn = rhs.min;
 
                 Some(FunctionDomain::Domain(SimpleDomain::<i32> {
-                    min: check_date(ln + rn).ok()?,
-                    max: check_date(lm + rm).ok()?,
+                    min: check_date_arithmetic(ln.saturating_add(rn), clamp).ok()?,
+                    max: check_date_arithmetic(lm.saturating_add(rm), clamp).ok()?,
                 }))
             })()
             .unwrap_or(FunctionDomain::MayThrow)
         },
         vectorize_with_builder_2_arg::<DateType, Int64Type, DateType>(|a, b, output, ctx| {
-            match check_date((a as i64) + b) {
+            let days = (a as i64).saturating_add(b);
+            match check_date_arithmetic(days, ctx.func_ctx.clamp_date_arithmetic) {
                 Ok(v) => output.push(v),
                 Err(err) => {
-                    ctx.set_error(output.len(), err);
+                    ctx.set_error_with_code(output.len(), ErrorCode::OVERFLOW, err);
                     output.push(0);
                 }
             }
@@ -1436,21 +2728,25 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
 
     registry.register_passthrough_nullable_2_arg::<TimestampType, Int64Type, TimestampType, _, _>(
         "plus",
-        |_, lhs, rhs| {
+        |ctx, lhs, rhs| {
+            let clamp = ctx.clamp_timestamp_arithmetic;
             (|| {
                 let lm = lhs.max;
                 let ln = lhs.min;
                 let rm = rhs.max;
                 let rn = rhs.min;
                 Some(FunctionDomain::Domain(SimpleDomain::<i64> {
-                    min: check_timestamp(ln + rn).ok()?,
-                    max: check_timestamp(lm + rm).ok()?,
+                    min: check_timestamp_arithmetic(ln.saturating_add(rn), clamp).ok()?,
+                    max: check_timestamp_arithmetic(lm.saturating_add(rm), clamp).ok()?,
                 }))
             })()
             .unwrap_or(FunctionDomain::MayThrow)
         },
         vectorize_with_builder_2_arg::<TimestampType, Int64Type, TimestampType>(
-            |a, b, output, ctx| match check_timestamp(a + b) {
+            |a, b, output, ctx| match check_timestamp_arithmetic(
+                a.saturating_add(b),
+                ctx.func_ctx.clamp_timestamp_arithmetic,
+            ) {
                 Ok(v) => output.push(v),
                 Err(err) => {
                     ctx.set_error(output.len(), err);
@@ -1480,7 +2776,8 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
 
     registry.register_passthrough_nullable_2_arg::<DateType, Int64Type, DateType, _, _>(
         "minus",
-        |_, lhs, rhs| {
+        |ctx, lhs, rhs| {
+            let clamp = ctx.clamp_date_arithmetic;
             (|| {
                 let lm: i64 = num_traits::cast::cast(lhs.max)?;
                 let ln: i64 = num_traits::cast::cast(lhs.min)?;
@@ -1488,17 +2785,18 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
                 let rn = rhs.min;
 
                 Some(FunctionDomain::Domain(SimpleDomain::<i32> {
-                    min: check_date(ln - rn).ok()?,
-                    max: check_date(lm - rm).ok()?,
+                    min: check_date_arithmetic(ln.saturating_sub(rm), clamp).ok()?,
+                    max: check_date_arithmetic(lm.saturating_sub(rn), clamp).ok()?,
                 }))
             })()
             .unwrap_or(FunctionDomain::MayThrow)
         },
         vectorize_with_builder_2_arg::<DateType, Int64Type, DateType>(|a, b, output, ctx| {
-            match check_date((a as i64) - b) {
+            let days = (a as i64).saturating_sub(b);
+            match check_date_arithmetic(days, ctx.func_ctx.clamp_date_arithmetic) {
                 Ok(v) => output.push(v),
                 Err(err) => {
-                    ctx.set_error(output.len(), err);
+                    ctx.set_error_with_code(output.len(), ErrorCode::OVERFLOW, err);
                     output.push(0);
                 }
             }
@@ -1526,7 +2824,8 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
 
     registry.register_passthrough_nullable_2_arg::<TimestampType, Int64Type, TimestampType, _, _>(
         "minus",
-        |_, lhs, rhs| {
+        |ctx, lhs, rhs| {
+            let clamp = ctx.clamp_timestamp_arithmetic;
             (|| {
                 let lm = lhs.max;
                 let ln = lhs.min;
@@ -1534,14 +2833,17 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
                 let rn = rhs.min;
 
                 Some(FunctionDomain::Domain(SimpleDomain::<i64> {
-                    min: check_timestamp(ln - rn).ok()?,
-                    max: check_timestamp(lm - rm).ok()?,
+                    min: check_timestamp_arithmetic(ln.saturating_sub(rm), clamp).ok()?,
+                    max: check_timestamp_arithmetic(lm.saturating_sub(rn), clamp).ok()?,
                 }))
             })()
             .unwrap_or(FunctionDomain::MayThrow)
         },
         vectorize_with_builder_2_arg::<TimestampType, Int64Type, TimestampType>(
-            |a, b, output, ctx| match check_timestamp(a - b) {
+            |a, b, output, ctx| match check_timestamp_arithmetic(
+                a.saturating_sub(b),
+                ctx.func_ctx.clamp_timestamp_arithmetic,
+            ) {
                 Ok(v) => output.push(v),
                 Err(err) => {
                     ctx.set_error(output.len(), err);
@@ -1608,6 +2910,183 @@ fn register_timestamp_add_sub(registry: &mut FunctionRegistry) {
         );
 }
 
+fn register_diff_functions(registry: &mut FunctionRegistry) {
+    registry
+        .register_passthrough_nullable_3_arg::<StringType, DateType, DateType, Int64Type, _, _>(
+            "date_diff",
+            |_, _, _, _| FunctionDomain::MayThrow,
+            vectorize_with_builder_3_arg::<StringType, DateType, DateType, Int64Type>(
+                |unit, start, end, output, ctx| match DateDiffUnit::parse(unit)
+                    .and_then(|unit| DateDiffImpl::eval_date(unit, start, end))
+                {
+                    Ok(diff) => output.push(diff),
+                    Err(err) => {
+                        ctx.set_error(output.len(), err);
+                        output.push(0);
+                    }
+                },
+            ),
+        );
+    registry.register_passthrough_nullable_3_arg::<StringType, TimestampType, TimestampType, Int64Type, _, _>(
+        "date_diff",
+        |_, _, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_3_arg::<StringType, TimestampType, TimestampType, Int64Type>(
+            |unit, start, end, output, ctx| match DateDiffUnit::parse(unit) {
+                Ok(unit) => output.push(DateDiffImpl::eval_timestamp(
+                    unit,
+                    start,
+                    end,
+                    ctx.func_ctx.tz,
+                )),
+                Err(err) => {
+                    ctx.set_error(output.len(), err);
+                    output.push(0);
+                }
+            },
+        ),
+    );
+
+    // diff_seconds_float(start, end), diff_minutes_float(start, end), diff_hours_float(start, end)
+    register_diff_float(registry, "diff_seconds_float", FACTOR_SECOND);
+    register_diff_float(registry, "diff_minutes_float", FACTOR_MINUTE);
+    register_diff_float(registry, "diff_hours_float", FACTOR_HOUR);
+
+    // diff_microseconds(start, end), diff_milliseconds(start, end)
+    register_diff_micros(registry, "diff_microseconds", 1);
+    register_diff_micros(registry, "diff_milliseconds", MICROS_IN_A_MILLI);
+}
+
+/// The timestamps are stored in microseconds, so the difference is exact, the milliseconds are
+/// truncated toward zero.
+fn register_diff_micros(registry: &mut FunctionRegistry, name: &str, factor_micros: i64) {
+    registry.register_passthrough_nullable_2_arg::<TimestampType, TimestampType, Int64Type, _, _>(
+        name,
+        |_, _, _| FunctionDomain::Full,
+        vectorize_2_arg::<TimestampType, TimestampType, Int64Type>(move |start, end, _| {
+            (end - start) / factor_micros
+        }),
+    );
+}
+
+fn register_diff_float(registry: &mut FunctionRegistry, name: &str, factor: i64) {
+    let factor_micros = (factor * MICROS_IN_A_SEC) as f64;
+    registry
+        .register_passthrough_nullable_2_arg::<TimestampType, TimestampType, Float64Type, _, _>(
+            name,
+            |_, _, _| FunctionDomain::Full,
+            vectorize_2_arg::<TimestampType, TimestampType, Float64Type>(move |start, end, _| {
+                ((end - start) as f64 / factor_micros).into()
+            }),
+        );
+}
+
+fn register_datetime_modifiers(registry: &mut FunctionRegistry) {
+    registry.register_function_factory("datetime", |_, args_type| {
+        if args_type.is_empty() {
+            return None;
+        }
+        let has_null = args_type.iter().any(|t| t.is_nullable_or_null());
+
+        let mut sig_args_type = vec![DataType::String; args_type.len()];
+        sig_args_type[0] = DataType::Timestamp;
+        let f = Function {
+            signature: FunctionSignature {
+                name: "datetime".to_string(),
+                args_type: sig_args_type,
+                return_type: DataType::Timestamp,
+            },
+            eval: FunctionEval::Scalar {
+                calc_domain: Box::new(|_, _| FunctionDomain::MayThrow),
+                eval: Box::new(eval_datetime_modifiers),
+            },
+        };
+        if has_null {
+            Some(Arc::new(f.passthrough_nullable()))
+        } else {
+            Some(Arc::new(f))
+        }
+    });
+
+    fn eval_datetime_modifiers(
+        args: &[ValueRef<AnyType>],
+        ctx: &mut EvalContext,
+    ) -> Value<AnyType> {
+        let len = args.iter().find_map(|arg| match arg {
+            ValueRef::Column(col) => Some(col.len()),
+            _ => None,
+        });
+        let base = args[0].try_downcast::<TimestampType>().unwrap();
+        let modifiers = args[1..]
+            .iter()
+            .map(|arg| arg.try_downcast::<StringType>().unwrap())
+            .collect::<Vec<_>>();
+
+        let mut builder = Vec::with_capacity(len.unwrap_or(1));
+        for row in 0..len.unwrap_or(1) {
+            let mut us = unsafe { base.index_unchecked(row) };
+            for modifier in modifiers.iter() {
+                let modifier = unsafe { modifier.index_unchecked(row) };
+                match apply_datetime_modifier(us, modifier, ctx.func_ctx.tz) {
+                    Ok(res) => us = res,
+                    Err(err) => {
+                        ctx.set_error(row, err);
+                        us = 0;
+                        break;
+                    }
+                }
+            }
+            builder.push(us);
+        }
+
+        match len {
+            Some(_) => Value::Column(TimestampType::upcast_column(builder.into())),
+            None => Value::Scalar(Scalar::Timestamp(builder[0])),
+        }
+    }
+
+    /// Applies a SQLite date modifier: `[+|-]N unit`, `start of [day | month | year]`
+    /// or `weekday N`, which advances to the next day whose weekday is N (0 is Sunday).
+    fn apply_datetime_modifier(us: i64, modifier: &str, tz: TzLUT) -> Result<i64, String> {
+        let invalid = || format!("Unrecognized datetime modifier '{modifier}'");
+        let normalized = modifier.trim().to_ascii_lowercase();
+
+        if let Some(unit) = normalized.strip_prefix("start of ") {
+            let date = match unit.trim() {
+                "day" => return Ok(tz.round_us(us, Round::Day)),
+                "month" => DateRounder::eval_timestamp::<ToStartOfMonth>(us, tz),
+                "year" => DateRounder::eval_timestamp::<ToStartOfYear>(us, tz),
+                _ => return Err(invalid()),
+            };
+            return Ok(calc_date_to_timestamp(date, tz.tz));
+        }
+
+        if let Some(weekday) = normalized.strip_prefix("weekday ") {
+            let weekday = weekday
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|weekday| *weekday < 7)
+                .ok_or_else(invalid)?;
+            let current = us.to_timestamp(tz.tz).weekday().num_days_from_sunday();
+            return AddDaysImpl::eval_timestamp(us, (weekday + 7 - current) % 7);
+        }
+
+        let (delta, unit) = normalized
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let delta = delta.parse::<i64>().map_err(|_| invalid())?;
+        match unit.trim().trim_end_matches('s') {
+            "year" => AddYearsImpl::eval_timestamp(us, tz, delta),
+            "month" => AddMonthsImpl::eval_timestamp(us, tz, delta),
+            "day" => AddDaysImpl::eval_timestamp(us, delta),
+            "hour" => AddTimesImpl::eval_timestamp(us, delta, FACTOR_HOUR),
+            "minute" => AddTimesImpl::eval_timestamp(us, delta, FACTOR_MINUTE),
+            "second" => AddTimesImpl::eval_timestamp(us, delta, FACTOR_SECOND),
+            _ => Err(invalid()),
+        }
+    }
+}
+
 fn register_rounder_functions(registry: &mut FunctionRegistry) {
     // timestamp -> timestamp
     registry.register_passthrough_nullable_1_arg::<TimestampType, TimestampType, _, _>(
@@ -1859,6 +3338,57 @@ fn register_rounder_functions(registry: &mut FunctionRegistry) {
             DateRounder::eval_timestamp::<ToStartOfISOYear>(val, ctx.func_ctx.tz)
         }),
     );
+
+    register_first_of_next::<ToStartOfMonth>(registry, "to_first_of_next_month", |date, tz| {
+        AddMonthsImpl::eval_date(date, tz, 1)
+    });
+    register_first_of_next::<ToStartOfQuarter>(registry, "to_first_of_next_quarter", |date, tz| {
+        AddMonthsImpl::eval_date(date, tz, 3)
+    });
+    register_first_of_next::<ToStartOfYear>(registry, "to_first_of_next_year", |date, tz| {
+        AddYearsImpl::eval_date(date, tz, 1)
+    });
+}
+
+/// Registers `name([date | timestamp]) -> date`, which rounds down to the start of the
+/// period with `T` and moves to the start of the next period with `next`.
+fn register_first_of_next<T: ToNumber<i32> + 'static>(
+    registry: &mut FunctionRegistry,
+    name: &str,
+    next: fn(i32, TzLUT) -> Result<i32, String>,
+) {
+    registry.register_passthrough_nullable_1_arg::<DateType, DateType, _, _>(
+        name,
+        |_, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_1_arg::<DateType, DateType>(move |val, output, ctx| {
+            let tz = ctx.func_ctx.tz;
+            let res = DateRounder::eval_date::<T>(val, tz, ctx.func_ctx.enable_dst_hour_fix)
+                .map_err(|e| format!("cannot parse to type `Date`. {}", e))
+                .and_then(|start| next(start, tz));
+            match res {
+                Ok(t) => output.push(t),
+                Err(e) => {
+                    ctx.set_error(output.len(), e);
+                    output.push(0);
+                }
+            }
+        }),
+    );
+    registry.register_passthrough_nullable_1_arg::<TimestampType, DateType, _, _>(
+        name,
+        |_, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_1_arg::<TimestampType, DateType>(move |val, output, ctx| {
+            let tz = ctx.func_ctx.tz;
+            let start = DateRounder::eval_timestamp::<T>(val, tz);
+            match next(start, tz) {
+                Ok(t) => output.push(t),
+                Err(e) => {
+                    ctx.set_error(output.len(), e);
+                    output.push(0);
+                }
+            }
+        }),
+    );
 }
 
 // current we don't consider tz here
@@ -1908,3 +3438,180 @@ fn months_between(date_a: i32, date_b: i32) -> f64 {
     // Total difference including fractional part
     total_months_diff as f64 + day_fraction
 }
+
+/// Localized month names, indexed by the zero-based month.
+const MONTH_NAMES: &[(&str, [&str; 12])] = &[
+    ("en", [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ]),
+    ("fr", [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ]),
+    ("de", [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ]),
+    ("es", [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ]),
+    ("zh", [
+        "一月",
+        "二月",
+        "三月",
+        "四月",
+        "五月",
+        "六月",
+        "七月",
+        "八月",
+        "九月",
+        "十月",
+        "十一月",
+        "十二月",
+    ]),
+];
+
+/// Localized day names, indexed by the number of days from Monday.
+const DAY_NAMES: &[(&str, [&str; 7])] = &[
+    ("en", [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ]),
+    ("fr", [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ]),
+    ("de", [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ]),
+    ("es", [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ]),
+    ("zh", [
+        "星期一",
+        "星期二",
+        "星期三",
+        "星期四",
+        "星期五",
+        "星期六",
+        "星期日",
+    ]),
+];
+
+/// Lookup the name table of the locale, the locale is matched case-insensitively.
+fn locale_names<const N: usize>(
+    names: &'static [(&'static str, [&'static str; N])],
+    locale: &str,
+) -> Option<&'static [&'static str; N]> {
+    names
+        .iter()
+        .find(|(l, _)| l.eq_ignore_ascii_case(locale))
+        .map(|(_, names)| names)
+}
+
+fn register_name_functions(registry: &mut FunctionRegistry) {
+    register_localized_name::<DateType, 12>(registry, "monthname", MONTH_NAMES, |val, tz| {
+        val.to_date(tz).month0() as usize
+    });
+    register_localized_name::<TimestampType, 12>(registry, "monthname", MONTH_NAMES, |val, tz| {
+        val.to_timestamp(tz).month0() as usize
+    });
+    register_localized_name::<DateType, 7>(registry, "dayname", DAY_NAMES, |val, tz| {
+        val.to_date(tz).weekday().num_days_from_monday() as usize
+    });
+    register_localized_name::<TimestampType, 7>(registry, "dayname", DAY_NAMES, |val, tz| {
+        val.to_timestamp(tz).weekday().num_days_from_monday() as usize
+    });
+}
+
+/// Register `name(val)`, which returns the English name, and `name(val, locale)`.
+/// An unsupported locale is an error rather than a silent fallback to English,
+/// so a typo in the locale can't go unnoticed in reports.
+fn register_localized_name<T: ArgType, const N: usize>(
+    registry: &mut FunctionRegistry,
+    name: &str,
+    names: &'static [(&'static str, [&'static str; N])],
+    index: fn(T::ScalarRef<'_>, Tz) -> usize,
+) {
+    registry.register_passthrough_nullable_1_arg::<T, StringType, _, _>(
+        name,
+        |_, _| FunctionDomain::Full,
+        vectorize_with_builder_1_arg::<T, StringType>(move |val, output, ctx| {
+            let names = &names[0].1;
+            output.put_str(names[index(val, ctx.func_ctx.tz.tz)]);
+            output.commit_row();
+        }),
+    );
+
+    registry.register_passthrough_nullable_2_arg::<T, StringType, StringType, _, _>(
+        name,
+        |_, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_2_arg::<T, StringType, StringType>(
+            move |val, locale, output, ctx| {
+                match locale_names(names, locale) {
+                    Some(names) => output.put_str(names[index(val, ctx.func_ctx.tz.tz)]),
+                    None => ctx.set_error(output.len(), format!("unsupported locale: {locale}")),
+                }
+                output.commit_row();
+            },
+        ),
+    );
+}
@@ -66,6 +66,7 @@ pub const ALL_STRING_FUNC_NAMES: &[&str] = &[
     "bin",
     "oct",
     "to_hex",
+    "conv",
     "repeat",
     "ord",
     "soundex",
@@ -574,6 +575,34 @@ pub fn register(registry: &mut FunctionRegistry) {
         }),
     );
 
+    registry.register_passthrough_nullable_3_arg::<StringType, NumberType<i64>, NumberType<i64>, StringType, _, _>(
+        "conv",
+        |_, _, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_3_arg::<StringType, NumberType<i64>, NumberType<i64>, StringType>(
+            |num, from_base, to_base, output, ctx| {
+                match conv(num, from_base, to_base) {
+                    Ok(res) => output.put_str(&res),
+                    Err(err) => ctx.set_error(output.len(), err),
+                }
+                output.commit_row();
+            },
+        ),
+    );
+    registry.register_passthrough_nullable_3_arg::<NumberType<i64>, NumberType<i64>, NumberType<i64>, StringType, _, _>(
+        "conv",
+        |_, _, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_3_arg::<NumberType<i64>, NumberType<i64>, NumberType<i64>, StringType>(
+            |num, from_base, to_base, output, ctx| {
+                // Like MySQL, the digits of the integer are read in `from_base`.
+                match conv(&num.to_string(), from_base, to_base) {
+                    Ok(res) => output.put_str(&res),
+                    Err(err) => ctx.set_error(output.len(), err),
+                }
+                output.commit_row();
+            },
+        ),
+    );
+
     const MAX_REPEAT_TIMES: u64 = 1000000;
     registry.register_passthrough_nullable_2_arg::<StringType, NumberType<u64>, StringType, _, _>(
         "repeat",
@@ -897,6 +926,41 @@ pub(crate) mod soundex {
     }
 }
 
+/// Converts the number from `from_base` to `to_base`, the digits above 9 are `A` to `Z`.
+fn conv(num: &str, from_base: i64, to_base: i64) -> Result<String, String> {
+    for base in [from_base, to_base] {
+        if !(2..=36).contains(&base) {
+            return Err(format!("Invalid base {base}, it must be between 2 and 36"));
+        }
+    }
+
+    let (negative, digits) = match num.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, num),
+    };
+    let value = u64::from_str_radix(digits, from_base as u32)
+        .map_err(|err| format!("Invalid number '{num}' in base {from_base}: {err}"))?;
+
+    let mut res = Vec::new();
+    let mut rest = value;
+    loop {
+        let digit = (rest % to_base as u64) as u32;
+        res.push(
+            std::char::from_digit(digit, to_base as u32)
+                .unwrap()
+                .to_ascii_uppercase(),
+        );
+        rest /= to_base as u64;
+        if rest == 0 {
+            break;
+        }
+    }
+    if negative && value != 0 {
+        res.push('-');
+    }
+    Ok(res.iter().rev().collect())
+}
+
 #[inline]
 fn substr(builder: &mut StringColumnBuilder, str: &str, pos: i64, len: u64) {
     if pos == 0 || len == 0 {
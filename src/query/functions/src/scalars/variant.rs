@@ -1148,6 +1148,58 @@ pub fn register(registry: &mut FunctionRegistry) {
         ),
     );
 
+    registry.register_combine_nullable_2_arg::<VariantType, StringType, TimestampType, _, _>(
+        "json_to_timestamp",
+        |_, _, _| FunctionDomain::MayThrow,
+        vectorize_with_builder_2_arg::<VariantType, StringType, NullableType<TimestampType>>(
+            |val, path, output, ctx| {
+                if let Some(validity) = &ctx.validity {
+                    if !validity.get_bit(output.len()) {
+                        output.push_null();
+                        return;
+                    }
+                }
+                match parse_json_path(path.as_bytes()) {
+                    Ok(json_path) => {
+                        let mut out_buf = Vec::new();
+                        let mut out_offsets = Vec::new();
+                        match get_by_path(val, json_path, &mut out_buf, &mut out_offsets) {
+                            Ok(()) => {
+                                let ts = if out_offsets.is_empty() {
+                                    None
+                                } else {
+                                    as_str(&out_buf).and_then(|s| {
+                                        string_to_timestamp(
+                                            s.as_bytes(),
+                                            ctx.func_ctx.tz.tz,
+                                            ctx.func_ctx.enable_dst_hour_fix,
+                                        )
+                                        .ok()
+                                    })
+                                };
+                                match ts {
+                                    Some(ts) => output.push(ts.timestamp_micros()),
+                                    None => output.push_null(),
+                                }
+                            }
+                            Err(_) => {
+                                ctx.set_error(
+                                    output.len(),
+                                    format!("Invalid JSONB value '0x{}'", hex::encode(val)),
+                                );
+                                output.push_null();
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        ctx.set_error(output.len(), format!("Invalid JSON Path '{path}'"));
+                        output.push_null();
+                    }
+                }
+            },
+        ),
+    );
+
     for dest_type in ALL_NUMERICS_TYPES {
         with_number_mapped_type!(|NUM_TYPE| match dest_type {
             NumberDataType::NUM_TYPE => {
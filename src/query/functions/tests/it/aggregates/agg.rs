@@ -14,17 +14,36 @@
 
 use std::io::Write;
 
+use bumpalo::Bump;
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::buffer::Buffer;
+use databend_common_base::runtime::catch_unwind;
+use databend_common_exception::ErrorCode;
+use databend_common_expression::types::decimal::Decimal;
 use databend_common_expression::types::decimal::Decimal128Type;
+use databend_common_expression::types::number::Float64Type;
+use databend_common_expression::types::number::Int32Type;
 use databend_common_expression::types::number::Int64Type;
 use databend_common_expression::types::number::UInt64Type;
+use databend_common_expression::types::string::StringColumnBuilder;
+use databend_common_expression::types::ArrayColumn;
 use databend_common_expression::types::BitmapType;
 use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::DecimalDataType;
 use databend_common_expression::types::DecimalSize;
+use databend_common_expression::types::NumberDataType;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::TimestampType;
 use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
 use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
 use databend_common_functions::aggregates::eval_aggr;
+use databend_common_functions::aggregates::AggregateFunctionFactory;
+use databend_common_functions::aggregates::DecimalSumState;
+use databend_common_functions::aggregates::StateAddr;
+use databend_common_functions::aggregates::UnaryState;
 use goldenfile::Mint;
 use itertools::Itertools;
 use roaring::RoaringTreemap;
@@ -113,6 +132,453 @@ fn test_agg_group_by() {
     test_agg_json_object_agg(file, eval_aggr);
 }
 
+#[test]
+fn test_agg_string_agg_non_utf8() {
+    let factory = AggregateFunctionFactory::instance();
+    let func = factory
+        .get("string_agg", vec![Scalar::String("|".to_string())], vec![
+            DataType::String,
+        ])
+        .unwrap();
+
+    let arena = Bump::new();
+    let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(addr);
+
+    // Partial states may carry arbitrary bytes, merging them must not assume UTF-8.
+    let values: Vec<&[u8]> = vec![b"a\xff", b"\xc3\x28|", b"b"];
+    for value in values {
+        let mut state = vec![];
+        state.extend_from_slice(&((value.len() + 1) as u32).to_le_bytes());
+        state.extend_from_slice(value);
+        state.push(b'|');
+        func.merge(addr, &mut state.as_slice()).unwrap();
+    }
+    // The rows accumulated later are appended after the bytes of the merged states.
+    let column = StringType::from_data(vec!["c"]);
+    func.accumulate(addr, (&[column]).into(), None, 1).unwrap();
+
+    let mut builder = ColumnBuilder::with_capacity(&DataType::String, 1);
+    func.merge_result(addr, &mut builder).unwrap();
+    let builder = builder.as_string_mut().unwrap();
+    assert_eq!(builder.offsets, vec![0, 10]);
+    assert_eq!(builder.data, b"a\xff|\xc3\x28||b|c".to_vec());
+
+    unsafe { func.drop_state(addr) };
+}
+
+#[test]
+fn test_agg_string_agg_accumulate_non_utf8() {
+    let factory = AggregateFunctionFactory::instance();
+    let func = factory
+        .get("string_agg", vec![Scalar::String("|".to_string())], vec![
+            DataType::String,
+        ])
+        .unwrap();
+
+    let build_column = || {
+        StringColumnBuilder {
+            need_estimated: false,
+            data: b"a\xff\xc3\x28b".to_vec(),
+            offsets: vec![0, 2, 4, 5],
+        }
+        .build()
+    };
+    // The string columns are checked to be UTF-8 when they're built in the debug builds, so the
+    // non-UTF8 rows can only reach the aggregation in the release builds.
+    if cfg!(debug_assertions) {
+        assert!(catch_unwind(build_column).is_err());
+        return;
+    }
+    let column = Column::String(build_column());
+
+    let arena = Bump::new();
+    let new_state = || {
+        let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+        addr
+    };
+
+    // The rows are accumulated as a whole block, row by row and with the validity.
+    let partial = new_state();
+    func.accumulate(partial, (&[column.clone()]).into(), None, 3)
+        .unwrap();
+    for row in 0..3 {
+        func.accumulate_row(partial, (&[column.clone()]).into(), row)
+            .unwrap();
+    }
+    let validity = Bitmap::from([false, true, false]);
+    func.accumulate(partial, (&[column]).into(), Some(&validity), 3)
+        .unwrap();
+
+    // The bytes are kept through the serialized state.
+    let mut buffer = vec![];
+    func.serialize(partial, &mut buffer).unwrap();
+    let addr = new_state();
+    func.merge(addr, &mut buffer.as_slice()).unwrap();
+
+    let mut builder = ColumnBuilder::with_capacity(&DataType::String, 1);
+    func.merge_result(addr, &mut builder).unwrap();
+    let builder = builder.as_string_mut().unwrap();
+    assert_eq!(
+        builder.data,
+        b"a\xff|\xc3\x28|b|a\xff|\xc3\x28|b|\xc3\x28".to_vec()
+    );
+
+    unsafe { func.drop_state(partial) };
+    unsafe { func.drop_state(addr) };
+}
+
+#[test]
+fn test_agg_decimal_sum_batch() {
+    let column: Buffer<i128> = vec![110i128, 999, -20, 330].into();
+    let validity = Bitmap::from([true, false, true, true]);
+
+    let mut state = DecimalSumState::<true, Decimal128Type>::default();
+    UnaryState::<Decimal128Type, Decimal128Type>::add_batch(
+        &mut state,
+        &column,
+        Some(&validity),
+        None,
+    )
+    .unwrap();
+    assert_eq!(state.value, 420);
+
+    UnaryState::<Decimal128Type, Decimal128Type>::add_batch(&mut state, &column, None, None)
+        .unwrap();
+    assert_eq!(state.value, 1839);
+
+    // The overflow is still detected when it is checked once per batch.
+    let mut state = DecimalSumState::<true, Decimal128Type> {
+        value: <i128 as Decimal>::MAX - 1000,
+    };
+    let err =
+        UnaryState::<Decimal128Type, Decimal128Type>::add_batch(&mut state, &column, None, None)
+            .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::OVERFLOW);
+}
+
+#[test]
+fn test_agg_decimal_sum_precision() {
+    let factory = AggregateFunctionFactory::instance();
+    let decimal = |precision, scale| {
+        DataType::Decimal(DecimalDataType::from_size(DecimalSize { precision, scale }).unwrap())
+    };
+
+    // The precision grows by 10 digits up to the max precision of the decimal type.
+    for (argument, result) in [
+        (decimal(10, 2), decimal(20, 2)),
+        (decimal(28, 0), decimal(38, 0)),
+        (decimal(30, 5), decimal(38, 5)),
+        (decimal(40, 3), decimal(50, 3)),
+        (decimal(70, 10), decimal(76, 10)),
+    ] {
+        let func = factory.get("sum", vec![], vec![argument.clone()]).unwrap();
+        assert_eq!(func.return_type().unwrap(), result, "sum({argument})");
+    }
+
+    // The sum is checked against the precision of the result.
+    let func = factory.get("sum", vec![], vec![decimal(10, 0)]).unwrap();
+    let arena = Bump::new();
+    let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(addr);
+
+    let partial = 10i128.pow(20) - 5;
+    func.merge(addr, &mut partial.to_le_bytes().as_slice())
+        .unwrap();
+    let mut builder = ColumnBuilder::with_capacity(&decimal(20, 0), 1);
+    func.merge_result(addr, &mut builder).unwrap();
+
+    let column = Decimal128Type::from_data_with_size(vec![3i128, 7], DecimalSize {
+        precision: 10,
+        scale: 0,
+    });
+    let err = func
+        .accumulate(addr, (&[column]).into(), None, 2)
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::OVERFLOW);
+
+    unsafe { func.drop_state(addr) };
+}
+
+#[test]
+fn test_agg_median_merge_sorted() {
+    let factory = AggregateFunctionFactory::instance();
+    let func = factory
+        .get("median", vec![], vec![DataType::Number(
+            NumberDataType::Int32,
+        )])
+        .unwrap();
+    let arena = Bump::new();
+    let new_state = || {
+        let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+        addr
+    };
+
+    // The partial states are serialized as sorted runs.
+    let mut partials = vec![];
+    for values in [vec![9, 1, 5], vec![4, 8], vec![7, 2, 6, 3]] {
+        let addr = new_state();
+        let num_rows = values.len();
+        let column = Int32Type::from_data(values);
+        func.accumulate(addr, (&[column]).into(), None, num_rows)
+            .unwrap();
+        let mut buffer = vec![];
+        func.serialize(addr, &mut buffer).unwrap();
+        partials.push(buffer);
+        unsafe { func.drop_state(addr) };
+    }
+    // The values of a state serialized by an older version are not sorted.
+    partials.push(borsh::to_vec(&vec![10.0f64, 0.0]).unwrap());
+
+    let addr = new_state();
+    for partial in partials.iter() {
+        func.merge(addr, &mut partial.as_slice()).unwrap();
+    }
+
+    // The merged values are sorted, the state is the length and the values only.
+    let mut buffer = vec![];
+    func.serialize(addr, &mut buffer).unwrap();
+    let len = u32::from_le_bytes(buffer[..4].try_into().unwrap()) as usize;
+    assert_eq!(buffer.len(), 4 + len * 8);
+    let values = buffer[4..]
+        .chunks(8)
+        .map(|v| f64::from_le_bytes(v.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(values, (0..=10).map(|v| v as f64).collect::<Vec<_>>());
+
+    let mut builder = ColumnBuilder::with_capacity(&func.return_type().unwrap(), 1);
+    func.merge_result(addr, &mut builder).unwrap();
+    assert_eq!(builder.build(), Float64Type::from_data(vec![5.0]));
+    unsafe { func.drop_state(addr) };
+}
+
+#[test]
+fn test_agg_group_array_moving_sum_state() {
+    let factory = AggregateFunctionFactory::instance();
+    let arena = Bump::new();
+
+    for (data_type, column, expected) in [
+        (
+            DataType::Number(NumberDataType::Int32),
+            Int32Type::from_data(vec![1, 2, 3]),
+            Int64Type::from_data(vec![1, 3, 5]),
+        ),
+        (
+            DataType::Number(NumberDataType::Int32).wrap_nullable(),
+            Int32Type::from_data_with_validity(vec![1, 2, 3], vec![true, false, true]),
+            Int64Type::from_data_with_validity(vec![1, 1, 3], vec![true, true, true]),
+        ),
+    ] {
+        let func = factory
+            .get(
+                "group_array_moving_sum",
+                vec![Scalar::Number(2u64.into())],
+                vec![data_type.clone()],
+            )
+            .unwrap();
+        let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+        func.accumulate(addr, (&[column]).into(), None, 3).unwrap();
+        let mut buffer = vec![];
+        func.serialize(addr, &mut buffer).unwrap();
+        unsafe { func.drop_state(addr) };
+
+        // The state of a non-nullable argument keeps the layout of the values only, the
+        // validity is appended for the nullable arguments.
+        let mut expected_buffer = vec![];
+        borsh::to_writer(&mut expected_buffer, &vec![1i32, 2, 3]).unwrap();
+        if data_type.is_nullable() {
+            borsh::to_writer(&mut expected_buffer, &vec![true, false, true]).unwrap();
+        }
+        assert_eq!(buffer, expected_buffer);
+
+        let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+        func.merge(addr, &mut buffer.as_slice()).unwrap();
+        let mut builder = ColumnBuilder::with_capacity(&func.return_type().unwrap(), 1);
+        func.merge_result(addr, &mut builder).unwrap();
+        assert_eq!(
+            builder.build(),
+            Column::Array(Box::new(ArrayColumn {
+                values: expected,
+                offsets: Buffer::from(vec![0u64, 3]),
+            }))
+        );
+        unsafe { func.drop_state(addr) };
+    }
+}
+
+#[test]
+fn test_agg_regression_merge() {
+    let factory = AggregateFunctionFactory::instance();
+    let arena = Bump::new();
+    let y = Float64Type::from_data(vec![3.0, 5.0, 7.0, 9.0, 12.0]);
+    let x = Float64Type::from_data(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    // The rows are split into two partial states, the merged result equals the known fit.
+    for (name, expected) in [
+        ("regr_slope", 2.2),
+        ("regr_intercept", 0.6),
+        ("regr_r2", 484.0 / 487.6),
+    ] {
+        let func = factory
+            .get(name, vec![], vec![y.data_type(), x.data_type()])
+            .unwrap();
+        let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+        let mut buffer = vec![];
+        for (offset, len) in [(0, 2), (2, 3)] {
+            let partial: StateAddr = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(partial);
+            let columns = [y.slice(offset..offset + len), x.slice(offset..offset + len)];
+            func.accumulate(partial, (&columns).into(), None, len)
+                .unwrap();
+            buffer.clear();
+            func.serialize(partial, &mut buffer).unwrap();
+            func.merge(addr, &mut buffer.as_slice()).unwrap();
+        }
+
+        let mut builder = ColumnBuilder::with_capacity(&func.return_type().unwrap(), 1);
+        func.merge_result(addr, &mut builder).unwrap();
+        let result = builder.build().index(0).unwrap().to_owned();
+        let result = result.as_number().unwrap().as_float64().unwrap().0;
+        assert!((result - expected).abs() < 1e-9, "{name}: {result}");
+    }
+}
+
+#[test]
+fn test_agg_array_sum() {
+    let factory = AggregateFunctionFactory::instance();
+    let arena = Bump::new();
+    let arrays = |values: Vec<f64>, offsets: Vec<u64>| {
+        Column::Array(Box::new(ArrayColumn {
+            values: Float64Type::from_data(values),
+            offsets: offsets.into(),
+        }))
+    };
+    // The rows [1.0, 2.0, 3.0], [0.5, 0.5, 0.5], [-1.0, 4.0, 0.25] and [2.0, 0.0, 1.0].
+    let column = arrays(
+        vec![1.0, 2.0, 3.0, 0.5, 0.5, 0.5, -1.0, 4.0, 0.25, 2.0, 0.0, 1.0],
+        vec![0, 3, 6, 9, 12],
+    );
+    let func = factory
+        .get("sum", vec![], vec![column.data_type()])
+        .unwrap();
+    let return_type = func.return_type().unwrap();
+    assert_eq!(
+        return_type,
+        DataType::Array(Box::new(DataType::Number(NumberDataType::Float64))).wrap_nullable()
+    );
+
+    // The rows are split into two partial states, the sums are merged position-wise.
+    let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(addr);
+    let mut buffer = vec![];
+    for (offset, len) in [(0, 1), (1, 3)] {
+        let partial: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(partial);
+        let columns = [column.slice(offset..offset + len)];
+        func.accumulate(partial, (&columns).into(), None, len)
+            .unwrap();
+        buffer.clear();
+        func.serialize(partial, &mut buffer).unwrap();
+        func.merge(addr, &mut buffer.as_slice()).unwrap();
+    }
+    let mut builder = ColumnBuilder::with_capacity(&return_type, 2);
+    func.merge_result(addr, &mut builder).unwrap();
+
+    // The empty group is NULL.
+    let empty: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(empty);
+    func.merge_result(empty, &mut builder).unwrap();
+
+    let result = builder.build();
+    assert_eq!(
+        result.index(0).unwrap().to_owned(),
+        Scalar::Array(Float64Type::from_data(vec![2.5, 6.5, 4.75]))
+    );
+    assert_eq!(result.index(1).unwrap().to_owned(), Scalar::Null);
+
+    // The arrays of different lengths are rejected.
+    let mismatched = arrays(vec![1.0, 2.0, 3.0, 4.0, 5.0], vec![0, 3, 5]);
+    let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+    func.init_state(place);
+    let err = func
+        .accumulate(place, (&[mismatched]).into(), None, 2)
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_DATA_ARRAY_LENGTH);
+}
+
+#[test]
+fn test_agg_min_max_by_tie() {
+    let factory = AggregateFunctionFactory::instance();
+    let arena = Bump::new();
+    let values = StringType::from_data(vec!["b", "a", "c", "d"]);
+    let keys = Int32Type::from_data(vec![1, 1, 2, 2]);
+
+    // The ties resolve to the smallest value, whichever partial state is merged first.
+    for (name, expected) in [("min_by", "a"), ("max_by", "c")] {
+        let func = factory
+            .get(name, vec![], vec![values.data_type(), keys.data_type()])
+            .unwrap();
+        for ranges in [[(0, 1), (1, 3)], [(2, 2), (0, 2)], [(1, 3), (0, 1)]] {
+            let addr: StateAddr = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(addr);
+            let mut buffer = vec![];
+            for (offset, len) in ranges {
+                let partial: StateAddr = arena.alloc_layout(func.state_layout()).into();
+                func.init_state(partial);
+                let columns = [
+                    values.slice(offset..offset + len),
+                    keys.slice(offset..offset + len),
+                ];
+                func.accumulate(partial, (&columns).into(), None, len)
+                    .unwrap();
+                buffer.clear();
+                func.serialize(partial, &mut buffer).unwrap();
+                func.merge(addr, &mut buffer.as_slice()).unwrap();
+            }
+
+            let mut builder = ColumnBuilder::with_capacity(&func.return_type().unwrap(), 1);
+            func.merge_result(addr, &mut builder).unwrap();
+            let result = builder.build().index(0).unwrap().to_owned();
+            assert_eq!(result, Scalar::String(expected.to_string()), "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_agg_number_sum_keys() {
+    // Runs of the same group are mixed with single rows and a group seen again later.
+    let column = Int32Type::from_data(vec![1, 2, 3, 4, 5, 6, 7]);
+    let factory = AggregateFunctionFactory::instance();
+    let func = factory
+        .get("sum", vec![], vec![column.data_type()])
+        .unwrap();
+
+    let arena = Bump::new();
+    let addrs: Vec<StateAddr> = (0..3)
+        .map(|_| {
+            let addr = arena.alloc_layout(func.state_layout()).into();
+            func.init_state(addr);
+            addr
+        })
+        .collect();
+
+    let places = [0, 0, 0, 1, 1, 0, 2].map(|i| addrs[i]);
+    func.accumulate_keys(&places, 0, (&[column]).into(), places.len())
+        .unwrap();
+
+    let data_type = func.return_type().unwrap();
+    let mut builder = ColumnBuilder::with_capacity(&data_type, addrs.len());
+    for addr in &addrs {
+        func.merge_result(*addr, &mut builder).unwrap();
+    }
+    assert_eq!(builder.build(), Int64Type::from_data(vec![12, 9, 7]));
+}
+
 fn gen_bitmap_data() -> Column {
     // construct bitmap column with 4 row:
     // 0..5, 1..6, 2..7, 3..8
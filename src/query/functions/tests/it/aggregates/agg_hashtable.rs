@@ -26,29 +26,49 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::alloc::Layout;
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bumpalo::Bump;
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
 use databend_common_expression::block_debug::assert_block_value_sort_eq;
 use databend_common_expression::types::ArgType;
 use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
 use databend_common_expression::types::Float32Type;
 use databend_common_expression::types::Float64Type;
 use databend_common_expression::types::Int16Type;
 use databend_common_expression::types::Int32Type;
 use databend_common_expression::types::Int64Type;
 use databend_common_expression::types::Int8Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
 use databend_common_expression::types::StringType;
 use databend_common_expression::types::UInt64Type;
 use databend_common_expression::types::F32;
 use databend_common_expression::types::F64;
+use databend_common_expression::AggregateFunction;
+use databend_common_expression::AggregateFunctionRef;
 use databend_common_expression::AggregateHashTable;
 use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
 use databend_common_expression::DataBlock;
 use databend_common_expression::FromData;
+use databend_common_expression::GroupHashFunction;
 use databend_common_expression::HashTableConfig;
+use databend_common_expression::InputColumns;
+use databend_common_expression::Payload;
 use databend_common_expression::PayloadFlushState;
 use databend_common_expression::ProbeState;
+use databend_common_expression::ProbeStatePool;
+use databend_common_expression::ScalarRef;
+use databend_common_expression::StateAddr;
 use databend_common_functions::aggregates::AggregateFunctionFactory;
 use itertools::Itertools;
 
@@ -174,3 +194,745 @@ fn test_agg_hashtable() {
         assert_block_value_sort_eq(&block, &block_expected);
     }
 }
+
+/// Builds a hashtable with the groups of the rows in `range`.
+fn new_hashtable(
+    group_types: &[DataType],
+    aggrs: &[AggregateFunctionRef],
+    config: HashTableConfig,
+    group_columns: &[Column],
+    params: &[Vec<Column>],
+    range: Range<usize>,
+) -> AggregateHashTable {
+    let group_columns = group_columns
+        .iter()
+        .map(|c| c.slice(range.clone()))
+        .collect_vec();
+    let params = params
+        .iter()
+        .map(|v| v.iter().map(|c| c.slice(range.clone())).collect_vec())
+        .collect_vec();
+    let params = params.iter().map(|v| v.into()).collect_vec();
+
+    let mut hashtable = AggregateHashTable::new(
+        group_types.to_vec(),
+        aggrs.to_vec(),
+        config,
+        Arc::new(Bump::new()),
+    );
+    let mut state = ProbeState::default();
+    hashtable
+        .add_groups(
+            &mut state,
+            (&group_columns).into(),
+            &params,
+            (&[]).into(),
+            range.len(),
+        )
+        .unwrap();
+    hashtable
+}
+
+/// Merges the groups and the aggregate results of the hashtable into one block.
+fn collect_hashtable(hashtable: &mut AggregateHashTable) -> DataBlock {
+    let mut merge_state = PayloadFlushState::default();
+    let mut blocks = Vec::new();
+    while hashtable.merge_result(&mut merge_state).unwrap() {
+        let mut columns = merge_state.take_group_columns();
+        columns.extend_from_slice(&merge_state.take_aggregate_results());
+        blocks.push(DataBlock::new_from_columns(columns));
+    }
+    DataBlock::concat(&blocks).unwrap()
+}
+
+#[test]
+fn test_agg_hashtable_combine_parallel() {
+    let factory = AggregateFunctionFactory::instance();
+    let m: usize = 100;
+    let n: usize = 10_000;
+    let columns = vec![
+        StringType::from_data((0..n).map(|x| format!("{}", x % m)).collect_vec()),
+        Int64Type::from_data((0..n).map(|x| x as i64).collect_vec()),
+    ];
+    let group_columns = vec![columns[0].clone()];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("sum", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+
+    let params: Vec<Vec<Column>> = aggrs.iter().map(|_| vec![columns[1].clone()]).collect();
+
+    let new_tables = |num: usize| {
+        (0..num)
+            .map(|i| {
+                let start = n / num * i;
+                let end = if i == num - 1 { n } else { n / num * (i + 1) };
+                new_hashtable(
+                    &group_types,
+                    &aggrs,
+                    HashTableConfig::default(),
+                    &group_columns,
+                    &params,
+                    start..end,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for num in [1, 2, 5, 8] {
+        let mut tables = new_tables(num).into_iter();
+        let mut serial = tables.next().unwrap();
+        let mut flush_state = PayloadFlushState::default();
+        for table in tables {
+            serial.combine(table, &mut flush_state).unwrap();
+        }
+        let expected = collect_hashtable(&mut serial);
+
+        for max_threads in [1, 2, 8] {
+            let mut parallel = AggregateHashTable::combine_parallel(new_tables(num), max_threads)
+                .unwrap()
+                .unwrap();
+            let block = collect_hashtable(&mut parallel);
+
+            assert_eq!(block.num_rows(), m);
+            assert_block_value_sort_eq(&block, &expected);
+        }
+    }
+
+    assert!(
+        AggregateHashTable::combine_parallel(vec![], 2)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_agg_hashtable_hash_seed() {
+    let factory = AggregateFunctionFactory::instance();
+    // More groups than the initial capacity, so the entries are resized from the stored hashes
+    let n: usize = 100_000;
+    let group_columns = vec![Int64Type::from_data((0..n).map(|x| x as i64).collect_vec())];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+    let params: Vec<Vec<Column>> = vec![group_columns.clone()];
+
+    let new_table = |hash_seed: u64| {
+        let hashtable = new_hashtable(
+            &group_types,
+            &aggrs,
+            HashTableConfig::default().with_hash_seed(hash_seed),
+            &group_columns,
+            &params,
+            0..n,
+        );
+        assert_eq!(hashtable.len(), n);
+        hashtable
+    };
+
+    let expected = collect_hashtable(&mut new_table(0));
+
+    for hash_seed in [1, 0x9e37_79b9_7f4a_7c15] {
+        let mut hashtable = new_table(hash_seed);
+        let block = collect_hashtable(&mut hashtable);
+        assert_eq!(block.num_rows(), n);
+        assert_block_value_sort_eq(&block, &expected);
+
+        // The groups must still match when combining tables of the same seed
+        let mut hashtable = new_table(hash_seed);
+        let mut flush_state = PayloadFlushState::default();
+        hashtable
+            .combine(new_table(hash_seed), &mut flush_state)
+            .unwrap();
+        let block = collect_hashtable(&mut hashtable);
+        assert_eq!(block.num_rows(), n);
+    }
+}
+
+#[test]
+fn test_agg_hashtable_probe_state_pool() {
+    let factory = AggregateFunctionFactory::instance();
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+    let pool = ProbeStatePool::create(2);
+
+    // The tables are probed one after another with the same pooled state, each of them
+    // has its own groups.
+    let n: usize = 10_000;
+    for m in [3, 4096, 7] {
+        let group_columns = vec![Int64Type::from_data(
+            (0..n).map(|x| (x % m) as i64).collect_vec(),
+        )];
+        let params: Vec<Vec<Column>> = vec![group_columns.clone()];
+        let params = params.iter().map(|v| v.into()).collect_vec();
+        let mut hashtable = AggregateHashTable::new(
+            vec![Int64Type::data_type()],
+            aggrs.clone(),
+            HashTableConfig::default(),
+            Arc::new(Bump::new()),
+        );
+
+        let mut state = pool.get();
+        assert_eq!(state.row_count, 0);
+        assert!(state.partition_count.is_empty());
+        let new_groups = hashtable
+            .add_groups(
+                &mut state,
+                (&group_columns).into(),
+                &params,
+                (&[]).into(),
+                n,
+            )
+            .unwrap();
+        assert_eq!(new_groups, m);
+        drop(state);
+        assert_eq!(pool.idle(), 1);
+
+        let mut merge_state = PayloadFlushState::default();
+        let mut blocks = Vec::new();
+        while hashtable.merge_result(&mut merge_state).unwrap() {
+            let mut columns = merge_state.take_group_columns();
+            columns.extend_from_slice(&merge_state.take_aggregate_results());
+            blocks.push(DataBlock::new_from_columns(columns));
+        }
+        let block = DataBlock::concat(&blocks).unwrap();
+        let expected = DataBlock::new_from_columns(vec![
+            Int64Type::from_data((0..m as i64).collect_vec()),
+            UInt64Type::from_data(
+                (0..m)
+                    .map(|group| ((n - group + m - 1) / m) as u64)
+                    .collect_vec(),
+            ),
+        ]);
+        assert_block_value_sort_eq(&block, &expected);
+    }
+
+    // The pool keeps no more idle states than its capacity.
+    let states = (0..3).map(|_| pool.get()).collect_vec();
+    assert_eq!(pool.idle(), 0);
+    drop(states);
+    assert_eq!(pool.idle(), 2);
+
+    // The borrowed states keep the pool alive, the pool and its idle states are freed with
+    // the last of them.
+    let state = pool.get();
+    let weak = Arc::downgrade(&pool);
+    drop(pool);
+    assert!(weak.upgrade().is_some());
+    drop(state);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_agg_hashtable_hash_function() {
+    let factory = AggregateFunctionFactory::instance();
+    let m: usize = 1000;
+    // More groups than the initial capacity, so the entries are resized from the stored hashes
+    let n: usize = 100_000;
+    let group_columns = vec![
+        StringType::from_data((0..n).map(|x| format!("{}", x % m)).collect_vec()),
+        Int64Type::from_data_with_validity(
+            (0..n).map(|x| x as i64).collect_vec(),
+            (0..n).map(|x| x % 7 != 0).collect_vec(),
+        ),
+    ];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+    let params: Vec<Vec<Column>> = vec![vec![Int64Type::from_data(
+        (0..n).map(|x| x as i64).collect_vec(),
+    )]];
+
+    let new_table = |hash_function: GroupHashFunction, range: Range<usize>| {
+        new_hashtable(
+            &group_types,
+            &aggrs,
+            HashTableConfig::default().with_hash_function(hash_function),
+            &group_columns,
+            &params,
+            range,
+        )
+    };
+
+    // The NULLs of the second key collapse into one group per value of the first key
+    let expected = collect_hashtable(&mut new_table(GroupHashFunction::Default, 0..n));
+    assert_eq!(expected.num_rows(), n - n / 7 - 1 + m);
+
+    for hash_function in [
+        GroupHashFunction::Default,
+        GroupHashFunction::XxHash,
+        GroupHashFunction::AHash,
+        GroupHashFunction::CityHash,
+    ] {
+        let block = collect_hashtable(&mut new_table(hash_function, 0..n));
+        assert_block_value_sort_eq(&block, &expected);
+
+        // The groups of the two halves must match when combining tables of the same function
+        let mut hashtable = new_table(hash_function, 0..n / 2);
+        let mut flush_state = PayloadFlushState::default();
+        hashtable
+            .combine(new_table(hash_function, n / 2..n), &mut flush_state)
+            .unwrap();
+        let block = collect_hashtable(&mut hashtable);
+        assert_block_value_sort_eq(&block, &expected);
+    }
+}
+
+#[test]
+fn test_agg_hashtable_remove() {
+    let factory = AggregateFunctionFactory::instance();
+    let m: usize = 1000;
+    // More groups than the initial capacity after the removal, so the entries are resized
+    let n: usize = 50_000;
+    let group_columns = vec![Int64Type::from_data((0..n).map(|x| x as i64).collect_vec())];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+
+    let mut hashtable = AggregateHashTable::new(
+        group_types.clone(),
+        aggrs.clone(),
+        HashTableConfig::default(),
+        Arc::new(Bump::new()),
+    );
+    let mut state = ProbeState::default();
+
+    let add_groups =
+        |hashtable: &mut AggregateHashTable, state: &mut ProbeState, range: Range<usize>| {
+            let group_columns = vec![group_columns[0].slice(range)];
+            let params: Vec<Vec<Column>> = vec![group_columns.clone()];
+            let params = params.iter().map(|v| v.into()).collect_vec();
+            hashtable
+                .add_groups(
+                    state,
+                    (&group_columns).into(),
+                    &params,
+                    (&[]).into(),
+                    group_columns[0].len(),
+                )
+                .unwrap()
+        };
+
+    assert_eq!(add_groups(&mut hashtable, &mut state, 0..m), m);
+
+    // The even groups are removed, the duplicate and the unknown groups are ignored
+    let remove_columns = vec![Int64Type::from_data(
+        (0..m as i64)
+            .step_by(2)
+            .chain([0, 2, -1, n as i64])
+            .collect_vec(),
+    )];
+    let removed = hashtable.remove(&mut state, (&remove_columns).into(), m / 2 + 4);
+    assert_eq!(removed, m / 2);
+
+    // The removed groups are created again, reusing the tombstones
+    assert_eq!(add_groups(&mut hashtable, &mut state, 0..m), m / 2);
+    assert_eq!(add_groups(&mut hashtable, &mut state, 0..m), 0);
+
+    let removed = hashtable.remove(&mut state, (&remove_columns).into(), m / 2 + 4);
+    assert_eq!(removed, m / 2);
+
+    // The resize drops the tombstones and keeps the groups not removed
+    assert_eq!(add_groups(&mut hashtable, &mut state, m..n), n - m);
+    assert_eq!(add_groups(&mut hashtable, &mut state, 0..n), m / 2);
+    assert_eq!(add_groups(&mut hashtable, &mut state, 0..n), 0);
+
+    // The rows of the removed groups are removed from the payload, so each group is flushed
+    // once with the count since it was added again.
+    assert_eq!(hashtable.len(), n);
+
+    let mut merge_state = PayloadFlushState::default();
+    let mut blocks = Vec::new();
+    while hashtable.merge_result(&mut merge_state).unwrap() {
+        let mut columns = merge_state.take_group_columns();
+        columns.extend_from_slice(&merge_state.take_aggregate_results());
+        blocks.push(DataBlock::new_from_columns(columns));
+    }
+    let block = DataBlock::concat(&blocks).unwrap();
+
+    let counts = (0..n)
+        .map(|x| match x {
+            x if x >= m => 3,
+            x if x % 2 == 0 => 2,
+            _ => 5,
+        })
+        .collect_vec();
+    let expected = DataBlock::new_from_columns(vec![
+        group_columns[0].clone(),
+        UInt64Type::from_data(counts),
+    ]);
+    assert_block_value_sort_eq(&block, &expected);
+}
+
+#[test]
+fn test_agg_hashtable_many_pages() {
+    let factory = AggregateFunctionFactory::instance();
+    // More rows than a page holds and than a 16-bit offset addresses, every group is added
+    // twice in batches.
+    let n: usize = 300_000;
+    let batch: usize = 65_536;
+    let group_columns = vec![Int64Type::from_data((0..n).map(|x| x as i64).collect_vec())];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+
+    let mut hashtable = AggregateHashTable::new(
+        group_types,
+        aggrs,
+        HashTableConfig::default(),
+        Arc::new(Bump::new()),
+    );
+    let mut state = ProbeState::default();
+    for _ in 0..2 {
+        for start in (0..n).step_by(batch) {
+            let columns = vec![group_columns[0].slice(start..(start + batch).min(n))];
+            let params: Vec<Vec<Column>> = vec![columns.clone()];
+            let params = params.iter().map(|v| v.into()).collect_vec();
+            let num_rows = columns[0].len();
+            hashtable
+                .add_groups(
+                    &mut state,
+                    (&columns).into(),
+                    &params,
+                    (&[]).into(),
+                    num_rows,
+                )
+                .unwrap();
+        }
+    }
+    assert_eq!(hashtable.len(), n);
+    assert!(hashtable.payload.page_count() > 1);
+
+    let mut merge_state = PayloadFlushState::default();
+    let mut keys = vec![];
+    while hashtable.merge_result(&mut merge_state).unwrap() {
+        let group_columns = merge_state.take_group_columns();
+        let counts = merge_state.take_aggregate_results();
+        let counts = counts[0].as_number().unwrap().as_u_int64().unwrap();
+        assert!(counts.iter().all(|count| *count == 2));
+        let group_keys = group_columns[0].as_number().unwrap().as_int64().unwrap();
+        keys.extend(group_keys.iter().copied());
+    }
+    keys.sort_unstable();
+    assert_eq!(keys, (0..n as i64).collect_vec());
+}
+
+#[test]
+fn test_payload_iter_rows() {
+    let factory = AggregateFunctionFactory::instance();
+    let m: usize = 10;
+    let n: usize = 1000;
+    let group_columns = vec![
+        StringType::from_data((0..n).map(|x| format!("{}", x % m)).collect_vec()),
+        Int64Type::from_data_with_validity(
+            (0..n).map(|x| (x % m) as i64).collect_vec(),
+            (0..n).map(|x| x % m != 0).collect_vec(),
+        ),
+        BooleanType::from_data((0..n).map(|x| x % 2 == 0).collect_vec()),
+    ];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("count", vec![], vec![group_types[1].clone()])
+            .unwrap(),
+    ];
+    let params: Vec<Vec<Column>> = vec![vec![group_columns[1].clone()]];
+    let params = params.iter().map(|v| v.into()).collect_vec();
+
+    let mut hashtable = AggregateHashTable::new(
+        group_types.clone(),
+        aggrs.clone(),
+        HashTableConfig::default(),
+        Arc::new(Bump::new()),
+    );
+    let mut state = ProbeState::default();
+    hashtable
+        .add_groups(
+            &mut state,
+            (&group_columns).into(),
+            &params,
+            (&[]).into(),
+            n,
+        )
+        .unwrap();
+
+    let mut builders = group_types
+        .iter()
+        .map(|t| ColumnBuilder::with_capacity(t, m))
+        .collect_vec();
+    let mut count_builder = ColumnBuilder::with_capacity(&UInt64Type::data_type(), m);
+    for payload in hashtable.payload.payloads.iter() {
+        for row in payload.iter_rows() {
+            for (builder, value) in builders.iter_mut().zip(row.group_values()) {
+                builder.push(value.as_ref());
+            }
+            let places = row.state_places();
+            aggrs[0]
+                .merge_result(places[0], &mut count_builder)
+                .unwrap();
+        }
+    }
+
+    let mut columns = builders.into_iter().map(|b| b.build()).collect_vec();
+    columns.push(count_builder.build());
+    let block = DataBlock::new_from_columns(columns);
+
+    let mut expected = group_columns.iter().map(|c| c.slice(0..m)).collect_vec();
+    expected.push(UInt64Type::from_data(
+        (0..m)
+            .map(|x| if x == 0 { 0 } else { (n / m) as u64 })
+            .collect_vec(),
+    ));
+    assert_block_value_sort_eq(&block, &DataBlock::new_from_columns(expected));
+}
+
+// Counts the rows of the groups, and how many states are created and dropped.
+#[derive(Default)]
+struct DropTrackingFunction {
+    inits: AtomicUsize,
+    drops: AtomicUsize,
+}
+
+impl fmt::Display for DropTrackingFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "drop_tracking")
+    }
+}
+
+impl AggregateFunction for DropTrackingFunction {
+    fn name(&self) -> &str {
+        "DropTrackingFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Number(NumberDataType::UInt64))
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        self.inits.fetch_add(1, Ordering::Relaxed);
+        place.write(Vec::<u64>::new);
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<Vec<u64>>()
+    }
+
+    fn accumulate(
+        &self,
+        _place: StateAddr,
+        _columns: InputColumns,
+        _validity: Option<&Bitmap>,
+        _input_rows: usize,
+    ) -> Result<()> {
+        unreachable!()
+    }
+
+    fn accumulate_row(&self, place: StateAddr, _columns: InputColumns, row: usize) -> Result<()> {
+        place.get::<Vec<u64>>().push(row as u64);
+        Ok(())
+    }
+
+    fn serialize(&self, _place: StateAddr, _writer: &mut Vec<u8>) -> Result<()> {
+        unreachable!()
+    }
+
+    fn merge(&self, _place: StateAddr, _reader: &mut &[u8]) -> Result<()> {
+        unreachable!()
+    }
+
+    fn merge_states(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let other = rhs.get::<Vec<u64>>().clone();
+        place.get::<Vec<u64>>().extend(other);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr, builder: &mut ColumnBuilder) -> Result<()> {
+        let rows = place.get::<Vec<u64>>().len() as u64;
+        builder.push(ScalarRef::Number(NumberScalar::UInt64(rows)));
+        Ok(())
+    }
+
+    fn need_manual_drop_state(&self) -> bool {
+        true
+    }
+
+    unsafe fn drop_state(&self, place: StateAddr) {
+        self.drops.fetch_add(1, Ordering::Relaxed);
+        std::ptr::drop_in_place(place.get::<Vec<u64>>());
+    }
+}
+
+#[test]
+fn test_agg_hashtable_combine_drop_states() {
+    let m: usize = 10;
+    let n: usize = 1000;
+    let group_columns = vec![Int64Type::from_data(
+        (0..n).map(|x| (x % m) as i64).collect_vec(),
+    )];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let func = Arc::new(DropTrackingFunction::default());
+    let aggrs: Vec<AggregateFunctionRef> = vec![func.clone()];
+
+    let params: Vec<Vec<Column>> = vec![group_columns.clone()];
+
+    let new_table = |range: Range<usize>| {
+        new_hashtable(
+            &group_types,
+            &aggrs,
+            HashTableConfig::default(),
+            &group_columns,
+            &params,
+            range,
+        )
+    };
+
+    let mut expected = group_columns.iter().map(|c| c.slice(0..m)).collect_vec();
+    expected.push(UInt64Type::from_data(vec![(n / m) as u64; m]));
+    let expected = DataBlock::new_from_columns(expected);
+
+    {
+        // The empty table takes over the states of the first table, the states of the
+        // second table are merged and dropped with it.
+        let mut hashtable = new_table(0..0);
+        let mut flush_state = PayloadFlushState::default();
+        hashtable
+            .combine(new_table(0..n / 2), &mut flush_state)
+            .unwrap();
+        hashtable
+            .combine(new_table(n / 2..n), &mut flush_state)
+            .unwrap();
+        assert_block_value_sort_eq(&collect_hashtable(&mut hashtable), &expected);
+
+        let mut tables = vec![new_table(0..0)];
+        tables.extend((0..4).map(|i| new_table(n / 4 * i..n / 4 * (i + 1))));
+        let mut hashtable = AggregateHashTable::combine_parallel(tables, 2)
+            .unwrap()
+            .unwrap();
+        assert_block_value_sort_eq(&collect_hashtable(&mut hashtable), &expected);
+    }
+
+    let inits = func.inits.load(Ordering::Relaxed);
+    assert!(inits > 0);
+    assert_eq!(func.drops.load(Ordering::Relaxed), inits);
+}
+
+#[test]
+fn test_payload_serde() {
+    let factory = AggregateFunctionFactory::instance();
+    let m: usize = 100;
+    let n: usize = 10_000;
+    let columns = vec![
+        StringType::from_data((0..n).map(|x| format!("{}", x % m)).collect_vec()),
+        Int64Type::from_data((0..n).map(|x| x as i64).collect_vec()),
+    ];
+    let group_columns = vec![columns[0].clone()];
+    let group_types: Vec<_> = group_columns.iter().map(|c| c.data_type()).collect();
+
+    let aggrs = vec![
+        factory
+            .get("sum", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+        factory
+            .get("count", vec![], vec![Int64Type::data_type()])
+            .unwrap(),
+    ];
+
+    let params: Vec<Vec<Column>> = aggrs.iter().map(|_| vec![columns[1].clone()]).collect();
+
+    let new_table = |range: Range<usize>| {
+        new_hashtable(
+            &group_types,
+            &aggrs,
+            HashTableConfig::default(),
+            &group_columns,
+            &params,
+            range,
+        )
+    };
+
+    let mut expected = new_table(0..n / 2);
+    let mut flush_state = PayloadFlushState::default();
+    expected
+        .combine(new_table(n / 2..n), &mut flush_state)
+        .unwrap();
+    let expected = collect_hashtable(&mut expected);
+
+    // The payloads of the second table are transferred as bytes and combined into the first.
+    let mut hashtable = new_table(0..n / 2);
+    let remote = new_table(n / 2..n);
+    for payload in remote.payload.payloads.iter() {
+        let bytes = payload.serialize().unwrap();
+        let payload = Payload::deserialize(
+            Arc::new(Bump::new()),
+            group_types.clone(),
+            aggrs.clone(),
+            &bytes,
+        )
+        .unwrap();
+        hashtable
+            .combine_payload(&payload, &mut flush_state)
+            .unwrap();
+    }
+    let block = collect_hashtable(&mut hashtable);
+
+    assert_eq!(block.num_rows(), m);
+    assert_block_value_sort_eq(&block, &expected);
+
+    // Truncated and trailing bytes are rejected.
+    let bytes = remote.payload.payloads[0].serialize().unwrap();
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    for bytes in [&bytes[..bytes.len() - 1], trailing.as_slice()] {
+        let err = Payload::deserialize(
+            Arc::new(Bump::new()),
+            group_types.clone(),
+            aggrs.clone(),
+            bytes,
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::BAD_BYTES);
+    }
+
+    // The columns must match the group types and the states.
+    let err = Payload::deserialize(
+        Arc::new(Bump::new()),
+        vec![Int64Type::data_type()],
+        aggrs.clone(),
+        &bytes,
+    )
+    .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BAD_BYTES);
+
+    // The empty payload is transferred as well.
+    let empty = new_table(0..0);
+    let bytes = empty.payload.payloads[0].serialize().unwrap();
+    let payload =
+        Payload::deserialize(Arc::new(Bump::new()), group_types.clone(), aggrs, &bytes).unwrap();
+    assert_eq!(payload.len(), 0);
+}
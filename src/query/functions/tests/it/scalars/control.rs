@@ -28,6 +28,7 @@ fn test_control() {
 
     test_if(file);
     test_is_not_null(file);
+    test_is_null(file);
 }
 
 fn test_if(file: &mut impl Write) {
@@ -137,3 +138,25 @@ fn test_is_not_null(file: &mut impl Write) {
         Int64Type::from_data_with_validity(vec![9i64, 10, 11, 12], vec![true, true, false, false]),
     )]);
 }
+
+fn test_is_null(file: &mut impl Write) {
+    run_ast(file, "is_null(1)", &[]);
+    run_ast(file, "is_null(NULL)", &[]);
+    run_ast(file, "is_null(int64_col)", &[(
+        "int64_col",
+        Int64Type::from_data(vec![5i64, 6, 7, 8]),
+    )]);
+
+    // The output is the validity bitmap of a sparse nullable column, negated for `is_null`.
+    let validity = vec![
+        false, true, false, false, false, false, true, false, false, false,
+    ];
+    run_ast(file, "is_null(nullable_col)", &[(
+        "nullable_col",
+        Int64Type::from_data_with_validity((1i64..=10).collect::<Vec<_>>(), validity.clone()),
+    )]);
+    run_ast(file, "is_not_null(sparse_col)", &[(
+        "sparse_col",
+        Int64Type::from_data_with_validity((1i64..=10).collect::<Vec<_>>(), validity),
+    )]);
+}
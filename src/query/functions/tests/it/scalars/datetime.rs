@@ -35,6 +35,7 @@ fn test_datetime() {
     test_timestamp_arith(file);
     test_to_number(file);
     test_rounder_functions(file);
+    test_to_string(file);
 }
 
 fn test_to_timestamp(file: &mut impl Write) {
@@ -597,3 +598,15 @@ fn test_rounder_functions(file: &mut impl Write) {
     run_ast(file, "date_trunc(minute, to_timestamp(1630812366))", &[]);
     run_ast(file, "date_trunc(second, to_timestamp(1630812366))", &[]);
 }
+
+fn test_to_string(file: &mut impl Write) {
+    // Out of range micros should be reported as an error instead of panicking.
+    run_ast(file, "to_string(a)", &[(
+        "a",
+        TimestampType::from_data(vec![i64::MAX]),
+    )]);
+    run_ast(file, "to_string(a, '%Y')", &[(
+        "a",
+        TimestampType::from_data(vec![i64::MAX]),
+    )]);
+}
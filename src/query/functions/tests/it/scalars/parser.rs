@@ -377,26 +377,12 @@ pub fn transform_expr(ast: AExpr, columns: &[(&str, DataType)]) -> RawExpr {
                 args,
             }
         }
-        AExpr::IsNull { span, expr, not } => {
-            let expr = transform_expr(*expr, columns);
-            let result = RawExpr::FunctionCall {
-                span,
-                name: "is_not_null".to_string(),
-                params: vec![],
-                args: vec![expr],
-            };
-
-            if not {
-                result
-            } else {
-                RawExpr::FunctionCall {
-                    span,
-                    name: "not".to_string(),
-                    params: vec![],
-                    args: vec![result],
-                }
-            }
-        }
+        AExpr::IsNull { span, expr, not } => RawExpr::FunctionCall {
+            span,
+            name: if not { "is_not_null" } else { "is_null" }.to_string(),
+            params: vec![],
+            args: vec![transform_expr(*expr, columns)],
+        },
         AExpr::DateAdd {
             span,
             unit,
@@ -26,6 +26,8 @@ use super::Compactor;
 pub struct BlockCompactor {
     thresholds: BlockThresholds,
     aborting: Arc<AtomicBool>,
+    // Whether the rows are output in the same order as they are input.
+    preserve_order: bool,
 }
 
 impl BlockCompactor {
@@ -33,8 +35,18 @@ impl BlockCompactor {
         BlockCompactor {
             thresholds,
             aborting: Arc::new(AtomicBool::new(false)),
+            preserve_order: false,
         }
     }
+
+    /// By default a perfect block is output as soon as it arrives, ahead of the smaller
+    /// blocks still waiting to be merged, so the order of rows is not kept. With
+    /// `preserve_order`, the blocks are only concatenated in order, which keeps the order of
+    /// sorted input at the cost of an occasional smaller block.
+    pub fn with_preserve_order(mut self, preserve_order: bool) -> Self {
+        self.preserve_order = preserve_order;
+        self
+    }
 }
 
 impl Compactor for BlockCompactor {
@@ -64,8 +76,13 @@ impl Compactor for BlockCompactor {
             .thresholds
             .check_perfect_block(block.num_rows(), block.memory_size())
         {
-            res.push(block);
             blocks.remove(size - 1);
+            if self.preserve_order && !blocks.is_empty() {
+                // The blocks before it are output first.
+                res.push(DataBlock::concat(blocks)?);
+                blocks.clear();
+            }
+            res.push(block);
         } else {
             let accumulated_rows: usize = blocks.iter_mut().map(|b| b.num_rows()).sum();
             let accumulated_bytes: usize = blocks.iter_mut().map(|b| b.memory_size()).sum();
@@ -110,9 +127,19 @@ impl Compactor for BlockCompactor {
                 .thresholds
                 .check_perfect_block(block.num_rows(), block.memory_size())
             {
+                if self.preserve_order && accumulated_rows != 0 {
+                    // The blocks before it are output first.
+                    res.push(DataBlock::concat(&temp_blocks)?);
+                    temp_blocks.clear();
+                    accumulated_rows = 0;
+                }
                 res.push(block.clone());
             } else {
-                let block = if block.num_rows() > self.thresholds.max_rows_per_block {
+                // The leading rows of a large block can't be output ahead of the accumulated
+                // blocks if the order is preserved, the whole block is accumulated instead.
+                let block = if !self.preserve_order
+                    && block.num_rows() > self.thresholds.max_rows_per_block
+                {
                     let b = block.slice(0..self.thresholds.max_rows_per_block);
                     res.push(b);
                     block.slice(self.thresholds.max_rows_per_block..block.num_rows())
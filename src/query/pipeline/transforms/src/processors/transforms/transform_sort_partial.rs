@@ -24,6 +24,9 @@ use databend_common_pipeline_core::processors::Processor;
 use crate::processors::transforms::Transform;
 use crate::processors::transforms::Transformer;
 
+/// Sorts each block on its own, it keeps no block in memory. The sorted blocks are the runs
+/// merged by `TransformSortMerge`, which spills the merged runs once the memory exceeds the
+/// sort spilling settings, and `TransformSortSpill` merges the spilled runs from the disk.
 pub struct TransformSortPartial {
     limit: Option<usize>,
     sort_columns_descriptions: Arc<Vec<SortColumnDescription>>,
@@ -0,0 +1,75 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use databend_common_exception::Result;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::BlockThresholds;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_pipeline_transforms::processors::BlockCompactor;
+use databend_common_pipeline_transforms::processors::Compactor;
+
+const MAX_ROWS: usize = 10;
+
+fn new_compactor() -> BlockCompactor {
+    let thresholds = BlockThresholds::new(MAX_ROWS, 5, usize::MAX);
+    BlockCompactor::new(thresholds).with_preserve_order(true)
+}
+
+/// Sorted input split into blocks, the blocks of 6 and 7 rows are perfect blocks.
+fn sorted_blocks() -> Vec<DataBlock> {
+    let mut start = 0;
+    [3, 6, 2, 25, 1, 7, 4]
+        .into_iter()
+        .map(|len| {
+            let block = DataBlock::new_from_columns(vec![Int32Type::from_data(
+                (start..start + len).collect::<Vec<i32>>(),
+            )]);
+            start += len;
+            block
+        })
+        .collect()
+}
+
+fn check_sorted(result: &[DataBlock]) -> Result<()> {
+    assert!(result.iter().all(|b| b.num_rows() <= MAX_ROWS));
+
+    let block = DataBlock::concat(result)?;
+    let expected = Int32Type::from_data((0..48).collect::<Vec<i32>>());
+    assert_eq!(block.get_by_offset(0).value.as_column(), Some(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_block_compactor_preserve_order_partial() -> Result<()> {
+    let mut compactor = new_compactor();
+    let mut blocks = vec![];
+    let mut result = vec![];
+
+    // The same as `TransformCompact`, compacts partially whenever a block is pushed.
+    for block in sorted_blocks() {
+        blocks.push(block);
+        result.extend(compactor.compact_partial(&mut blocks)?);
+    }
+    result.extend(compactor.compact_final(blocks)?);
+
+    check_sorted(&result)
+}
+
+#[test]
+fn test_block_compactor_preserve_order_final() -> Result<()> {
+    let mut compactor = new_compactor();
+    let result = compactor.compact_final(sorted_blocks())?;
+
+    check_sorted(&result)
+}
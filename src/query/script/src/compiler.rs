@@ -299,6 +299,7 @@ impl Compiler {
             order_by: vec![],
             limit: vec![],
             offset: None,
+            with_ties: false,
             ignore_result: false,
         }));
         let stmt = StatementTemplate::new(expr.whole_span(), select_stmt);
@@ -392,6 +393,7 @@ impl Compiler {
             order_by: vec![],
             limit: vec![],
             offset: None,
+            with_ties: false,
             ignore_result: false,
         }));
         let stmt = StatementTemplate::new(variable.span, select_stmt);
@@ -0,0 +1,84 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[macro_use]
+extern crate criterion;
+
+use std::collections::HashMap;
+
+use criterion::Criterion;
+use databend_common_expression::type_check::check_function;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
+use databend_common_expression::FromData;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::Scalar;
+use databend_common_functions::BUILTIN_FUNCTIONS;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformFillNull;
+
+fn bench_fill_null(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bench_fill_null");
+
+    for rows in [1024, 65536] {
+        let values = (0..rows as i32).collect::<Vec<_>>();
+        let validity = (0..rows).map(|i| i % 3 != 0).collect::<Vec<_>>();
+        let block =
+            DataBlock::new_from_columns(vec![Int32Type::from_data_with_validity(values, validity)]);
+        let default = Scalar::Number(NumberScalar::Int32(0));
+
+        let mut transform = TransformFillNull::new(HashMap::from([(0, default.clone())]));
+        group.bench_function(format!("fill_null/{rows}"), |b| {
+            b.iter(|| transform.transform(block.clone()).unwrap())
+        });
+
+        // `coalesce(a, 0)` is rewritten into `if(is_not_null(a), assume_not_null(a), 0)`.
+        let column = Expr::ColumnRef {
+            span: None,
+            id: 0,
+            data_type: DataType::Nullable(Box::new(DataType::Number(NumberDataType::Int32))),
+            display_name: "a".to_string(),
+        };
+        let args = [
+            check_function(
+                None,
+                "is_not_null",
+                &[],
+                &[column.clone()],
+                &BUILTIN_FUNCTIONS,
+            )
+            .unwrap(),
+            check_function(None, "assume_not_null", &[], &[column], &BUILTIN_FUNCTIONS).unwrap(),
+            Expr::Constant {
+                span: None,
+                scalar: default,
+                data_type: DataType::Number(NumberDataType::Int32),
+            },
+        ];
+        let expr = check_function(None, "if", &[], &args, &BUILTIN_FUNCTIONS).unwrap();
+        let func_ctx = FunctionContext::default();
+        let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+        group.bench_function(format!("coalesce/{rows}"), |b| {
+            b.iter(|| evaluator.run(&expr).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, bench_fill_null);
+criterion_main!(benches);
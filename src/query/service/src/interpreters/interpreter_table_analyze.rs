@@ -301,6 +301,7 @@ fn remove_exchange(plan: PhysicalPlan) -> PhysicalPlan {
                     enable_experimental_aggregate_hashtable: plan
                         .enable_experimental_aggregate_hashtable,
                     group_by_display: plan.group_by_display,
+                    hash_seed: plan.hash_seed,
                     stat_info: plan.stat_info,
                 })
             }
@@ -312,6 +313,7 @@ fn remove_exchange(plan: PhysicalPlan) -> PhysicalPlan {
                 before_group_by_schema: plan.before_group_by_schema,
                 limit: plan.limit,
                 group_by_display: plan.group_by_display,
+                hash_seed: plan.hash_seed,
                 stat_info: plan.stat_info,
             }),
             PhysicalPlan::Window(plan) => PhysicalPlan::Window(Window {
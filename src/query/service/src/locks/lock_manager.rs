@@ -171,15 +171,15 @@ impl LockManager {
                 key: watch_delete_ident.to_string_key(),
                 key_end: None,
                 filter_type: FilterType::Delete.into(),
+                max_batch_size: None,
+                max_batch_delay_ms: None,
             };
             let mut watch_stream = meta_api.watch(req).await?;
             // Add a timeout period for watch.
             match timeout(duration, async move {
                 while let Some(Ok(resp)) = watch_stream.next().await {
-                    if let Some(event) = resp.event {
-                        if event.current.is_none() {
-                            break;
-                        }
+                    if resp.into_events().iter().any(|e| e.current.is_none()) {
+                        break;
                     }
                 }
             })
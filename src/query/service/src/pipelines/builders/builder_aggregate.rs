@@ -27,6 +27,7 @@ use databend_common_functions::aggregates::AggregateFunctionFactory;
 use databend_common_pipeline_core::processors::ProcessorPtr;
 use databend_common_pipeline_core::query_spill_prefix;
 use databend_common_pipeline_transforms::processors::TransformPipelineHelper;
+use databend_common_settings::Settings;
 use databend_common_sql::executor::physical_plans::AggregateExpand;
 use databend_common_sql::executor::physical_plans::AggregateFinal;
 use databend_common_sql::executor::physical_plans::AggregateFunctionDesc;
@@ -113,6 +114,11 @@ impl PipelineBuilder {
             in_cluster,
             max_block_size as usize,
             None,
+            with_agg_hashtable_settings(
+                HashTableConfig::default().with_hash_seed(aggregate.hash_seed),
+                &self.settings,
+            )?,
+            max_threads as usize,
         )?;
 
         if params.group_columns.is_empty() {
@@ -130,9 +136,14 @@ impl PipelineBuilder {
 
         // Need a global atomic to read the max current radix bits hint
         let partial_agg_config = if self.ctx.get_cluster().is_empty() {
-            HashTableConfig::default().with_partial(true, max_threads as usize)
+            params
+                .hashtable_config
+                .clone()
+                .with_partial(true, max_threads as usize)
         } else {
-            HashTableConfig::default()
+            params
+                .hashtable_config
+                .clone()
                 .cluster_with_partial(true, self.ctx.get_cluster().nodes.len())
         };
 
@@ -213,6 +224,7 @@ impl PipelineBuilder {
 
     pub(crate) fn build_aggregate_final(&mut self, aggregate: &AggregateFinal) -> Result<()> {
         let max_block_size = self.settings.get_max_block_size()?;
+        let max_threads = self.settings.get_max_threads()?;
         let enable_experimental_aggregate_hashtable = self
             .settings
             .get_enable_experimental_aggregate_hashtable()?;
@@ -225,6 +237,11 @@ impl PipelineBuilder {
             in_cluster,
             max_block_size as usize,
             aggregate.limit,
+            with_agg_hashtable_settings(
+                HashTableConfig::default().with_hash_seed(aggregate.hash_seed),
+                &self.settings,
+            )?,
+            max_threads as usize,
         )?;
 
         if params.group_columns.is_empty() {
@@ -291,6 +308,8 @@ impl PipelineBuilder {
         in_cluster: bool,
         max_block_size: usize,
         limit: Option<usize>,
+        hashtable_config: HashTableConfig,
+        max_threads: usize,
     ) -> Result<Arc<AggregatorParams>> {
         let mut agg_args = Vec::with_capacity(agg_funcs.len());
         let (group_by, group_data_types) = group_by
@@ -333,8 +352,21 @@ impl PipelineBuilder {
             in_cluster,
             max_block_size,
             limit,
+            hashtable_config,
+            max_threads,
         )?;
 
         Ok(params)
     }
 }
+
+/// Applies the load factor and the initial capacity of the aggregate hash table from the
+/// settings, the invalid values fall back to the defaults.
+pub fn with_agg_hashtable_settings(
+    config: HashTableConfig,
+    settings: &Settings,
+) -> Result<HashTableConfig> {
+    Ok(config
+        .with_load_factor(settings.get_agg_hashtable_load_factor()?)
+        .with_initial_capacity(settings.get_agg_hashtable_initial_capacity()?))
+}
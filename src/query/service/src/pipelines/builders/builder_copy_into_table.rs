@@ -40,6 +40,7 @@ use log::debug;
 use log::info;
 
 use crate::pipelines::processors::transforms::TransformAddConstColumns;
+use crate::pipelines::processors::CastErrorPolicy;
 use crate::pipelines::processors::TransformCastSchema;
 use crate::pipelines::processors::TransformNullIf;
 use crate::pipelines::PipelineBuilder;
@@ -158,6 +159,7 @@ impl PipelineBuilder {
                     source_schema.clone(),
                     plan_required_source_schema.clone(),
                     func_ctx.clone(),
+                    CastErrorPolicy::Strict,
                 )
             })?;
         }
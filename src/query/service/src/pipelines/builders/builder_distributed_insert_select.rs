@@ -17,6 +17,7 @@ use databend_common_exception::Result;
 use databend_common_pipeline_transforms::processors::TransformPipelineHelper;
 use databend_common_sql::executor::physical_plans::DistributedInsertSelect;
 
+use crate::pipelines::processors::CastErrorPolicy;
 use crate::pipelines::processors::TransformCastSchema;
 use crate::pipelines::PipelineBuilder;
 
@@ -45,6 +46,7 @@ impl PipelineBuilder {
                     select_schema.clone(),
                     insert_schema.clone(),
                     self.func_ctx.clone(),
+                    CastErrorPolicy::Strict,
                 )
             })?;
         }
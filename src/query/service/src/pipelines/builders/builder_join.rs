@@ -15,16 +15,24 @@
 use std::sync::Arc;
 
 use databend_common_base::base::tokio::sync::Barrier;
+use databend_common_catalog::plan::DataSourceInfo;
+use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
+use databend_common_expression::RemoteExpr;
+use databend_common_functions::BUILTIN_FUNCTIONS;
 use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_core::ExecutionInfo;
 use databend_common_pipeline_sinks::Sinker;
 use databend_common_sql::executor::physical_plans::HashJoin;
 use databend_common_sql::executor::physical_plans::MaterializedCte;
 use databend_common_sql::executor::physical_plans::RangeJoin;
 use databend_common_sql::executor::PhysicalPlan;
+use databend_common_sql::plans::JoinType;
 use databend_common_sql::ColumnBinding;
 use databend_common_sql::IndexType;
+use databend_storages_common_table_meta::table::OPT_KEY_SNAPSHOT_LOCATION;
 
+use crate::clusters::ClusterHelper;
 use crate::pipelines::processors::transforms::range_join::RangeJoinState;
 use crate::pipelines::processors::transforms::range_join::TransformRangeJoinLeft;
 use crate::pipelines::processors::transforms::range_join::TransformRangeJoinRight;
@@ -99,6 +107,19 @@ impl PipelineBuilder {
         let (enable_merge_into_optimization, merge_into_is_distributed) =
             self.merge_into_get_optimization_flag(join);
 
+        let build_cache_key = match enable_merge_into_optimization {
+            true => None,
+            false => self.hash_join_build_cache_key(join)?,
+        };
+        if let Some(state) = build_cache_key
+            .as_ref()
+            .and_then(|key| self.ctx.get_hash_join_build_cache(key))
+        {
+            // The hash table is built by a previous statement, skip the build side.
+            state.reuse_build();
+            return self.build_join_probe(join, state);
+        }
+
         let state = self.build_join_state(
             join,
             merge_into_is_distributed,
@@ -108,10 +129,53 @@ impl PipelineBuilder {
             self.hash_join_states
                 .insert(build_cache_index, state.clone());
         }
-        self.expand_build_side_pipeline(&join.build, join, state.clone())?;
+        self.expand_build_side_pipeline(&join.build, join, state.clone(), build_cache_key)?;
         self.build_join_probe(join, state)
     }
 
+    /// The key of the built hash table kept in the session, `None` if it can't be reused.
+    ///
+    /// The key contains the snapshots of the scanned tables, so it changes once the tables are
+    /// modified. The build sides reading the unversioned sources or evaluating the
+    /// non-deterministic functions are never reused.
+    fn hash_join_build_cache_key(&self, join: &HashJoin) -> Result<Option<String>> {
+        if !self.settings.get_enable_hash_join_build_cache()?
+            || !self.ctx.get_cluster().is_empty()
+            || join.need_hold_hash_table
+            || join.build_side_cache_info.is_some()
+            || join.single_to_inner.is_some()
+            || !matches!(
+                join.join_type,
+                JoinType::Inner
+                    | JoinType::Left
+                    | JoinType::LeftSemi
+                    | JoinType::LeftAnti
+                    | JoinType::LeftSingle
+            )
+        {
+            return Ok(None);
+        }
+
+        let mut versions = vec![];
+        if !collect_build_versions(&join.build, &mut versions) {
+            return Ok(None);
+        }
+
+        let key = serde_json::to_string(&(
+            &versions,
+            &join.build,
+            &join.build_projections,
+            &join.build_keys,
+            &join.probe_keys,
+            &join.is_null_equal,
+            &join.non_equi_conditions,
+            &join.join_type,
+            &join.probe_to_build,
+            join.from_correlated_subquery,
+        ))?;
+        Ok(Some(key))
+    }
+
     fn build_join_state(
         &mut self,
         join: &HashJoin,
@@ -135,6 +199,7 @@ impl PipelineBuilder {
         build: &PhysicalPlan,
         hash_join_plan: &HashJoin,
         join_state: Arc<HashJoinState>,
+        build_cache_key: Option<String>,
     ) -> Result<()> {
         let build_side_context = QueryContext::create_from(self.ctx.clone());
         let mut build_side_builder = PipelineBuilder::create(
@@ -170,6 +235,20 @@ impl PipelineBuilder {
         }
         build_res.main_pipeline.add_sink(create_sink_processor)?;
 
+        if let Some(key) = build_cache_key {
+            let ctx = self.ctx.clone();
+            let build_state = build_state.clone();
+            build_res
+                .main_pipeline
+                .set_on_finished(move |info: &ExecutionInfo| {
+                    if info.res.is_ok() && join_state.can_reuse_build() {
+                        let memory_size = build_state.keep_for_reuse();
+                        ctx.set_hash_join_build_cache(key, join_state, memory_size)?;
+                    }
+                    Ok(())
+                });
+        }
+
         self.pipelines.push(build_res.main_pipeline.finalize());
         self.pipelines.extend(build_res.sources_pipelines);
         Ok(())
@@ -276,3 +355,60 @@ impl PipelineBuilder {
         Ok(())
     }
 }
+
+/// Whether the build side reads the same rows in every statement, i.e. it only reads the fuse
+/// tables at their snapshots and evaluates the deterministic expressions. The versions of the
+/// scanned tables are collected into `versions`.
+fn collect_build_versions(plan: &PhysicalPlan, versions: &mut Vec<(u64, u64, String)>) -> bool {
+    let is_deterministic = |expr: &RemoteExpr| {
+        expr.as_expr(&BUILTIN_FUNCTIONS)
+            .is_deterministic(&BUILTIN_FUNCTIONS)
+    };
+    let reusable = match plan {
+        PhysicalPlan::TableScan(scan) => {
+            let DataSourceInfo::TableSource(table_info) = &scan.source.source_info else {
+                return false;
+            };
+            let Some(snapshot_location) = table_info.options().get(OPT_KEY_SNAPSHOT_LOCATION)
+            else {
+                return false;
+            };
+            if table_info.engine() != "FUSE" {
+                return false;
+            }
+            versions.push((
+                table_info.ident.table_id,
+                table_info.ident.seq,
+                snapshot_location.clone(),
+            ));
+            scan.source
+                .push_downs
+                .as_ref()
+                .and_then(|push_downs| push_downs.filters.as_ref())
+                .map_or(true, |filters| {
+                    filters
+                        .filter
+                        .as_expr(&BUILTIN_FUNCTIONS)
+                        .is_deterministic(&BUILTIN_FUNCTIONS)
+                })
+        }
+        PhysicalPlan::ConstantTableScan(_)
+        | PhysicalPlan::AggregateExpand(_)
+        | PhysicalPlan::AggregatePartial(_)
+        | PhysicalPlan::AggregateFinal(_)
+        | PhysicalPlan::Sort(_) => true,
+        PhysicalPlan::Filter(filter) => filter.predicates.iter().all(is_deterministic),
+        PhysicalPlan::EvalScalar(eval) => eval.exprs.iter().all(|(expr, _)| is_deterministic(expr)),
+        PhysicalPlan::HashJoin(join) => join
+            .build_keys
+            .iter()
+            .chain(&join.probe_keys)
+            .chain(&join.non_equi_conditions)
+            .all(is_deterministic),
+        _ => false,
+    };
+    reusable
+        && plan
+            .children()
+            .all(|child| collect_build_versions(child, versions))
+}
@@ -24,11 +24,19 @@ impl PipelineBuilder {
         self.build_pipeline(&limit.input)?;
 
         if limit.limit.is_some() || limit.offset != 0 {
+            let input_schema = limit.input.output_schema()?;
+            let ties_offsets = limit
+                .ties_keys
+                .iter()
+                .map(|index| input_schema.index_of(&index.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+
             self.main_pipeline.try_resize(1)?;
             return self.main_pipeline.add_transform(|input, output| {
                 Ok(ProcessorPtr::create(TransformLimit::try_create(
                     limit.limit,
                     limit.offset,
+                    ties_offsets.clone(),
                     input,
                     output,
                 )?))
@@ -55,6 +55,7 @@ use databend_common_storages_fuse::operations::UnbranchedReplaceIntoProcessor;
 use databend_common_storages_fuse::FuseTable;
 use parking_lot::RwLock;
 
+use crate::pipelines::processors::CastErrorPolicy;
 use crate::pipelines::processors::TransformCastSchema;
 use crate::pipelines::PipelineBuilder;
 
@@ -312,6 +313,7 @@ impl PipelineBuilder {
                         select_schema.clone(),
                         target_schema.clone(),
                         self.func_ctx.clone(),
+                        CastErrorPolicy::Strict,
                     )
                 })?;
             }
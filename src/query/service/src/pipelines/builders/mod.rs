@@ -48,6 +48,7 @@ mod builder_window;
 mod merge_into_join_optimizations;
 mod transform_builder;
 
+pub use builder_aggregate::with_agg_hashtable_settings;
 pub use builder_replace_into::RawValueSource;
 pub use builder_replace_into::ValueSource;
 pub use builder_sort::SortPipelineBuilder;
@@ -41,6 +41,7 @@ use databend_common_storages_fuse::FuseTable;
 use databend_storages_common_table_meta::meta::Statistics;
 
 use crate::pipelines::processors::transforms::TransformFilter;
+use crate::pipelines::processors::CastErrorPolicy;
 use crate::pipelines::processors::InputPort;
 use crate::pipelines::processors::OutputPort;
 use crate::pipelines::processors::TransformCastSchema;
@@ -194,6 +195,7 @@ impl PipelineBuilder {
                 source_schema.clone(),
                 target_schema.clone(),
                 func_ctx.clone(),
+                CastErrorPolicy::Strict,
             )
         })
     }
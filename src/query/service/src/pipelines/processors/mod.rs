@@ -15,14 +15,24 @@
 pub use databend_common_pipeline_core::processors::*;
 pub(crate) mod transforms;
 
+pub use transforms::aggregator::TransformExpandGroupingSets;
+pub use transforms::CastErrorPolicy;
 pub use transforms::HashJoinBuildState;
 pub use transforms::HashJoinDesc;
 pub use transforms::HashJoinState;
+pub use transforms::RunningTotalKind;
 pub use transforms::TransformAddStreamColumns;
 pub use transforms::TransformCastSchema;
 pub use transforms::TransformCreateSets;
+pub use transforms::TransformDistinctSorted;
+pub use transforms::TransformFillNull;
 pub use transforms::TransformLimit;
 pub use transforms::TransformNullIf;
+pub use transforms::TransformProject;
+pub use transforms::TransformRepartition;
 pub use transforms::TransformResortAddOn;
 pub use transforms::TransformResortAddOnWithoutSourceSchema;
+pub use transforms::TransformRunningTotal;
+pub use transforms::TransformUnionDistinct;
+pub use transforms::TransformUnnest;
 pub use transforms::TransformWindow;
@@ -171,7 +171,7 @@ fn scatter_payload(mut payload: Payload, buckets: usize) -> Result<Vec<Payload>>
 
             if count > 0 {
                 let sel = &state.probe_state.partition_entries[idx];
-                bucket.copy_rows(sel, count, &state.addresses);
+                bucket.copy_rows(sel, count, &state.addresses)?;
             }
         }
     }
@@ -219,7 +219,7 @@ fn scatter_partitioned_payload(
 
                 if count > 0 {
                     let sel = &state.probe_state.partition_entries[idx];
-                    bucket.copy_rows(sel, count, &state.addresses);
+                    bucket.copy_rows(sel, count, &state.addresses)?;
                 }
             }
         }
@@ -228,7 +228,7 @@ fn scatter_partitioned_payload(
     }
 
     for (idx, payload) in payloads.into_iter().enumerate() {
-        buckets[idx].combine_single(payload, &mut state, None);
+        buckets[idx].combine_single(payload, &mut state, None)?;
     }
 
     Ok(buckets)
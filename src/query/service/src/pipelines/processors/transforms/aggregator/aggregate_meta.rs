@@ -30,7 +30,7 @@ use databend_common_expression::HashTableConfig;
 use databend_common_expression::InputColumns;
 use databend_common_expression::PartitionedPayload;
 use databend_common_expression::Payload;
-use databend_common_expression::ProbeState;
+use databend_common_expression::ProbeStatePool;
 
 use crate::pipelines::processors::transforms::aggregator::HashTableCell;
 use crate::pipelines::processors::transforms::group_by::HashMethodBounds;
@@ -59,13 +59,15 @@ impl SerializedPayload {
         group_types: Vec<DataType>,
         aggrs: Vec<Arc<dyn AggregateFunction>>,
         radix_bits: u64,
+        config: &HashTableConfig,
+        probe_state_pool: &Arc<ProbeStatePool>,
         arena: Arc<Bump>,
         need_init_entry: bool,
     ) -> Result<AggregateHashTable> {
         let rows_num = self.data_block.num_rows();
-        let capacity = AggregateHashTable::get_capacity_for_count(rows_num);
-        let config = HashTableConfig::default().with_initial_radix_bits(radix_bits);
-        let mut state = ProbeState::default();
+        let capacity = AggregateHashTable::get_capacity_for_count(rows_num, config);
+        let config = config.clone().with_initial_radix_bits(radix_bits);
+        let mut state = probe_state_pool.get();
         let agg_len = aggrs.len();
         let group_len = group_types.len();
         let mut hashtable = AggregateHashTable::new_directly(
@@ -100,10 +102,19 @@ impl SerializedPayload {
         group_types: Vec<DataType>,
         aggrs: Vec<Arc<dyn AggregateFunction>>,
         radix_bits: u64,
+        config: &HashTableConfig,
+        probe_state_pool: &Arc<ProbeStatePool>,
         arena: Arc<Bump>,
     ) -> Result<PartitionedPayload> {
-        let hashtable =
-            self.convert_to_aggregate_table(group_types, aggrs, radix_bits, arena, false)?;
+        let hashtable = self.convert_to_aggregate_table(
+            group_types,
+            aggrs,
+            radix_bits,
+            config,
+            probe_state_pool,
+            arena,
+            false,
+        )?;
         Ok(hashtable.payload)
     }
 }
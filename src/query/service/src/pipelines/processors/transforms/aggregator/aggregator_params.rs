@@ -20,6 +20,8 @@ use databend_common_expression::types::DataType;
 use databend_common_expression::ColumnBuilder;
 use databend_common_expression::DataBlock;
 use databend_common_expression::DataSchemaRef;
+use databend_common_expression::HashTableConfig;
+use databend_common_expression::ProbeStatePool;
 use databend_common_functions::aggregates::get_layout_offsets;
 use databend_common_functions::aggregates::AggregateFunctionRef;
 use databend_common_functions::aggregates::StateAddr;
@@ -46,6 +48,11 @@ pub struct AggregatorParams {
     pub max_block_size: usize,
     // Limit is push down to AggregatorTransform
     pub limit: Option<usize>,
+    // The seed of the group hashes, the load factor and the initial capacity, shared by all
+    // the hash tables of the query
+    pub hashtable_config: HashTableConfig,
+    // The probe states borrowed by the hash tables of the aggregation, one for each thread
+    pub probe_state_pool: Arc<ProbeStatePool>,
 }
 
 impl AggregatorParams {
@@ -59,6 +66,8 @@ impl AggregatorParams {
         in_cluster: bool,
         max_block_size: usize,
         limit: Option<usize>,
+        hashtable_config: HashTableConfig,
+        max_threads: usize,
     ) -> Result<Arc<AggregatorParams>> {
         let mut states_offsets: Vec<usize> = Vec::with_capacity(agg_funcs.len());
         let mut states_layout = None;
@@ -79,6 +88,8 @@ impl AggregatorParams {
             in_cluster,
             max_block_size,
             limit,
+            hashtable_config,
+            probe_state_pool: ProbeStatePool::create(max_threads),
         }))
     }
 
@@ -362,6 +362,8 @@ impl<Method: HashMethodBounds, V: Copy + Send + Sync + 'static>
             self.params.group_data_types.clone(),
             self.params.aggregate_functions.clone(),
             0,
+            &self.params.hashtable_config,
+            &self.params.probe_state_pool,
             Arc::new(Bump::new()),
         )?;
 
@@ -371,7 +373,7 @@ impl<Method: HashMethodBounds, V: Copy + Send + Sync + 'static>
             self.max_partition_count as u64,
             p.arenas.clone(),
         );
-        partitioned_payload.combine(p, &mut self.flush_state);
+        partitioned_payload.combine(p, &mut self.flush_state)?;
 
         for (bucket, payload) in partitioned_payload.payloads.into_iter().enumerate() {
             blocks.push(Some(DataBlock::empty_with_meta(
@@ -413,7 +415,7 @@ impl<Method: HashMethodBounds, V: Copy + Send + Sync + 'static>
             vec![payload.payload.arena.clone()],
         );
 
-        partitioned_payload.combine_single(payload.payload, &mut self.flush_state, None);
+        partitioned_payload.combine_single(payload.payload, &mut self.flush_state, None)?;
 
         for (bucket, payload) in partitioned_payload.payloads.into_iter().enumerate() {
             blocks.push(Some(DataBlock::empty_with_meta(
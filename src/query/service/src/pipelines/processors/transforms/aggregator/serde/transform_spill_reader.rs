@@ -191,6 +191,19 @@ impl<Method: HashMethodBounds, V: Send + Sync + 'static> Processor
                         .await?
                         .to_vec();
 
+                    // perf
+                    {
+                        Profile::record_usize_profile(ProfileStatisticsName::SpillReadCount, 1);
+                        Profile::record_usize_profile(
+                            ProfileStatisticsName::SpillReadBytes,
+                            data.len(),
+                        );
+                        Profile::record_usize_profile(
+                            ProfileStatisticsName::SpillReadTime,
+                            instant.elapsed().as_millis() as usize,
+                        );
+                    }
+
                     info!(
                         "Read aggregate spill {} successfully, elapsed: {:?}",
                         &payload.location,
@@ -22,6 +22,10 @@ use databend_common_expression::Scalar;
 use databend_common_expression::Value;
 use databend_common_pipeline_transforms::processors::Transform;
 
+/// Replicates each input row once per grouping set. For the grouping id of a set, the bit `i`
+/// is set if `group_bys[i]` is not grouped, the group by columns become nullable and the ones
+/// that are not grouped are NULLs. The original group by columns and the grouping id are
+/// appended to the block.
 pub struct TransformExpandGroupingSets {
     group_bys: Vec<usize>,
     grouping_ids: Vec<usize>,
@@ -21,7 +21,6 @@ use databend_common_exception::Result;
 use databend_common_expression::AggregateHashTable;
 use databend_common_expression::ColumnBuilder;
 use databend_common_expression::DataBlock;
-use databend_common_expression::HashTableConfig;
 use databend_common_expression::PayloadFlushState;
 use databend_common_functions::aggregates::StateAddr;
 use databend_common_hashtable::HashtableEntryMutRefLike;
@@ -80,6 +79,8 @@ impl<Method: HashMethodBounds> TransformFinalAggregate<Method> {
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
                                 0,
+                                &self.params.hashtable_config,
+                                &self.params.probe_state_pool,
                                 Arc::new(Bump::new()),
                             )?;
                             ht.combine_payloads(&payload, &mut self.flush_state)?;
@@ -90,6 +91,8 @@ impl<Method: HashMethodBounds> TransformFinalAggregate<Method> {
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
                                 0,
+                                &self.params.hashtable_config,
+                                &self.params.probe_state_pool,
                                 Arc::new(Bump::new()),
                                 true,
                             )?);
@@ -102,12 +105,15 @@ impl<Method: HashMethodBounds> TransformFinalAggregate<Method> {
                         }
                         None => {
                             debug_assert!(bucket == payload.bucket);
-                            let capacity =
-                                AggregateHashTable::get_capacity_for_count(payload.payload.len());
+                            let config = &self.params.hashtable_config;
+                            let capacity = AggregateHashTable::get_capacity_for_count(
+                                payload.payload.len(),
+                                config,
+                            );
                             let mut hashtable = AggregateHashTable::new_with_capacity(
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
-                                HashTableConfig::default().with_initial_radix_bits(0),
+                                config.clone().with_initial_radix_bits(0),
                                 capacity,
                                 Arc::new(Bump::new()),
                             );
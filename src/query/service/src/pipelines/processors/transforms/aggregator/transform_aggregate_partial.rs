@@ -29,7 +29,7 @@ use databend_common_expression::DataBlock;
 use databend_common_expression::HashTableConfig;
 use databend_common_expression::InputColumns;
 use databend_common_expression::PayloadFlushState;
-use databend_common_expression::ProbeState;
+use databend_common_expression::PooledProbeState;
 use databend_common_functions::aggregates::StateAddr;
 use databend_common_functions::aggregates::StateAddrs;
 use databend_common_hashtable::HashtableEntryMutRefLike;
@@ -109,7 +109,7 @@ pub struct TransformPartialAggregate<Method: HashMethodBounds> {
     method: Method,
     settings: AggregateSettings,
     hash_table: HashTable<Method>,
-    probe_state: ProbeState,
+    probe_state: PooledProbeState,
     params: Arc<AggregatorParams>,
 }
 
@@ -155,6 +155,7 @@ impl<Method: HashMethodBounds> TransformPartialAggregate<Method> {
             }
         };
 
+        let probe_state = params.probe_state_pool.get();
         Ok(AccumulatingTransformer::create(
             input,
             output,
@@ -162,7 +163,7 @@ impl<Method: HashMethodBounds> TransformPartialAggregate<Method> {
                 method,
                 params,
                 hash_table,
-                probe_state: ProbeState::default(),
+                probe_state,
                 settings: AggregateSettings::try_from(ctx)?,
             },
         ))
@@ -391,7 +392,7 @@ impl<Method: HashMethodBounds> AccumulatingTransform for TransformPartialAggrega
                 // repartition to max for normalization
                 let partitioned_payload = v
                     .payload
-                    .repartition(1 << config.max_radix_bits, &mut state);
+                    .repartition(1 << config.max_radix_bits, &mut state)?;
 
                 let blocks = vec![DataBlock::empty_with_meta(
                     AggregateMeta::<Method, usize>::create_agg_spilling(partitioned_payload),
@@ -19,7 +19,6 @@ use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_expression::AggregateHashTable;
 use databend_common_expression::DataBlock;
-use databend_common_expression::HashTableConfig;
 use databend_common_expression::PayloadFlushState;
 use databend_common_hashtable::HashtableEntryRefLike;
 use databend_common_hashtable::HashtableLike;
@@ -74,6 +73,8 @@ impl<Method: HashMethodBounds> TransformFinalGroupBy<Method> {
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
                                 0,
+                                &self.params.hashtable_config,
+                                &self.params.probe_state_pool,
                                 Arc::new(Bump::new()),
                             )?;
                             ht.combine_payloads(&payload, &mut self.flush_state)?;
@@ -84,6 +85,8 @@ impl<Method: HashMethodBounds> TransformFinalGroupBy<Method> {
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
                                 0,
+                                &self.params.hashtable_config,
+                                &self.params.probe_state_pool,
                                 Arc::new(Bump::new()),
                                 true,
                             )?);
@@ -96,12 +99,15 @@ impl<Method: HashMethodBounds> TransformFinalGroupBy<Method> {
                         }
                         None => {
                             debug_assert!(bucket == payload.bucket);
-                            let capacity =
-                                AggregateHashTable::get_capacity_for_count(payload.payload.len());
+                            let config = &self.params.hashtable_config;
+                            let capacity = AggregateHashTable::get_capacity_for_count(
+                                payload.payload.len(),
+                                config,
+                            );
                             let mut hashtable = AggregateHashTable::new_with_capacity(
                                 self.params.group_data_types.clone(),
                                 self.params.aggregate_functions.clone(),
-                                HashTableConfig::default().with_initial_radix_bits(0),
+                                config.clone().with_initial_radix_bits(0),
                                 capacity,
                                 Arc::new(Bump::new()),
                             );
@@ -27,7 +27,7 @@ use databend_common_expression::DataBlock;
 use databend_common_expression::HashTableConfig;
 use databend_common_expression::InputColumns;
 use databend_common_expression::PayloadFlushState;
-use databend_common_expression::ProbeState;
+use databend_common_expression::PooledProbeState;
 use databend_common_hashtable::HashtableLike;
 use databend_common_pipeline_core::processors::InputPort;
 use databend_common_pipeline_core::processors::OutputPort;
@@ -104,7 +104,7 @@ impl TryFrom<Arc<QueryContext>> for GroupBySettings {
 pub struct TransformPartialGroupBy<Method: HashMethodBounds> {
     method: Method,
     hash_table: HashTable<Method>,
-    probe_state: ProbeState,
+    probe_state: PooledProbeState,
     settings: GroupBySettings,
     params: Arc<AggregatorParams>,
 }
@@ -133,13 +133,14 @@ impl<Method: HashMethodBounds> TransformPartialGroupBy<Method> {
             ))
         };
 
+        let probe_state = params.probe_state_pool.get();
         Ok(AccumulatingTransformer::create(
             input,
             output,
             TransformPartialGroupBy::<Method> {
                 method,
                 hash_table,
-                probe_state: ProbeState::default(),
+                probe_state,
                 params,
                 settings: GroupBySettings::try_from(ctx)?,
             },
@@ -246,7 +247,7 @@ impl<Method: HashMethodBounds> AccumulatingTransform for TransformPartialGroupBy
                     // repartition to max for normalization
                     let partitioned_payload = v
                         .payload
-                        .repartition(1 << config.max_radix_bits, &mut state);
+                        .repartition(1 << config.max_radix_bits, &mut state)?;
                     let blocks = vec![DataBlock::empty_with_meta(
                         AggregateMeta::<Method, ()>::create_agg_spilling(partitioned_payload),
                     )];
@@ -14,8 +14,13 @@
 
 use databend_common_exception::Result;
 use databend_common_expression::type_check::check_function;
+use databend_common_expression::types::nullable::NullableColumn;
+use databend_common_expression::types::string::StringColumn;
+use databend_common_expression::types::DataType;
+use databend_common_expression::Column;
 use databend_common_expression::Expr;
 use databend_common_expression::RemoteExpr;
+use databend_common_expression::SelectExprBuilder;
 use databend_common_functions::BUILTIN_FUNCTIONS;
 use databend_common_sql::executor::physical_plans::HashJoin;
 use databend_common_sql::IndexType;
@@ -36,6 +41,8 @@ pub struct HashJoinDesc {
     pub(crate) build_keys: Vec<Expr>,
     pub(crate) probe_keys: Vec<Expr>,
     pub(crate) is_null_equal: Vec<bool>,
+    /// Whether the string keys are compared case-insensitively, per key, see `lowercase_keys`.
+    pub(crate) case_insensitive: Vec<bool>,
     pub(crate) join_type: JoinType,
     pub(crate) single_to_inner: Option<JoinType>,
     /// when we have non-equal conditions for hash join,
@@ -50,6 +57,15 @@ pub struct HashJoinDesc {
     pub broadcast: bool,
     // If enable bloom runtime filter
     pub enable_bloom_runtime_filter: bool,
+    /// Whether the inner join appends the `build_row_id` of the matched build rows as the
+    /// last column, see `build_row_id`.
+    pub emit_build_row_id: bool,
+    /// Whether the left semi join appends the number of the build rows matched by each probe
+    /// row as the last column.
+    pub emit_match_count: bool,
+    /// Whether the build keys are known to be unique, e.g. the build side is grouped by them.
+    /// Each probe row then stops at its first match, unless a duplicated build key is inserted.
+    pub build_keys_unique: bool,
 }
 
 impl HashJoinDesc {
@@ -82,6 +98,7 @@ impl HashJoinDesc {
             build_keys,
             probe_keys,
             is_null_equal: join.is_null_equal.clone(),
+            case_insensitive: join.case_insensitive.clone(),
             other_predicate,
             marker_join_desc: MarkJoinDesc {
                 has_null: RwLock::new(false),
@@ -92,15 +109,85 @@ impl HashJoinDesc {
             broadcast: join.broadcast,
             single_to_inner: join.single_to_inner.clone(),
             enable_bloom_runtime_filter: join.enable_bloom_runtime_filter,
+            emit_build_row_id: join.emit_build_row_id,
+            emit_match_count: join.emit_match_count,
+            build_keys_unique: join.build_keys_unique,
         })
     }
 
+    /// Lowercases the ASCII letters of the string keys that are compared case-insensitively,
+    /// both the build and the probe keys are lowercased before they are hashed and compared.
+    /// Only the evaluated keys are changed, the emitted columns keep their original case.
+    pub fn lowercase_keys(case_insensitive: &[bool], keys_columns: &mut [Column]) {
+        for (column, _) in keys_columns
+            .iter_mut()
+            .zip(case_insensitive.iter())
+            .filter(|(_, case_insensitive)| **case_insensitive)
+        {
+            *column = Self::ascii_lowercase(column);
+        }
+    }
+
+    fn ascii_lowercase(column: &Column) -> Column {
+        match column {
+            Column::String(column) => {
+                // The lowercase letters are of the same length, the offsets are kept
+                let data = column
+                    .data()
+                    .iter()
+                    .map(u8::to_ascii_lowercase)
+                    .collect::<Vec<_>>();
+                Column::String(StringColumn::new(data.into(), column.offsets().clone()))
+            }
+            Column::Nullable(box NullableColumn { column, validity }) => {
+                NullableColumn::new_column(Self::ascii_lowercase(column), validity.clone())
+            }
+            column => column.clone(),
+        }
+    }
+
     fn join_predicate(non_equi_conditions: &[RemoteExpr]) -> Result<Option<Expr>> {
-        non_equi_conditions
+        let conditions = non_equi_conditions
             .iter()
             .map(|expr| expr.as_expr(&BUILTIN_FUNCTIONS))
+            .collect();
+        Self::order_non_equi_conditions(conditions)
+            .into_iter()
             .try_reduce(|lhs, rhs| {
                 check_function(None, "and_filters", &[], &[lhs, rhs], &BUILTIN_FUNCTIONS)
             })
     }
+
+    /// Orders the non-equi conditions from the cheapest, the conditions are evaluated in the
+    /// order and short-circuited, the rows failing a condition are not evaluated by the rest.
+    ///
+    /// The declared order is kept if any condition can't be reordered, e.g. `b <> 0` must be
+    /// evaluated before `a / b > 1`.
+    pub fn order_non_equi_conditions(mut conditions: Vec<Expr>) -> Vec<Expr> {
+        if conditions.iter().all(SelectExprBuilder::can_reorder) {
+            conditions.sort_by_cached_key(Self::estimated_cost);
+        }
+        conditions
+    }
+
+    // The functions on strings and the nested types are weighted heavier than the others.
+    fn estimated_cost(expr: &Expr) -> usize {
+        let args = match expr {
+            Expr::Constant { .. } | Expr::ColumnRef { .. } => return 0,
+            Expr::Cast { expr, .. } => return 1 + Self::estimated_cost(expr),
+            Expr::FunctionCall { args, .. } | Expr::LambdaFunctionCall { args, .. } => args,
+        };
+        let heavy = args.iter().any(|arg| {
+            matches!(
+                arg.data_type().remove_nullable(),
+                DataType::String
+                    | DataType::Variant
+                    | DataType::Array(_)
+                    | DataType::Map(_)
+                    | DataType::Tuple(_)
+            )
+        });
+        let cost = if heavy { 10 } else { 1 };
+        cost + args.iter().map(Self::estimated_cost).sum::<usize>()
+    }
 }
@@ -73,6 +73,7 @@ use crate::pipelines::processors::transforms::hash_join::FixedKeyHashJoinHashTab
 use crate::pipelines::processors::transforms::hash_join::HashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::SerializerHashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::SingleBinaryHashJoinHashTable;
+use crate::pipelines::processors::HashJoinDesc;
 use crate::pipelines::processors::HashJoinState;
 use crate::sessions::QueryContext;
 
@@ -115,6 +116,10 @@ pub struct HashJoinBuildState {
     pub(crate) global_memory_threshold: usize,
     /// Max memory usage threshold for each processor.
     pub(crate) processor_memory_threshold: usize,
+    /// Max memory usage of the build side when spilling is disabled, it's `max_memory_usage`.
+    pub(crate) build_memory_limit: usize,
+    /// The memory size of the input data blocks added by all build processors.
+    pub(crate) build_memory_size: AtomicUsize,
 
     /// Runtime filter related states
     pub(crate) enable_inlist_runtime_filter: bool,
@@ -162,6 +167,12 @@ impl HashJoinBuildState {
         let chunk_size_limit = settings.get_max_block_size()? as usize * 16;
         let (global_memory_threshold, processor_memory_threshold) =
             Self::get_memory_threshold(ctx.clone(), num_threads)?;
+        // The hash tables kept by the session for the later statements are charged to the limit.
+        let build_memory_limit = match settings.get_max_memory_usage()? {
+            0 => usize::MAX,
+            max_memory_usage => (max_memory_usage as usize)
+                .saturating_sub(ctx.get_hash_join_build_cache_memory_size()),
+        };
 
         Ok(Arc::new(Self {
             ctx: ctx.clone(),
@@ -181,6 +192,8 @@ impl HashJoinBuildState {
             mutex: Default::default(),
             global_memory_threshold,
             processor_memory_threshold,
+            build_memory_limit,
+            build_memory_size: AtomicUsize::new(0),
             enable_bloom_runtime_filter,
             enable_inlist_runtime_filter,
             enable_min_max_runtime_filter,
@@ -214,6 +227,8 @@ impl HashJoinBuildState {
 
     /// Add input `DataBlock` to `hash_join_state.row_space`.
     pub fn build(&self, input: DataBlock) -> Result<()> {
+        self.check_memory_limit(input.memory_size())?;
+
         let mut buffer = self.hash_join_state.row_space.buffer.write();
 
         let input_rows = input.num_rows();
@@ -242,6 +257,27 @@ impl HashJoinBuildState {
         self.add_build_block(data_block)
     }
 
+    // Without spilling, the whole build side is kept in memory, so the query fails before it
+    // exceeds `max_memory_usage` instead of running out of memory.
+    fn check_memory_limit(&self, memory_size: usize) -> Result<()> {
+        if self.hash_join_state.enable_spill {
+            return Ok(());
+        }
+
+        let build_memory_size = self
+            .build_memory_size
+            .fetch_add(memory_size, Ordering::Relaxed)
+            + memory_size;
+        if build_memory_size > self.build_memory_limit {
+            return Err(ErrorCode::ExceedMemoryLimit(format!(
+                "The build side of hash join uses {} bytes, which exceeds the max memory usage {} bytes. \
+                 Please enable join spilling by setting `join_spilling_memory_ratio`, or increase `max_memory_usage`",
+                build_memory_size, self.build_memory_limit
+            )));
+        }
+        Ok(())
+    }
+
     // Add `data_block` for build table to `row_space`
     pub(crate) fn add_build_block(&self, data_block: DataBlock) -> Result<()> {
         let block_outer_scan_map = if self.hash_join_state.need_outer_scan()
@@ -441,6 +477,11 @@ impl HashJoinBuildState {
         let mut local_raw_entry_spaces: Vec<Vec<u8>> = Vec::new();
         let hashtable = unsafe { &mut *self.hash_join_state.hash_table.get() };
         let build_state = unsafe { &mut *self.hash_join_state.build_state.get() };
+        let build_keys_unique = self
+            .hash_join_state
+            .build_keys_unique
+            .load(Ordering::Acquire);
+        let mut has_duplicate_key = false;
 
         macro_rules! insert_key {
             ($table: expr, $method: expr, $chunk: expr, $build_keys: expr, $valids: expr, $chunk_index: expr, $entry_size: expr, $local_raw_entry_spaces: expr, $t: ty,) => {{
@@ -478,7 +519,10 @@ impl HashJoinBuildState {
                                     next: 0,
                                 }
                             }
-                            $table.insert(*key, raw_entry_ptr);
+                            if !build_keys_unique || !$table.insert_unique(*key, raw_entry_ptr) {
+                                has_duplicate_key |= build_keys_unique;
+                                $table.insert(*key, raw_entry_ptr);
+                            }
                             raw_entry_ptr = unsafe { raw_entry_ptr.add(1) };
                         }
                     }
@@ -498,7 +542,10 @@ impl HashJoinBuildState {
                                     next: 0,
                                 }
                             }
-                            $table.insert(*key, raw_entry_ptr);
+                            if !build_keys_unique || !$table.insert_unique(*key, raw_entry_ptr) {
+                                has_duplicate_key |= build_keys_unique;
+                                $table.insert(*key, raw_entry_ptr);
+                            }
                             raw_entry_ptr = unsafe { raw_entry_ptr.add(1) };
                         }
                     }
@@ -561,7 +608,10 @@ impl HashJoinBuildState {
                                 string_local_space_ptr = string_local_space_ptr.add(key.len());
                             }
 
-                            $table.insert(key, raw_entry_ptr);
+                            if !build_keys_unique || !$table.insert_unique(key, raw_entry_ptr) {
+                                has_duplicate_key |= build_keys_unique;
+                                $table.insert(key, raw_entry_ptr);
+                            }
                             raw_entry_ptr = unsafe { raw_entry_ptr.add(1) };
                         }
                     }
@@ -590,7 +640,10 @@ impl HashJoinBuildState {
                                 string_local_space_ptr = string_local_space_ptr.add(key.len());
                             }
 
-                            $table.insert(key, raw_entry_ptr);
+                            if !build_keys_unique || !$table.insert_unique(key, raw_entry_ptr) {
+                                has_duplicate_key |= build_keys_unique;
+                                $table.insert(key, raw_entry_ptr);
+                            }
                             raw_entry_ptr = unsafe { raw_entry_ptr.add(1) };
                         }
                     }
@@ -640,6 +693,10 @@ impl HashJoinBuildState {
                     .convert_to_full_column(expr.data_type(), chunk.num_rows()))
             })
             .collect::<Result<Vec<_>>>()?;
+        HashJoinDesc::lowercase_keys(
+            &self.hash_join_state.hash_join_desc.case_insensitive,
+            &mut keys_columns,
+        );
 
         let column_nums = chunk.num_columns();
         let mut block_entries = Vec::with_capacity(self.build_projections.len());
@@ -760,9 +817,41 @@ impl HashJoinBuildState {
             let mut raw_entry_spaces = self.raw_entry_spaces.lock();
             raw_entry_spaces.extend(local_raw_entry_spaces);
         }
+        if has_duplicate_key {
+            // The uniqueness is derived by the planner, if a key is duplicated anyway, the probe
+            // falls back to walk all the matches instead of failing the query.
+            self.hash_join_state
+                .build_keys_unique
+                .store(false, Ordering::Release);
+        }
         Ok(())
     }
 
+    /// Moves the entries of the hash table into `HashJoinState`, so the finished build outlives
+    /// this state and is reused by the later statements. Returns the memory size of the build.
+    pub(crate) fn keep_for_reuse(&self) -> usize {
+        let raw_entry_spaces = std::mem::take(&mut *self.raw_entry_spaces.lock());
+        let build_state = unsafe { &*self.hash_join_state.build_state.get() };
+        let generation_state = &build_state.generation_state;
+        // The hash table holds a pointer per slot, the slots are at least twice the rows.
+        let pointers_size = std::cmp::max(
+            (generation_state.build_num_rows * 2).next_power_of_two(),
+            1 << 10,
+        ) * std::mem::size_of::<u64>();
+        let memory_size = generation_state
+            .chunks
+            .iter()
+            .map(DataBlock::memory_size)
+            .sum::<usize>()
+            + raw_entry_spaces.iter().map(Vec::capacity).sum::<usize>()
+            + pointers_size;
+        self.hash_join_state
+            .reused_raw_entry_spaces
+            .lock()
+            .extend(raw_entry_spaces);
+        memory_size
+    }
+
     /// Get one build hash table task.
     pub fn finalize_task(&self) -> Option<usize> {
         let mut tasks = self.build_hash_table_tasks.write();
@@ -837,13 +926,15 @@ impl HashJoinBuildState {
     }
 
     fn add_runtime_filter(&self, build_chunks: &[DataBlock], build_num_rows: usize) -> Result<()> {
-        for (build_key, probe_key, table_index) in self
-            .hash_join_state
-            .hash_join_desc
+        let hash_join_desc = &self.hash_join_state.hash_join_desc;
+        // The runtime filters compare the original keys, the case-insensitive keys are skipped
+        for (build_key, probe_key, table_index) in hash_join_desc
             .build_keys
             .iter()
-            .zip(self.hash_join_state.hash_join_desc.probe_keys_rt.iter())
-            .filter_map(|(b, p)| p.as_ref().map(|(p, index)| (b, p, index)))
+            .zip(hash_join_desc.probe_keys_rt.iter())
+            .zip(hash_join_desc.case_insensitive.iter())
+            .filter(|(_, case_insensitive)| !**case_insensitive)
+            .filter_map(|((b, p), _)| p.as_ref().map(|(p, index)| (b, p, index)))
         {
             let mut runtime_filter = RuntimeFilterInfo::default();
             if self.enable_inlist_runtime_filter && build_num_rows < INLIST_RUNTIME_FILTER_THRESHOLD
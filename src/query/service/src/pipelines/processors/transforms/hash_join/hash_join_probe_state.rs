@@ -52,6 +52,7 @@ use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_NULL;
 use crate::pipelines::processors::transforms::hash_join::desc::MARKER_KIND_TRUE;
 use crate::pipelines::processors::transforms::hash_join::hash_join_state::HashJoinHashTable;
 use crate::pipelines::processors::transforms::hash_join::util::probe_schema_wrap_nullable;
+use crate::pipelines::processors::HashJoinDesc;
 use crate::pipelines::processors::HashJoinState;
 use crate::sessions::QueryContext;
 use crate::sql::planner::plans::JoinType;
@@ -197,6 +198,10 @@ impl HashJoinProbeState {
                     .convert_to_full_column(expr.data_type(), input_num_rows))
             })
             .collect::<Result<Vec<_>>>()?;
+        HashJoinDesc::lowercase_keys(
+            &self.hash_join_state.hash_join_desc.case_insensitive,
+            &mut keys_columns,
+        );
 
         if self.hash_join_state.hash_join_desc.join_type == JoinType::RightMark
             && self
@@ -220,21 +225,7 @@ impl HashJoinProbeState {
             let ty = expr.data_type();
             ty.is_nullable() || ty.is_null()
         }) {
-            let valids = keys_columns
-                .iter()
-                .zip(is_null_equal.iter().copied())
-                .filter(|(_, is_null_equal)| !is_null_equal)
-                .map(|(col, _)| col.validity())
-                .try_fold(None, |valids, (is_all_null, tmp_valids)| {
-                    if is_all_null {
-                        ControlFlow::Break(Some(Bitmap::new_constant(false, input_num_rows)))
-                    } else {
-                        ControlFlow::Continue(and_validities(valids, tmp_valids.cloned()))
-                    }
-                });
-            match valids {
-                ControlFlow::Continue(valids) | ControlFlow::Break(valids) => valids,
-            }
+            Self::keys_validity(&keys_columns, is_null_equal, input_num_rows)
         } else {
             None
         };
@@ -343,6 +334,30 @@ impl HashJoinProbeState {
         })
     }
 
+    /// Combines the validities of the keys which are not null-equal, the rows with NULL keys
+    /// can't match any build row.
+    pub(crate) fn keys_validity(
+        keys_columns: &[Column],
+        is_null_equal: &[bool],
+        num_rows: usize,
+    ) -> Option<Bitmap> {
+        let valids = keys_columns
+            .iter()
+            .zip(is_null_equal.iter().copied())
+            .filter(|(_, is_null_equal)| !is_null_equal)
+            .map(|(col, _)| col.validity())
+            .try_fold(None, |valids, (is_all_null, tmp_valids)| {
+                if is_all_null {
+                    ControlFlow::Break(Some(Bitmap::new_constant(false, num_rows)))
+                } else {
+                    ControlFlow::Continue(and_validities(valids, tmp_valids.cloned()))
+                }
+            });
+        match valids {
+            ControlFlow::Continue(valids) | ControlFlow::Break(valids) => valids,
+        }
+    }
+
     /// Checks if a join type can eliminate valids.
     pub fn check_for_eliminate_valids(
         from_correlated_subquery: bool,
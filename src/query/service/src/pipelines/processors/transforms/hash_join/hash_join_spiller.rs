@@ -44,6 +44,8 @@ pub struct HashJoinSpiller {
     /// Used for partition.
     spill_partition_bits: usize,
     hash_keys: Vec<Expr>,
+    // Whether each of the hash keys is case-insensitive, the same as the hash table.
+    case_insensitive: Vec<bool>,
     hash_method: HashMethodKind,
     /// Next restore file index, only used for cross join.
     next_restore_file: usize,
@@ -76,11 +78,13 @@ impl HashJoinSpiller {
         let spill_buffer = SpillBuffer::create(1 << spill_partition_bits, spill_buffer_threshold);
 
         let join_type = join_state.join_type();
+        let case_insensitive = join_state.hash_join_desc.case_insensitive.clone();
         Ok(Self {
             spiller,
             spill_buffer,
             spill_partition_bits,
             hash_keys,
+            case_insensitive,
             hash_method,
             join_type,
             func_ctx: ctx.get_function_context()?,
@@ -209,6 +213,7 @@ impl HashJoinSpiller {
             &self.func_ctx,
             data_block,
             &self.hash_keys,
+            &self.case_insensitive,
             &self.hash_method,
             join_type,
             self.is_build_side,
@@ -38,6 +38,7 @@ use databend_common_hashtable::HashtableKeyable;
 use databend_common_sql::plans::JoinType;
 use databend_common_sql::ColumnSet;
 use ethnum::U256;
+use parking_lot::Mutex;
 use parking_lot::RwLock;
 
 use super::merge_into_hash_join_optimization::MergeIntoState;
@@ -131,6 +132,14 @@ pub struct HashJoinState {
     pub(crate) column_map: HashMap<usize, usize>,
     // The index of the next cache block to be read.
     pub(crate) next_cache_block_index: AtomicUsize,
+
+    /// Whether the build keys are unique, it's planned by `HashJoinDesc::build_keys_unique`,
+    /// and turns false once a duplicated build key is inserted.
+    pub(crate) build_keys_unique: AtomicBool,
+
+    /// The entries of the hash table kept for the later statements, they are moved out of the
+    /// `HashJoinBuildState` which is dropped with the statement building the hash table.
+    pub(crate) reused_raw_entry_spaces: Mutex<Vec<Vec<u8>>>,
 }
 
 impl HashJoinState {
@@ -163,6 +172,7 @@ impl HashJoinState {
         } else {
             HashMap::new()
         };
+        let build_keys_unique = AtomicBool::new(hash_join_desc.build_keys_unique);
         Ok(Arc::new(HashJoinState {
             hash_table: SyncUnsafeCell::new(HashJoinHashTable::Null),
             build_watcher,
@@ -190,6 +200,8 @@ impl HashJoinState {
             },
             column_map,
             next_cache_block_index: AtomicUsize::new(0),
+            build_keys_unique,
+            reused_raw_entry_spaces: Default::default(),
         }))
     }
 
@@ -197,6 +209,22 @@ impl HashJoinState {
         self.interrupt.store(true, Ordering::Release);
     }
 
+    /// Whether the finished build can be kept for the later statements, the spilled hash table
+    /// only holds the last restored partition.
+    pub(crate) fn can_reuse_build(&self) -> bool {
+        let hash_table_type = *self.build_watcher.borrow();
+        matches!(
+            hash_table_type,
+            HashTableType::FirstRound | HashTableType::Empty
+        ) && !self.is_spill_happened.load(Ordering::Acquire)
+            && !self.need_final_scan()
+    }
+
+    /// Prepares the cached build for the probe of another statement.
+    pub(crate) fn reuse_build(&self) {
+        self.interrupt.store(false, Ordering::Release);
+    }
+
     /// Used by hash join probe processors, wait for build phase finished.
     #[async_backtrace::framed]
     pub async fn wait_build_notify(&self) -> Result<HashTableType> {
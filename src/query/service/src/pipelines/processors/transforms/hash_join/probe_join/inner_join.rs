@@ -17,14 +17,20 @@ use std::sync::atomic::Ordering;
 
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::types::ArgType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockEntry;
 use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
 use databend_common_expression::KeyAccessor;
+use databend_common_expression::Value;
 use databend_common_hashtable::HashJoinHashtableLike;
 use databend_common_hashtable::RowPtr;
 
 use crate::pipelines::processors::transforms::hash_join::build_state::BuildBlockGenerationState;
 use crate::pipelines::processors::transforms::hash_join::common::wrap_true_validity;
 use crate::pipelines::processors::transforms::hash_join::probe_state::ProbeBlockGenerationState;
+use crate::pipelines::processors::transforms::hash_join::row::build_row_id;
 use crate::pipelines::processors::transforms::hash_join::HashJoinProbeState;
 use crate::pipelines::processors::transforms::hash_join::ProbeState;
 
@@ -51,6 +57,10 @@ impl HashJoinProbeState {
         let build_indexes = &mut mutable_indexes.build_indexes;
         let build_indexes_ptr = build_indexes.as_mut_ptr();
         let pointers = probe_state.hashes.as_slice();
+        let build_keys_unique = self
+            .hash_join_state
+            .build_keys_unique
+            .load(Ordering::Acquire);
 
         // Build states.
         let build_state = unsafe { &mut *self.hash_join_state.build_state.get() };
@@ -78,8 +88,11 @@ impl HashJoinProbeState {
                 let ptr = unsafe { *pointers.get_unchecked(*idx as usize) };
 
                 // Probe hash table and fill `build_indexes`.
-                let (match_count, mut incomplete_ptr) =
-                    hash_table.next_probe(key, ptr, build_indexes_ptr, matched_idx, max_block_size);
+                let (match_count, mut incomplete_ptr) = if build_keys_unique {
+                    Self::probe_unique_key(hash_table, key, ptr, build_indexes_ptr, matched_idx)
+                } else {
+                    hash_table.next_probe(key, ptr, build_indexes_ptr, matched_idx, max_block_size)
+                };
                 if match_count == 0 {
                     continue;
                 }
@@ -124,8 +137,11 @@ impl HashJoinProbeState {
                 let ptr = unsafe { *pointers.get_unchecked(idx) };
 
                 // Probe hash table and fill `build_indexes`.
-                let (match_count, mut incomplete_ptr) =
-                    hash_table.next_probe(key, ptr, build_indexes_ptr, matched_idx, max_block_size);
+                let (match_count, mut incomplete_ptr) = if build_keys_unique {
+                    Self::probe_unique_key(hash_table, key, ptr, build_indexes_ptr, matched_idx)
+                } else {
+                    hash_table.next_probe(key, ptr, build_indexes_ptr, matched_idx, max_block_size)
+                };
                 if match_count == 0 {
                     continue;
                 }
@@ -199,6 +215,27 @@ impl HashJoinProbeState {
         }
     }
 
+    /// Each probe row matches at most one build row if the build keys are unique, so the
+    /// probe stops at the first match, the returned incomplete pointer is always zero.
+    #[inline]
+    fn probe_unique_key<H: HashJoinHashtableLike>(
+        hash_table: &H,
+        key: &H::Key,
+        ptr: u64,
+        build_indexes_ptr: *mut RowPtr,
+        matched_idx: usize,
+    ) -> (usize, u64) {
+        match hash_table.next_first(key, ptr) {
+            Some(row_ptr) => {
+                // # Safety
+                // `matched_idx` is less than the capacity of `build_indexes`.
+                unsafe { *build_indexes_ptr.add(matched_idx) = row_ptr };
+                (1, 0)
+            }
+            None => (0, 0),
+        }
+    }
+
     #[inline]
     fn process_inner_join_block<const FROM_RIGHT_SINGLE: bool>(
         &self,
@@ -256,6 +293,17 @@ impl HashJoinProbeState {
             }
         }
 
+        if self.hash_join_state.hash_join_desc.emit_build_row_id {
+            let build_row_ids = build_indexes[0..matched_idx]
+                .iter()
+                .map(build_row_id)
+                .collect::<Vec<_>>();
+            result_block.add_column(BlockEntry::new(
+                UInt64Type::data_type(),
+                Value::Column(UInt64Type::from_data(build_row_ids)),
+            ));
+        }
+
         if FROM_RIGHT_SINGLE {
             self.update_right_single_scan_map(
                 &build_indexes[0..matched_idx],
@@ -0,0 +1,101 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::Ordering;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::ArgType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::KeyAccessor;
+use databend_common_expression::Value;
+use databend_common_hashtable::HashJoinHashtableLike;
+
+use crate::pipelines::processors::transforms::hash_join::HashJoinProbeState;
+use crate::pipelines::processors::transforms::hash_join::ProbeState;
+
+impl HashJoinProbeState {
+    /// The left semi join that appends the number of the build rows matched by each probe
+    /// row, it's the cardinality of the semi join, so a correlated `count(*)` subquery can be
+    /// answered without emitting the joined rows. It's only used without other conditions.
+    pub(crate) fn left_semi_join_with_match_count<'a, H: HashJoinHashtableLike>(
+        &self,
+        input: &DataBlock,
+        keys: Box<(dyn KeyAccessor<Key = H::Key>)>,
+        hash_table: &H,
+        probe_state: &mut ProbeState,
+    ) -> Result<Vec<DataBlock>>
+    where
+        H::Key: 'a,
+    {
+        // Probe states.
+        let mutable_indexes = &mut probe_state.mutable_indexes;
+        let probe_indexes = &mut mutable_indexes.probe_indexes;
+        let pointers = probe_state.hashes.as_slice();
+
+        // Results.
+        let mut matched_idx = 0;
+        let mut match_counts = Vec::new();
+
+        // Probe hash table and count the matched build rows.
+        if probe_state.probe_with_selection {
+            let selection = &probe_state.selection.as_slice()[0..probe_state.selection_count];
+            for idx in selection.iter() {
+                let key = unsafe { keys.key_unchecked(*idx as usize) };
+                let ptr = unsafe { *pointers.get_unchecked(*idx as usize) };
+                let match_count = hash_table.next_count(key, ptr);
+                if match_count > 0 {
+                    unsafe { *probe_indexes.get_unchecked_mut(matched_idx) = *idx };
+                    matched_idx += 1;
+                    match_counts.push(match_count as u64);
+                }
+            }
+        } else {
+            for idx in 0..input.num_rows() {
+                let key = unsafe { keys.key_unchecked(idx) };
+                let ptr = unsafe { *pointers.get_unchecked(idx) };
+                let match_count = hash_table.next_count(key, ptr);
+                if match_count > 0 {
+                    unsafe { *probe_indexes.get_unchecked_mut(matched_idx) = idx as u32 };
+                    matched_idx += 1;
+                    match_counts.push(match_count as u64);
+                }
+            }
+        }
+
+        if self.hash_join_state.interrupt.load(Ordering::Relaxed) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
+        if matched_idx == 0 {
+            return Ok(vec![]);
+        }
+        let mut result_block = DataBlock::take(
+            input,
+            &probe_indexes[0..matched_idx],
+            &mut probe_state.generation_state.string_items_buf,
+        )?;
+        // The match count is the last column, see `MATCH_COUNT_COL_NAME`.
+        result_block.add_column(BlockEntry::new(
+            UInt64Type::data_type(),
+            Value::Column(UInt64Type::from_data(match_counts)),
+        ));
+        Ok(vec![result_block])
+    }
+}
@@ -18,6 +18,7 @@ mod left_anti_join;
 mod left_join;
 mod left_mark_join;
 mod left_semi_join;
+mod match_count;
 mod right_join;
 mod right_mark_join;
 mod right_semi_anti_join;
@@ -80,7 +80,9 @@ impl HashJoinProbeState {
                 }
             }
             JoinType::LeftSemi => {
-                if no_other_predicate {
+                if self.hash_join_state.hash_join_desc.emit_match_count {
+                    self.left_semi_join_with_match_count(input, keys, hash_table, probe_state)
+                } else if no_other_predicate {
                     self.left_semi_join(input, keys, hash_table, probe_state)
                 } else {
                     self.left_semi_join_with_conjunct(input, keys, hash_table, probe_state)
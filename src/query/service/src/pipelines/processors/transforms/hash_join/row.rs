@@ -87,3 +87,15 @@ impl RowSpace {
         self.buffer_row_size.store(0, Ordering::Release);
     }
 }
+
+/// The `build_row_id` of a matched build row, the index of its chunk is in the high 32 bits
+/// and the index of the row in the chunk is in the low 32 bits. The ids are only valid for
+/// the chunks of the current build.
+pub fn build_row_id(row_ptr: &RowPtr) -> u64 {
+    ((row_ptr.chunk_index as u64) << 32) | row_ptr.row_index as u64
+}
+
+/// The build row identified by the `build_row_id`.
+pub fn build_row_ptr(build_row_id: u64) -> RowPtr {
+    RowPtr::new((build_row_id >> 32) as u32, build_row_id as u32)
+}
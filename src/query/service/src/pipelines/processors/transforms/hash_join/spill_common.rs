@@ -26,11 +26,13 @@ use databend_common_sql::plans::JoinType;
 
 use crate::pipelines::processors::transforms::hash_join::common::wrap_true_validity;
 use crate::pipelines::processors::transforms::hash_join::util::hash_by_method;
+use crate::pipelines::processors::HashJoinDesc;
 
 pub fn get_hashes(
     func_ctx: &FunctionContext,
     block: &DataBlock,
     keys: &[Expr],
+    case_insensitive: &[bool],
     method: &HashMethodKind,
     join_type: &JoinType,
     from_build: bool,
@@ -56,7 +58,7 @@ pub fn get_hashes(
 
     let evaluator = Evaluator::new(&block, func_ctx, &BUILTIN_FUNCTIONS);
     // When chose hash method, the keys are removed nullable, so we need to remove nullable here.
-    let columns = keys
+    let mut columns = keys
         .iter()
         .map(|expr| {
             let column = evaluator
@@ -69,6 +71,7 @@ pub fn get_hashes(
             }
         })
         .collect::<Result<Vec<_>>>()?;
+    HashJoinDesc::lowercase_keys(case_insensitive, &mut columns);
     hash_by_method(method, (&columns).into(), block.num_rows(), hashes)?;
     Ok(())
 }
@@ -24,20 +24,27 @@ mod transform_async_function;
 mod transform_cache_scan;
 mod transform_cast_schema;
 mod transform_create_sets;
+mod transform_distinct_sorted;
 mod transform_expression_scan;
+mod transform_fill_null;
 mod transform_filter;
 mod transform_limit;
 mod transform_materialized_cte;
 mod transform_merge_block;
 mod transform_null_if;
+mod transform_project;
 mod transform_recursive_cte_scan;
 mod transform_recursive_cte_source;
+mod transform_repartition;
 mod transform_resort_addon;
 mod transform_resort_addon_without_source_schema;
+mod transform_running_total;
 mod transform_sort_spill;
 mod transform_srf;
 mod transform_udf_script;
 mod transform_udf_server;
+mod transform_union_distinct;
+mod transform_unnest;
 mod window;
 
 pub use hash_join::*;
@@ -49,9 +56,12 @@ pub use transform_async_function::TransformAsyncFunction;
 pub use transform_cache_scan::CacheSourceState;
 pub use transform_cache_scan::HashJoinCacheState;
 pub use transform_cache_scan::TransformCacheScan;
+pub use transform_cast_schema::CastErrorPolicy;
 pub use transform_cast_schema::TransformCastSchema;
 pub use transform_create_sets::TransformCreateSets;
+pub use transform_distinct_sorted::TransformDistinctSorted;
 pub use transform_expression_scan::TransformExpressionScan;
+pub use transform_fill_null::TransformFillNull;
 pub use transform_filter::TransformFilter;
 pub use transform_limit::TransformLimit;
 pub use transform_materialized_cte::MaterializedCteSink;
@@ -59,12 +69,19 @@ pub use transform_materialized_cte::MaterializedCteSource;
 pub use transform_materialized_cte::MaterializedCteState;
 pub use transform_merge_block::TransformMergeBlock;
 pub use transform_null_if::TransformNullIf;
+pub use transform_project::TransformProject;
 pub use transform_recursive_cte_scan::TransformRecursiveCteScan;
 pub use transform_recursive_cte_source::TransformRecursiveCteSource;
+pub use transform_repartition::build_repartition;
+pub use transform_repartition::TransformRepartition;
 pub use transform_resort_addon::TransformResortAddOn;
 pub use transform_resort_addon_without_source_schema::TransformResortAddOnWithoutSourceSchema;
+pub use transform_running_total::RunningTotalKind;
+pub use transform_running_total::TransformRunningTotal;
 pub use transform_sort_spill::create_transform_sort_spill;
 pub use transform_srf::TransformSRF;
 pub use transform_udf_script::TransformUdfScript;
 pub use transform_udf_server::TransformUdfServer;
+pub use transform_union_distinct::TransformUnionDistinct;
+pub use transform_unnest::TransformUnnest;
 pub use window::*;
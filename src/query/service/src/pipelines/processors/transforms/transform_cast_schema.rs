@@ -30,6 +30,16 @@ use crate::pipelines::processors::InputPort;
 use crate::pipelines::processors::OutputPort;
 use crate::pipelines::processors::ProcessorPtr;
 
+/// How to handle the values which can't be casted to the target type,
+/// e.g. narrowing an integer or an out-of-range timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastErrorPolicy {
+    /// Fail the whole block.
+    Strict,
+    /// Produce NULL for the failed values, the output column becomes nullable.
+    NullOnError,
+}
+
 pub struct TransformCastSchema {
     func_ctx: FunctionContext,
     insert_schema: DataSchemaRef,
@@ -44,6 +54,7 @@ where Self: Transform
         select_schema: DataSchemaRef,
         insert_schema: DataSchemaRef,
         func_ctx: FunctionContext,
+        error_policy: CastErrorPolicy,
     ) -> Result<Self> {
         let exprs = select_schema
             .fields()
@@ -56,7 +67,8 @@ where Self: Transform
                     data_type: from.data_type().clone(),
                     display_name: from.name().clone(),
                 };
-                check_cast(None, false, expr, to.data_type(), &BUILTIN_FUNCTIONS)
+                let is_try = error_policy == CastErrorPolicy::NullOnError;
+                check_cast(None, is_try, expr, to.data_type(), &BUILTIN_FUNCTIONS)
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -74,8 +86,9 @@ where Self: Transform
         select_schema: DataSchemaRef,
         insert_schema: DataSchemaRef,
         func_ctx: FunctionContext,
+        error_policy: CastErrorPolicy,
     ) -> Result<ProcessorPtr> {
-        let me = Self::try_new(select_schema, insert_schema, func_ctx)?;
+        let me = Self::try_new(select_schema, insert_schema, func_ctx, error_policy)?;
         Ok(ProcessorPtr::create(Transformer::create(
             input_port,
             output_port,
@@ -107,7 +120,7 @@ impl Transform for TransformCastSchema {
                 );
                 err.add_message(msg)
             })?;
-            let column = BlockEntry::new(field.data_type().clone(), value);
+            let column = BlockEntry::new(expr.data_type().clone(), value);
             columns.push(column);
         }
         Ok(DataBlock::new(columns, data_block.num_rows()))
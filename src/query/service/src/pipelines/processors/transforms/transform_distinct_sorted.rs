@@ -0,0 +1,99 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_arrow::arrow::bitmap::MutableBitmap;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Scalar;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+
+/// Removes the duplicate rows of the input sorted by the distinct keys.
+///
+/// The equal rows are adjacent in the sorted input, so only the keys of the last row are
+/// kept to compare with the next block, instead of the hash set of all the distinct rows.
+pub struct TransformDistinctSorted {
+    // The offsets of the distinct keys.
+    keys: Vec<usize>,
+    // The keys of the last row of the previous block.
+    last_row: Option<Vec<Scalar>>,
+}
+
+impl TransformDistinctSorted
+where Self: Transform
+{
+    pub fn new(keys: Vec<usize>) -> Self {
+        Self {
+            keys,
+            last_row: None,
+        }
+    }
+
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        keys: Vec<usize>,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(
+            input_port,
+            output_port,
+            Self::new(keys),
+        ))
+    }
+}
+
+impl Transform for TransformDistinctSorted {
+    const NAME: &'static str = "DistinctSortedTransform";
+
+    fn transform(&mut self, data_block: DataBlock) -> Result<DataBlock> {
+        let num_rows = data_block.num_rows();
+        if num_rows == 0 {
+            return Ok(data_block);
+        }
+
+        let keys = self
+            .keys
+            .iter()
+            .map(|offset| &data_block.get_by_offset(*offset).value)
+            .collect::<Vec<_>>();
+
+        let mut bitmap = MutableBitmap::with_capacity(num_rows);
+        bitmap.push(match &self.last_row {
+            Some(last_row) => keys
+                .iter()
+                .zip(last_row.iter())
+                .any(|(key, last)| key.index(0).unwrap() != last.as_ref()),
+            None => true,
+        });
+        for row in 1..num_rows {
+            bitmap.push(
+                keys.iter()
+                    .any(|key| key.index(row).unwrap() != key.index(row - 1).unwrap()),
+            );
+        }
+
+        self.last_row = Some(
+            keys.iter()
+                .map(|key| key.index(num_rows - 1).unwrap().to_owned())
+                .collect(),
+        );
+        data_block.filter_with_bitmap(&bitmap.into())
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use databend_common_exception::Result;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::types::NullableColumn;
+use databend_common_expression::types::NumberColumn;
+use databend_common_expression::types::NumberType;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::with_number_mapped_type;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+
+/// Replaces the NULLs of the columns with constant defaults, the same as `coalesce(x, default)`.
+///
+/// Instead of evaluating the expression row by row, the values masked by the validity
+/// bitmap are filled in bulk, and the columns become non-nullable.
+pub struct TransformFillNull {
+    // The offset of the column and the default of its NULLs.
+    defaults: HashMap<usize, Scalar>,
+}
+
+impl TransformFillNull
+where Self: Transform
+{
+    pub fn new(defaults: HashMap<usize, Scalar>) -> Self {
+        Self { defaults }
+    }
+
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        defaults: HashMap<usize, Scalar>,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(
+            input_port,
+            output_port,
+            Self::new(defaults),
+        ))
+    }
+
+    /// Fills the NULLs of the column with the default, which must be of the inner type.
+    pub fn fill_null(column: &NullableColumn<AnyType>, default: &Scalar) -> Column {
+        let NullableColumn { column, validity } = column;
+        if validity.unset_bits() == 0 {
+            return column.clone();
+        }
+
+        with_number_mapped_type!(|NUM| match column {
+            Column::Number(NumberColumn::NUM(values)) => {
+                if let Some(default) = NumberType::<NUM>::try_downcast_scalar(&default.as_ref()) {
+                    let values = values
+                        .iter()
+                        .zip(validity.iter())
+                        .map(|(value, is_valid)| if is_valid { *value } else { default })
+                        .collect::<Vec<_>>();
+                    return NumberType::<NUM>::upcast_column(values.into());
+                }
+            }
+            Column::Boolean(values) => {
+                if let Some(default) = default.as_boolean() {
+                    let values = if *default {
+                        values | &(!validity)
+                    } else {
+                        values & validity
+                    };
+                    return Column::Boolean(values);
+                }
+            }
+            _ => {}
+        });
+
+        let mut builder = ColumnBuilder::with_capacity(&column.data_type(), column.len());
+        for (value, is_valid) in column.iter().zip(validity.iter()) {
+            if is_valid {
+                builder.push(value);
+            } else {
+                builder.push(default.as_ref());
+            }
+        }
+        builder.build()
+    }
+
+    fn fill_entry(entry: &BlockEntry, default: &Scalar) -> BlockEntry {
+        let value = match &entry.value {
+            Value::Scalar(Scalar::Null) => Value::Scalar(default.clone()),
+            Value::Column(Column::Nullable(column)) => {
+                Value::Column(Self::fill_null(column, default))
+            }
+            value => value.clone(),
+        };
+        BlockEntry::new(entry.data_type.remove_nullable(), value)
+    }
+}
+
+impl Transform for TransformFillNull {
+    const NAME: &'static str = "FillNullTransform";
+
+    fn transform(&mut self, data_block: DataBlock) -> Result<DataBlock> {
+        let columns = data_block
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(offset, entry)| match self.defaults.get(&offset) {
+                Some(default) if !default.is_null() => Self::fill_entry(entry, default),
+                _ => entry.clone(),
+            })
+            .collect();
+        Ok(DataBlock::new(columns, data_block.num_rows()))
+    }
+}
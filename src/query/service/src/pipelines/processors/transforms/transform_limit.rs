@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use databend_common_exception::Result;
 use databend_common_expression::DataBlock;
+use databend_common_expression::Scalar;
 
 use crate::pipelines::processors::Event;
 use crate::pipelines::processors::InputPort;
@@ -26,12 +27,22 @@ use crate::pipelines::processors::Processor;
 pub struct TransformLimit;
 
 impl TransformLimit {
+    /// Creates the transform of `LIMIT` and `OFFSET`, the rows are skipped and taken
+    /// in a streaming fashion. If `ties_offsets` is not empty, the rows tying with the
+    /// last taken row on these sort key columns are also emitted.
     pub fn try_create(
         limit: Option<usize>,
         offset: usize,
+        ties_offsets: Vec<usize>,
         input: Arc<InputPort>,
         output: Arc<OutputPort>,
     ) -> Result<Box<dyn Processor>> {
+        if let Some(limit) = limit {
+            if !ties_offsets.is_empty() {
+                return LimitWithTiesTransform::create(input, output, limit, offset, ties_offsets);
+            }
+        }
+
         match (limit, offset) {
             (Some(_), 0) => OnlyLimitTransform::create(input, output, limit, offset),
             (None, _) => OnlyOffsetTransform::create(input, output, limit, offset),
@@ -202,3 +213,173 @@ impl<const MODE: usize> Processor for TransformLimitImpl<MODE> {
         Ok(())
     }
 }
+
+/// The transform of `OFFSET m FETCH FIRST n ROWS WITH TIES`, the input must be sorted.
+///
+/// After `n` rows are taken, the sort keys of the last one are kept, and the following
+/// rows are emitted until one of them does not tie with it.
+struct LimitWithTiesTransform {
+    take_remaining: usize,
+    skip_remaining: usize,
+    ties_offsets: Vec<usize>,
+    // The sort keys of the last taken row, `None` if the ties are not started or finished.
+    last_keys: Option<Vec<Scalar>>,
+
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+
+    input_data_block: Option<DataBlock>,
+    output_data_block: Option<DataBlock>,
+}
+
+impl LimitWithTiesTransform {
+    pub fn create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        limit: usize,
+        offset: usize,
+        ties_offsets: Vec<usize>,
+    ) -> Result<Box<dyn Processor>> {
+        Ok(Box::new(Self {
+            input,
+            output,
+            input_data_block: None,
+            output_data_block: None,
+            skip_remaining: offset,
+            take_remaining: limit,
+            ties_offsets,
+            last_keys: None,
+        }))
+    }
+
+    fn is_finished(&self) -> bool {
+        self.skip_remaining == 0 && self.take_remaining == 0 && self.last_keys.is_none()
+    }
+
+    fn sort_keys(&self, data_block: &DataBlock, row: usize) -> Vec<Scalar> {
+        self.ties_offsets
+            .iter()
+            .map(|offset| {
+                let value = &data_block.get_by_offset(*offset).value;
+                value.index(row).unwrap().to_owned()
+            })
+            .collect()
+    }
+
+    /// Returns the number of rows tying with the last taken row from `start`.
+    fn count_ties(&self, data_block: &DataBlock, start: usize) -> usize {
+        let last_keys = self.last_keys.as_ref().unwrap();
+        (start..data_block.num_rows())
+            .take_while(|row| {
+                self.ties_offsets
+                    .iter()
+                    .zip(last_keys.iter())
+                    .all(|(offset, key)| {
+                        let value = &data_block.get_by_offset(*offset).value;
+                        value.index(*row).unwrap() == key.as_ref()
+                    })
+            })
+            .count()
+    }
+
+    fn take_rows(&mut self, data_block: DataBlock) -> Option<DataBlock> {
+        let rows = data_block.num_rows();
+
+        if self.skip_remaining >= rows {
+            self.skip_remaining -= rows;
+            return None;
+        }
+
+        let offset = self.skip_remaining;
+        self.skip_remaining = 0;
+
+        if self.take_remaining == 0 {
+            // `FETCH FIRST 0 ROWS WITH TIES` takes no row, so there are no ties either.
+            if self.last_keys.is_none() {
+                return None;
+            }
+            // Emit the rows tying with the last taken row.
+            let ties = self.count_ties(&data_block, offset);
+            if offset + ties < rows {
+                self.last_keys = None;
+            }
+            return (ties != 0).then(|| data_block.slice(offset..(offset + ties)));
+        }
+
+        if self.take_remaining >= rows - offset {
+            self.take_remaining -= rows - offset;
+            if self.take_remaining == 0 {
+                self.last_keys = Some(self.sort_keys(&data_block, rows - 1));
+            }
+            return Some(data_block.slice(offset..rows));
+        }
+
+        let end = offset + self.take_remaining;
+        self.take_remaining = 0;
+        self.last_keys = Some(self.sort_keys(&data_block, end - 1));
+        let ties = self.count_ties(&data_block, end);
+        if end + ties < rows {
+            self.last_keys = None;
+        }
+        Some(data_block.slice(offset..(end + ties)))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for LimitWithTiesTransform {
+    fn name(&self) -> String {
+        "LimitWithTiesTransform".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data_block.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.is_finished() {
+            self.input.finish();
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.input_data_block.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.input.has_data() {
+            self.input.set_need_data();
+            return Ok(Event::NeedData);
+        }
+
+        self.input_data_block = Some(self.input.pull_data().unwrap()?);
+        Ok(Event::Sync)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data_block.take() {
+            self.output_data_block = self.take_rows(data_block);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+
+/// Keeps the columns of the projection in its order and drops the others, so the columns
+/// not needed downstream are released early.
+///
+/// The kept columns share the buffers with the input block, nothing is copied.
+pub struct TransformProject {
+    projection: Vec<usize>,
+}
+
+impl TransformProject
+where Self: Transform
+{
+    pub fn new(projection: Vec<usize>) -> Self {
+        Self { projection }
+    }
+
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        projection: Vec<usize>,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(
+            input_port,
+            output_port,
+            Self::new(projection),
+        ))
+    }
+}
+
+impl Transform for TransformProject {
+    const NAME: &'static str = "ProjectTransform";
+
+    fn transform(&mut self, mut data_block: DataBlock) -> Result<DataBlock> {
+        let num_columns = data_block.num_columns();
+        let meta = data_block.take_meta();
+        let columns = self
+            .projection
+            .iter()
+            .map(|&offset| {
+                data_block.columns().get(offset).cloned().ok_or_else(|| {
+                    ErrorCode::Internal(format!(
+                        "Projection offset {offset} is out of the {num_columns} columns of the block"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataBlock::new_with_meta(
+            columns,
+            data_block.num_rows(),
+            meta,
+        ))
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::group_hash_columns_slice;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Value;
+use databend_common_pipeline_core::processors::Event;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::OutputPort;
+use databend_common_pipeline_core::processors::Processor;
+use databend_common_pipeline_core::processors::ProcessorPtr;
+use databend_common_pipeline_core::Pipe;
+use databend_common_pipeline_core::PipeItem;
+use databend_common_pipeline_core::Pipeline;
+
+/// Routes the rows to the output ports by the hash of the key columns mod the number of
+/// outputs, so the rows with equal keys always land in the same partition.
+///
+/// The keys are hashed the same as the groups of the aggregation.
+pub struct TransformRepartition {
+    input: Arc<InputPort>,
+    outputs: Vec<Arc<OutputPort>>,
+    hash_keys: Vec<usize>,
+
+    input_data: Option<DataBlock>,
+    output_data: Vec<Option<DataBlock>>,
+}
+
+impl TransformRepartition {
+    pub fn create(hash_keys: Vec<usize>, partitions: usize) -> Self {
+        TransformRepartition {
+            input: InputPort::create(),
+            outputs: (0..partitions).map(|_| OutputPort::create()).collect(),
+            hash_keys,
+            input_data: None,
+            output_data: vec![None; partitions],
+        }
+    }
+
+    pub fn get_input(&self) -> Arc<InputPort> {
+        self.input.clone()
+    }
+
+    pub fn get_outputs(&self) -> Vec<Arc<OutputPort>> {
+        self.outputs.clone()
+    }
+
+    /// Computes the partition of each row.
+    pub fn partition_indices(
+        block: &DataBlock,
+        hash_keys: &[usize],
+        partitions: usize,
+    ) -> Vec<u64> {
+        let num_rows = block.num_rows();
+        if hash_keys.is_empty() {
+            return vec![0; num_rows];
+        }
+
+        let hash_cols = hash_keys
+            .iter()
+            .map(|&offset| {
+                let entry = block.get_by_offset(offset);
+                match &entry.value {
+                    Value::Scalar(s) => {
+                        ColumnBuilder::repeat(&s.as_ref(), num_rows, &entry.data_type).build()
+                    }
+                    Value::Column(c) => c.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut hashes = vec![0u64; num_rows];
+        group_hash_columns_slice(&hash_cols, &mut hashes);
+        hashes
+            .iter_mut()
+            .for_each(|hash| *hash %= partitions as u64);
+        hashes
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformRepartition {
+    fn name(&self) -> String {
+        String::from("TransformRepartition")
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.outputs.iter().all(|output| output.is_finished()) {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        let mut all_pushed = true;
+        for (output, data) in self.outputs.iter().zip(self.output_data.iter_mut()) {
+            if output.is_finished() {
+                data.take();
+            } else if data.is_some() {
+                if output.can_push() {
+                    output.push_data(Ok(data.take().unwrap()));
+                } else {
+                    all_pushed = false;
+                }
+            }
+        }
+
+        if !all_pushed {
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.outputs.iter().for_each(|output| output.finish());
+            return Ok(Event::Finished);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(block) = self.input_data.take() {
+            let partitions = self.outputs.len();
+            let indices = Self::partition_indices(&block, &self.hash_keys, partitions);
+            let blocks = DataBlock::scatter(&block, &indices, partitions)?;
+            for (data, block) in self.output_data.iter_mut().zip(blocks) {
+                if !block.is_empty() {
+                    *data = Some(block);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Repartitions the outputs of the pipeline into `partitions` outputs by the hash keys,
+/// the output `i` receives the rows of the partition `i` from every upstream output.
+pub fn build_repartition(
+    pipeline: &mut Pipeline,
+    hash_keys: Vec<usize>,
+    partitions: usize,
+) -> Result<()> {
+    if partitions == 0 {
+        return Err(ErrorCode::Internal(
+            "The number of partitions must be greater than 0.",
+        ));
+    }
+
+    let upstreams = pipeline.output_len();
+    let mut items = Vec::with_capacity(upstreams);
+    for _ in 0..upstreams {
+        let transform = TransformRepartition::create(hash_keys.clone(), partitions);
+        let input = transform.get_input();
+        let outputs = transform.get_outputs();
+        items.push(PipeItem::create(
+            ProcessorPtr::create(Box::new(transform)),
+            vec![input],
+            outputs,
+        ));
+    }
+    pipeline.add_pipe(Pipe::create(upstreams, upstreams * partitions, items));
+
+    // The output `j` of the upstream `i` is moved to `j * upstreams + i`, then the ports of
+    // each partition are adjacent and merged into one.
+    let rule = (0..upstreams * partitions)
+        .map(|index| (index % partitions) * upstreams + index / partitions)
+        .collect();
+    pipeline.reorder_inputs(rule);
+    pipeline.resize_partial_one_with_width(vec![upstreams; partitions])
+}
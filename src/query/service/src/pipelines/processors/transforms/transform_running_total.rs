@@ -0,0 +1,180 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::F64;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_expression::Value;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunningTotalKind {
+    Sum,
+    Count,
+    Min,
+    Max,
+}
+
+/// Appends the running total of the column, the aggregate of the rows from the first one to
+/// the current one in the arrival order, the same as a window function of
+/// `ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW` over the input already in order.
+///
+/// The accumulator is carried across the blocks, so nothing is buffered or sorted. The NULLs
+/// are skipped, the total is NULL until the first non-NULL value, except the count.
+pub struct TransformRunningTotal {
+    // The offset of the aggregated column.
+    offset: usize,
+    kind: RunningTotalKind,
+    // The total of the rows so far, it's None before the first non-NULL value.
+    total: Option<Scalar>,
+}
+
+impl TransformRunningTotal
+where Self: Transform
+{
+    pub fn new(offset: usize, kind: RunningTotalKind) -> Self {
+        Self {
+            offset,
+            kind,
+            total: None,
+        }
+    }
+
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        offset: usize,
+        kind: RunningTotalKind,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(
+            input_port,
+            output_port,
+            Self::new(offset, kind),
+        ))
+    }
+
+    /// The type of the running total of the column type.
+    pub fn return_type(data_type: &DataType, kind: RunningTotalKind) -> Result<DataType> {
+        let return_type = match (kind, data_type.remove_nullable()) {
+            (RunningTotalKind::Count, _) => return Ok(DataType::Number(NumberDataType::UInt64)),
+            (RunningTotalKind::Min | RunningTotalKind::Max, _) => return Ok(data_type.clone()),
+            (RunningTotalKind::Sum, DataType::Number(ty)) if ty.is_float() => {
+                DataType::Number(NumberDataType::Float64)
+            }
+            (RunningTotalKind::Sum, DataType::Number(ty)) if ty.is_signed() => {
+                DataType::Number(NumberDataType::Int64)
+            }
+            (RunningTotalKind::Sum, DataType::Number(_)) => {
+                DataType::Number(NumberDataType::UInt64)
+            }
+            (RunningTotalKind::Sum, ty) => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "Running sum expects a number column, but got {ty}"
+                )));
+            }
+        };
+        if data_type.is_nullable_or_null() {
+            Ok(return_type.wrap_nullable())
+        } else {
+            Ok(return_type)
+        }
+    }
+
+    fn accumulate(&self, value: ScalarRef, return_type: &DataType) -> Result<Scalar> {
+        let total = self.total.as_ref().map(|total| total.as_ref());
+        let total = match (self.kind, total) {
+            (RunningTotalKind::Count, total) => {
+                let count = total.and_then(|t| t.as_number().cloned());
+                let count = count.and_then(|n| n.integer_to_i128()).unwrap_or(0);
+                Scalar::Number(NumberScalar::UInt64(count as u64 + 1))
+            }
+            (RunningTotalKind::Min, Some(total)) if total <= value => total.to_owned(),
+            (RunningTotalKind::Max, Some(total)) if total >= value => total.to_owned(),
+            (RunningTotalKind::Min | RunningTotalKind::Max, _) => value.to_owned(),
+            (RunningTotalKind::Sum, total) => {
+                let value = value.as_number().unwrap();
+                let total = total.and_then(|t| t.as_number().cloned());
+                Scalar::Number(Self::add(value, total.as_ref(), return_type)?)
+            }
+        };
+        Ok(total)
+    }
+
+    fn add(
+        value: &NumberScalar,
+        total: Option<&NumberScalar>,
+        return_type: &DataType,
+    ) -> Result<NumberScalar> {
+        if let Some(value) = value.float_to_f64() {
+            let total = total.and_then(|t| t.float_to_f64()).unwrap_or(0.0);
+            return Ok(NumberScalar::Float64(F64::from(total + value)));
+        }
+
+        let value = value.integer_to_i128().unwrap();
+        let total = total.and_then(|t| t.integer_to_i128()).unwrap_or(0);
+        let sum = total + value;
+        let sum = match return_type.remove_nullable() {
+            DataType::Number(NumberDataType::Int64) => i64::try_from(sum).map(NumberScalar::Int64),
+            _ => u64::try_from(sum).map(NumberScalar::UInt64),
+        };
+        sum.map_err(|_| {
+            ErrorCode::Overflow(format!("Running sum overflows the type {return_type}"))
+        })
+    }
+}
+
+impl Transform for TransformRunningTotal {
+    const NAME: &'static str = "RunningTotalTransform";
+
+    fn transform(&mut self, mut data_block: DataBlock) -> Result<DataBlock> {
+        let num_rows = data_block.num_rows();
+        let entry = data_block.get_by_offset(self.offset);
+        let return_type = Self::return_type(&entry.data_type, self.kind)?;
+        let column = entry
+            .value
+            .convert_to_full_column(&entry.data_type, num_rows);
+
+        let mut builder = ColumnBuilder::with_capacity(&return_type, num_rows);
+        for value in column.iter() {
+            if !value.is_null() {
+                self.total = Some(self.accumulate(value, &return_type)?);
+            }
+            match &self.total {
+                Some(total) => builder.push(total.as_ref()),
+                None if self.kind == RunningTotalKind::Count => {
+                    builder.push(ScalarRef::Number(NumberScalar::UInt64(0)))
+                }
+                None => builder.push_default(),
+            }
+        }
+
+        data_block.add_column(BlockEntry::new(return_type, Value::Column(builder.build())));
+        Ok(data_block)
+    }
+}
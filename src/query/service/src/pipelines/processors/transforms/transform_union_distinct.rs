@@ -0,0 +1,306 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::AggregateHashTable;
+use databend_common_expression::DataBlock;
+use databend_common_expression::HashTableConfig;
+use databend_common_expression::PayloadFlushState;
+use databend_common_expression::ProbeState;
+use databend_common_pipeline_core::processors::Event;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::OutputPort;
+use databend_common_pipeline_core::processors::Processor;
+
+use crate::pipelines::processors::transforms::TransformRepartition;
+use crate::spillers::Spiller;
+
+// The spilled rows are scattered into the partitions by the hash of the whole row.
+const SPILL_PARTITIONS: usize = 8;
+
+enum State {
+    /// Adds the rows of the inputs to the hash table.
+    Consume,
+    /// Spills the distinct rows of the hash table by partitions, and starts with an empty one.
+    Spill,
+    /// Outputs the distinct rows of the hash table, nothing was spilled.
+    Flush,
+    /// Restores the spilled partitions one by one and outputs their distinct rows.
+    Restore,
+    Finished,
+}
+
+/// Merges the inputs and removes the duplicate rows in one pass, the same as `UNION`.
+///
+/// The rows are the keys of an `AggregateHashTable` without aggregate functions, so each
+/// distinct row is stored once however many inputs it comes from, and output after all the
+/// inputs are finished. With a spiller, the distinct rows are spilled by partitions when the
+/// hash table grows beyond `spill_bytes`, then each partition is deduplicated on its own.
+pub struct TransformUnionDistinct {
+    inputs: Vec<Arc<InputPort>>,
+    output: Arc<OutputPort>,
+    data_types: Vec<DataType>,
+
+    hashtable: AggregateHashTable,
+    probe_state: ProbeState,
+    spiller: Option<Spiller>,
+    spill_bytes: usize,
+
+    state: State,
+    inputs_finished: bool,
+    restore_partitions: Vec<u8>,
+    input_data: Vec<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+}
+
+impl TransformUnionDistinct {
+    pub fn create(num_inputs: usize, data_types: Vec<DataType>) -> Self {
+        TransformUnionDistinct {
+            inputs: (0..num_inputs).map(|_| InputPort::create()).collect(),
+            output: OutputPort::create(),
+            hashtable: Self::new_hashtable(&data_types),
+            data_types,
+            probe_state: ProbeState::default(),
+            spiller: None,
+            spill_bytes: 0,
+            state: State::Consume,
+            inputs_finished: false,
+            restore_partitions: vec![],
+            input_data: vec![],
+            output_data: VecDeque::new(),
+        }
+    }
+
+    /// Spills the distinct rows once the hash table allocates more than `spill_bytes`.
+    pub fn with_spiller(mut self, spiller: Spiller, spill_bytes: usize) -> Self {
+        self.spiller = Some(spiller);
+        self.spill_bytes = spill_bytes;
+        self
+    }
+
+    pub fn get_inputs(&self) -> Vec<Arc<InputPort>> {
+        self.inputs.clone()
+    }
+
+    pub fn get_output(&self) -> Arc<OutputPort> {
+        self.output.clone()
+    }
+
+    fn new_hashtable(data_types: &[DataType]) -> AggregateHashTable {
+        AggregateHashTable::new(
+            data_types.to_vec(),
+            vec![],
+            HashTableConfig::default(),
+            Arc::new(Bump::new()),
+        )
+    }
+
+    fn add_block(
+        hashtable: &mut AggregateHashTable,
+        probe_state: &mut ProbeState,
+        block: &DataBlock,
+    ) -> Result<()> {
+        let num_rows = block.num_rows();
+        if num_rows == 0 {
+            return Ok(());
+        }
+
+        let group_columns = block
+            .columns()
+            .iter()
+            .map(|entry| {
+                entry
+                    .value
+                    .convert_to_full_column(&entry.data_type, num_rows)
+            })
+            .collect::<Vec<_>>();
+        hashtable.add_groups(
+            probe_state,
+            (&group_columns).into(),
+            &[],
+            (&[]).into(),
+            num_rows,
+        )?;
+        Ok(())
+    }
+
+    fn take_distinct_rows(hashtable: &mut AggregateHashTable) -> Result<Vec<DataBlock>> {
+        let mut flush_state = PayloadFlushState::default();
+        let mut blocks = vec![];
+        while hashtable.merge_result(&mut flush_state)? {
+            blocks.push(DataBlock::new_from_columns(
+                flush_state.take_group_columns(),
+            ));
+        }
+        Ok(blocks)
+    }
+
+    fn exceeds_spill_bytes(&self) -> bool {
+        self.spiller.is_some() && self.hashtable.allocated_bytes() > self.spill_bytes
+    }
+
+    fn is_spilled(&self) -> bool {
+        self.spiller
+            .as_ref()
+            .is_some_and(|spiller| !spiller.partition_location.is_empty())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformUnionDistinct {
+    fn name(&self) -> String {
+        String::from("TransformUnionDistinct")
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.inputs.iter().for_each(|input| input.finish());
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(block) = self.output_data.pop_front() {
+            self.output.push_data(Ok(block));
+            return Ok(Event::NeedConsume);
+        }
+
+        match self.state {
+            State::Consume => {}
+            State::Spill | State::Restore => return Ok(Event::Async),
+            State::Flush => return Ok(Event::Sync),
+            State::Finished => {
+                self.output.finish();
+                return Ok(Event::Finished);
+            }
+        }
+
+        if !self.input_data.is_empty() {
+            return Ok(Event::Sync);
+        }
+
+        let mut all_finished = true;
+        for input in self.inputs.iter() {
+            if input.is_finished() {
+                continue;
+            }
+            all_finished = false;
+            if input.has_data() {
+                self.input_data.push(input.pull_data().unwrap()?);
+            }
+            input.set_need_data();
+        }
+
+        if !self.input_data.is_empty() {
+            return Ok(Event::Sync);
+        }
+
+        if all_finished {
+            self.inputs_finished = true;
+            // The rows left in memory are spilled too, then every partition is restored.
+            self.state = if self.is_spilled() {
+                State::Spill
+            } else {
+                State::Flush
+            };
+            return self.event();
+        }
+
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.state {
+            State::Consume => {
+                for block in std::mem::take(&mut self.input_data) {
+                    Self::add_block(&mut self.hashtable, &mut self.probe_state, &block)?;
+                }
+                if self.exceeds_spill_bytes() {
+                    self.state = State::Spill;
+                }
+            }
+            State::Flush => {
+                let blocks = Self::take_distinct_rows(&mut self.hashtable)?;
+                self.output_data.extend(blocks);
+                self.state = State::Finished;
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        match self.state {
+            State::Spill => {
+                let blocks = Self::take_distinct_rows(&mut self.hashtable)?;
+                self.hashtable = Self::new_hashtable(&self.data_types);
+
+                let spiller = self.spiller.as_mut().unwrap();
+                if !blocks.is_empty() {
+                    let block = DataBlock::concat(&blocks)?;
+                    let keys = (0..block.num_columns()).collect::<Vec<_>>();
+                    let indices =
+                        TransformRepartition::partition_indices(&block, &keys, SPILL_PARTITIONS);
+                    let partitions = DataBlock::scatter(&block, &indices, SPILL_PARTITIONS)?;
+                    for (partition, block) in partitions.into_iter().enumerate() {
+                        if !block.is_empty() {
+                            spiller.spill_with_partition(partition as u8, block).await?;
+                        }
+                    }
+                }
+
+                self.state = if self.inputs_finished {
+                    let mut partitions =
+                        spiller.spilled_partitions().into_iter().collect::<Vec<_>>();
+                    // Popped in the ascending order
+                    partitions.sort_unstable_by(|a, b| b.cmp(a));
+                    self.restore_partitions = partitions;
+                    State::Restore
+                } else {
+                    State::Consume
+                };
+            }
+            State::Restore => {
+                let spiller = self.spiller.as_mut().unwrap();
+                if let Some(partition) = self.restore_partitions.pop() {
+                    let blocks = spiller.read_spilled_partition(&partition).await?;
+                    let mut hashtable = Self::new_hashtable(&self.data_types);
+                    for block in blocks.iter() {
+                        Self::add_block(&mut hashtable, &mut self.probe_state, block)?;
+                    }
+                    self.output_data
+                        .extend(Self::take_distinct_rows(&mut hashtable)?);
+                }
+                if self.restore_partitions.is_empty() {
+                    self.state = State::Finished;
+                }
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_arrow::arrow::bitmap::MutableBitmap;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::types::nullable::NullableColumn;
+use databend_common_expression::types::AnyType;
+use databend_common_expression::types::ArrayColumn;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::Column;
+use databend_common_expression::ColumnBuilder;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Value;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_common_pipeline_transforms::processors::Transformer;
+
+use crate::pipelines::processors::InputPort;
+use crate::pipelines::processors::OutputPort;
+use crate::pipelines::processors::ProcessorPtr;
+
+/// Expands the array column into rows, each row of the input is repeated once per element of
+/// its array, and the element is appended after the other columns. Only the outermost level
+/// is expanded, the elements of a nested array are arrays.
+///
+/// With ordinality, the 1-based position of the element is appended as well. The empty and
+/// NULL arrays produce no rows, unless it's outer, which produces one row with a NULL element.
+pub struct TransformUnnest {
+    // The offset of the array column.
+    offset: usize,
+    with_ordinality: bool,
+    outer: bool,
+}
+
+impl TransformUnnest
+where Self: Transform
+{
+    pub fn new(offset: usize, with_ordinality: bool, outer: bool) -> Self {
+        Self {
+            offset,
+            with_ordinality,
+            outer,
+        }
+    }
+
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        offset: usize,
+        with_ordinality: bool,
+        outer: bool,
+    ) -> ProcessorPtr {
+        ProcessorPtr::create(Transformer::create(
+            input_port,
+            output_port,
+            Self::new(offset, with_ordinality, outer),
+        ))
+    }
+
+    /// The type of the elements of the array type.
+    pub fn element_type(data_type: &DataType, outer: bool) -> Result<DataType> {
+        let element_type = match data_type.remove_nullable() {
+            DataType::Array(box inner) => inner,
+            DataType::EmptyArray | DataType::Null => DataType::Null,
+            ty => {
+                return Err(ErrorCode::Internal(format!(
+                    "Unnest expects an array column, but got {ty}"
+                )));
+            }
+        };
+        if outer {
+            Ok(element_type.wrap_nullable())
+        } else {
+            Ok(element_type)
+        }
+    }
+
+    // The arrays and the validity of the rows, the NULL rows are empty arrays.
+    fn to_array_column(column: Column) -> Result<(ArrayColumn<AnyType>, Option<Bitmap>)> {
+        let empty_arrays = |len: usize| ArrayColumn {
+            values: Column::Null { len: 0 },
+            offsets: vec![0; len + 1].into(),
+        };
+        match column {
+            Column::Array(box array) => Ok((array, None)),
+            Column::EmptyArray { len } => Ok((empty_arrays(len), None)),
+            Column::Null { len } => Ok((empty_arrays(len), Some(Bitmap::new_zeroed(len)))),
+            Column::Nullable(box NullableColumn { column, validity }) => {
+                let (array, _) = Self::to_array_column(column)?;
+                Ok((array, Some(validity)))
+            }
+            column => Err(ErrorCode::Internal(format!(
+                "Unnest expects an array column, but got {}",
+                column.data_type()
+            ))),
+        }
+    }
+}
+
+impl Transform for TransformUnnest {
+    const NAME: &'static str = "UnnestTransform";
+
+    fn transform(&mut self, data_block: DataBlock) -> Result<DataBlock> {
+        let num_rows = data_block.num_rows();
+        let entry = data_block.get_by_offset(self.offset);
+        let element_type = Self::element_type(&entry.data_type, self.outer)?;
+        let column = entry
+            .value
+            .convert_to_full_column(&entry.data_type, num_rows);
+        let (array, validity) = Self::to_array_column(column)?;
+
+        // The input row, the offset of the element in the underlying column and the ordinality
+        // of each output row, the element is None for the NULL row of an empty array.
+        let mut rows = Vec::with_capacity(array.values.len());
+        let mut elements = Vec::with_capacity(array.values.len());
+        let mut ordinalities = Vec::with_capacity(array.values.len());
+        for row in 0..num_rows {
+            let is_valid = validity.as_ref().map_or(true, |v| v.get_bit(row));
+            let (start, end) = (array.offsets[row], array.offsets[row + 1]);
+            if is_valid && start < end {
+                for (ordinality, element) in (start..end).enumerate() {
+                    rows.push(row as u32);
+                    elements.push(Some(element));
+                    ordinalities.push(ordinality as u64 + 1);
+                }
+            } else if self.outer {
+                rows.push(row as u32);
+                elements.push(None);
+                ordinalities.push(0);
+            }
+        }
+
+        let columns = data_block
+            .columns()
+            .iter()
+            .enumerate()
+            .filter(|(offset, _)| *offset != self.offset)
+            .map(|(_, entry)| entry.clone())
+            .collect::<Vec<_>>();
+        let mut block = DataBlock::new(columns, num_rows).take(&rows, &mut None)?;
+
+        let element_indices = elements.iter().flatten().copied().collect::<Vec<_>>();
+        let values = array.values.take(&element_indices, &mut None);
+        let element_column = if !self.outer {
+            values
+        } else {
+            let mut builder = ColumnBuilder::with_capacity(&values.data_type(), elements.len());
+            let mut validity = MutableBitmap::with_capacity(elements.len());
+            let mut next = 0;
+            for element in elements.iter() {
+                match element {
+                    Some(_) => {
+                        builder.push(values.index(next).unwrap());
+                        next += 1;
+                    }
+                    None => builder.push_default(),
+                }
+                validity.push(element.is_some());
+            }
+            builder.build().wrap_nullable(Some(validity.into()))
+        };
+        block.add_column(BlockEntry::new(element_type, Value::Column(element_column)));
+
+        if self.with_ordinality {
+            let column = UInt64Type::from_data(ordinalities);
+            let column = if self.outer {
+                let validity = elements
+                    .iter()
+                    .map(|e| e.is_some())
+                    .collect::<MutableBitmap>();
+                column.wrap_nullable(Some(validity.into()))
+            } else {
+                column
+            };
+            block.add_column(BlockEntry::new(column.data_type(), Value::Column(column)));
+        }
+        Ok(block)
+    }
+}
@@ -226,6 +226,7 @@ impl PhysicalPlanReplacer for Fragmenter {
             build_keys: plan.build_keys.clone(),
             probe_keys: plan.probe_keys.clone(),
             is_null_equal: plan.is_null_equal.clone(),
+            case_insensitive: plan.case_insensitive.clone(),
             non_equi_conditions: plan.non_equi_conditions.clone(),
             join_type: plan.join_type.clone(),
             marker_index: plan.marker_index,
@@ -239,6 +240,9 @@ impl PhysicalPlanReplacer for Fragmenter {
             broadcast: plan.broadcast,
             single_to_inner: plan.single_to_inner.clone(),
             build_side_cache_info: plan.build_side_cache_info.clone(),
+            build_keys_unique: plan.build_keys_unique,
+            emit_build_row_id: plan.emit_build_row_id,
+            emit_match_count: plan.emit_match_count,
         }))
     }
 
@@ -28,6 +28,7 @@ use std::time::Instant;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+use chrono::DateTime;
 use chrono::Utc;
 use chrono_tz::Tz;
 use dashmap::mapref::multiple::RefMulti;
@@ -63,6 +64,7 @@ use databend_common_expression::BlockThresholds;
 use databend_common_expression::DataBlock;
 use databend_common_expression::Expr;
 use databend_common_expression::FunctionContext;
+use databend_common_expression::LeapSecondPolicy;
 use databend_common_expression::Scalar;
 use databend_common_expression::TableDataType;
 use databend_common_expression::TableField;
@@ -120,6 +122,7 @@ use crate::catalogs::Catalog;
 use crate::clusters::Cluster;
 use crate::locks::LockManager;
 use crate::pipelines::executor::PipelineExecutor;
+use crate::pipelines::processors::HashJoinState;
 use crate::servers::flight::v1::exchange::DataExchangeManager;
 use crate::sessions::query_affect::QueryAffect;
 use crate::sessions::ProcessInfo;
@@ -255,6 +258,35 @@ impl QueryContext {
         self.shared.session.clone()
     }
 
+    pub fn get_hash_join_build_cache(&self, key: &str) -> Option<Arc<HashJoinState>> {
+        self.shared
+            .session
+            .session_ctx
+            .get_hash_join_build_cache(key)
+    }
+
+    pub fn set_hash_join_build_cache(
+        &self,
+        key: String,
+        state: Arc<HashJoinState>,
+        memory_size: usize,
+    ) -> Result<bool> {
+        let max_memory_size = self.get_settings().get_hash_join_build_cache_size()?;
+        Ok(self.shared.session.session_ctx.set_hash_join_build_cache(
+            key,
+            state,
+            memory_size,
+            max_memory_size,
+        ))
+    }
+
+    pub fn get_hash_join_build_cache_memory_size(&self) -> usize {
+        self.shared
+            .session
+            .session_ctx
+            .get_hash_join_build_cache_memory_size()
+    }
+
     // Get one session by session id.
     pub fn get_session_by_id(self: &Arc<Self>, id: &str) -> Option<Arc<Session>> {
         SessionManager::instance().get_session_by_id(id)
@@ -698,7 +730,9 @@ impl TableContext for QueryContext {
 
         let tz = settings.get_timezone()?;
         let tz = TzFactory::instance().get_by_name(&tz)?;
-        let now = Utc::now();
+        // The clock is frozen at the start of the query, so all the function contexts of the
+        // query, e.g. the constant folding and each of the processors, see the same `now()`.
+        let now = DateTime::<Utc>::from(self.shared.created_time);
         let numeric_cast_option = settings.get_numeric_cast_option()?;
         let rounding_mode = numeric_cast_option.as_str() == "rounding";
         let disable_variant_check = settings.get_disable_variant_check()?;
@@ -706,8 +740,25 @@ impl TableContext for QueryContext {
         let parse_datetime_ignore_remainder = settings.get_parse_datetime_ignore_remainder()?;
         let enable_dst_hour_fix = settings.get_enable_dst_hour_fix()?;
         let enable_strict_datetime_parser = settings.get_enable_strict_datetime_parser()?;
+        let clamp_date_arithmetic = settings.get_date_arithmetic_overflow()?.as_str() == "clamp";
+        let clamp_timestamp_arithmetic =
+            settings.get_timestamp_arithmetic_overflow()?.as_str() == "clamp";
+        let datetime_parse_error_with_value = settings.get_datetime_parse_error_with_value()?;
+        let datetime_parse_error_summary = settings.get_datetime_parse_error_summary()?;
+        let parse_datetime_relative_keywords = settings.get_parse_datetime_relative_keywords()?;
+        let parse_datetime_comma_fraction = settings.get_parse_datetime_comma_fraction()?;
+        let parse_datetime_ordinal_suffix = settings.get_parse_datetime_ordinal_suffix()?;
+        let leap_second_policy = match settings.get_leap_second_policy()?.as_str() {
+            "roll_forward" => LeapSecondPolicy::RollForward,
+            "clamp" => LeapSecondPolicy::Clamp,
+            _ => LeapSecondPolicy::Error,
+        };
+        let two_digit_year_pivot = settings.get_two_digit_year_pivot()?;
         let query_config = &GlobalConfig::instance().query;
         let random_function_seed = settings.get_random_function_seed()?;
+        let timestamp_output_precision = settings.get_timestamp_output_precision()?;
+        let timestamp_display_format = settings.get_timestamp_display_format()?;
+        let date_display_format = settings.get_date_display_format()?;
 
         Ok(FunctionContext {
             tz,
@@ -726,7 +777,19 @@ impl TableContext for QueryContext {
             parse_datetime_ignore_remainder,
             enable_dst_hour_fix,
             enable_strict_datetime_parser,
+            clamp_date_arithmetic,
+            clamp_timestamp_arithmetic,
+            datetime_parse_error_with_value,
+            datetime_parse_error_summary,
+            parse_datetime_relative_keywords,
+            parse_datetime_comma_fraction,
+            parse_datetime_ordinal_suffix,
+            leap_second_policy,
+            two_digit_year_pivot,
             random_function_seed,
+            timestamp_output_precision,
+            timestamp_display_format,
+            date_display_format,
         })
     }
 
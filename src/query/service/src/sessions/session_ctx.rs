@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -35,8 +36,21 @@ use parking_lot::Mutex;
 use parking_lot::RwLock;
 
 use super::SessionType;
+use crate::pipelines::processors::HashJoinState;
 use crate::sessions::QueryContextShared;
 
+// The number of the hash join build tables kept in a session.
+const MAX_HASH_JOIN_BUILD_CACHE_ENTRIES: usize = 16;
+
+/// The built hash tables of the joins kept for the later statements, keyed by the build plan.
+#[derive(Default)]
+struct HashJoinBuildCache {
+    // The states with their memory sizes, the oldest first.
+    entries: VecDeque<(String, Arc<HashJoinState>, usize)>,
+    // The memory size of all the kept hash tables.
+    memory_size: usize,
+}
+
 pub struct SessionContext {
     abort: AtomicBool,
     settings: Arc<Settings>,
@@ -77,6 +91,8 @@ pub struct SessionContext {
     typ: SessionType,
     txn_mgr: Mutex<TxnManagerRef>,
     temp_tbl_mgr: Mutex<TempTblMgrRef>,
+    /// The built hash tables of the joins kept for the later statements, keyed by the build plan.
+    hash_join_build_cache: RwLock<HashJoinBuildCache>,
     /// The uniq id for session from the perspective of client.
     /// for HTTP handler, client session lives longer then the `Session` object. the uniq id is
     /// http handler: should set the id in session token, if token is not used, session id is not available,
@@ -106,6 +122,7 @@ impl SessionContext {
             txn_mgr: Mutex::new(TxnManager::init()),
             client_session_id: Default::default(),
             temp_tbl_mgr: Mutex::new(TempTblMgr::init()),
+            hash_join_build_cache: Default::default(),
         })
     }
 
@@ -325,6 +342,48 @@ impl SessionContext {
         *self.temp_tbl_mgr.lock() = temp_tbl_mgr;
     }
 
+    pub fn get_hash_join_build_cache(&self, key: &str) -> Option<Arc<HashJoinState>> {
+        let cache = self.hash_join_build_cache.read();
+        cache
+            .entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, state, _)| state.clone())
+    }
+
+    /// Keeps the hash table, the oldest ones are evicted to keep the memory of the cache within
+    /// `max_memory_size`. Returns false if the hash table alone exceeds it.
+    pub fn set_hash_join_build_cache(
+        &self,
+        key: String,
+        state: Arc<HashJoinState>,
+        memory_size: usize,
+        max_memory_size: usize,
+    ) -> bool {
+        if memory_size > max_memory_size {
+            return false;
+        }
+
+        let mut cache = self.hash_join_build_cache.write();
+        if let Some(index) = cache.entries.iter().position(|(k, _, _)| k == &key) {
+            let (_, _, size) = cache.entries.remove(index).unwrap();
+            cache.memory_size -= size;
+        }
+        while cache.entries.len() >= MAX_HASH_JOIN_BUILD_CACHE_ENTRIES
+            || cache.memory_size + memory_size > max_memory_size
+        {
+            let (_, _, size) = cache.entries.pop_front().unwrap();
+            cache.memory_size -= size;
+        }
+        cache.memory_size += memory_size;
+        cache.entries.push_back((key, state, memory_size));
+        true
+    }
+
+    pub fn get_hash_join_build_cache_memory_size(&self) -> usize {
+        self.hash_join_build_cache.read().memory_size
+    }
+
     pub fn set_variable(&self, key: String, value: Scalar) {
         self.variables.write().insert(key, value);
     }
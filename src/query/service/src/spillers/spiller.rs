@@ -28,6 +28,7 @@ use databend_common_exception::Result;
 use databend_common_expression::arrow::deserialize_column;
 use databend_common_expression::arrow::serialize_column;
 use databend_common_expression::DataBlock;
+use log::info;
 use opendal::Operator;
 
 use crate::sessions::QueryContext;
@@ -38,6 +39,7 @@ pub enum SpillerType {
     HashJoinBuild,
     HashJoinProbe,
     OrderBy,
+    UnionDistinct,
     // Todo: Add more spillers type
     // Aggregation
 }
@@ -48,6 +50,7 @@ impl Display for SpillerType {
             SpillerType::HashJoinBuild => write!(f, "HashJoinBuild"),
             SpillerType::HashJoinProbe => write!(f, "HashJoinProbe"),
             SpillerType::OrderBy => write!(f, "OrderBy"),
+            SpillerType::UnionDistinct => write!(f, "UnionDistinct"),
         }
     }
 }
@@ -75,7 +78,7 @@ pub struct Spiller {
     ctx: Arc<QueryContext>,
     operator: Operator,
     config: SpillerConfig,
-    _spiller_type: SpillerType,
+    spiller_type: SpillerType,
     pub join_spilling_partition_bits: usize,
     /// 1 partition -> N partition files
     pub partition_location: HashMap<u8, Vec<String>>,
@@ -98,7 +101,7 @@ impl Spiller {
             ctx: ctx.clone(),
             operator,
             config,
-            _spiller_type: spiller_type,
+            spiller_type,
             join_spilling_partition_bits,
             partition_location: Default::default(),
             columns_layout: Default::default(),
@@ -114,11 +117,12 @@ impl Spiller {
     /// We should guarantee that the file is managed by this spiller.
     pub async fn read_spilled_file(&self, file: &str) -> Result<DataBlock> {
         debug_assert!(self.columns_layout.contains_key(file));
+        // The time of restoring includes the time of reading the file.
+        let instant = Instant::now();
         let data = self.operator.read(file).await?.to_bytes();
         let bytes = data.len();
 
         let mut begin = 0;
-        let instant = Instant::now();
         let mut columns = Vec::with_capacity(self.columns_layout.len());
         let columns_layout = self.columns_layout.get(file).unwrap();
         for column_layout in columns_layout.iter() {
@@ -134,6 +138,14 @@ impl Spiller {
             instant.elapsed().as_millis() as usize,
         );
 
+        info!(
+            "Read {} spill {} successfully, bytes: {}, elapsed: {:?}",
+            self.spiller_type,
+            file,
+            bytes,
+            instant.elapsed()
+        );
+
         Ok(block)
     }
 
@@ -178,6 +190,14 @@ impl Spiller {
             instant.elapsed().as_millis() as usize,
         );
 
+        info!(
+            "Write {} spill {} successfully, bytes: {}, elapsed: {:?}",
+            self.spiller_type,
+            location,
+            write_bytes,
+            instant.elapsed()
+        );
+
         Ok(location)
     }
 
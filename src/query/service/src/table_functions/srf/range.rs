@@ -25,9 +25,19 @@ use databend_common_catalog::table_args::TableArgs;
 use databend_common_catalog::table_function::TableFunction;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::date_helper::AddDaysImpl;
+use databend_common_expression::date_helper::AddMonthsImpl;
+use databend_common_expression::date_helper::AddTimesImpl;
+use databend_common_expression::date_helper::DateDiffUnit;
+use databend_common_expression::date_helper::TzLUT;
+use databend_common_expression::date_helper::FACTOR_HOUR;
+use databend_common_expression::date_helper::FACTOR_MINUTE;
+use databend_common_expression::date_helper::FACTOR_SECOND;
 use databend_common_expression::infer_schema_type;
 use databend_common_expression::type_check::check_number;
+use databend_common_expression::types::timestamp::MICROS_IN_A_SEC;
 use databend_common_expression::types::*;
+use databend_common_expression::Column;
 use databend_common_expression::DataBlock;
 use databend_common_expression::Expr;
 use databend_common_expression::FromData;
@@ -91,7 +101,7 @@ impl RangeTable {
         } else {
             Scalar::Number(NumberScalar::Int64(1))
         };
-        if let Scalar::Timestamp(_) = start {
+        if let (Scalar::Timestamp(_), Scalar::Number(_)) = (&start, &step) {
             // since `to_timestamp` return value in micro seconds, we need to to change step as the same unit
             let step_i64 = get_i64_number(&step)?;
             if step_i64 < 1000 {
@@ -228,6 +238,98 @@ struct RangeSource<const INCLUSIVE: bool> {
     start: i64,
     end: i64,
     step: i64,
+    // The step of the dates and timestamps given as an interval, `step` is unused if it's set.
+    interval: Option<SeriesInterval>,
+    tz: TzLUT,
+}
+
+// The series of dates or timestamps with an interval step can't have more rows than it.
+const MAX_SERIES_ROWS: i64 = 1_000_000_000;
+
+/// The step of the series of dates or timestamps like `'1 day'` or `'3 months'`.
+#[derive(Clone, Copy)]
+struct SeriesInterval {
+    unit: DateDiffUnit,
+    count: i64,
+}
+
+impl SeriesInterval {
+    fn parse(interval: &str, data_type: &DataType) -> Result<Self> {
+        let invalid = || {
+            ErrorCode::BadArguments(format!(
+                "Invalid interval '{interval}' of generate_series, expected like '1 day'"
+            ))
+        };
+        let (count, unit) = interval
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let count = count.parse::<i64>().map_err(|_| invalid())?;
+        let unit = DateDiffUnit::parse(unit).map_err(ErrorCode::BadArguments)?;
+
+        if count <= 0 {
+            return Err(ErrorCode::BadArguments(format!(
+                "The interval of generate_series must be positive, but got '{interval}'"
+            )));
+        }
+        if unit.is_sub_day() && *data_type == DataType::Date {
+            return Err(ErrorCode::BadArguments(format!(
+                "The interval of generate_series on dates must be days or longer, but got '{interval}'"
+            )));
+        }
+        Ok(Self { unit, count })
+    }
+
+    /// The length of the step in days of the dates or seconds of the timestamps, `None` if
+    /// it's months or years.
+    fn fixed_step(&self, data_type: &DataType) -> Option<i64> {
+        let factor = match (self.unit, data_type) {
+            (DateDiffUnit::Week, DataType::Date) => 7,
+            (DateDiffUnit::Day, DataType::Date) => 1,
+            (DateDiffUnit::Week, _) => 7 * 24 * FACTOR_HOUR,
+            (DateDiffUnit::Day, _) => 24 * FACTOR_HOUR,
+            (DateDiffUnit::Hour, _) => FACTOR_HOUR,
+            (DateDiffUnit::Minute, _) => FACTOR_MINUTE,
+            (DateDiffUnit::Second, _) => FACTOR_SECOND,
+            _ => return None,
+        };
+        Some(self.count.saturating_mul(factor))
+    }
+
+    /// Adds `idx` steps to the start, the steps of months are added at once from the start,
+    /// so the days clamped to the end of a short month are not carried to the next months.
+    fn add(
+        &self,
+        data_type: &DataType,
+        start: i64,
+        idx: i64,
+        tz: TzLUT,
+    ) -> std::result::Result<i64, String> {
+        let delta = self.count.checked_mul(idx).ok_or("Overflow on interval")?;
+        let months = match self.unit {
+            DateDiffUnit::Year => delta.checked_mul(12),
+            DateDiffUnit::Quarter => delta.checked_mul(3),
+            DateDiffUnit::Month => Some(delta),
+            _ => None,
+        };
+        match (months, data_type) {
+            (Some(months), DataType::Date) => {
+                AddMonthsImpl::eval_date(start as i32, tz, months).map(|date| date as i64)
+            }
+            (Some(months), _) => AddMonthsImpl::eval_timestamp(start, tz, months),
+            (None, _) => {
+                let step = self.fixed_step(data_type).unwrap();
+                let delta = step
+                    .checked_mul(idx)
+                    .filter(|delta| delta.checked_mul(MICROS_IN_A_SEC).is_some())
+                    .ok_or("Overflow on interval")?;
+                match data_type {
+                    DataType::Date => AddDaysImpl::eval_date(start as i32, delta).map(|d| d as i64),
+                    _ => AddTimesImpl::eval_timestamp(start, delta, FACTOR_SECOND),
+                }
+            }
+        }
+    }
 }
 
 fn get_i64_number(scalar: &Scalar) -> Result<i64> {
@@ -252,9 +354,27 @@ impl<const INCLUSIVE: bool> RangeSource<INCLUSIVE> {
         end: Scalar,
         step: Scalar,
     ) -> Result<ProcessorPtr> {
+        let tz = ctx.get_function_context()?.tz;
+        let interval = match &step {
+            Scalar::String(interval) => Some(SeriesInterval::parse(interval, &data_type)?),
+            _ => None,
+        };
         let start = get_i64_number(&start)?;
         let mut end = get_i64_number(&end)?;
-        let step = get_i64_number(&step)?;
+        let step = match &interval {
+            Some(interval) => {
+                // The months have 28 days at least, so the rows of the series are bounded.
+                let step = interval.fixed_step(&data_type).unwrap_or(match data_type {
+                    DataType::Date => 28,
+                    _ => 28 * 24 * FACTOR_HOUR,
+                });
+                match data_type {
+                    DataType::Date => step,
+                    _ => step.saturating_mul(MICROS_IN_A_SEC),
+                }
+            }
+            None => get_i64_number(&step)?,
+        };
 
         if INCLUSIVE {
             if step > 0 {
@@ -276,15 +396,51 @@ impl<const INCLUSIVE: bool> RangeSource<INCLUSIVE> {
             ));
         }
 
+        if interval.is_some() {
+            let rows = (end as i128 - start as i128) / step as i128;
+            if rows > MAX_SERIES_ROWS as i128 {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The series has {rows} rows, which exceeds the limit of {MAX_SERIES_ROWS}"
+                )));
+            }
+        }
+
         SyncSourcer::create(ctx.clone(), output, Self {
             current_idx: 0,
             data_type,
             start,
             end,
             step,
+            interval,
+            tz,
             finished: false,
         })
     }
+
+    fn generate_interval(&mut self, interval: SeriesInterval) -> Option<Column> {
+        static MAX_BLOCK_SIZE: usize = 1024 * 1024;
+
+        let mut values = Vec::new();
+        while values.len() < MAX_BLOCK_SIZE {
+            match interval.add(&self.data_type, self.start, self.current_idx, self.tz) {
+                Ok(value) if value < self.end => values.push(value),
+                // The series ends if the next value is out of the range of the type.
+                _ => {
+                    self.finished = true;
+                    break;
+                }
+            }
+            self.current_idx += 1;
+        }
+
+        if values.is_empty() {
+            return None;
+        }
+        Some(match self.data_type {
+            DataType::Date => DateType::from_data(values.into_iter().map(|v| v as i32).collect()),
+            _ => TimestampType::from_data(values),
+        })
+    }
 }
 
 impl<const INCLUSIVE: bool> SyncSource for RangeSource<INCLUSIVE> {
@@ -295,6 +451,11 @@ impl<const INCLUSIVE: bool> SyncSource for RangeSource<INCLUSIVE> {
             return Ok(None);
         }
 
+        if let Some(interval) = self.interval {
+            let column = self.generate_interval(interval);
+            return Ok(column.map(|column| DataBlock::new_from_columns(vec![column])));
+        }
+
         let current_start = self.start + self.step * self.current_idx;
         let offset = if self.step < 0 { 1 } else { -1 };
 
@@ -351,12 +512,20 @@ fn validate_args(args: &Vec<Scalar>, table_func_name: &str) -> Result<()> {
         )));
     }
 
+    // The step of the dates and timestamps can be an interval like '1 day'.
+    let is_interval = |arg: &Scalar| {
+        matches!(arg, Scalar::String(_))
+            && matches!(args[0], Scalar::Date(_) | Scalar::Timestamp(_))
+    };
     if args.iter().all(|arg| {
         matches!(
             arg,
             Scalar::Number(_) | Scalar::Date(_) | Scalar::Timestamp(_)
         )
-    }) {
+    }) || (args.len() == 3
+        && is_interval(&args[2])
+        && args[..2].iter().all(|arg| !is_interval(arg)))
+    {
         Ok(())
     } else {
         Err(ErrorCode::BadDataValueType(format!(
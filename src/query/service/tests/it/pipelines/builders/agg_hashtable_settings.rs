@@ -0,0 +1,108 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::AggregateHashTable;
+use databend_common_expression::HashTableConfig;
+use databend_query::pipelines::builders::with_agg_hashtable_settings;
+use databend_query::sessions::TableContext;
+use databend_query::test_kits::TestFixture;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_agg_hashtable_from_settings() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let settings = ctx.get_settings();
+
+    // Defaults.
+    let config = with_agg_hashtable_settings(HashTableConfig::default(), &settings)?;
+    assert_eq!(config.load_factor, 1.5);
+    assert_eq!(
+        config.initial_capacity,
+        AggregateHashTable::initial_capacity()
+    );
+
+    settings.set_setting("agg_hashtable_load_factor".to_string(), "200".to_string())?;
+    settings.set_setting(
+        "agg_hashtable_initial_capacity".to_string(),
+        "5000".to_string(),
+    )?;
+    let config = with_agg_hashtable_settings(HashTableConfig::default(), &settings)?;
+    assert_eq!(config.load_factor, 2.0);
+    assert_eq!(config.initial_capacity, 8192);
+
+    let hashtable = AggregateHashTable::new(
+        vec![DataType::Number(NumberDataType::UInt64)],
+        vec![],
+        config,
+        Arc::new(Bump::new()),
+    );
+    assert_eq!(hashtable.capacity(), 8192);
+
+    // The final hash tables are sized from the same values.
+    assert_eq!(
+        AggregateHashTable::get_capacity_for_count(10_000, &config),
+        32768
+    );
+
+    // The partial hash table starts no larger than its max capacity.
+    let config = HashTableConfig::default()
+        .with_initial_capacity(1 << 17)
+        .with_partial(true, 1);
+    assert_eq!(
+        config.initial_capacity,
+        config.max_partial_capacity.min(1 << 17)
+    );
+
+    // Out of the ranges of the settings.
+    assert!(
+        settings
+            .set_setting("agg_hashtable_load_factor".to_string(), "100".to_string())
+            .is_err()
+    );
+    assert!(
+        settings
+            .set_setting(
+                "agg_hashtable_initial_capacity".to_string(),
+                "0".to_string()
+            )
+            .is_err()
+    );
+    assert!(
+        settings
+            .set_setting(
+                "agg_hashtable_initial_capacity".to_string(),
+                "262144".to_string()
+            )
+            .is_err()
+    );
+
+    // Out of the ranges of the config.
+    let config = HashTableConfig::default()
+        .with_load_factor(0.5)
+        .with_initial_capacity(1 << 40);
+    assert_eq!(config.load_factor, 1.5);
+    assert_eq!(
+        config.initial_capacity,
+        AggregateHashTable::initial_capacity()
+    );
+
+    Ok(())
+}
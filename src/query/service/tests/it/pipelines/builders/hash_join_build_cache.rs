@@ -0,0 +1,139 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_common_sql::executor::PhysicalPlanBuilder;
+use databend_common_sql::plans::Plan;
+use databend_common_sql::Planner;
+use databend_query::schedulers::build_query_pipeline_without_render_result_set;
+use databend_query::sessions::QueryContext;
+use databend_query::test_kits::TestFixture;
+use futures_util::TryStreamExt;
+
+// Returns the number of the pipelines besides the main pipeline, the build side of the join
+// is one of them.
+async fn num_source_pipelines(ctx: Arc<QueryContext>, sql: &str) -> Result<usize> {
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _) = planner.plan_sql(sql).await?;
+    let Plan::Query {
+        s_expr,
+        metadata,
+        bind_context,
+        ..
+    } = plan
+    else {
+        unreachable!("Query plan expected")
+    };
+    let mut builder = PhysicalPlanBuilder::new(metadata.clone(), ctx.clone(), false);
+    let plan = builder.build(&s_expr, bind_context.column_set()).await?;
+    let build_res = build_query_pipeline_without_render_result_set(&ctx, &plan).await?;
+    Ok(build_res.sources_pipelines.len())
+}
+
+async fn query_count(fixture: &TestFixture, sql: &str) -> Result<DataBlock> {
+    let stream = fixture.execute_query(sql).await?;
+    let blocks = stream.try_collect::<Vec<DataBlock>>().await?;
+    DataBlock::concat(&blocks)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_reuse_hash_join_build() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture
+        .execute_command("CREATE TABLE dim (id int, name string) AS SELECT number, to_string(number) FROM numbers(10)")
+        .await?;
+    fixture
+        .execute_command("CREATE TABLE fact (id int) AS SELECT number % 20 FROM numbers(1000)")
+        .await?;
+    fixture
+        .execute_command("SET enable_hash_join_build_cache = 1")
+        .await?;
+
+    let sql = "SELECT count(*) FROM fact JOIN dim ON fact.id = dim.id";
+    let num_pipelines = num_source_pipelines(fixture.new_query_ctx().await?, sql).await?;
+    assert!(num_pipelines > 0);
+
+    // The first statement builds the hash table and keeps it in the session.
+    let expected = query_count(&fixture, sql).await?;
+
+    // The second statement skips the build phase.
+    assert_eq!(
+        num_source_pipelines(fixture.new_query_ctx().await?, sql).await?,
+        num_pipelines - 1
+    );
+    let actual = query_count(&fixture, sql).await?;
+    assert_eq!(
+        actual.get_by_offset(0).value,
+        expected.get_by_offset(0).value
+    );
+
+    // The table version is part of the key, the modified table is built again.
+    fixture
+        .execute_command("INSERT INTO dim VALUES (10, '10')")
+        .await?;
+    assert_eq!(
+        num_source_pipelines(fixture.new_query_ctx().await?, sql).await?,
+        num_pipelines
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_not_reuse_hash_join_build() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture
+        .execute_command("CREATE TABLE dim (id int, name string) AS SELECT number, to_string(number) FROM numbers(10)")
+        .await?;
+    fixture
+        .execute_command("CREATE TABLE fact (id int) AS SELECT number % 20 FROM numbers(1000)")
+        .await?;
+    fixture
+        .execute_command("SET enable_hash_join_build_cache = 1")
+        .await?;
+
+    // The build side evaluating a non-deterministic function is built by every statement.
+    let sql = "SELECT count(*) FROM fact JOIN (SELECT id, rand() AS r FROM dim) d ON fact.id = d.id WHERE d.r < 2";
+    let num_pipelines = num_source_pipelines(fixture.new_query_ctx().await?, sql).await?;
+    query_count(&fixture, sql).await?;
+    assert_eq!(
+        num_source_pipelines(fixture.new_query_ctx().await?, sql).await?,
+        num_pipelines
+    );
+
+    // The build side reading an unversioned source is built by every statement.
+    let sql = "SELECT count(*) FROM fact JOIN numbers(10) n ON fact.id = n.number";
+    let num_pipelines = num_source_pipelines(fixture.new_query_ctx().await?, sql).await?;
+    query_count(&fixture, sql).await?;
+    assert_eq!(
+        num_source_pipelines(fixture.new_query_ctx().await?, sql).await?,
+        num_pipelines
+    );
+
+    // The hash table beyond the memory of the cache is not kept.
+    fixture
+        .execute_command("SET hash_join_build_cache_size_mb = 0")
+        .await?;
+    let sql = "SELECT count(*) FROM fact JOIN dim ON fact.id = dim.id";
+    let num_pipelines = num_source_pipelines(fixture.new_query_ctx().await?, sql).await?;
+    query_count(&fixture, sql).await?;
+    assert_eq!(
+        num_source_pipelines(fixture.new_query_ctx().await?, sql).await?,
+        num_pipelines
+    );
+    Ok(())
+}
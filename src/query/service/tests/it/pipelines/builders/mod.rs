@@ -12,4 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod agg_hashtable_settings;
+mod hash_join_build_cache;
 mod runtime_filter;
@@ -15,3 +15,4 @@
 mod builders;
 mod executor;
 mod filter;
+mod transforms;
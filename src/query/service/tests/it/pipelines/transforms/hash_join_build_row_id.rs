@@ -0,0 +1,188 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::BUILD_ROW_ID_COL_NAME;
+use databend_common_hashtable::RowPtr;
+use databend_common_sql::executor::PhysicalPlanBuilder;
+use databend_common_sql::optimizer::SExpr;
+use databend_common_sql::plans::Plan;
+use databend_common_sql::plans::RelOperator;
+use databend_common_sql::Planner;
+use databend_query::pipelines::executor::ExecutorSettings;
+use databend_query::pipelines::executor::PipelinePullingExecutor;
+use databend_query::pipelines::processors::build_row_id;
+use databend_query::pipelines::processors::build_row_ptr;
+use databend_query::schedulers::build_query_pipeline_without_render_result_set;
+use databend_query::stream::PullingExecutorStream;
+use databend_query::test_kits::TestFixture;
+use futures_util::TryStreamExt;
+
+#[test]
+fn test_build_row_id() {
+    // The build side is kept as chunks, the same as the hash join build state.
+    let chunks = [
+        DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![10, 11, 12]),
+            StringType::from_data(vec!["a", "b", "c"]),
+        ]),
+        DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![20, 21]),
+            StringType::from_data(vec!["d", "e"]),
+        ]),
+    ];
+    let data_types = vec![DataType::Number(NumberDataType::Int32), DataType::String];
+    let build_columns = (0..data_types.len())
+        .map(|offset| {
+            let columns = chunks
+                .iter()
+                .map(|chunk| {
+                    chunk
+                        .get_by_offset(offset)
+                        .value
+                        .as_column()
+                        .unwrap()
+                        .clone()
+                })
+                .collect::<Vec<_>>();
+            Column::take_downcast_column_vec(&columns, data_types[offset].clone())
+        })
+        .collect::<Vec<_>>();
+
+    // The matched build rows, some of them are matched more than once.
+    let row_ptrs = [(1, 1), (0, 2), (1, 0), (0, 0), (1, 1)]
+        .map(|(chunk_index, row_index)| RowPtr::new(chunk_index, row_index));
+    let row_ids = row_ptrs.iter().map(build_row_id).collect::<Vec<_>>();
+    assert_eq!(row_ids, vec![(1 << 32) | 1, 2, 1 << 32, 0, (1 << 32) | 1]);
+
+    // The build rows are gathered by the ids the same as by the matched entries.
+    let decoded = row_ids
+        .iter()
+        .map(|row_id| build_row_ptr(*row_id))
+        .collect::<Vec<_>>();
+    for (decoded, row_ptr) in decoded.iter().zip(row_ptrs.iter()) {
+        assert_eq!(decoded.chunk_index, row_ptr.chunk_index);
+        assert_eq!(decoded.row_index, row_ptr.row_index);
+    }
+    let block = DataBlock::take_column_vec(
+        &build_columns,
+        &data_types,
+        &decoded,
+        decoded.len(),
+        &mut None,
+    );
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![21, 12, 20, 10, 21])
+    );
+    assert_eq!(
+        block.get_by_offset(1).value.as_column().unwrap(),
+        &StringType::from_data(vec!["e", "c", "d", "a", "e"])
+    );
+}
+
+// Asks every join of the plan to emit the `build_row_id` of the matched build rows.
+fn with_build_row_id(s_expr: &SExpr) -> SExpr {
+    let children = s_expr
+        .children()
+        .map(|child| Arc::new(with_build_row_id(child)))
+        .collect::<Vec<_>>();
+    let s_expr = s_expr.replace_children(children);
+    match s_expr.plan() {
+        RelOperator::Join(join) => {
+            let mut join = join.clone();
+            join.emit_build_row_id = true;
+            s_expr.replace_plan(Arc::new(join.into()))
+        }
+        _ => s_expr,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_probe_emits_build_row_id() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture
+        .execute_command("CREATE TABLE dim (id int, name string)")
+        .await?;
+    fixture
+        .execute_command("INSERT INTO dim VALUES (1, 'a'), (2, 'b'), (3, 'c')")
+        .await?;
+    fixture
+        .execute_command("CREATE TABLE fact (id int) AS SELECT number % 5 FROM numbers(100)")
+        .await?;
+    fixture.execute_command("SET max_threads = 1").await?;
+
+    let ctx = fixture.new_query_ctx().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _) = planner
+        .plan_sql("SELECT fact.id, dim.id FROM fact JOIN dim ON fact.id = dim.id")
+        .await?;
+    let Plan::Query {
+        s_expr,
+        metadata,
+        bind_context,
+        ..
+    } = plan
+    else {
+        unreachable!("Query plan expected")
+    };
+    let s_expr = with_build_row_id(&s_expr);
+    let mut builder = PhysicalPlanBuilder::new(metadata.clone(), ctx.clone(), false);
+    let plan = builder.build(&s_expr, bind_context.column_set()).await?;
+
+    // The `build_row_id` is the last column of the output schema.
+    let output_schema = plan.output_schema()?;
+    let num_fields = output_schema.num_fields();
+    assert_eq!(
+        output_schema.field(num_fields - 1).name(),
+        BUILD_ROW_ID_COL_NAME
+    );
+
+    let build_res = build_query_pipeline_without_render_result_set(&ctx, &plan).await?;
+    let settings = ExecutorSettings::try_create(ctx.clone())?;
+    let executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+    ctx.set_executor(executor.get_inner())?;
+    let blocks = PullingExecutorStream::create(executor)?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let block = DataBlock::concat(&blocks)?;
+    assert_eq!(block.num_columns(), num_fields);
+    assert_eq!(block.num_rows(), 60);
+
+    // The build side `dim` is a single chunk, every id points to the row of the matched key.
+    let ids = block.get_by_offset(0).to_column(block.num_rows());
+    let ids = Int32Type::try_downcast_column(&ids.remove_nullable()).unwrap();
+    let row_ids = block
+        .get_by_offset(num_fields - 1)
+        .to_column(block.num_rows());
+    let row_ids = UInt64Type::try_downcast_column(&row_ids).unwrap();
+    for (id, row_id) in ids.iter().zip(row_ids.iter()) {
+        let row_ptr = build_row_ptr(*row_id);
+        assert_eq!(row_ptr.chunk_index, 0);
+        assert_eq!(row_ptr.row_index, (*id - 1) as u32);
+    }
+    Ok(())
+}
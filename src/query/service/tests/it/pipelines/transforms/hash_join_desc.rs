@@ -0,0 +1,208 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_exception::Result;
+use databend_common_expression::filter::FilterExecutor;
+use databend_common_expression::type_check::check_function;
+use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::Evaluator;
+use databend_common_expression::Expr;
+use databend_common_expression::FromData;
+use databend_common_expression::FunctionContext;
+use databend_common_expression::Scalar;
+use databend_common_expression::ScalarRef;
+use databend_common_expression::SelectExprBuilder;
+use databend_common_functions::BUILTIN_FUNCTIONS;
+use databend_query::pipelines::processors::HashJoinDesc;
+
+fn column_ref(id: usize, data_type: DataType) -> Expr {
+    Expr::ColumnRef {
+        span: None,
+        id,
+        data_type,
+        display_name: format!("c{id}"),
+    }
+}
+
+fn constant(scalar: Scalar) -> Expr {
+    Expr::Constant {
+        span: None,
+        data_type: scalar.as_ref().infer_data_type(),
+        scalar,
+    }
+}
+
+fn filter(block: &DataBlock, conditions: &[Expr]) -> Result<DataBlock> {
+    let mut predicate = conditions[0].clone();
+    for condition in &conditions[1..] {
+        predicate = check_function(
+            None,
+            "and_filters",
+            &[],
+            &[predicate, condition.clone()],
+            &BUILTIN_FUNCTIONS,
+        )?;
+    }
+    let (select_expr, has_or) = SelectExprBuilder::new().build(&predicate).into();
+    let mut filter_executor = FilterExecutor::new(
+        select_expr,
+        FunctionContext::default(),
+        has_or,
+        block.num_rows(),
+        None,
+        &BUILTIN_FUNCTIONS,
+        true,
+    );
+    filter_executor.filter(block.clone())
+}
+
+#[test]
+fn test_order_non_equi_conditions() -> Result<()> {
+    let num_rows = 1000;
+    let block = DataBlock::new_from_columns(vec![
+        Int32Type::from_data((0..num_rows as i32).collect::<Vec<_>>()),
+        StringType::from_data(
+            (0..num_rows)
+                .map(|i| format!("name_{i}"))
+                .collect::<Vec<_>>(),
+        ),
+    ]);
+
+    // The condition on strings is declared before the cheaper comparison of integers.
+    let like = check_function(
+        None,
+        "like",
+        &[],
+        &[
+            column_ref(1, DataType::String),
+            constant(Scalar::String("%9%".to_string())),
+        ],
+        &BUILTIN_FUNCTIONS,
+    )?;
+    let lt = check_function(
+        None,
+        "lt",
+        &[],
+        &[
+            column_ref(0, DataType::Number(NumberDataType::Int32)),
+            constant(Scalar::Number(NumberScalar::Int32(100))),
+        ],
+        &BUILTIN_FUNCTIONS,
+    )?;
+    let declared = vec![like.clone(), lt.clone()];
+    let ordered = HashJoinDesc::order_non_equi_conditions(declared.clone());
+    assert_eq!(
+        ordered.iter().map(Expr::sql_display).collect::<Vec<_>>(),
+        vec![lt.sql_display(), like.sql_display()]
+    );
+
+    // The output is the same in both orders.
+    let expected = filter(&block, &declared)?;
+    let actual = filter(&block, &ordered)?;
+    assert_eq!(expected.num_rows(), 19);
+    assert_eq!(expected.num_rows(), actual.num_rows());
+    for (expected, actual) in expected.columns().iter().zip(actual.columns()) {
+        assert_eq!(expected.value, actual.value);
+    }
+
+    // The conditions are short-circuited, so the second condition is only evaluated on the
+    // rows passing the first one, 100 rows instead of 271 rows.
+    let func_ctx = FunctionContext::default();
+    let evaluator = Evaluator::new(&block, &func_ctx, &BUILTIN_FUNCTIONS);
+    let passed = |condition: &Expr| -> Result<usize> {
+        let value = evaluator
+            .run(condition)?
+            .try_downcast::<BooleanType>()
+            .unwrap();
+        let bitmap = value.into_column().unwrap();
+        Ok(bitmap.len() - bitmap.unset_bits())
+    };
+    assert_eq!(passed(&ordered[0])?, 100);
+    assert_eq!(passed(&declared[0])?, 271);
+
+    // The conditions which can't be reordered keep the declared order.
+    let cast = Expr::Cast {
+        span: None,
+        is_try: false,
+        expr: Box::new(column_ref(1, DataType::String)),
+        dest_type: DataType::Number(NumberDataType::Int32),
+    };
+    let lt_cast = check_function(
+        None,
+        "lt",
+        &[],
+        &[cast, constant(Scalar::Number(NumberScalar::Int32(100)))],
+        &BUILTIN_FUNCTIONS,
+    )?;
+    let declared = vec![like.clone(), lt_cast.clone()];
+    let ordered = HashJoinDesc::order_non_equi_conditions(declared);
+    assert_eq!(
+        ordered.iter().map(Expr::sql_display).collect::<Vec<_>>(),
+        vec![like.sql_display(), lt_cast.sql_display()]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lowercase_case_insensitive_keys() {
+    let build_emails = StringType::from_data(vec!["Alice@Example.com", "BOB@example.com"]);
+    let build_ids = Int32Type::from_data(vec![1, 2]);
+    let probe_emails = StringType::from_data_with_validity(
+        vec![
+            "alice@EXAMPLE.com",
+            "carol@example.com",
+            "",
+            "bob@Example.COM",
+        ],
+        vec![true, true, false, true],
+    );
+
+    // Only the string key is case-insensitive, the lowercased keys are hashed and compared.
+    let case_insensitive = [true, false];
+    let mut build_keys = vec![build_emails, build_ids.clone()];
+    HashJoinDesc::lowercase_keys(&case_insensitive, &mut build_keys);
+    assert_eq!(
+        build_keys[0],
+        StringType::from_data(vec!["alice@example.com", "bob@example.com"])
+    );
+    assert_eq!(build_keys[1], build_ids);
+    let mut probe_keys = vec![probe_emails.clone()];
+    HashJoinDesc::lowercase_keys(&case_insensitive, &mut probe_keys);
+
+    assert_eq!(
+        probe_keys[0],
+        StringType::from_data_with_validity(
+            vec![
+                "alice@example.com",
+                "carol@example.com",
+                "",
+                "bob@example.com",
+            ],
+            vec![true, true, false, true],
+        )
+    );
+
+    // The NULLs stay NULL, the case-sensitive keys are left as they are.
+    assert_eq!(probe_keys[0].index(2), Some(ScalarRef::Null));
+    let mut keys = vec![probe_emails.clone()];
+    HashJoinDesc::lowercase_keys(&[false], &mut keys);
+    assert_eq!(keys[0], probe_emails);
+}
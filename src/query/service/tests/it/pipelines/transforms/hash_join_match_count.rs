@@ -0,0 +1,134 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use databend_common_base::base::tokio;
+use databend_common_exception::Result;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::MATCH_COUNT_COL_NAME;
+use databend_common_sql::executor::PhysicalPlanBuilder;
+use databend_common_sql::optimizer::SExpr;
+use databend_common_sql::plans::JoinType;
+use databend_common_sql::plans::Plan;
+use databend_common_sql::plans::RelOperator;
+use databend_common_sql::Planner;
+use databend_query::pipelines::executor::ExecutorSettings;
+use databend_query::pipelines::executor::PipelinePullingExecutor;
+use databend_query::schedulers::build_query_pipeline_without_render_result_set;
+use databend_query::stream::PullingExecutorStream;
+use databend_query::test_kits::TestFixture;
+use futures_util::TryStreamExt;
+
+// Asks the left semi joins of the plan to emit the match counts.
+fn with_match_count(s_expr: &SExpr, num_joins: &mut usize) -> SExpr {
+    let children = s_expr
+        .children()
+        .map(|child| Arc::new(with_match_count(child, num_joins)))
+        .collect::<Vec<_>>();
+    let s_expr = s_expr.replace_children(children);
+    match s_expr.plan() {
+        RelOperator::Join(join) if join.join_type == JoinType::LeftSemi => {
+            *num_joins += 1;
+            let mut join = join.clone();
+            join.emit_match_count = true;
+            s_expr.replace_plan(Arc::new(join.into()))
+        }
+        _ => s_expr,
+    }
+}
+
+// Collects the pairs of the int key in the first column and the count in the last column.
+fn key_counts(blocks: &[DataBlock]) -> Result<BTreeMap<i32, Vec<u64>>> {
+    let block = DataBlock::concat(blocks)?;
+    let num_rows = block.num_rows();
+    let keys = block.get_by_offset(0).to_column(num_rows).remove_nullable();
+    let keys = Int32Type::try_downcast_column(&keys).unwrap();
+    let counts = block
+        .get_by_offset(block.num_columns() - 1)
+        .to_column(num_rows);
+    let counts = UInt64Type::try_downcast_column(&counts).unwrap();
+    let mut key_counts = BTreeMap::<i32, Vec<u64>>::new();
+    for (key, count) in keys.iter().zip(counts.iter()) {
+        key_counts.entry(*key).or_default().push(*count);
+    }
+    Ok(key_counts)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_probe_emits_match_count() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture.execute_command("CREATE TABLE dim (id int)").await?;
+    fixture
+        .execute_command("INSERT INTO dim VALUES (1), (1), (2), (3), (3), (3), (NULL)")
+        .await?;
+    fixture
+        .execute_command("CREATE TABLE fact (id int) AS SELECT number % 5 FROM numbers(100)")
+        .await?;
+
+    let ctx = fixture.new_query_ctx().await?;
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _) = planner
+        .plan_sql("SELECT id FROM fact WHERE id IN (SELECT id FROM dim)")
+        .await?;
+    let Plan::Query {
+        s_expr,
+        metadata,
+        bind_context,
+        ..
+    } = plan
+    else {
+        unreachable!("Query plan expected")
+    };
+    let mut num_joins = 0;
+    let s_expr = with_match_count(&s_expr, &mut num_joins);
+    assert_eq!(num_joins, 1);
+    let mut builder = PhysicalPlanBuilder::new(metadata.clone(), ctx.clone(), false);
+    let plan = builder.build(&s_expr, bind_context.column_set()).await?;
+
+    // The match count is the last column of the output schema.
+    let output_schema = plan.output_schema()?;
+    let num_fields = output_schema.num_fields();
+    assert_eq!(
+        output_schema.field(num_fields - 1).name(),
+        MATCH_COUNT_COL_NAME
+    );
+
+    let build_res = build_query_pipeline_without_render_result_set(&ctx, &plan).await?;
+    let settings = ExecutorSettings::try_create(ctx.clone())?;
+    let executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+    ctx.set_executor(executor.get_inner())?;
+    let blocks = PullingExecutorStream::create(executor)?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let actual = key_counts(&blocks)?;
+
+    // Every matched probe row has the count of the grouped join.
+    let blocks = fixture
+        .execute_query("SELECT fact.id, d.c FROM fact JOIN (SELECT id, count(*) c FROM dim GROUP BY id) d ON fact.id = d.id")
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let expected = key_counts(&blocks)?;
+    assert_eq!(actual.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    for (key, counts) in actual.iter() {
+        assert_eq!(counts.len(), 20);
+        assert_eq!(counts, &expected[key]);
+    }
+    Ok(())
+}
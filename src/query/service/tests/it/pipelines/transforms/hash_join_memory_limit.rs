@@ -0,0 +1,54 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_base::base::tokio;
+use databend_common_exception::ErrorCode;
+use databend_common_exception::Result;
+use databend_common_expression::DataBlock;
+use databend_query::test_kits::TestFixture;
+use futures_util::TryStreamExt;
+
+async fn query(fixture: &TestFixture, sql: &str) -> Result<Vec<DataBlock>> {
+    let stream = fixture.execute_query(sql).await?;
+    stream.try_collect::<Vec<DataBlock>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_hash_join_build_exceeds_memory_limit() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    fixture
+        .execute_command("CREATE TABLE t1 (id int) AS SELECT number FROM numbers(10000)")
+        .await?;
+    fixture
+        .execute_command("CREATE TABLE t2 (id int) AS SELECT number FROM numbers(10000)")
+        .await?;
+    let sql = "SELECT count(*) FROM t1 JOIN t2 ON t1.id = t2.id";
+
+    // Spilling is disabled, the build side can't fit into 1KB.
+    fixture
+        .execute_command("SET max_memory_usage = 1024")
+        .await?;
+    fixture
+        .execute_command("SET join_spilling_memory_ratio = 0")
+        .await?;
+    let err = query(&fixture, sql).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::EXCEED_MEMORY_LIMIT);
+    assert!(err.message().contains("join_spilling_memory_ratio"));
+
+    // Without the limit, the whole build side is kept in memory.
+    fixture.execute_command("SET max_memory_usage = 0").await?;
+    let blocks = query(&fixture, sql).await?;
+    assert_eq!(DataBlock::concat(&blocks)?.num_rows(), 1);
+    Ok(())
+}
@@ -0,0 +1,27 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod hash_join_build_row_id;
+mod hash_join_desc;
+mod hash_join_match_count;
+mod hash_join_memory_limit;
+mod transform_cast_schema;
+mod transform_distinct_sorted;
+mod transform_expand_grouping_sets;
+mod transform_fill_null;
+mod transform_project;
+mod transform_repartition;
+mod transform_running_total;
+mod transform_union_distinct;
+mod transform_unnest;
@@ -0,0 +1,88 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt8Type;
+use databend_common_expression::DataBlock;
+use databend_common_expression::DataField;
+use databend_common_expression::DataSchemaRef;
+use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::FromData;
+use databend_common_expression::FunctionContext;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::CastErrorPolicy;
+use databend_query::pipelines::processors::TransformCastSchema;
+
+fn schema(data_type: DataType) -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![DataField::new("a", data_type)])
+}
+
+fn narrowing_cast(error_policy: CastErrorPolicy) -> databend_common_exception::Result<DataBlock> {
+    let mut transform = TransformCastSchema::try_new(
+        schema(DataType::Number(NumberDataType::Int64)),
+        schema(DataType::Number(NumberDataType::UInt8)),
+        FunctionContext::default(),
+        error_policy,
+    )?;
+    let block = DataBlock::new_from_columns(vec![Int64Type::from_data(vec![1, 300, -1, 255])]);
+    transform.transform(block)
+}
+
+#[test]
+fn test_cast_schema_strict() {
+    let err = narrowing_cast(CastErrorPolicy::Strict).unwrap_err();
+    assert!(
+        err.message()
+            .contains("fail to auto cast column a (Int64) to column a (UInt8)"),
+        "{}",
+        err.message()
+    );
+}
+
+#[test]
+fn test_cast_schema_null_on_error() {
+    let block = narrowing_cast(CastErrorPolicy::NullOnError).unwrap();
+    let entry = block.get_by_offset(0);
+    assert_eq!(
+        entry.data_type,
+        DataType::Nullable(Box::new(DataType::Number(NumberDataType::UInt8)))
+    );
+
+    let expected =
+        UInt8Type::from_data_with_validity(vec![1, 0, 0, 255], vec![true, false, false, true]);
+    let column = entry.value.convert_to_full_column(&entry.data_type, 4);
+    assert_eq!(column, expected);
+}
+
+#[test]
+fn test_cast_schema_null_on_error_in_range() {
+    // Nothing is lost, the values are kept as is
+    let mut transform = TransformCastSchema::try_new(
+        schema(DataType::Number(NumberDataType::Int64)),
+        schema(DataType::Number(NumberDataType::UInt8)),
+        FunctionContext::default(),
+        CastErrorPolicy::NullOnError,
+    )
+    .unwrap();
+    let block = DataBlock::new_from_columns(vec![Int64Type::from_data(vec![0, 42])]);
+    let block = transform.transform(block).unwrap();
+    let entry = block.get_by_offset(0);
+    let column = entry.value.convert_to_full_column(&entry.data_type, 2);
+    assert_eq!(
+        column,
+        UInt8Type::from_data_with_validity(vec![0, 42], vec![true, true])
+    );
+}
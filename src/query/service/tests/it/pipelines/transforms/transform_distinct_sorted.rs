@@ -0,0 +1,69 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::StringType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformDistinctSorted;
+
+#[test]
+fn test_distinct_sorted() {
+    let mut transform = TransformDistinctSorted::new(vec![0, 1]);
+
+    let block = transform
+        .transform(DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![1, 1, 1, 2, 2, 3]),
+            StringType::from_data(vec!["a", "a", "b", "b", "b", "c"]),
+            Int32Type::from_data(vec![10, 11, 12, 13, 14, 15]),
+        ]))
+        .unwrap();
+    // The first row of each run is kept.
+    assert_eq!(block.num_rows(), 4);
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![1, 1, 2, 3])
+    );
+    assert_eq!(
+        block.get_by_offset(1).value.as_column().unwrap(),
+        &StringType::from_data(vec!["a", "b", "b", "c"])
+    );
+    assert_eq!(
+        block.get_by_offset(2).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![10, 12, 13, 15])
+    );
+
+    // The run of the last row continues in the next block.
+    let block = transform
+        .transform(DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![3, 3, 4, 4]),
+            StringType::from_data(vec!["c", "c", "c", "c"]),
+            Int32Type::from_data(vec![16, 17, 18, 19]),
+        ]))
+        .unwrap();
+    assert_eq!(
+        block.get_by_offset(2).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![18])
+    );
+
+    let block = transform
+        .transform(DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![4, 4]),
+            StringType::from_data(vec!["c", "c"]),
+            Int32Type::from_data(vec![20, 21]),
+        ]))
+        .unwrap();
+    assert_eq!(block.num_rows(), 0);
+}
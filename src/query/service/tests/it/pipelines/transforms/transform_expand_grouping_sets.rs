@@ -0,0 +1,94 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::UInt32Type;
+use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformExpandGroupingSets;
+
+#[test]
+fn test_expand_cube_grouping_sets() {
+    let block = DataBlock::new_from_columns(vec![
+        Int32Type::from_data(vec![1, 2]),
+        StringType::from_data(vec!["x", "y"]),
+        Int64Type::from_data(vec![10, 20]),
+    ]);
+
+    // CUBE (a, b): (a, b), (b), (a), ()
+    let mut transform = TransformExpandGroupingSets::new(vec![0, 1], vec![0, 1, 2, 3]);
+    let block = transform.transform(block).unwrap();
+    assert_eq!(block.num_rows(), 8);
+    assert_eq!(block.num_columns(), 6);
+
+    let column = |index: usize, data_type: DataType| -> Column {
+        let entry = block.get_by_offset(index);
+        assert_eq!(entry.data_type, data_type);
+        entry
+            .value
+            .convert_to_full_column(&data_type, block.num_rows())
+    };
+
+    let int32 = DataType::Number(NumberDataType::Int32);
+    assert_eq!(
+        column(0, int32.wrap_nullable()),
+        Int32Type::from_opt_data(vec![
+            Some(1),
+            Some(2),
+            None,
+            None,
+            Some(1),
+            Some(2),
+            None,
+            None
+        ])
+    );
+    assert_eq!(
+        column(1, DataType::String.wrap_nullable()),
+        StringType::from_opt_data(vec![
+            Some("x"),
+            Some("y"),
+            Some("x"),
+            Some("y"),
+            None,
+            None,
+            None,
+            None
+        ])
+    );
+    assert_eq!(
+        column(2, DataType::Number(NumberDataType::Int64)),
+        Int64Type::from_data(vec![10, 20, 10, 20, 10, 20, 10, 20])
+    );
+
+    // The original group by columns are appended, followed by the grouping ids.
+    assert_eq!(
+        column(3, int32),
+        Int32Type::from_data(vec![1, 2, 1, 2, 1, 2, 1, 2])
+    );
+    assert_eq!(
+        column(4, DataType::String),
+        StringType::from_data(vec!["x", "y", "x", "y", "x", "y", "x", "y"])
+    );
+    assert_eq!(
+        column(5, DataType::Number(NumberDataType::UInt32)),
+        UInt32Type::from_data(vec![0, 0, 1, 1, 2, 2, 3, 3])
+    );
+}
@@ -0,0 +1,90 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use databend_common_expression::types::BooleanType;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformFillNull;
+
+#[test]
+fn test_fill_null() {
+    let block = DataBlock::new_from_columns(vec![
+        Int32Type::from_data_with_validity(vec![1, 0, 3, 0], vec![true, false, true, false]),
+        BooleanType::from_data_with_validity(vec![true, false, false, true], vec![
+            true, false, true, false,
+        ]),
+        StringType::from_data_with_validity(vec!["a", "", "c", ""], vec![true, false, true, false]),
+        Int32Type::from_data_with_validity(vec![1, 0, 3, 0], vec![true, false, true, false]),
+    ]);
+    let mut transform = TransformFillNull::new(HashMap::from([
+        (0, Scalar::Number(NumberScalar::Int32(-1))),
+        (1, Scalar::Boolean(true)),
+        (2, Scalar::String("x".to_string())),
+    ]));
+    let block = transform.transform(block).unwrap();
+
+    assert_eq!(
+        block.get_by_offset(0).data_type,
+        DataType::Number(NumberDataType::Int32)
+    );
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![1, -1, 3, -1])
+    );
+    assert_eq!(
+        block.get_by_offset(1).value.as_column().unwrap(),
+        &BooleanType::from_data(vec![true, true, false, true])
+    );
+    assert_eq!(
+        block.get_by_offset(2).value.as_column().unwrap(),
+        &StringType::from_data(vec!["a", "x", "c", "x"])
+    );
+    // The columns without default are kept as is.
+    assert_eq!(
+        block.get_by_offset(3).value.as_column().unwrap(),
+        &Int32Type::from_data_with_validity(vec![1, 0, 3, 0], vec![true, false, true, false])
+    );
+}
+
+#[test]
+fn test_fill_null_scalar() {
+    let block = DataBlock::new(
+        vec![BlockEntry::new(
+            DataType::Nullable(Box::new(DataType::Number(NumberDataType::Int32))),
+            Value::Scalar(Scalar::Null),
+        )],
+        3,
+    );
+    let mut transform =
+        TransformFillNull::new(HashMap::from([(0, Scalar::Number(NumberScalar::Int32(7)))]));
+    let block = transform.transform(block).unwrap();
+
+    let entry = block.get_by_offset(0);
+    assert_eq!(entry.data_type, DataType::Number(NumberDataType::Int32));
+    assert_eq!(
+        entry.value,
+        Value::Scalar(Scalar::Number(NumberScalar::Int32(7)))
+    );
+}
@@ -0,0 +1,55 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::StringType;
+use databend_common_expression::types::ValueType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformProject;
+
+#[test]
+fn test_project() {
+    let block = DataBlock::new_from_columns(vec![
+        Int32Type::from_data(vec![1, 2, 3]),
+        StringType::from_data(vec!["a", "b", "c"]),
+        Int32Type::from_data(vec![4, 5, 6]),
+    ]);
+    let mut transform = TransformProject::new(vec![2, 0]);
+    let projected = transform.transform(block.clone()).unwrap();
+
+    // The string column is dropped, and the kept columns are in the order of the projection.
+    assert_eq!(projected.num_columns(), 2);
+    assert_eq!(projected.num_rows(), 3);
+    assert_eq!(
+        projected.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![4, 5, 6])
+    );
+    assert_eq!(
+        projected.get_by_offset(1).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![1, 2, 3])
+    );
+
+    // The kept columns share the buffers with the input block.
+    let buffer = |block: &DataBlock, offset: usize| {
+        let column = block.get_by_offset(offset).value.as_column().unwrap();
+        Int32Type::try_downcast_column(column).unwrap().as_ptr()
+    };
+    assert_eq!(buffer(&projected, 0), buffer(&block, 2));
+    assert_eq!(buffer(&projected, 1), buffer(&block, 0));
+
+    let mut transform = TransformProject::new(vec![3]);
+    assert!(transform.transform(block).is_err());
+}
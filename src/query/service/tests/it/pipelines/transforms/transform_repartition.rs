@@ -0,0 +1,86 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::BlockEntry;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_expression::Value;
+use databend_query::pipelines::processors::TransformRepartition;
+
+#[test]
+fn test_repartition_equal_keys() {
+    let partitions = 4;
+    let blocks = [
+        DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![1, 2, 3, 1, 2, 3]),
+            StringType::from_data(vec!["a", "b", "c", "a", "b", "d"]),
+        ]),
+        DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![3, 2, 1, 3]),
+            StringType::from_data(vec!["d", "b", "a", "c"]),
+        ]),
+    ];
+
+    let mut seen = HashMap::new();
+    for block in blocks.iter() {
+        let indices = TransformRepartition::partition_indices(block, &[0, 1], partitions);
+        assert_eq!(indices.len(), block.num_rows());
+
+        let keys = block.get_by_offset(0).value.as_column().unwrap();
+        let names = block.get_by_offset(1).value.as_column().unwrap();
+        for (row, partition) in indices.into_iter().enumerate() {
+            assert!(partition < partitions as u64);
+            let key = (
+                keys.index(row).unwrap().to_owned(),
+                names.index(row).unwrap().to_owned(),
+            );
+            assert_eq!(*seen.entry(key).or_insert(partition), partition);
+        }
+    }
+    assert_eq!(seen.len(), 4);
+}
+
+#[test]
+fn test_repartition_scalar_keys() {
+    let partitions = 3;
+    let column = DataBlock::new_from_columns(vec![Int32Type::from_data(vec![7, 7, 7])]);
+    let scalar = DataBlock::new(
+        vec![BlockEntry::new(
+            DataType::Number(NumberDataType::Int32),
+            Value::Scalar(Scalar::Number(NumberScalar::Int32(7))),
+        )],
+        3,
+    );
+
+    let expected = TransformRepartition::partition_indices(&column, &[0], partitions);
+    assert_eq!(
+        TransformRepartition::partition_indices(&scalar, &[0], partitions),
+        expected
+    );
+    assert!(expected.iter().all(|partition| *partition == expected[0]));
+
+    // Without the keys, all the rows go to the first partition.
+    assert_eq!(
+        TransformRepartition::partition_indices(&column, &[], partitions),
+        vec![0; 3]
+    );
+}
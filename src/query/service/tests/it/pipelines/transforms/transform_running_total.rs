@@ -0,0 +1,122 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_expression::types::Float64Type;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::Int64Type;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::types::F64;
+use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::RunningTotalKind;
+use databend_query::pipelines::processors::TransformRunningTotal;
+
+// The time series 1..=9 is split into three blocks, the values are NULL at 3 and 7.
+fn sorted_blocks() -> Vec<DataBlock> {
+    [1..4, 4..8, 8..10]
+        .into_iter()
+        .map(|range| {
+            let times = range.clone().collect::<Vec<i32>>();
+            let values = range.clone().map(|t| 10 - t).collect::<Vec<i32>>();
+            let validity = range.map(|t| t != 3 && t != 7).collect::<Vec<_>>();
+            DataBlock::new_from_columns(vec![
+                Int32Type::from_data(times),
+                Int32Type::from_data_with_validity(values, validity),
+            ])
+        })
+        .collect()
+}
+
+fn running_totals(kind: RunningTotalKind) -> Vec<Column> {
+    let mut transform = TransformRunningTotal::new(1, kind);
+    sorted_blocks()
+        .into_iter()
+        .map(|block| {
+            let block = transform.transform(block).unwrap();
+            assert_eq!(block.num_columns(), 3);
+            block.get_by_offset(2).value.as_column().unwrap().clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_running_sum() {
+    // The values are 9, 8, NULL, 6, 5, 4, NULL, 2, 1
+    let totals = running_totals(RunningTotalKind::Sum);
+    let expected = [vec![9, 17, 17], vec![23, 28, 32, 32], vec![34, 35]];
+    for (total, expected) in totals.iter().zip(expected) {
+        let validity = vec![true; expected.len()];
+        assert_eq!(
+            total,
+            &Int64Type::from_data_with_validity(expected, validity)
+        );
+    }
+}
+
+#[test]
+fn test_running_count_min_max() {
+    let totals = running_totals(RunningTotalKind::Count);
+    assert_eq!(totals[0], UInt64Type::from_data(vec![1, 2, 2]));
+    assert_eq!(totals[1], UInt64Type::from_data(vec![3, 4, 5, 5]));
+    assert_eq!(totals[2], UInt64Type::from_data(vec![6, 7]));
+
+    // The values are descending, so the min changes on every non-NULL row
+    let totals = running_totals(RunningTotalKind::Min);
+    assert_eq!(
+        totals[1],
+        Int32Type::from_data_with_validity(vec![6, 5, 4, 4], vec![true; 4])
+    );
+    assert_eq!(
+        totals[2],
+        Int32Type::from_data_with_validity(vec![2, 1], vec![true; 2])
+    );
+
+    let totals = running_totals(RunningTotalKind::Max);
+    for total in totals.iter() {
+        let len = total.len();
+        assert_eq!(
+            total,
+            &Int32Type::from_data_with_validity(vec![9; len], vec![true; len])
+        );
+    }
+}
+
+#[test]
+fn test_running_sum_leading_nulls() {
+    let mut transform = TransformRunningTotal::new(0, RunningTotalKind::Sum);
+    let block = DataBlock::new_from_columns(vec![Float64Type::from_data_with_validity(
+        vec![
+            F64::from(0.0),
+            F64::from(1.5),
+            F64::from(0.0),
+            F64::from(2.5),
+        ],
+        vec![false, true, false, true],
+    )]);
+    let block = transform.transform(block).unwrap();
+    assert_eq!(
+        block.get_by_offset(1).value.as_column().unwrap(),
+        &Float64Type::from_data_with_validity(
+            vec![
+                F64::from(0.0),
+                F64::from(1.5),
+                F64::from(1.5),
+                F64::from(4.0)
+            ],
+            vec![false, true, true, true]
+        )
+    );
+}
@@ -0,0 +1,164 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use databend_common_base::base::tokio;
+use databend_common_catalog::table_context::TableContext;
+use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::NumberScalar;
+use databend_common_expression::types::StringType;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::Scalar;
+use databend_common_pipeline_core::processors::connect;
+use databend_common_pipeline_core::processors::Event;
+use databend_common_pipeline_core::processors::InputPort;
+use databend_common_pipeline_core::processors::OutputPort;
+use databend_common_pipeline_core::processors::Processor;
+use databend_common_pipeline_core::query_spill_prefix;
+use databend_common_storage::DataOperator;
+use databend_query::pipelines::processors::TransformUnionDistinct;
+use databend_query::spillers::Spiller;
+use databend_query::spillers::SpillerConfig;
+use databend_query::spillers::SpillerType;
+use databend_query::test_kits::TestFixture;
+
+fn block(ids: Vec<i32>, names: Vec<&str>) -> DataBlock {
+    DataBlock::new_from_columns(vec![
+        Int32Type::from_data(ids),
+        StringType::from_data(names),
+    ])
+}
+
+// The three inputs overlap, each input also repeats its own rows.
+fn input_blocks() -> Vec<Vec<DataBlock>> {
+    vec![
+        vec![
+            block(vec![1, 2, 3, 1], vec!["a", "b", "c", "a"]),
+            block(vec![4, 2], vec!["d", "b"]),
+        ],
+        vec![
+            block(vec![2, 3, 5], vec!["b", "x", "e"]),
+            block(vec![1, 1, 6], vec!["a", "a", "f"]),
+        ],
+        vec![block(vec![6, 7, 3, 4, 4], vec!["f", "g", "c", "d", "d"])],
+    ]
+}
+
+async fn run_union_distinct(mut transform: TransformUnionDistinct) -> Result<Vec<DataBlock>> {
+    let mut upstreams = Vec::new();
+    for input in transform.get_inputs() {
+        let output = OutputPort::create();
+        unsafe { connect(&input, &output) };
+        upstreams.push(output);
+    }
+    let downstream = InputPort::create();
+    unsafe { connect(&downstream, &transform.get_output()) };
+
+    let mut pending = input_blocks()
+        .into_iter()
+        .map(VecDeque::from)
+        .collect::<Vec<_>>();
+    let mut results = Vec::new();
+    loop {
+        for (output, blocks) in upstreams.iter().zip(pending.iter_mut()) {
+            if output.can_push() {
+                match blocks.pop_front() {
+                    Some(block) => output.push_data(Ok(block)),
+                    None => output.finish(),
+                }
+            }
+        }
+        if downstream.has_data() {
+            results.push(downstream.pull_data().unwrap()?);
+        }
+        downstream.set_need_data();
+
+        match transform.event()? {
+            Event::Sync => transform.process()?,
+            Event::Async => transform.async_process().await?,
+            Event::Finished => break,
+            _ => {}
+        }
+    }
+    Ok(results)
+}
+
+fn assert_distinct_rows(blocks: &[DataBlock]) {
+    let mut rows = HashMap::new();
+    for block in blocks {
+        let ids = block.get_by_offset(0).value.as_column().unwrap();
+        let names = block.get_by_offset(1).value.as_column().unwrap();
+        for row in 0..block.num_rows() {
+            let key = (
+                ids.index(row).unwrap().to_owned(),
+                names.index(row).unwrap().to_owned(),
+            );
+            *rows.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let expected = [
+        (1, "a"),
+        (2, "b"),
+        (3, "c"),
+        (4, "d"),
+        (3, "x"),
+        (5, "e"),
+        (6, "f"),
+        (7, "g"),
+    ];
+    assert_eq!(rows.len(), expected.len());
+    for (id, name) in expected {
+        let key = (
+            Scalar::Number(NumberScalar::Int32(id)),
+            Scalar::String(name.to_string()),
+        );
+        assert_eq!(rows.get(&key), Some(&1), "row ({id}, {name})");
+    }
+}
+
+fn data_types() -> Vec<DataType> {
+    vec![DataType::Number(NumberDataType::Int32), DataType::String]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_union_distinct() -> Result<()> {
+    let transform = TransformUnionDistinct::create(3, data_types());
+    let blocks = run_union_distinct(transform).await?;
+    assert_distinct_rows(&blocks);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_union_distinct_spill() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+    let ctx = fixture.new_query_ctx().await?;
+    let tenant = ctx.get_tenant();
+    let spiller_config =
+        SpillerConfig::create(query_spill_prefix(tenant.tenant_name(), &ctx.get_id()));
+    let operator = DataOperator::instance().operator();
+    let spiller = Spiller::create(ctx, operator, spiller_config, SpillerType::UnionDistinct)?;
+
+    // Spills after every block, the duplicates are spilled into the same partition
+    let transform = TransformUnionDistinct::create(3, data_types()).with_spiller(spiller, 0);
+    let blocks = run_union_distinct(transform).await?;
+    assert_distinct_rows(&blocks);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use databend_common_arrow::arrow::bitmap::Bitmap;
+use databend_common_expression::types::nullable::NullableColumn;
+use databend_common_expression::types::ArrayColumn;
+use databend_common_expression::types::DataType;
+use databend_common_expression::types::Int32Type;
+use databend_common_expression::types::NumberDataType;
+use databend_common_expression::types::UInt64Type;
+use databend_common_expression::Column;
+use databend_common_expression::DataBlock;
+use databend_common_expression::FromData;
+use databend_common_expression::ScalarRef;
+use databend_common_pipeline_transforms::processors::Transform;
+use databend_query::pipelines::processors::TransformUnnest;
+
+// The inner arrays [1, 2], [3], [] and [4, 5].
+fn inner_arrays() -> Column {
+    Column::Array(Box::new(ArrayColumn {
+        values: Int32Type::from_data(vec![1, 2, 3, 4, 5]),
+        offsets: vec![0, 2, 3, 3, 5].into(),
+    }))
+}
+
+// The ids 10, 20, 30, 40 and the nested arrays [[1, 2], [3]], [], [[], [4, 5]] and NULL.
+fn nested_block() -> DataBlock {
+    let nested = Column::Array(Box::new(ArrayColumn {
+        values: inner_arrays(),
+        offsets: vec![0, 2, 2, 4, 4].into(),
+    }));
+    let validity = Bitmap::from([true, true, true, false]);
+    DataBlock::new_from_columns(vec![
+        Int32Type::from_data(vec![10, 20, 30, 40]),
+        NullableColumn::new_column(nested, validity),
+    ])
+}
+
+#[test]
+fn test_unnest_nested_array() {
+    let mut transform = TransformUnnest::new(1, true, false);
+    let block = transform.transform(nested_block()).unwrap();
+
+    assert_eq!(block.num_columns(), 3);
+    assert_eq!(block.num_rows(), 4);
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![10, 10, 30, 30])
+    );
+    // The elements are the inner arrays, they are not flattened
+    assert_eq!(
+        block.get_by_offset(1).data_type,
+        DataType::Array(Box::new(DataType::Number(NumberDataType::Int32)))
+    );
+    assert_eq!(
+        block.get_by_offset(1).value.as_column().unwrap(),
+        &inner_arrays()
+    );
+    assert_eq!(
+        block.get_by_offset(2).value.as_column().unwrap(),
+        &UInt64Type::from_data(vec![1, 2, 1, 2])
+    );
+}
+
+#[test]
+fn test_unnest_outer() {
+    let mut transform = TransformUnnest::new(1, true, true);
+    let block = transform.transform(nested_block()).unwrap();
+
+    // The empty array and the NULL array produce a NULL row each
+    assert_eq!(block.num_rows(), 6);
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![10, 10, 20, 30, 30, 40])
+    );
+
+    let elements = block.get_by_offset(1).value.as_column().unwrap();
+    assert_eq!(
+        block.get_by_offset(1).data_type,
+        DataType::Array(Box::new(DataType::Number(NumberDataType::Int32))).wrap_nullable()
+    );
+    let inner = inner_arrays();
+    let expected = [Some(0), Some(1), None, Some(2), Some(3), None];
+    for (row, expected) in expected.into_iter().enumerate() {
+        match expected {
+            Some(index) => assert_eq!(elements.index(row), inner.index(index)),
+            None => assert_eq!(elements.index(row), Some(ScalarRef::Null)),
+        }
+    }
+
+    assert_eq!(
+        block.get_by_offset(2).value.as_column().unwrap(),
+        &UInt64Type::from_data_with_validity(vec![1, 2, 0, 1, 2, 0], vec![
+            true, true, false, true, true, false
+        ])
+    );
+}
+
+#[test]
+fn test_unnest_without_ordinality() {
+    let mut transform = TransformUnnest::new(0, false, false);
+    let block = DataBlock::new_from_columns(vec![inner_arrays()]);
+    let block = transform.transform(block).unwrap();
+
+    assert_eq!(block.num_columns(), 1);
+    assert_eq!(
+        block.get_by_offset(0).value.as_column().unwrap(),
+        &Int32Type::from_data(vec![1, 2, 3, 4, 5])
+    );
+}
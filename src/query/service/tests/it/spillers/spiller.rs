@@ -12,7 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use databend_common_base::base::tokio;
+use databend_common_base::runtime::profile::Profile;
+use databend_common_base::runtime::profile::ProfileStatisticsName;
+use databend_common_base::runtime::ThreadTracker;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::Result;
 use databend_common_expression::types::DataType;
@@ -72,3 +77,56 @@ async fn test_spill_with_partition() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_spill_profile() -> Result<()> {
+    let fixture = TestFixture::setup().await?;
+
+    let ctx = fixture.new_query_ctx().await?;
+    let tenant = ctx.get_tenant();
+    let spiller_config =
+        SpillerConfig::create(query_spill_prefix(tenant.tenant_name(), &ctx.get_id()));
+    let operator = DataOperator::instance().operator();
+
+    let mut spiller = Spiller::create(ctx, operator, spiller_config, SpillerType::HashJoinBuild)?;
+
+    let profile = Arc::new(Profile::create(
+        0,
+        "HashJoinBuild".to_string(),
+        None,
+        None,
+        None,
+        Arc::new(String::new()),
+        Arc::new(vec![]),
+        None,
+    ));
+    let mut payload = ThreadTracker::new_tracking_payload();
+    payload.profile = Some(profile.clone());
+    let _guard = ThreadTracker::tracking(payload);
+
+    ThreadTracker::tracking_future(async move {
+        let data =
+            DataBlock::new_from_columns(vec![Int32Type::from_data((0..100).collect::<Vec<_>>())]);
+        spiller.spill_with_partition(0_u8, data.clone()).await?;
+        spiller.spill_with_partition(1_u8, data).await?;
+        spiller.read_spilled_partition(&(0_u8)).await?;
+        Result::Ok(())
+    })
+    .await?;
+
+    assert_eq!(
+        profile.load_profile(ProfileStatisticsName::SpillWriteCount),
+        2
+    );
+    assert!(profile.load_profile(ProfileStatisticsName::SpillWriteBytes) > 0);
+    assert_eq!(
+        profile.load_profile(ProfileStatisticsName::SpillReadCount),
+        1
+    );
+    assert_eq!(
+        profile.load_profile(ProfileStatisticsName::SpillReadBytes) * 2,
+        profile.load_profile(ProfileStatisticsName::SpillWriteBytes)
+    );
+
+    Ok(())
+}
@@ -272,6 +272,18 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("enable_hash_join_build_cache", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Keeps the built hash table of a standalone hash join in the session, later statements with the same build side and keys reuse it instead of building again.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("hash_join_build_cache_size_mb", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(256),
+                    desc: "Sets the memory (MB) of the built hash tables kept in the session by `enable_hash_join_build_cache`, the oldest ones are evicted beyond it.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=u64::MAX)),
+                }),
                 ("join_spilling_memory_ratio", DefaultSettingValue {
                     value: UserSettingValue::UInt64(60),
                     desc: "Sets the maximum memory ratio in bytes that hash join can use before spilling data to storage during query execution, 0 is unlimited",
@@ -356,6 +368,12 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("hash_join_case_insensitive_keys", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Compares the string equi-join keys ASCII case-insensitively, meant to be set for a query by the SET_VAR hint. The other comparisons are not affected.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
                 ("storage_fetch_part_num", DefaultSettingValue {
                     value: UserSettingValue::UInt64(2),
                     desc: "Sets the number of partitions that are fetched in parallel from storage during query execution.",
@@ -685,6 +703,18 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("agg_hashtable_load_factor", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(150),
+                    desc: "Sets the load factor in percent of the aggregate hashtable, it grows once its capacity is less than the groups times the load factor.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(101..=1600)),
+                }),
+                ("agg_hashtable_initial_capacity", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(32768),
+                    desc: "Sets the initial capacity of the aggregate hashtable, rounded up to a power of two.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(1024..=1 << 17)),
+                }),
                 ("numeric_cast_option", DefaultSettingValue {
                     value: UserSettingValue::String("rounding".to_string()),
                     desc: "Set numeric cast mode as \"rounding\" or \"truncating\".",
@@ -739,6 +769,96 @@ impl DefaultSettings {
                     mode: SettingMode::Both,
                     range: Some(SettingRange::Numeric(0..=1)),
                 }),
+                ("datetime_parse_error_with_value", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Include the offending value and its row in the error of parsing date and timestamp.(disable by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("datetime_parse_error_summary", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Report the number of the failed rows and some of their values in the error of parsing date and timestamp, instead of the first failure only.(disable by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("parse_datetime_relative_keywords", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Resolve 'now', 'today', 'yesterday' and 'tomorrow' when parsing date and timestamp strings, only works if enable_strict_datetime_parser is disabled.(disable by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("parse_datetime_comma_fraction", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Accept a comma before the fractional seconds when parsing timestamp strings, like '2023-01-01 12:00:00,500'.(disable by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("parse_datetime_ordinal_suffix", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Remove the ordinal suffixes following the day numbers when parsing date and timestamp strings, like 'March 1st, 2023'.(disable by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("leap_second_policy", DefaultSettingValue {
+                    value: UserSettingValue::String("error".to_string()),
+                    desc: "Set the parsing of the leap second like '2016-12-31 23:59:60' in timestamp strings as \"error\", \"roll_forward\" to the next second or \"clamp\" to the last microsecond of the minute.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::String(vec!["error".into(), "roll_forward".into(), "clamp".into()])),
+                }),
+                ("two_digit_year_pivot", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "The two-digit years parsed by `%y` below the pivot are in the 2000s, the others are in the 1900s. 0 keeps the default parsing of `%y`.(0 by default)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=100)),
+                }),
+                ("timestamp_output_precision", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(6),
+                    desc: "The digits of the fractional seconds kept in the timestamps produced by `now()`, `to_timestamp()` and `try_to_timestamp()`, the other digits are truncated, 0 for seconds and 3 for milliseconds.(6 by default, no truncation)",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=6)),
+                }),
+                ("timestamp_display_format", DefaultSettingValue {
+                    value: UserSettingValue::String("%Y-%m-%d %H:%M:%S%.6f".to_string()),
+                    desc: "The strftime format of `to_string(timestamp)` and the cast of timestamps to strings.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("date_display_format", DefaultSettingValue {
+                    value: UserSettingValue::String("%Y-%m-%d".to_string()),
+                    desc: "The strftime format of `to_string(date)` and the cast of dates to strings.",
+                    mode: SettingMode::Both,
+                    range: None,
+                }),
+                ("date_arithmetic_overflow", DefaultSettingValue {
+                    value: UserSettingValue::String("error".to_string()),
+                    desc: "Set the behavior of date arithmetic overflow as \"error\" or \"clamp\".",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::String(vec!["error".into(), "clamp".into()])),
+                }),
+                ("timestamp_arithmetic_overflow", DefaultSettingValue {
+                    value: UserSettingValue::String("error".to_string()),
+                    desc: "Set the behavior of timestamp arithmetic overflow as \"error\" or \"clamp\".",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::String(vec!["error".into(), "clamp".into()])),
+                }),
+                ("decimal_avg_rounding_mode", DefaultSettingValue {
+                    value: UserSettingValue::String("half_up".to_string()),
+                    desc: "Set the rounding of the decimal `avg` aggregate function as \"truncate\", \"half_up\" or \"half_even\".",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::String(vec!["truncate".into(), "half_up".into(), "half_even".into()])),
+                }),
+                ("sum_integers_as_decimal", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Accumulates the integer `sum` and the internal sum of `avg` into a Decimal(38, 0) instead of a 64-bit integer, so it doesn't overflow.",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::Numeric(0..=1)),
+                }),
+                ("float_sum_non_finite", DefaultSettingValue {
+                    value: UserSettingValue::String("propagate".to_string()),
+                    desc: "Set the behavior of the float `sum` on NaN and infinite inputs as \"propagate\" (IEEE 754), \"skip\" (like NULL) or \"error\".",
+                    mode: SettingMode::Both,
+                    range: Some(SettingRange::String(vec!["propagate".into(), "skip".into(), "error".into()])),
+                }),
                 ("disable_variant_check", DefaultSettingValue {
                     value: UserSettingValue::UInt64(0),
                     desc: "Disable variant check to allow insert invalid JSON values",
@@ -270,6 +270,17 @@ impl Settings {
         Ok(self.unchecked_try_get_u64("disable_join_reorder")? != 0)
     }
 
+    pub fn get_enable_hash_join_build_cache(&self) -> Result<bool> {
+        Ok(self.try_get_u64("enable_hash_join_build_cache")? != 0)
+    }
+
+    pub fn get_hash_join_build_cache_size(&self) -> Result<usize> {
+        Ok(
+            (self.try_get_u64("hash_join_build_cache_size_mb")? as usize)
+                .saturating_mul(1024 * 1024),
+        )
+    }
+
     pub fn get_join_spilling_memory_ratio(&self) -> Result<usize> {
         Ok(self.try_get_u64("join_spilling_memory_ratio")? as usize)
     }
@@ -306,6 +317,10 @@ impl Settings {
         Ok(self.try_get_u64("enforce_shuffle_join")? != 0)
     }
 
+    pub fn get_hash_join_case_insensitive_keys(&self) -> Result<bool> {
+        Ok(self.try_get_u64("hash_join_case_insensitive_keys")? != 0)
+    }
+
     pub fn get_enable_merge_into_row_fetch(&self) -> Result<bool> {
         Ok(self.try_get_u64("enable_merge_into_row_fetch")? != 0)
     }
@@ -415,6 +430,14 @@ impl Settings {
         Ok(self.try_get_u64("enable_experimental_aggregate_hashtable")? == 1)
     }
 
+    pub fn get_agg_hashtable_load_factor(&self) -> Result<f64> {
+        Ok(self.try_get_u64("agg_hashtable_load_factor")? as f64 / 100.0)
+    }
+
+    pub fn get_agg_hashtable_initial_capacity(&self) -> Result<usize> {
+        Ok(self.try_get_u64("agg_hashtable_initial_capacity")? as usize)
+    }
+
     pub fn get_lazy_read_threshold(&self) -> Result<u64> {
         self.try_get_u64("lazy_read_threshold")
     }
@@ -626,6 +649,66 @@ impl Settings {
         Ok(self.try_get_u64("enable_strict_datetime_parser")? != 0)
     }
 
+    pub fn get_datetime_parse_error_with_value(&self) -> Result<bool> {
+        Ok(self.try_get_u64("datetime_parse_error_with_value")? != 0)
+    }
+
+    pub fn get_datetime_parse_error_summary(&self) -> Result<bool> {
+        Ok(self.try_get_u64("datetime_parse_error_summary")? != 0)
+    }
+
+    pub fn get_parse_datetime_relative_keywords(&self) -> Result<bool> {
+        Ok(self.try_get_u64("parse_datetime_relative_keywords")? != 0)
+    }
+
+    pub fn get_parse_datetime_comma_fraction(&self) -> Result<bool> {
+        Ok(self.try_get_u64("parse_datetime_comma_fraction")? != 0)
+    }
+
+    pub fn get_parse_datetime_ordinal_suffix(&self) -> Result<bool> {
+        Ok(self.try_get_u64("parse_datetime_ordinal_suffix")? != 0)
+    }
+
+    pub fn get_leap_second_policy(&self) -> Result<String> {
+        self.try_get_string("leap_second_policy")
+    }
+
+    pub fn get_two_digit_year_pivot(&self) -> Result<u8> {
+        Ok(self.try_get_u64("two_digit_year_pivot")? as u8)
+    }
+
+    pub fn get_timestamp_output_precision(&self) -> Result<u8> {
+        Ok(self.try_get_u64("timestamp_output_precision")? as u8)
+    }
+
+    pub fn get_timestamp_display_format(&self) -> Result<String> {
+        self.try_get_string("timestamp_display_format")
+    }
+
+    pub fn get_date_display_format(&self) -> Result<String> {
+        self.try_get_string("date_display_format")
+    }
+
+    pub fn get_date_arithmetic_overflow(&self) -> Result<String> {
+        self.try_get_string("date_arithmetic_overflow")
+    }
+
+    pub fn get_timestamp_arithmetic_overflow(&self) -> Result<String> {
+        self.try_get_string("timestamp_arithmetic_overflow")
+    }
+
+    pub fn get_decimal_avg_rounding_mode(&self) -> Result<String> {
+        self.try_get_string("decimal_avg_rounding_mode")
+    }
+
+    pub fn get_sum_integers_as_decimal(&self) -> Result<bool> {
+        Ok(self.try_get_u64("sum_integers_as_decimal")? != 0)
+    }
+
+    pub fn get_float_sum_non_finite(&self) -> Result<String> {
+        self.try_get_string("float_sum_non_finite")
+    }
+
     pub fn get_enable_dst_hour_fix(&self) -> Result<bool> {
         Ok(self.try_get_u64("enable_dst_hour_fix")? != 0)
     }
@@ -1299,6 +1299,15 @@ fn limit_to_format_tree(
         FormatTreeNode::new(format!("offset: {}", plan.offset)),
     ];
 
+    if !plan.ties_keys.is_empty() {
+        let ties_keys = plan
+            .ties_keys
+            .iter()
+            .map(|&index| metadata.column(index).name())
+            .join(", ");
+        children.push(FormatTreeNode::new(format!("with ties: [{ties_keys}]")));
+    }
+
     if let Some(info) = &plan.stat_info {
         let items = plan_stats_info_to_format_tree(info);
         children.extend(items);
@@ -1450,6 +1459,10 @@ fn hash_join_to_format_tree(
         FormatTreeNode::new(format!("filters: [{filters}]")),
     ];
 
+    if plan.build_keys_unique {
+        children.push(FormatTreeNode::new("build keys unique: true".to_string()));
+    }
+
     if let Some((cache_index, column_map)) = &plan.build_side_cache_info {
         let mut column_indexes = column_map.keys().collect::<Vec<_>>();
         column_indexes.sort();
@@ -199,6 +199,7 @@ pub trait PhysicalPlanReplacer {
             group_by: plan.group_by.clone(),
             group_by_display: plan.group_by_display.clone(),
             agg_funcs: plan.agg_funcs.clone(),
+            hash_seed: plan.hash_seed,
             stat_info: plan.stat_info.clone(),
         }))
     }
@@ -213,6 +214,7 @@ pub trait PhysicalPlanReplacer {
             group_by: plan.group_by.clone(),
             agg_funcs: plan.agg_funcs.clone(),
             group_by_display: plan.group_by_display.clone(),
+            hash_seed: plan.hash_seed,
             stat_info: plan.stat_info.clone(),
             limit: plan.limit,
         }))
@@ -260,6 +262,7 @@ pub trait PhysicalPlanReplacer {
             build_keys: plan.build_keys.clone(),
             probe_keys: plan.probe_keys.clone(),
             is_null_equal: plan.is_null_equal.clone(),
+            case_insensitive: plan.case_insensitive.clone(),
             non_equi_conditions: plan.non_equi_conditions.clone(),
             join_type: plan.join_type.clone(),
             marker_index: plan.marker_index,
@@ -273,6 +276,9 @@ pub trait PhysicalPlanReplacer {
             broadcast: plan.broadcast,
             single_to_inner: plan.single_to_inner.clone(),
             build_side_cache_info: plan.build_side_cache_info.clone(),
+            build_keys_unique: plan.build_keys_unique,
+            emit_build_row_id: plan.emit_build_row_id,
+            emit_match_count: plan.emit_match_count,
         }))
     }
 
@@ -327,6 +333,7 @@ pub trait PhysicalPlanReplacer {
             input: Box::new(input),
             limit: plan.limit,
             offset: plan.offset,
+            ties_keys: plan.ties_keys.clone(),
             stat_info: plan.stat_info.clone(),
         }))
     }
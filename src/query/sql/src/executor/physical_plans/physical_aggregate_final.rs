@@ -48,6 +48,8 @@ pub struct AggregateFinal {
     pub limit: Option<usize>,
 
     pub group_by_display: Vec<String>,
+    // Seed of the group hashes, the same as the partial aggregation.
+    pub hash_seed: u64,
 
     // Only used for explain
     pub stat_info: Option<PlanStatsInfo>,
@@ -159,6 +161,8 @@ impl PhysicalPlanBuilder {
                 let group_by_shuffle_mode = settings.get_group_by_shuffle_mode()?;
                 let enable_experimental_aggregate_hashtable =
                     settings.get_enable_experimental_aggregate_hashtable()?;
+                // A random seed, so the collisions of the group hashes can't be predicted.
+                let hash_seed = rand::random::<u64>();
 
                 if let Some(grouping_sets) = agg.grouping_sets.as_ref() {
                     assert_eq!(grouping_sets.dup_group_items.len(), group_items.len() - 1); // ignore `_grouping_id`.
@@ -196,6 +200,7 @@ impl PhysicalPlanBuilder {
                                 enable_experimental_aggregate_hashtable,
                                 group_by_display,
                                 group_by: group_items,
+                                hash_seed,
                                 stat_info: Some(stat_info),
                             }
                         } else {
@@ -206,6 +211,7 @@ impl PhysicalPlanBuilder {
                                 enable_experimental_aggregate_hashtable,
                                 group_by_display,
                                 group_by: group_items,
+                                hash_seed,
                                 stat_info: Some(stat_info),
                             }
                         };
@@ -273,6 +279,7 @@ impl PhysicalPlanBuilder {
                                 enable_experimental_aggregate_hashtable,
                                 group_by_display,
                                 group_by: group_items,
+                                hash_seed,
                                 input: Box::new(PhysicalPlan::AggregateExpand(expand)),
                                 stat_info: Some(stat_info),
                             })
@@ -283,6 +290,7 @@ impl PhysicalPlanBuilder {
                                 enable_experimental_aggregate_hashtable,
                                 group_by_display,
                                 group_by: group_items,
+                                hash_seed,
                                 input: Box::new(input),
                                 stat_info: Some(stat_info),
                             })
@@ -362,6 +370,7 @@ impl PhysicalPlanBuilder {
                         PhysicalPlan::AggregateFinal(AggregateFinal {
                             plan_id: 0,
                             group_by_display: partial.group_by_display.clone(),
+                            hash_seed: partial.hash_seed,
                             input: Box::new(input),
                             group_by: group_items,
                             agg_funcs,
@@ -382,6 +391,7 @@ impl PhysicalPlanBuilder {
                         PhysicalPlan::AggregateFinal(AggregateFinal {
                             plan_id: 0,
                             group_by_display: partial.group_by_display.clone(),
+                            hash_seed: partial.hash_seed,
                             input: Box::new(input),
                             group_by: group_items,
                             agg_funcs,
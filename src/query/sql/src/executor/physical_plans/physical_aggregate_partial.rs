@@ -34,6 +34,9 @@ pub struct AggregatePartial {
     pub agg_funcs: Vec<AggregateFunctionDesc>,
     pub enable_experimental_aggregate_hashtable: bool,
     pub group_by_display: Vec<String>,
+    // Seed of the group hashes, generated randomly when the query is planned and shared
+    // with the final aggregation on every node.
+    pub hash_seed: u64,
 
     // Only used for explain
     pub stat_info: Option<PlanStatsInfo>,
@@ -20,13 +20,19 @@ use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
 use databend_common_expression::type_check::check_cast;
+use databend_common_expression::type_check::check_function;
 use databend_common_expression::type_check::common_super_type;
 use databend_common_expression::types::DataType;
+use databend_common_expression::types::NumberDataType;
 use databend_common_expression::ConstantFolder;
 use databend_common_expression::DataField;
 use databend_common_expression::DataSchemaRef;
 use databend_common_expression::DataSchemaRefExt;
+use databend_common_expression::Expr;
 use databend_common_expression::RemoteExpr;
+use databend_common_expression::Scalar;
+use databend_common_expression::BUILD_ROW_ID_COL_NAME;
+use databend_common_expression::MATCH_COUNT_COL_NAME;
 use databend_common_expression::ROW_NUMBER_COL_NAME;
 use databend_common_functions::BUILTIN_FUNCTIONS;
 use databend_storages_common_table_meta::table::get_change_type;
@@ -64,6 +70,8 @@ pub struct HashJoin {
     pub build_keys: Vec<RemoteExpr>,
     pub probe_keys: Vec<RemoteExpr>,
     pub is_null_equal: Vec<bool>,
+    // Whether the string keys are compared ASCII case-insensitively, per key.
+    pub case_insensitive: Vec<bool>,
     pub non_equi_conditions: Vec<RemoteExpr>,
     pub join_type: JoinType,
     pub marker_index: Option<IndexType>,
@@ -92,6 +100,14 @@ pub struct HashJoin {
     // Hash join build side cache information for ExpressionScan, which includes the cache index and
     // a HashMap for mapping the column indexes to the BlockEntry indexes in DataBlock.
     pub build_side_cache_info: Option<(usize, HashMap<IndexType, usize>)>,
+    // Whether the build side is known to be unique on the build keys, e.g. it's grouped by them.
+    pub build_keys_unique: bool,
+    // Whether the inner join appends the `build_row_id` of the matched build rows as the last
+    // column of the output.
+    pub emit_build_row_id: bool,
+    // Whether the left semi join appends the number of the build rows matched by each probe
+    // row as the last column of the output.
+    pub emit_match_count: bool,
 }
 
 impl HashJoin {
@@ -154,22 +170,29 @@ impl PhysicalPlanBuilder {
                         &probe_expr, &build_expr
                     ))
                 })?;
-                *probe_key = check_cast(
+                let mut probe_expr = check_cast(
                     probe_expr.span(),
                     false,
                     probe_expr,
                     &common_ty,
                     &BUILTIN_FUNCTIONS,
-                )?
-                .as_remote_expr();
-                *build_key = check_cast(
+                )?;
+                let mut build_expr = check_cast(
                     build_expr.span(),
                     false,
                     build_expr,
                     &common_ty,
                     &BUILTIN_FUNCTIONS,
-                )?
-                .as_remote_expr();
+                )?;
+                // The keys equal case-insensitively must be shuffled to the same node.
+                if join.has_case_insensitive_equi_condition()
+                    && common_ty.remove_nullable() == DataType::String
+                {
+                    probe_expr = ascii_lowercase_key(probe_expr)?;
+                    build_expr = ascii_lowercase_key(build_expr)?;
+                }
+                *probe_key = probe_expr.as_remote_expr();
+                *build_key = build_expr.as_remote_expr();
             }
         }
 
@@ -209,9 +232,24 @@ impl PhysicalPlanBuilder {
             _ => probe_side.output_schema()?,
         };
 
+        let build_key_columns = join
+            .equi_conditions
+            .iter()
+            .filter(|condition| !condition.case_insensitive)
+            .filter_map(|condition| match &condition.right {
+                ScalarExpr::BoundColumnRef(column) => Some(column.column.index),
+                _ => None,
+            })
+            .collect::<ColumnSet>();
+        // Only the inner join stops probing at the first match.
+        let build_keys_unique = join.join_type == JoinType::Inner
+            && !join.equi_conditions.is_empty()
+            && is_unique_on(&build_side, &build_key_columns);
+
         let mut left_join_conditions = Vec::new();
         let mut right_join_conditions = Vec::new();
         let mut is_null_equal = Vec::new();
+        let mut case_insensitive = Vec::new();
         let mut left_join_conditions_rt = Vec::new();
         let mut probe_to_build_index = Vec::new();
         let mut table_index = None;
@@ -267,6 +305,7 @@ impl PhysicalPlanBuilder {
                             probe_schema.index_of(&left.column.index.to_string()),
                             build_schema.index_of(&right.column.index.to_string()),
                         ) {
+                            // The case-insensitive keys may differ, the build side keeps its own.
                             if probe_schema
                                 .field(probe_index)
                                 .data_type()
@@ -275,6 +314,7 @@ impl PhysicalPlanBuilder {
                                     .field(build_index)
                                     .data_type()
                                     .remove_nullable()
+                                && !condition.case_insensitive
                             {
                                 probe_to_build_index.push(((probe_index, false), build_index));
                                 if !pre_column_projections.contains(&left.column.index) {
@@ -336,6 +376,10 @@ impl PhysicalPlanBuilder {
             left_join_conditions.push(left_expr.as_remote_expr());
             right_join_conditions.push(right_expr.as_remote_expr());
             is_null_equal.push(condition.is_null_equal);
+            case_insensitive.push(condition.case_insensitive);
+            // The runtime filters of the build keys would drop the probe rows of other cases.
+            let left_expr_for_runtime_filter =
+                left_expr_for_runtime_filter.filter(|_| !condition.case_insensitive);
             left_join_conditions_rt
                 .push(left_expr_for_runtime_filter.map(|(expr, idx)| (expr.as_remote_expr(), idx)));
         }
@@ -419,7 +463,7 @@ impl PhysicalPlanBuilder {
         merged_fields.extend(tail_fields);
         let merged_schema = DataSchemaRefExt::create(merged_fields);
 
-        let merged_fields = match join.join_type {
+        let mut merged_fields = match join.join_type {
             JoinType::Cross
             | JoinType::Inner
             | JoinType::Left
@@ -488,6 +532,22 @@ impl PhysicalPlanBuilder {
                 probe_fields
             }
         };
+        let emit_build_row_id = join.emit_build_row_id && join.join_type == JoinType::Inner;
+        if emit_build_row_id {
+            merged_fields.push(DataField::new(
+                BUILD_ROW_ID_COL_NAME,
+                DataType::Number(NumberDataType::UInt64),
+            ));
+        }
+        let emit_match_count = join.emit_match_count
+            && join.join_type == JoinType::LeftSemi
+            && join.non_equi_conditions.is_empty();
+        if emit_match_count {
+            merged_fields.push(DataField::new(
+                MATCH_COUNT_COL_NAME,
+                DataType::Number(NumberDataType::UInt64),
+            ));
+        }
         let mut projections = ColumnSet::new();
         let projected_schema = DataSchemaRefExt::create(merged_fields.clone());
         for column in column_projections.iter() {
@@ -502,6 +562,11 @@ impl PhysicalPlanBuilder {
             projections.insert(index);
         }
 
+        // the `build_row_id` or the match count is always the last column of the probe result.
+        if emit_build_row_id || emit_match_count {
+            projections.insert(merged_fields.len() - 1);
+        }
+
         let mut output_fields = Vec::with_capacity(column_projections.len());
         for (i, field) in merged_fields.iter().enumerate() {
             if projections.contains(&i) {
@@ -520,6 +585,7 @@ impl PhysicalPlanBuilder {
             build_keys: right_join_conditions,
             probe_keys: left_join_conditions,
             is_null_equal,
+            case_insensitive,
             probe_keys_rt: left_join_conditions_rt,
             non_equi_conditions: join
                 .non_equi_conditions
@@ -550,6 +616,9 @@ impl PhysicalPlanBuilder {
             )
             .await?,
             build_side_cache_info,
+            build_keys_unique,
+            emit_build_row_id,
+            emit_match_count,
         }))
     }
 }
@@ -584,3 +653,40 @@ async fn adjust_bloom_runtime_filter(
     }
     Ok(false)
 }
+
+/// Whether the rows of the plan are unique on the columns, i.e. the plan is grouped by a subset
+/// of the columns, and the operators above the aggregation don't duplicate the rows.
+fn is_unique_on(plan: &PhysicalPlan, columns: &ColumnSet) -> bool {
+    match plan {
+        PhysicalPlan::AggregateFinal(aggregate) => aggregate
+            .group_by
+            .iter()
+            .all(|index| columns.contains(index)),
+        PhysicalPlan::Filter(filter) => is_unique_on(&filter.input, columns),
+        PhysicalPlan::EvalScalar(eval) => is_unique_on(&eval.input, columns),
+        PhysicalPlan::Sort(sort) => is_unique_on(&sort.input, columns),
+        PhysicalPlan::Limit(limit) => is_unique_on(&limit.input, columns),
+        PhysicalPlan::Exchange(exchange) => is_unique_on(&exchange.input, columns),
+        _ => false,
+    }
+}
+
+/// Folds the ASCII letters of the string key to lowercase, the same as the hash table does for
+/// the case-insensitive keys, the other characters are kept.
+fn ascii_lowercase_key(key: Expr) -> Result<Expr> {
+    let span = key.span();
+    let args = [
+        key,
+        Expr::Constant {
+            span,
+            scalar: Scalar::String("ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string()),
+            data_type: DataType::String,
+        },
+        Expr::Constant {
+            span,
+            scalar: Scalar::String("abcdefghijklmnopqrstuvwxyz".to_string()),
+            data_type: DataType::String,
+        },
+    ];
+    check_function(span, "translate", &[], &args, &BUILTIN_FUNCTIONS)
+}
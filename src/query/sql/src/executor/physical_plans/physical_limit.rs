@@ -26,6 +26,7 @@ use crate::executor::PhysicalPlanBuilder;
 use crate::optimizer::SExpr;
 use crate::ColumnEntry;
 use crate::ColumnSet;
+use crate::IndexType;
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Limit {
@@ -34,6 +35,8 @@ pub struct Limit {
     pub input: Box<PhysicalPlan>,
     pub limit: Option<usize>,
     pub offset: usize,
+    // The `ORDER BY` keys of `WITH TIES`, empty if without ties.
+    pub ties_keys: Vec<IndexType>,
 
     // Only used for explain
     pub stat_info: Option<PlanStatsInfo>,
@@ -62,6 +65,7 @@ impl PhysicalPlanBuilder {
             .cloned()
             .collect::<ColumnSet>();
         required.extend(metadata.row_id_indexes());
+        required.extend(limit.ties_keys.iter().cloned());
 
         // 2. Build physical plan.
         let input_plan = self.build(s_expr.child(0)?, required).await?;
@@ -72,6 +76,7 @@ impl PhysicalPlanBuilder {
                 input: Box::new(input_plan),
                 limit: limit.limit,
                 offset: limit.offset,
+                ties_keys: limit.ties_keys.clone(),
                 stat_info: Some(stat_info),
             }));
         }
@@ -92,6 +97,7 @@ impl PhysicalPlanBuilder {
                 input: Box::new(input_plan),
                 limit: limit.limit,
                 offset: limit.offset,
+                ties_keys: limit.ties_keys.clone(),
                 stat_info: Some(stat_info),
             }));
         }
@@ -116,6 +122,7 @@ impl PhysicalPlanBuilder {
                 input: Box::new(input_plan),
                 limit: limit.limit,
                 offset: limit.offset,
+                ties_keys: limit.ties_keys.clone(),
                 stat_info: Some(stat_info),
             }));
         }
@@ -155,6 +162,7 @@ impl PhysicalPlanBuilder {
                 input: Box::new(input_plan),
                 limit: limit.limit,
                 offset: limit.offset,
+                ties_keys: limit.ties_keys.clone(),
                 stat_info: Some(stat_info.clone()),
             })),
             source: Box::new(source_info),
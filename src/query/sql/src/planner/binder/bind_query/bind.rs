@@ -51,7 +51,7 @@ impl Binder {
         s_expr = self.bind_query_order_by(&mut bind_context, query, s_expr)?;
 
         // Bind limit.
-        s_expr = self.bind_query_limit(query, s_expr, limit, offset);
+        s_expr = self.bind_query_limit(query, s_expr, limit, offset)?;
 
         Ok((s_expr, bind_context))
     }
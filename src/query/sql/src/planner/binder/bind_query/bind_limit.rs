@@ -24,6 +24,8 @@ use databend_common_exception::Result;
 use crate::binder::Binder;
 use crate::optimizer::SExpr;
 use crate::plans::Limit;
+use crate::plans::RelOperator;
+use crate::IndexType;
 
 impl Binder {
     pub(super) fn bind_query_limit(
@@ -32,17 +34,44 @@ impl Binder {
         s_expr: SExpr,
         limit: Option<usize>,
         offset: usize,
-    ) -> SExpr {
+    ) -> Result<SExpr> {
         if limit.is_none() && query.offset.is_none() {
-            return s_expr;
+            return Ok(s_expr);
         }
 
+        let ties_keys = if query.with_ties {
+            if query.order_by.is_empty() {
+                return Err(ErrorCode::SemanticError(
+                    "WITH TIES cannot be specified without ORDER BY clause",
+                ));
+            }
+            Self::find_ties_keys(&s_expr)?
+        } else {
+            vec![]
+        };
+
         let limit_plan = Limit {
             before_exchange: false,
             limit,
             offset,
+            ties_keys,
         };
-        SExpr::create_unary(Arc::new(limit_plan.into()), Arc::new(s_expr))
+        Ok(SExpr::create_unary(
+            Arc::new(limit_plan.into()),
+            Arc::new(s_expr),
+        ))
+    }
+
+    /// Finds the `ORDER BY` keys of the query, the rows tying on them are kept by `WITH TIES`.
+    /// The sort is the nearest one below the projection.
+    fn find_ties_keys(s_expr: &SExpr) -> Result<Vec<IndexType>> {
+        match s_expr.plan() {
+            RelOperator::Sort(sort) => Ok(sort.items.iter().map(|item| item.index).collect()),
+            _ if s_expr.arity() == 1 => Self::find_ties_keys(s_expr.child(0)?),
+            _ => Err(ErrorCode::Internal(
+                "Cannot find the sort of ORDER BY clause for WITH TIES",
+            )),
+        }
     }
 
     pub(crate) fn extract_limit_and_offset(&self, query: &Query) -> Result<(Option<usize>, usize)> {
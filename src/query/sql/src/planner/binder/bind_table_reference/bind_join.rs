@@ -25,6 +25,7 @@ use databend_common_ast::Span;
 use databend_common_catalog::table_context::TableContext;
 use databend_common_exception::ErrorCode;
 use databend_common_exception::Result;
+use databend_common_expression::types::DataType;
 use indexmap::IndexMap;
 
 use crate::binder::CteInfo;
@@ -222,6 +223,9 @@ impl Binder {
         let mut right_conditions = join_conditions.right_conditions;
         let mut non_equi_conditions = join_conditions.non_equi_conditions;
         let other_conditions = join_conditions.other_conditions;
+        // Only the conditions of the `ON` clause are compared case-insensitively, not the ones
+        // added by the decorrelation below.
+        let num_join_conditions = left_conditions.len();
 
         if join_type == JoinType::Cross
             && (!left_conditions.is_empty() || !right_conditions.is_empty())
@@ -292,12 +296,21 @@ impl Binder {
                     right_conditions,
                 )
             };
+        let mut equi_conditions =
+            JoinEquiCondition::new_conditions(left_conditions, right_conditions, is_null_equal);
+        if self
+            .ctx
+            .get_settings()
+            .get_hash_join_case_insensitive_keys()?
+        {
+            for condition in equi_conditions.iter_mut().take(num_join_conditions) {
+                condition.case_insensitive = condition.left.data_type()?.remove_nullable()
+                    == DataType::String
+                    && condition.right.data_type()?.remove_nullable() == DataType::String;
+            }
+        }
         let logical_join = Join {
-            equi_conditions: JoinEquiCondition::new_conditions(
-                left_conditions,
-                right_conditions,
-                is_null_equal,
-            ),
+            equi_conditions,
             non_equi_conditions,
             join_type,
             marker_index: None,
@@ -306,6 +319,8 @@ impl Binder {
             is_lateral,
             single_to_inner: None,
             build_side_cache_info,
+            emit_build_row_id: false,
+            emit_match_count: false,
         };
         Ok(SExpr::create_binary(
             Arc::new(logical_join.into()),
@@ -643,7 +643,8 @@ impl Binder {
                         expr
                     )));
                 }
-                let micros = Utc::now().timestamp_micros() + v * 1_000_000;
+                let now = self.ctx.get_function_context()?.now;
+                let micros = now.timestamp_micros() + v * 1_000_000;
                 Ok(NavigationPoint::TimePoint(
                     Utc.timestamp_nanos(micros * 1000),
                 ))
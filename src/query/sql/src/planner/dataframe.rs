@@ -336,6 +336,7 @@ impl Dataframe {
             before_exchange: false,
             limit,
             offset,
+            ties_keys: vec![],
         };
         self.s_expr =
             SExpr::create_unary(Arc::new(limit_plan.into()), Arc::new(self.s_expr.clone()));
@@ -206,6 +206,8 @@ impl SubqueryRewriter {
             is_lateral: false,
             single_to_inner: None,
             build_side_cache_info: None,
+            emit_build_row_id: false,
+            emit_match_count: false,
         };
 
         // Rewrite plan to semi-join.
@@ -300,6 +302,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 };
                 let s_expr = SExpr::create_binary(
                     Arc::new(join_plan.into()),
@@ -354,6 +358,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 };
                 let s_expr = SExpr::create_binary(
                     Arc::new(join_plan.into()),
@@ -423,6 +429,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 }
                 .into();
                 Ok((
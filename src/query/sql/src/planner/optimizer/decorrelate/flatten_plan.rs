@@ -169,6 +169,8 @@ impl SubqueryRewriter {
                 is_lateral: false,
                 single_to_inner: None,
                 build_side_cache_info: None,
+                emit_build_row_id: false,
+                emit_match_count: false,
             }
             .into();
 
@@ -527,6 +529,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 }
                 .into(),
             ),
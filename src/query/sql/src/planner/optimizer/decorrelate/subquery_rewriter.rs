@@ -431,6 +431,7 @@ impl SubqueryRewriter {
                     limit: Some(1),
                     offset: 0,
                     before_exchange: false,
+                    ties_keys: vec![],
                 };
                 subquery_expr =
                     SExpr::create_unary(Arc::new(limit.into()), Arc::new(subquery_expr.clone()));
@@ -532,6 +533,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 }
                 .into();
                 Ok((
@@ -607,6 +610,8 @@ impl SubqueryRewriter {
                     is_lateral: false,
                     single_to_inner: None,
                     build_side_cache_info: None,
+                    emit_build_row_id: false,
+                    emit_match_count: false,
                 }
                 .into();
                 let s_expr = SExpr::create_binary(
@@ -638,6 +643,8 @@ impl SubqueryRewriter {
             is_lateral: false,
             single_to_inner: None,
             build_side_cache_info: None,
+            emit_build_row_id: false,
+            emit_match_count: false,
         }
         .into();
 
@@ -707,6 +714,7 @@ impl SubqueryRewriter {
                     limit: Some(1),
                     offset: 0,
                     before_exchange: false,
+                    ties_keys: vec![],
                 }
                 .into(),
             ),
@@ -56,7 +56,10 @@ impl DeduplicateJoinConditionOptimizer {
 
     pub fn deduplicate(&mut self, s_expr: &SExpr) -> Result<SExpr> {
         match s_expr.plan.as_ref() {
-            RelOperator::Join(join) if join.join_type == JoinType::Inner => {
+            RelOperator::Join(join)
+                if join.join_type == JoinType::Inner
+                    && !join.has_case_insensitive_equi_condition() =>
+            {
                 self.deduplicate_join_conditions(s_expr, join)
             }
             _ => self.deduplicate_children(s_expr),
@@ -68,7 +68,11 @@ impl PullUpFilterOptimizer {
     pub fn pull_up(&mut self, s_expr: &SExpr) -> Result<SExpr> {
         match s_expr.plan.as_ref() {
             RelOperator::Filter(filter) => self.pull_up_filter(s_expr, filter),
-            RelOperator::Join(join) if !join.is_lateral && !join.has_null_equi_condition() => {
+            RelOperator::Join(join)
+                if !join.is_lateral
+                    && !join.has_null_equi_condition()
+                    && !join.has_case_insensitive_equi_condition() =>
+            {
                 self.pull_up_join(s_expr, join)
             }
             RelOperator::EvalScalar(eval_scalar) => self.pull_up_eval_scalar(s_expr, eval_scalar),
@@ -146,8 +146,10 @@ impl DPhpy {
                     return Ok((Arc::new(s_expr.clone()), true));
                 }
                 let mut is_inner_join = true;
-                if !matches!(op.join_type, JoinType::Inner)
-                    && !matches!(op.join_type, JoinType::Cross)
+                // The case-insensitive conditions can't be moved to the reordered joins.
+                if (!matches!(op.join_type, JoinType::Inner)
+                    && !matches!(op.join_type, JoinType::Cross))
+                    || op.has_case_insensitive_equi_condition()
                 {
                     is_inner_join = false;
                 }
@@ -94,6 +94,8 @@ impl JoinNode {
             is_lateral: false,
             single_to_inner: None,
             build_side_cache_info: None,
+            emit_build_row_id: false,
+            emit_match_count: false,
         });
         let children = self
             .children
@@ -196,7 +196,10 @@ pub fn try_push_down_filter_join(s_expr: &SExpr, metadata: MetadataRef) -> Resul
         return Ok((false, s_expr.clone()));
     }
 
-    if !matches!(join.join_type, JoinType::Full) && !join.has_null_equi_condition() {
+    if !matches!(join.join_type, JoinType::Full)
+        && !join.has_null_equi_condition()
+        && !join.has_case_insensitive_equi_condition()
+    {
         // Infer new predicate and push down filter.
         for equi_condition in join.equi_conditions.iter() {
             let left = equi_condition.left.clone();
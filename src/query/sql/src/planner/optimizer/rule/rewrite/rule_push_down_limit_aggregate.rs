@@ -67,6 +67,9 @@ impl Rule for RulePushDownLimitAggregate {
         state: &mut TransformResult,
     ) -> databend_common_exception::Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            return Ok(());
+        }
         if let Some(mut count) = limit.limit {
             count += limit.offset;
             let agg = s_expr.child(0)?;
@@ -70,6 +70,9 @@ impl Rule for RulePushDownLimitOuterJoin {
         state: &mut TransformResult,
     ) -> databend_common_exception::Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            return Ok(());
+        }
         if limit.limit.is_some() {
             let child = s_expr.child(0)?;
             let join: Join = child.plan().clone().try_into()?;
@@ -87,6 +90,7 @@ impl Rule for RulePushDownLimitOuterJoin {
                             before_exchange: limit.before_exchange,
                             limit: limit.limit,
                             offset: 0,
+                            ties_keys: vec![],
                         })),
                         Arc::new(child),
                     );
@@ -106,6 +110,7 @@ impl Rule for RulePushDownLimitOuterJoin {
                             before_exchange: limit.before_exchange,
                             limit: limit.limit,
                             offset: 0,
+                            ties_keys: vec![],
                         })),
                         child,
                     );
@@ -62,6 +62,9 @@ impl Rule for RulePushDownLimitScan {
 
     fn apply(&self, s_expr: &SExpr, state: &mut TransformResult) -> Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            return Ok(());
+        }
         if let Some(mut count) = limit.limit {
             let child = s_expr.child(0)?;
             let mut get: Scan = child.plan().clone().try_into()?;
@@ -69,6 +69,10 @@ impl Rule for RulePushDownLimitSort {
         state: &mut TransformResult,
     ) -> databend_common_exception::Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            // The tying rows after the limit must be kept, so the sort can't be truncated.
+            return Ok(());
+        }
         if let Some(mut count) = limit.limit {
             count += limit.offset;
             let sort = s_expr.child(0)?;
@@ -58,6 +58,9 @@ impl Rule for RulePushDownLimitUnion {
 
     fn apply(&self, s_expr: &SExpr, state: &mut TransformResult) -> Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            return Ok(());
+        }
         let union_s_expr = s_expr.child(0)?;
         let union: UnionAll = union_s_expr.plan().clone().try_into()?;
 
@@ -72,6 +75,7 @@ impl Rule for RulePushDownLimitUnion {
                 .map(|origin_limit| cmp::max(origin_limit, limit_offset)),
             offset: 0,
             before_exchange: false,
+            ties_keys: vec![],
         };
 
         // Push down new_limit to union children
@@ -71,6 +71,9 @@ impl Rule for RulePushDownLimitWindow {
 
     fn apply(&self, s_expr: &SExpr, state: &mut TransformResult) -> Result<()> {
         let limit: Limit = s_expr.plan().clone().try_into()?;
+        if limit.with_ties() {
+            return Ok(());
+        }
         if let Some(mut count) = limit.limit {
             count += limit.offset;
             let window = s_expr.child(0)?;
@@ -56,7 +56,10 @@ impl Rule for RuleSemiToInnerJoin {
 
     fn apply(&self, s_expr: &SExpr, state: &mut TransformResult) -> Result<()> {
         let mut join: Join = s_expr.plan().clone().try_into()?;
-        if !matches!(join.join_type, JoinType::LeftSemi | JoinType::RightSemi) {
+        // The keys grouped case-sensitively may still match a probe row more than once.
+        if !matches!(join.join_type, JoinType::LeftSemi | JoinType::RightSemi)
+            || join.has_case_insensitive_equi_condition()
+        {
             return Ok(());
         }
 
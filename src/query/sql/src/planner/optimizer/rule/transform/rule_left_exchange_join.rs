@@ -105,6 +105,8 @@ impl Rule for RuleLeftExchangeJoin {
         // Ensure inner joins or cross joins.
         if !matches!(join1.join_type, JoinType::Inner | JoinType::Cross)
             || !matches!(join2.join_type, JoinType::Inner | JoinType::Cross)
+            || join1.has_case_insensitive_equi_condition()
+            || join2.has_case_insensitive_equi_condition()
         {
             return Ok(());
         }
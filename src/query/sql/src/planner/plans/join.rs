@@ -156,6 +156,9 @@ pub struct JoinEquiCondition {
     pub right: ScalarExpr,
     // Used for "is (not) distinct from".
     pub is_null_equal: bool,
+    // Whether the string keys are compared ASCII case-insensitively, see the setting
+    // `hash_join_case_insensitive_keys`.
+    pub case_insensitive: bool,
 }
 
 impl JoinEquiCondition {
@@ -164,6 +167,7 @@ impl JoinEquiCondition {
             left,
             right,
             is_null_equal,
+            case_insensitive: false,
         }
     }
 
@@ -201,6 +205,12 @@ pub struct Join {
     pub single_to_inner: Option<JoinType>,
     // Cache info for ExpressionScan.
     pub build_side_cache_info: Option<HashJoinBuildCacheInfo>,
+    // Whether the inner hash join appends the `_build_row_id` of the matched build rows
+    // to its output, see `BUILD_ROW_ID_COL_NAME`.
+    pub emit_build_row_id: bool,
+    // Whether the left semi hash join appends the number of the build rows matched by each
+    // probe row to its output, see `MATCH_COUNT_COL_NAME`.
+    pub emit_match_count: bool,
 }
 
 impl Default for Join {
@@ -215,6 +225,8 @@ impl Default for Join {
             is_lateral: false,
             single_to_inner: None,
             build_side_cache_info: None,
+            emit_build_row_id: false,
+            emit_match_count: false,
         }
     }
 }
@@ -419,6 +431,14 @@ impl Join {
             .iter()
             .any(|condition| condition.is_null_equal)
     }
+
+    /// The case-insensitive keys are not equal in the other expressions, so the join must not
+    /// be rewritten by the rules that infer, move or regroup its conditions.
+    pub fn has_case_insensitive_equi_condition(&self) -> bool {
+        self.equi_conditions
+            .iter()
+            .any(|condition| condition.case_insensitive)
+    }
 }
 
 impl Operator for Join {
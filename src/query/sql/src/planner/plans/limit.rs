@@ -25,12 +25,22 @@ use crate::optimizer::StatInfo;
 use crate::optimizer::Statistics;
 use crate::plans::Operator;
 use crate::plans::RelOp;
+use crate::IndexType;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Limit {
     pub before_exchange: bool,
     pub limit: Option<usize>,
     pub offset: usize,
+    /// The `ORDER BY` keys of `FETCH FIRST n ROWS WITH TIES`, the rows tying with
+    /// the last row on them are also returned. Empty if without ties.
+    pub ties_keys: Vec<IndexType>,
+}
+
+impl Limit {
+    pub fn with_ties(&self) -> bool {
+        !self.ties_keys.is_empty()
+    }
 }
 
 impl Operator for Limit {
@@ -72,6 +82,8 @@ impl Operator for Limit {
             _ => stat_info.cardinality,
         };
         let precise_cardinality = match (self.limit, stat_info.statistics.precise_cardinality) {
+            // The number of tying rows is unknown.
+            (Some(_), _) if self.with_ties() => None,
             (Some(limit), Some(pc)) => {
                 Some((pc.saturating_sub(self.offset as u64)).min(limit as u64))
             }
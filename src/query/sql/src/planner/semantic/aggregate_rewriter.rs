@@ -17,6 +17,7 @@ use databend_common_ast::ast::Expr;
 use databend_common_ast::ast::FunctionCall;
 use databend_common_ast::ast::Identifier;
 use databend_common_ast::ast::Literal;
+use databend_common_ast::Span;
 use derive_visitor::VisitorMut;
 
 #[derive(Debug, Clone, Default, VisitorMut)]
@@ -32,11 +33,12 @@ impl AggregateRewriter {
                         distinct,
                         name,
                         args,
+                        params,
                         window,
                         ..
                     },
                 ..
-            } if !*distinct && args.len() == 1 && window.is_none() => {
+            } if !*distinct && args.len() == 1 && params.is_empty() && window.is_none() => {
                 match name.name.to_ascii_lowercase().to_lowercase().as_str() {
                     "sum" => self.rewrite_sum(args),
                     "avg" => Some(self.rewrite_avg(args)),
@@ -53,6 +55,38 @@ impl AggregateRewriter {
 }
 
 impl AggregateRewriter {
+    /// Returns the argument `x` if the division is exactly what `avg(x)` is rewritten into.
+    ///
+    /// All the nodes of the rewritten expression take the span of `x`, which a handwritten
+    /// `sum(x) / if(count(x) = 0, 1, count(x))` never does, so it's left as a division.
+    pub fn rewritten_avg_argument<'a>(
+        span: Span,
+        left: &'a Expr,
+        right: &Expr,
+    ) -> Option<&'a Expr> {
+        let Expr::FunctionCall {
+            func: FunctionCall { name, args, .. },
+            ..
+        } = left
+        else {
+            return None;
+        };
+        let [arg] = args.as_slice() else {
+            return None;
+        };
+        if span != arg.span() || name.name != "sum" {
+            return None;
+        }
+        match AggregateRewriter.rewrite_avg(args) {
+            Expr::BinaryOp {
+                left: rewritten_left,
+                right: rewritten_right,
+                ..
+            } if *rewritten_left == *left && *rewritten_right == *right => Some(arg),
+            _ => None,
+        }
+    }
+
     // sum(c + expr) --> c * count(expr) + sum(expr)
     fn rewrite_sum(&self, args: &[Expr]) -> Option<Expr> {
         match &args[0] {
@@ -89,6 +89,7 @@ impl DistinctToGroupBy {
                         order_by: vec![],
                         limit: vec![],
                         offset: None,
+                        with_ties: false,
                         ignore_result: false,
                     };
 
@@ -107,6 +107,7 @@ use simsearch::SimSearch;
 
 use super::name_resolution::NameResolutionContext;
 use super::normalize_identifier;
+use super::AggregateRewriter;
 use crate::binder::bind_values;
 use crate::binder::resolve_file_location;
 use crate::binder::wrap_cast;
@@ -1729,6 +1730,53 @@ impl<'a> TypeChecker<'a> {
             params
         };
 
+        // Pass the rounding mode of decimal average to `avg`, `half_up` is taken if absent.
+        let params = if func_name.eq_ignore_ascii_case("avg")
+            && params.is_empty()
+            && arg_types.len() == 1
+            && arg_types[0].remove_nullable().is_decimal()
+        {
+            let mode = self.ctx.get_settings().get_decimal_avg_rounding_mode()?;
+            if mode == "half_up" {
+                params
+            } else {
+                vec![Scalar::String(mode)]
+            }
+        } else {
+            params
+        };
+
+        // Accumulate the integer sum of `sum` and `avg` into a decimal
+        // if `sum_integers_as_decimal` is enabled.
+        let params = if (func_name.eq_ignore_ascii_case("sum")
+            || func_name.eq_ignore_ascii_case("avg"))
+            && params.is_empty()
+            && arg_types.len() == 1
+            && arg_types[0].remove_nullable().is_integer()
+            && self.ctx.get_settings().get_sum_integers_as_decimal()?
+        {
+            vec![Scalar::String("decimal".to_string())]
+        } else {
+            params
+        };
+
+        // Pass the behavior of the float `sum` on NaN and infinite inputs, IEEE 754 is
+        // followed if absent.
+        let params = if func_name.eq_ignore_ascii_case("sum")
+            && params.is_empty()
+            && arg_types.len() == 1
+            && arg_types[0].remove_nullable().is_floating()
+        {
+            let policy = self.ctx.get_settings().get_float_sum_non_finite()?;
+            if policy == "propagate" {
+                params
+            } else {
+                vec![Scalar::String(policy)]
+            }
+        } else {
+            params
+        };
+
         // Rewrite `xxx(distinct)` to `xxx_distinct(...)`
         let (func_name, distinct) = if func_name.eq_ignore_ascii_case("count") && distinct {
             ("count_distinct", false)
@@ -2736,6 +2784,36 @@ impl<'a> TypeChecker<'a> {
                     vec![left, right],
                 )
             }
+            BinaryOperator::Divide => {
+                // `avg(x)` is rewritten into `sum(x) / count(x)` before binding, but the sum of
+                // dates isn't meaningful, the division doesn't take the rounding mode of
+                // decimal average, and the average of the integers accumulated into a decimal
+                // is a double, so these are restored to `avg(x)`.
+                if let Some(arg) = AggregateRewriter::rewritten_avg_argument(span, left, right) {
+                    let box (_, data_type) = self.resolve(arg)?;
+                    let data_type = data_type.remove_nullable();
+                    let settings = self.ctx.get_settings();
+                    if data_type == DataType::Date
+                        || (data_type.is_decimal()
+                            && settings.get_decimal_avg_rounding_mode()? != "half_up")
+                        || (data_type.is_integer() && settings.get_sum_integers_as_decimal()?)
+                    {
+                        return self.resolve(&Expr::FunctionCall {
+                            span,
+                            func: ASTFunctionCall {
+                                distinct: false,
+                                name: Identifier::from_name(span, "avg"),
+                                args: vec![arg.clone()],
+                                params: vec![],
+                                window: None,
+                                lambda: None,
+                            },
+                        });
+                    }
+                }
+                let name = op.to_func_name();
+                self.resolve_function(span, name.as_str(), vec![], &[left, right])
+            }
             BinaryOperator::Like => {
                 // Convert `Like` to compare function , such as `p_type like PROMO%` will be converted to `p_type >= PROMO and p_type < PROMP`
                 if let Expr::Literal {
@@ -2986,7 +3064,6 @@ impl<'a> TypeChecker<'a> {
             "ifnull",
             "nvl",
             "nvl2",
-            "is_null",
             "is_error",
             "error_or",
             "coalesce",
@@ -3002,6 +3079,66 @@ impl<'a> TypeChecker<'a> {
         ]
     }
 
+    /// Resolves `nullif` and `ifnull` to the functions merging the validity bitmaps.
+    ///
+    /// Unlike `if`, both arguments are evaluated for all the rows, so `y` of `ifnull` must be
+    /// a column or a literal which can't fail. `nullif` requires the arguments of the same
+    /// type whose equality is the same as `=`. Otherwise the resolved arguments are rewritten
+    /// to `if`, as `nullif(x, y)` is `if(x = y, null, x)` and `ifnull(x, y)` is `coalesce(x, y)`.
+    fn resolve_null_function(
+        &mut self,
+        span: Span,
+        func_name: &str,
+        arg_x: &Expr,
+        arg_y: &Expr,
+    ) -> Result<Box<(ScalarExpr, DataType)>> {
+        let box (x, x_type) = self.resolve(arg_x)?;
+        let box (y, y_type) = self.resolve(arg_y)?;
+        let merge_validity = if func_name == "nullif" {
+            let data_type = x_type.remove_nullable();
+            data_type == y_type.remove_nullable()
+                && matches!(
+                    data_type,
+                    DataType::Number(_)
+                        | DataType::Decimal(_)
+                        | DataType::Boolean
+                        | DataType::String
+                        | DataType::Timestamp
+                        | DataType::Date
+                )
+        } else {
+            // `ifnull` evaluates y for all the rows, but `coalesce` only for the NULL rows of x,
+            // so the validity is only merged if y can't fail.
+            matches!(
+                y,
+                ScalarExpr::BoundColumnRef(_) | ScalarExpr::ConstantExpr(_)
+            )
+        };
+        if merge_validity {
+            return self.resolve_scalar_function_call(span, func_name, vec![], vec![x, y]);
+        }
+
+        if func_name == "nullif" {
+            // Rewrite nullif(x, y) to if(x = y, null, x)
+            self.resolve_function(span, "if", vec![], &[
+                &Expr::BinaryOp {
+                    span,
+                    op: BinaryOperator::Eq,
+                    left: Box::new(arg_x.clone()),
+                    right: Box::new(arg_y.clone()),
+                },
+                &Expr::Literal {
+                    span,
+                    value: Literal::Null,
+                },
+                arg_x,
+            ])
+        } else {
+            // Rewrite ifnull(x, y) to coalesce(x, y)
+            self.resolve_function(span, "coalesce", vec![], &[arg_x, arg_y])
+        }
+    }
+
     fn try_rewrite_sugar_function(
         &mut self,
         span: Span,
@@ -3049,24 +3186,10 @@ impl<'a> TypeChecker<'a> {
                 }))
             }
             ("nullif", &[arg_x, arg_y]) => {
-                // Rewrite nullif(x, y) to if(x = y, null, x)
-                Some(self.resolve_function(span, "if", vec![], &[
-                    &Expr::BinaryOp {
-                        span,
-                        op: BinaryOperator::Eq,
-                        left: Box::new(arg_x.clone()),
-                        right: Box::new(arg_y.clone()),
-                    },
-                    &Expr::Literal {
-                        span,
-                        value: Literal::Null,
-                    },
-                    arg_x,
-                ]))
+                Some(self.resolve_null_function(span, "nullif", arg_x, arg_y))
             }
             ("ifnull", &[arg_x, arg_y]) => {
-                // Rewrite ifnull(x, y) to coalesce(x, y)
-                Some(self.resolve_function(span, "coalesce", vec![], &[arg_x, arg_y]))
+                Some(self.resolve_null_function(span, "ifnull", arg_x, arg_y))
             }
             ("nvl", &[arg_x, arg_y]) => {
                 // Rewrite nvl(x, y) to coalesce(x, y)
@@ -3085,22 +3208,6 @@ impl<'a> TypeChecker<'a> {
                     arg_z,
                 ]))
             }
-            ("is_null", &[arg_x]) => {
-                // Rewrite is_null(x) to not(is_not_null(x))
-                Some(
-                    self.resolve_unary_op(span, &UnaryOperator::Not, &Expr::FunctionCall {
-                        span,
-                        func: ASTFunctionCall {
-                            distinct: false,
-                            name: Identifier::from_name(span, "is_not_null"),
-                            args: vec![arg_x.clone()],
-                            params: vec![],
-                            window: None,
-                            lambda: None,
-                        },
-                    }),
-                )
-            }
             ("is_error", &[arg_x]) => {
                 // Rewrite is_error(x) to not(is_not_error(x))
                 Some(
@@ -65,6 +65,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
             order_by,
             limit,
             offset,
+            with_ties: false,
             ignore_result: false,
         }
     }
@@ -133,6 +134,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
             order_by: vec![],
             limit: vec![],
             offset: None,
+            with_ties: false,
             ignore_result: false,
         };
 